@@ -0,0 +1,313 @@
+use crate::role_name;
+use crate::role_state;
+use crate::roles;
+use crate::timestamp;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+const ARCHIVE_SUFFIX: &str = ".tar.gz";
+
+/// A role tarball under `.juliet/.archive/`, named `<role>-<YYYYMMDDTHHMMSSZ>.tar.gz`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RoleArchive {
+    pub role_name: String,
+    pub stamp: String,
+    pub path: PathBuf,
+}
+
+impl RoleArchive {
+    fn from_path(path: PathBuf) -> Option<Self> {
+        let file_name = path.file_name()?.to_str()?;
+        let (role_name, stamp) = file_name.strip_suffix(ARCHIVE_SUFFIX)?.rsplit_once('-')?;
+        if !role_name::is_valid_role_name(role_name) || !is_compact_stamp(stamp) {
+            return None;
+        }
+
+        Some(Self {
+            role_name: role_name.to_string(),
+            stamp: stamp.to_string(),
+            path,
+        })
+    }
+}
+
+/// Packs `.juliet/<role>/` into a new tarball. Unless `keep` is set the role directory is
+/// removed afterwards, which is refused while `processes.md` lists active runs.
+pub fn archive_role(project_root: &Path, role_name: &str, keep: bool) -> Result<PathBuf, String> {
+    role_name::validate_role_name(role_name)?;
+    if !role_state::role_state_exists(project_root, role_name) {
+        return Err(format!(
+            "Role not found: {role_name}. Run: juliet init --project {role_name}"
+        ));
+    }
+
+    if !keep {
+        let active = roles::active_process_count(project_root, role_name)
+            .map_err(|err| format!("failed to read processes for role {role_name}: {err}"))?;
+        if active > 0 {
+            return Err(format!(
+                "Role {role_name} has {active} active process(es) in processes.md. Wait for them to finish, or pass --keep to archive without removing the role."
+            ));
+        }
+    }
+
+    let archive_dir = role_state::archive_dir(project_root);
+    fs::create_dir_all(&archive_dir)
+        .map_err(|err| format!("failed to create {}: {err}", archive_dir.display()))?;
+    let stamp = timestamp::now_utc().replace(['-', ':'], "");
+    let archive_path = archive_dir.join(format!("{role_name}-{stamp}{ARCHIVE_SUFFIX}"));
+    if archive_path.exists() {
+        return Err(format!(
+            "archive already exists: {}. Try again in a second.",
+            archive_path.display()
+        ));
+    }
+
+    run_tar(
+        Command::new("tar")
+            .arg("-czf")
+            .arg(&archive_path)
+            .arg("-C")
+            .arg(role_state::state_root(project_root))
+            .arg(role_name),
+    )?;
+
+    if !keep {
+        fs::remove_dir_all(role_state::role_state_dir(project_root, role_name))
+            .map_err(|err| format!("failed to remove role {role_name} after archiving: {err}"))?;
+    }
+    Ok(archive_path)
+}
+
+/// Archives in `.juliet/.archive/`, oldest first, optionally limited to one role.
+pub fn list_archives(project_root: &Path, role_name: Option<&str>) -> io::Result<Vec<RoleArchive>> {
+    let entries = match fs::read_dir(role_state::archive_dir(project_root)) {
+        Ok(entries) => entries,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => return Err(err),
+    };
+
+    let mut archives = Vec::new();
+    for entry in entries {
+        let Some(archive) = RoleArchive::from_path(entry?.path()) else {
+            continue;
+        };
+        if role_name.is_none_or(|role_name| archive.role_name == role_name) {
+            archives.push(archive);
+        }
+    }
+
+    archives.sort_by(|left, right| {
+        (&left.stamp, &left.role_name).cmp(&(&right.stamp, &right.role_name))
+    });
+    Ok(archives)
+}
+
+/// Unpacks an archive back into `.juliet/<role>/`. `archive_name` picks a specific file in
+/// `.juliet/.archive/`; otherwise the role's most recent archive is used. The tarball is kept.
+pub fn restore_role(
+    project_root: &Path,
+    role_name: &str,
+    archive_name: Option<&str>,
+) -> Result<RoleArchive, String> {
+    role_name::validate_role_name(role_name)?;
+    if role_state::role_state_exists(project_root, role_name) {
+        return Err(format!(
+            "Role already exists: {role_name}. Archive or delete it before restoring."
+        ));
+    }
+
+    let archives = list_archives(project_root, Some(role_name))
+        .map_err(|err| format!("failed to read archives: {err}"))?;
+    let archive = match archive_name {
+        Some(name) => archives
+            .into_iter()
+            .find(|archive| archive.path.file_name().is_some_and(|file| file == name))
+            .ok_or_else(|| format!("Archive not found for role {role_name}: {name}"))?,
+        None => archives
+            .into_iter()
+            .last()
+            .ok_or_else(|| format!("No archives found for role {role_name}"))?,
+    };
+
+    let listing = run_tar(Command::new("tar").arg("-tzf").arg(&archive.path))?;
+    let role_prefix = format!("{role_name}/");
+    if let Some(stray) = listing
+        .lines()
+        .find(|entry| *entry != role_name && !entry.starts_with(&role_prefix))
+    {
+        return Err(format!(
+            "refusing to restore {}: entry {stray} is outside {role_prefix}",
+            archive.path.display()
+        ));
+    }
+
+    run_tar(
+        Command::new("tar")
+            .arg("-xzf")
+            .arg(&archive.path)
+            .arg("-C")
+            .arg(role_state::state_root(project_root)),
+    )?;
+    Ok(archive)
+}
+
+fn run_tar(command: &mut Command) -> Result<String, String> {
+    let output = command
+        .output()
+        .map_err(|err| format!("failed to run tar: {err}"))?;
+    if !output.status.success() {
+        return Err(format!(
+            "tar failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+fn is_compact_stamp(stamp: &str) -> bool {
+    stamp.len() == 16
+        && stamp.ends_with('Z')
+        && stamp.as_bytes()[8] == b'T'
+        && stamp
+            .bytes()
+            .enumerate()
+            .all(|(index, byte)| matches!(index, 8 | 15) || byte.is_ascii_digit())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state_files::{self, ProcessEntry, Processes};
+    use crate::test_support::TestDir;
+
+    fn seed_role(root: &Path) {
+        role_state::create_role_state(root, "eng").expect("role state should be created");
+        fs::write(
+            role_state::role_prompt_path(root, "eng"),
+            "# eng\n\nShip it.\n",
+        )
+        .expect("prompt should be written");
+        fs::write(role_state::projects_path(root, "eng"), "- name: wind\n")
+            .expect("projects should be written");
+        fs::write(
+            role_state::artifacts_dir(root, "eng").join("report.md"),
+            "sprint report\n",
+        )
+        .expect("artifact should be written");
+    }
+
+    #[test]
+    fn archive_and_restore_round_trip_role_state() {
+        let temp = TestDir::new("round-trip");
+        seed_role(temp.path());
+
+        let archive_path = archive_role(temp.path(), "eng", false).expect("archive should work");
+
+        assert!(archive_path.starts_with(role_state::archive_dir(temp.path())));
+        assert!(!role_state::role_state_exists(temp.path(), "eng"));
+        assert!(role_state::discover_configured_roles(temp.path())
+            .expect("roles should be discovered")
+            .is_empty());
+
+        let restored = restore_role(temp.path(), "eng", None).expect("restore should work");
+
+        assert_eq!(restored.path, archive_path);
+        assert!(archive_path.is_file());
+        assert_eq!(
+            fs::read_to_string(role_state::role_prompt_path(temp.path(), "eng"))
+                .expect("prompt should be restored"),
+            "# eng\n\nShip it.\n"
+        );
+        assert_eq!(
+            fs::read_to_string(role_state::projects_path(temp.path(), "eng"))
+                .expect("projects should be restored"),
+            "- name: wind\n"
+        );
+        assert_eq!(
+            fs::read_to_string(role_state::artifacts_dir(temp.path(), "eng").join("report.md"))
+                .expect("artifact should be restored"),
+            "sprint report\n"
+        );
+    }
+
+    #[test]
+    fn archive_refuses_to_remove_roles_with_active_runs_unless_kept() {
+        let temp = TestDir::new("active");
+        seed_role(temp.path());
+        state_files::save_processes(
+            temp.path(),
+            "eng",
+            &Processes {
+                active: vec![ProcessEntry {
+                    pid: Some(42),
+                    ..ProcessEntry::default()
+                }],
+                completed: Vec::new(),
+            },
+        )
+        .expect("processes should save");
+
+        assert_eq!(
+            archive_role(temp.path(), "eng", false),
+            Err("Role eng has 1 active process(es) in processes.md. Wait for them to finish, or pass --keep to archive without removing the role.".to_string())
+        );
+
+        let archive_path = archive_role(temp.path(), "eng", true).expect("keep should archive");
+        assert!(archive_path.is_file());
+        assert!(role_state::role_state_exists(temp.path(), "eng"));
+    }
+
+    #[test]
+    fn restore_requires_an_archive_and_a_free_role_name() {
+        let temp = TestDir::new("restore-errors");
+
+        assert_eq!(
+            restore_role(temp.path(), "eng", None),
+            Err("No archives found for role eng".to_string())
+        );
+
+        seed_role(temp.path());
+        assert_eq!(
+            restore_role(temp.path(), "eng", None),
+            Err("Role already exists: eng. Archive or delete it before restoring.".to_string())
+        );
+    }
+
+    #[test]
+    fn list_archives_parses_names_and_sorts_by_time() {
+        let temp = TestDir::new("list");
+        let archive_dir = role_state::archive_dir(temp.path());
+        fs::create_dir_all(&archive_dir).expect("archive dir should be created");
+        for name in [
+            "eng-lead-20260301T090000Z.tar.gz",
+            "eng-lead-20260101T090000Z.tar.gz",
+            "ops-20260201T090000Z.tar.gz",
+            "notes.txt",
+            "eng-lead-latest.tar.gz",
+        ] {
+            fs::write(archive_dir.join(name), "").expect("archive should be written");
+        }
+
+        let archives = list_archives(temp.path(), None).expect("archives should list");
+        assert_eq!(
+            archives
+                .iter()
+                .map(|archive| (archive.role_name.as_str(), archive.stamp.as_str()))
+                .collect::<Vec<_>>(),
+            vec![
+                ("eng-lead", "20260101T090000Z"),
+                ("ops", "20260201T090000Z"),
+                ("eng-lead", "20260301T090000Z"),
+            ]
+        );
+        assert_eq!(
+            list_archives(temp.path(), Some("ops"))
+                .expect("archives should list")
+                .len(),
+            1
+        );
+    }
+}
@@ -0,0 +1,23 @@
+const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+/// Stable 64-bit FNV-1a digest of `text`, formatted as `fnv1a64:<16 hex digits>`. Digests are
+/// stored on disk, so the output must never change.
+pub fn fnv1a64(text: &str) -> String {
+    let digest = text.bytes().fold(FNV_OFFSET_BASIS, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(FNV_PRIME)
+    });
+    format!("fnv1a64:{digest:016x}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fnv1a64_matches_reference_values() {
+        assert_eq!(fnv1a64(""), "fnv1a64:cbf29ce484222325");
+        assert_eq!(fnv1a64("a"), "fnv1a64:af63dc4c8601ec8c");
+        assert_eq!(fnv1a64("foobar"), "fnv1a64:85944171f73967e8");
+    }
+}
@@ -0,0 +1,468 @@
+use crate::role_state;
+use crate::state_files::Session;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::time::Duration;
+
+pub const CODEX_LOGGED_IN_MARKER: &str = "Logged in using";
+pub const CLAUDE_PROBE_PROMPT: &str = "PRINT exactly 'CLAUDE_READY'";
+pub const CLAUDE_READY_REPLY: &str = "CLAUDE_READY";
+/// Upper bound for each probe, since `claude -p` makes a real model call.
+pub const PROBE_TIMEOUT: Duration = Duration::from_secs(90);
+pub const EMAIL_FILE: &str = ".swarm-hug/email.txt";
+const SESSION_STATUS_READY: &str = "active";
+const SESSION_STATUS_RESET: &str = "reset-required";
+
+/// Captured result of one probe command.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct ProbeOutput {
+    pub success: bool,
+    pub stdout: String,
+    pub stderr: String,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum CheckLevel {
+    Ok,
+    Warn,
+    Fail,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Check {
+    pub name: &'static str,
+    pub level: CheckLevel,
+    pub detail: String,
+}
+
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct DoctorReport {
+    pub checks: Vec<Check>,
+    pub available_engines: Vec<String>,
+    pub default_engine: Option<String>,
+    pub swarm_engine_property_syntax: Option<String>,
+}
+
+impl DoctorReport {
+    pub fn has_failures(&self) -> bool {
+        self.checks
+            .iter()
+            .any(|check| check.level == CheckLevel::Fail)
+    }
+
+    fn push(&mut self, name: &'static str, level: CheckLevel, detail: impl Into<String>) {
+        self.checks.push(Check {
+            name,
+            level,
+            detail: detail.into(),
+        });
+    }
+}
+
+/// Runs the conversation-start discovery from the Juliet prompt without an engine turn.
+/// `run_probe` executes a program with arguments in `project_root`; an `Err` means it could
+/// not be started at all.
+pub fn run_doctor<R>(project_root: &Path, mut run_probe: R) -> DoctorReport
+where
+    R: FnMut(&str, &[&str]) -> io::Result<ProbeOutput>,
+{
+    let mut report = DoctorReport::default();
+
+    match run_probe("swarm", &["--help"]) {
+        Ok(output) => {
+            let help = format!("{}\n{}", output.stdout, output.stderr);
+            report.swarm_engine_property_syntax = parse_swarm_engine_syntax(&help);
+            match &report.swarm_engine_property_syntax {
+                Some(syntax) => {
+                    report.push("swarm", CheckLevel::Ok, format!("engine syntax `{syntax}`"))
+                }
+                None => report.push(
+                    "swarm",
+                    CheckLevel::Warn,
+                    "found, but `swarm --help` does not mention an engine option",
+                ),
+            }
+        }
+        Err(err) => report.push("swarm", CheckLevel::Fail, format!("not runnable: {err}")),
+    }
+
+    match run_probe("codex", &["login", "status"]) {
+        Ok(output) if codex_is_logged_in(&output) => {
+            report.available_engines.push("codex".to_string());
+            report.push("codex", CheckLevel::Ok, "logged in");
+        }
+        Ok(_) => report.push(
+            "codex",
+            CheckLevel::Warn,
+            "not logged in (run `codex login`)",
+        ),
+        Err(err) => report.push("codex", CheckLevel::Warn, format!("not runnable: {err}")),
+    }
+
+    match run_probe("claude", &["-p", CLAUDE_PROBE_PROMPT]) {
+        Ok(output) if output.stdout.trim() == CLAUDE_READY_REPLY => {
+            report.available_engines.push("claude".to_string());
+            report.push("claude", CheckLevel::Ok, "ready");
+        }
+        Ok(output) => report.push(
+            "claude",
+            CheckLevel::Warn,
+            format!(
+                "did not reply {CLAUDE_READY_REPLY}: {}",
+                first_line(&output)
+            ),
+        ),
+        Err(err) => report.push("claude", CheckLevel::Warn, format!("not runnable: {err}")),
+    }
+
+    // Codex is preferred when both engines are available.
+    report.default_engine = report.available_engines.first().cloned();
+    match &report.default_engine {
+        Some(default_engine) => report.push(
+            "engines",
+            CheckLevel::Ok,
+            format!(
+                "{} available, default {default_engine}",
+                report.available_engines.join(", ")
+            ),
+        ),
+        None => report.push(
+            "engines",
+            CheckLevel::Fail,
+            "no engine available; log in to codex or enable claude",
+        ),
+    }
+
+    match check_email(project_root) {
+        Ok(email) => report.push("email", CheckLevel::Ok, email),
+        Err(problem) => report.push("email", CheckLevel::Warn, format!("{EMAIL_FILE} {problem}")),
+    }
+
+    let (level, detail) = check_git(&mut run_probe);
+    report.push("git", level, detail);
+
+    let (level, detail) = check_state_gitignore(project_root);
+    report.push("gitignore", level, detail);
+
+    report
+}
+
+/// Copies the discovered bootstrap fields into `session`, keeping any other fields.
+/// Without an available engine the session stays `reset-required` so discovery reruns.
+pub fn apply_to_session(session: &mut Session, report: &DoctorReport, now_utc: &str) {
+    session.started_at_utc = Some(now_utc.to_string());
+    session.status = Some(
+        if report.default_engine.is_some() {
+            SESSION_STATUS_READY
+        } else {
+            SESSION_STATUS_RESET
+        }
+        .to_string(),
+    );
+    session.available_engines = report.available_engines.clone();
+    session.default_engine = report.default_engine.clone();
+    session.swarm_engine_property_syntax = report.swarm_engine_property_syntax.clone();
+}
+
+pub fn format_report(report: &DoctorReport) -> String {
+    let mut output = String::new();
+    for check in &report.checks {
+        let level = match check.level {
+            CheckLevel::Ok => "ok",
+            CheckLevel::Warn => "warn",
+            CheckLevel::Fail => "fail",
+        };
+        output.push_str(&format!("{level:<4}  {}: {}\n", check.name, check.detail));
+    }
+    output
+}
+
+/// Finds the engine option in `swarm --help`, e.g. `--engine <ENGINE>` or `engine=<name>`.
+pub fn parse_swarm_engine_syntax(help: &str) -> Option<String> {
+    for line in help.lines() {
+        let tokens = line
+            .split_whitespace()
+            .map(|token| token.trim_end_matches(','))
+            .collect::<Vec<_>>();
+
+        for (index, token) in tokens.iter().enumerate() {
+            let is_flag = token.starts_with('-') && token.contains("engine");
+            let is_property = token.contains("engine=");
+            if !is_flag && !is_property {
+                continue;
+            }
+
+            let mut syntax = token.trim_matches(['`', '"', '\'']).to_string();
+            if is_flag && !syntax.contains('=') {
+                if let Some(value) = tokens
+                    .get(index + 1)
+                    .filter(|value| value.starts_with('<') || value.starts_with('['))
+                {
+                    syntax.push(' ');
+                    syntax.push_str(value);
+                }
+            }
+            return Some(syntax);
+        }
+    }
+    None
+}
+
+pub fn codex_is_logged_in(output: &ProbeOutput) -> bool {
+    // Codex has printed its login status to either stream depending on the version.
+    output.stdout.contains(CODEX_LOGGED_IN_MARKER) || output.stderr.contains(CODEX_LOGGED_IN_MARKER)
+}
+
+/// Validates `.swarm-hug/email.txt`: a single non-empty line containing `@` and no spaces.
+pub fn check_email(project_root: &Path) -> Result<String, String> {
+    let contents = match fs::read_to_string(project_root.join(EMAIL_FILE)) {
+        Ok(contents) => contents,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Err("is missing".to_string()),
+        Err(err) => return Err(format!("could not be read: {err}")),
+    };
+
+    let lines = contents
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .collect::<Vec<_>>();
+    match lines.as_slice() {
+        [] => Err("is empty".to_string()),
+        [email] if email.contains('@') && !email.contains(char::is_whitespace) => {
+            Ok(email.to_string())
+        }
+        [_] => Err("does not hold a valid email (needs `@` and no spaces)".to_string()),
+        _ => Err("must hold a single line".to_string()),
+    }
+}
+
+fn check_git<R>(run_probe: &mut R) -> (CheckLevel, String)
+where
+    R: FnMut(&str, &[&str]) -> io::Result<ProbeOutput>,
+{
+    match run_probe("git", &["rev-parse", "--is-inside-work-tree"]) {
+        Ok(output) if output.success && output.stdout.trim() == "true" => {}
+        Ok(_) => return (CheckLevel::Fail, "not inside a git work tree".to_string()),
+        Err(err) => return (CheckLevel::Fail, format!("not runnable: {err}")),
+    }
+
+    let branch = run_probe("git", &["branch", "--show-current"])
+        .map(|output| output.stdout.trim().to_string())
+        .unwrap_or_default();
+    let changes = run_probe("git", &["status", "--porcelain"])
+        .map(|output| {
+            output
+                .stdout
+                .lines()
+                .filter(|line| !line.is_empty())
+                .count()
+        })
+        .unwrap_or(0);
+    let tree = match changes {
+        0 => "clean".to_string(),
+        1 => "1 uncommitted change".to_string(),
+        count => format!("{count} uncommitted changes"),
+    };
+
+    if branch.is_empty() {
+        (CheckLevel::Warn, format!("detached HEAD ({tree})"))
+    } else {
+        (CheckLevel::Ok, format!("on branch {branch} ({tree})"))
+    }
+}
+
+fn check_state_gitignore(project_root: &Path) -> (CheckLevel, String) {
+    let path = role_state::state_gitignore_path(project_root);
+    match fs::read_to_string(&path) {
+        Ok(contents) if contents == role_state::STATE_GITIGNORE_CONTENTS => {
+            (CheckLevel::Ok, format!("{} is current", path.display()))
+        }
+        Ok(_) => (
+            CheckLevel::Warn,
+            format!(
+                "{} differs from the managed template; rerun juliet init to restore it",
+                path.display()
+            ),
+        ),
+        Err(_) => (
+            CheckLevel::Warn,
+            format!(
+                "{} is missing; rerun juliet init to create it",
+                path.display()
+            ),
+        ),
+    }
+}
+
+fn first_line(output: &ProbeOutput) -> String {
+    output
+        .stdout
+        .lines()
+        .chain(output.stderr.lines())
+        .map(str::trim)
+        .find(|line| !line.is_empty())
+        .unwrap_or("no output")
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::TestDir;
+
+    fn probe(stdout: &str) -> io::Result<ProbeOutput> {
+        Ok(ProbeOutput {
+            success: true,
+            stdout: stdout.to_string(),
+            stderr: String::new(),
+        })
+    }
+
+    fn healthy_probe(program: &str, args: &[&str]) -> io::Result<ProbeOutput> {
+        match (program, args.first().copied()) {
+            ("swarm", _) => {
+                probe("Usage: swarm [OPTIONS]\n  -e, --engine <ENGINE>  Engine to use\n")
+            }
+            ("codex", _) => probe("Logged in using ChatGPT\n"),
+            ("claude", _) => probe("CLAUDE_READY\n"),
+            ("git", Some("rev-parse")) => probe("true\n"),
+            ("git", Some("branch")) => probe("main\n"),
+            ("git", Some("status")) => probe(" M juliet.rs\n?? notes.md\n"),
+            _ => Err(io::Error::new(io::ErrorKind::NotFound, "unexpected probe")),
+        }
+    }
+
+    #[test]
+    fn parses_swarm_engine_syntax_from_help() {
+        assert_eq!(
+            parse_swarm_engine_syntax("Options:\n  -e, --engine <ENGINE>  Engine to use\n"),
+            Some("--engine <ENGINE>".to_string())
+        );
+        assert_eq!(
+            parse_swarm_engine_syntax("  --engine=<claude|codex>  Pick an engine\n"),
+            Some("--engine=<claude|codex>".to_string())
+        );
+        assert_eq!(
+            parse_swarm_engine_syntax("Set properties with `engine=codex` on run.\n"),
+            Some("engine=codex".to_string())
+        );
+        assert_eq!(parse_swarm_engine_syntax("Usage: swarm run\n"), None);
+    }
+
+    #[test]
+    fn check_email_requires_single_valid_line() {
+        let temp = TestDir::new("email");
+        assert_eq!(check_email(temp.path()), Err("is missing".to_string()));
+
+        let email_path = temp.path().join(EMAIL_FILE);
+        fs::create_dir_all(email_path.parent().expect("email has a parent"))
+            .expect("swarm dir should be created");
+        for (contents, expected) in [
+            ("\n", Err("is empty".to_string())),
+            (
+                "dev example.com\n",
+                Err("does not hold a valid email (needs `@` and no spaces)".to_string()),
+            ),
+            (
+                "a@example.com\nb@example.com\n",
+                Err("must hold a single line".to_string()),
+            ),
+            ("dev@example.com\n", Ok("dev@example.com".to_string())),
+        ] {
+            fs::write(&email_path, contents).expect("email should be written");
+            assert_eq!(check_email(temp.path()), expected, "contents: {contents:?}");
+        }
+    }
+
+    #[test]
+    fn doctor_reports_engines_and_environment() {
+        let temp = TestDir::new("healthy");
+        role_state::create_role_state(temp.path(), "eng").expect("role state should be created");
+
+        let report = run_doctor(temp.path(), healthy_probe);
+
+        assert!(!report.has_failures());
+        assert_eq!(report.available_engines, vec!["codex", "claude"]);
+        assert_eq!(report.default_engine.as_deref(), Some("codex"));
+        assert_eq!(
+            report.swarm_engine_property_syntax.as_deref(),
+            Some("--engine <ENGINE>")
+        );
+        let formatted = format_report(&report);
+        assert!(formatted.starts_with(
+            "ok    swarm: engine syntax `--engine <ENGINE>`\nok    codex: logged in\nok    claude: ready\nok    engines: codex, claude available, default codex\nwarn  email: .swarm-hug/email.txt is missing\nok    git: on branch main (2 uncommitted changes)\nok    gitignore: "
+        ));
+    }
+
+    #[test]
+    fn doctor_fails_without_engines_or_git() {
+        let temp = TestDir::new("broken");
+
+        let report = run_doctor(temp.path(), |program, args| match program {
+            "codex" => Ok(ProbeOutput {
+                success: false,
+                stdout: String::new(),
+                stderr: "Not logged in\n".to_string(),
+            }),
+            "claude" => probe("Invalid API key\n"),
+            "git" => Ok(ProbeOutput {
+                success: false,
+                ..ProbeOutput::default()
+            }),
+            _ => healthy_probe(program, args),
+        });
+
+        assert!(report.has_failures());
+        assert!(report.available_engines.is_empty());
+        assert_eq!(
+            format_report(&report)
+                .lines()
+                .filter(|line| line.starts_with("fail"))
+                .collect::<Vec<_>>(),
+            vec![
+                "fail  engines: no engine available; log in to codex or enable claude",
+                "fail  git: not inside a git work tree",
+            ]
+        );
+        assert!(format_report(&report)
+            .contains("warn  claude: did not reply CLAUDE_READY: Invalid API key\n"));
+    }
+
+    #[test]
+    fn apply_to_session_writes_bootstrap_fields_and_keeps_extras() {
+        let mut session = Session {
+            status: Some("reset-required".to_string()),
+            extra: vec![("notes".to_string(), "keep me".to_string())],
+            ..Session::default()
+        };
+        let report = DoctorReport {
+            available_engines: vec!["claude".to_string()],
+            default_engine: Some("claude".to_string()),
+            swarm_engine_property_syntax: Some("--engine <ENGINE>".to_string()),
+            ..DoctorReport::default()
+        };
+
+        apply_to_session(&mut session, &report, "2026-02-06T10:00:00Z");
+
+        assert_eq!(
+            session,
+            Session {
+                started_at_utc: Some("2026-02-06T10:00:00Z".to_string()),
+                status: Some("active".to_string()),
+                available_engines: vec!["claude".to_string()],
+                default_engine: Some("claude".to_string()),
+                swarm_engine_property_syntax: Some("--engine <ENGINE>".to_string()),
+                extra: vec![("notes".to_string(), "keep me".to_string())],
+            }
+        );
+
+        apply_to_session(
+            &mut session,
+            &DoctorReport::default(),
+            "2026-02-06T11:00:00Z",
+        );
+        assert_eq!(session.status.as_deref(), Some("reset-required"));
+        assert!(session.available_engines.is_empty());
+    }
+}
@@ -0,0 +1,557 @@
+use serde_json::Value;
+use std::process::Command;
+
+pub const BUILTIN_ENGINE_NAMES: [&str; 2] = ["claude", "codex"];
+
+#[derive(Debug, Eq, PartialEq)]
+pub struct ExecResult {
+    pub text: String,
+    pub resume_id: String,
+}
+
+/// Engine-independent progress event observed while a streamed exec turn runs.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum StreamEvent {
+    ToolCall {
+        name: String,
+        detail: Option<String>,
+    },
+    MessageDelta {
+        text: String,
+    },
+}
+
+/// An agent CLI that Juliet can launch interactively or drive one exec turn at a time.
+pub trait Engine {
+    /// Name used to select the engine on the command line.
+    fn name(&self) -> &str;
+
+    /// Command that starts an interactive session seeded with `prompt`.
+    fn launch_command(&self, prompt: &str) -> Command;
+
+    /// Command that runs a single non-interactive turn with machine-readable output,
+    /// resuming `continue_id` when one is given.
+    fn exec_command(&self, prompt: &str, continue_id: Option<&str>) -> Command;
+
+    /// Whether `exec_command` honors `continue_id`.
+    fn supports_resume(&self) -> bool {
+        true
+    }
+
+    /// Normalizes the stdout of `exec_command` into the final text and resume id.
+    fn parse_exec_output(&self, raw_stdout: &str) -> Result<ExecResult, String>;
+
+    /// Command that runs one turn while printing JSON events line by line. Its full stdout
+    /// must still be accepted by `parse_exec_output`.
+    fn stream_exec_command(&self, prompt: &str, continue_id: Option<&str>) -> Command {
+        self.exec_command(prompt, continue_id)
+    }
+
+    /// Normalizes one line of `stream_exec_command` output. Engines without a known event
+    /// format report nothing until the final result.
+    fn parse_stream_line(&self, _line: &str) -> Vec<StreamEvent> {
+        Vec::new()
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct ClaudeEngine;
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct CodexEngine;
+
+impl Engine for ClaudeEngine {
+    fn name(&self) -> &str {
+        "claude"
+    }
+
+    fn launch_command(&self, prompt: &str) -> Command {
+        let mut command = Command::new("claude");
+        command
+            .arg("--dangerously-skip-permissions")
+            .arg(prompt)
+            .env("IS_SANDBOX", "1");
+        command
+    }
+
+    fn exec_command(&self, prompt: &str, continue_id: Option<&str>) -> Command {
+        claude_print_command(prompt, continue_id, "json")
+    }
+
+    fn parse_exec_output(&self, raw_stdout: &str) -> Result<ExecResult, String> {
+        parse_claude_exec_result(raw_stdout)
+    }
+
+    fn stream_exec_command(&self, prompt: &str, continue_id: Option<&str>) -> Command {
+        // Print mode only emits stream-json together with --verbose.
+        let mut command = claude_print_command(prompt, continue_id, "stream-json");
+        command.arg("--verbose");
+        command
+    }
+
+    fn parse_stream_line(&self, line: &str) -> Vec<StreamEvent> {
+        let Ok(value) = serde_json::from_str::<Value>(line) else {
+            return Vec::new();
+        };
+        if value.get("type").and_then(Value::as_str) != Some("assistant") {
+            return Vec::new();
+        }
+
+        let Some(content) = value.pointer("/message/content").and_then(Value::as_array) else {
+            return Vec::new();
+        };
+        content
+            .iter()
+            .filter_map(|block| match block.get("type").and_then(Value::as_str) {
+                Some("text") => block.get("text").and_then(Value::as_str).map(|text| {
+                    StreamEvent::MessageDelta {
+                        text: text.to_string(),
+                    }
+                }),
+                Some("tool_use") => Some(StreamEvent::ToolCall {
+                    name: block
+                        .get("name")
+                        .and_then(Value::as_str)
+                        .unwrap_or("tool")
+                        .to_string(),
+                    detail: block
+                        .pointer("/input/command")
+                        .or_else(|| block.pointer("/input/description"))
+                        .and_then(Value::as_str)
+                        .map(str::to_string),
+                }),
+                _ => None,
+            })
+            .collect()
+    }
+}
+
+impl Engine for CodexEngine {
+    fn name(&self) -> &str {
+        "codex"
+    }
+
+    fn launch_command(&self, prompt: &str) -> Command {
+        let mut command = Command::new("codex");
+        command
+            .arg("--dangerously-bypass-approvals-and-sandbox")
+            .arg(prompt);
+        command
+    }
+
+    fn exec_command(&self, prompt: &str, continue_id: Option<&str>) -> Command {
+        let mut command = Command::new("codex");
+        command
+            .arg("--dangerously-bypass-approvals-and-sandbox")
+            .arg("exec");
+
+        if let Some(resume_id) = continue_id {
+            command.arg("resume").arg(resume_id);
+        }
+
+        command.arg(prompt).arg("--json");
+        command
+    }
+
+    fn parse_exec_output(&self, raw_stdout: &str) -> Result<ExecResult, String> {
+        parse_codex_exec_result(raw_stdout)
+    }
+
+    // `codex exec --json` already streams one event per line, so the exec command is reused.
+    fn parse_stream_line(&self, line: &str) -> Vec<StreamEvent> {
+        let Ok(value) = serde_json::from_str::<Value>(line) else {
+            return Vec::new();
+        };
+        let Some(item) = value.get("item") else {
+            return Vec::new();
+        };
+        let item_type = item.get("type").and_then(Value::as_str);
+
+        match (value.get("type").and_then(Value::as_str), item_type) {
+            (Some("item.completed"), None | Some("agent_message")) => item
+                .get("text")
+                .and_then(Value::as_str)
+                .map(|text| StreamEvent::MessageDelta {
+                    text: text.to_string(),
+                })
+                .into_iter()
+                .collect(),
+            (Some("item.started"), Some("command_execution")) => vec![StreamEvent::ToolCall {
+                name: "command_execution".to_string(),
+                detail: item
+                    .get("command")
+                    .and_then(Value::as_str)
+                    .map(str::to_string),
+            }],
+            (Some("item.started"), Some("mcp_tool_call")) => vec![StreamEvent::ToolCall {
+                name: item
+                    .get("tool")
+                    .and_then(Value::as_str)
+                    .unwrap_or("mcp_tool_call")
+                    .to_string(),
+                detail: item
+                    .get("server")
+                    .and_then(Value::as_str)
+                    .map(str::to_string),
+            }],
+            (Some("item.started"), Some(kind @ ("file_change" | "web_search"))) => {
+                vec![StreamEvent::ToolCall {
+                    name: kind.to_string(),
+                    detail: None,
+                }]
+            }
+            _ => Vec::new(),
+        }
+    }
+}
+
+fn claude_print_command(prompt: &str, continue_id: Option<&str>, output_format: &str) -> Command {
+    let mut command = Command::new("claude");
+    command.arg("--dangerously-skip-permissions");
+
+    if let Some(resume_id) = continue_id {
+        command.arg("--resume").arg(resume_id);
+    }
+
+    command
+        .arg("-p")
+        .arg(prompt)
+        .arg("--output-format")
+        .arg(output_format)
+        .env("IS_SANDBOX", "1");
+    command
+}
+
+pub fn builtin_engine(name: &str) -> Option<Box<dyn Engine>> {
+    match name {
+        "claude" => Some(Box::new(ClaudeEngine)),
+        "codex" => Some(Box::new(CodexEngine)),
+        _ => None,
+    }
+}
+
+pub fn parse_json_values(raw: &str) -> Vec<Value> {
+    let mut values = Vec::new();
+    for line in raw.lines() {
+        let trimmed = line.trim();
+        if trimmed.is_empty() {
+            continue;
+        }
+        if let Ok(value) = serde_json::from_str::<Value>(trimmed) {
+            values.push(value);
+        }
+    }
+
+    if values.is_empty() {
+        let trimmed = raw.trim();
+        if !trimmed.is_empty() {
+            if let Ok(value) = serde_json::from_str::<Value>(trimmed) {
+                values.push(value);
+            }
+        }
+    }
+
+    values
+}
+
+fn extract_text_candidate(value: &Value) -> Option<String> {
+    for pointer in [
+        "/item/text",
+        "/text",
+        "/result",
+        "/output_text",
+        "/message/text",
+        "/content/0/text",
+        "/message/content/0/text",
+    ] {
+        if let Some(text) = value.pointer(pointer).and_then(Value::as_str) {
+            return Some(text.to_string());
+        }
+    }
+
+    if let Some(content) = value.get("content").and_then(Value::as_array) {
+        for item in content {
+            if let Some(text) = item.as_str() {
+                return Some(text.to_string());
+            }
+            if let Some(text) = item.get("text").and_then(Value::as_str) {
+                return Some(text.to_string());
+            }
+        }
+    }
+
+    None
+}
+
+fn parse_codex_exec_result(raw_stdout: &str) -> Result<ExecResult, String> {
+    let values = parse_json_values(raw_stdout);
+    if values.is_empty() {
+        return Err("codex returned no parseable JSON output".to_string());
+    }
+
+    let mut resume_id = None;
+    let mut text = None;
+    for value in &values {
+        if resume_id.is_none() {
+            resume_id = value
+                .get("thread_id")
+                .and_then(Value::as_str)
+                .map(|id| id.to_string());
+        }
+
+        if value.get("type").and_then(Value::as_str) == Some("item.completed") {
+            if let Some(item_text) = value.pointer("/item/text").and_then(Value::as_str) {
+                text = Some(item_text.to_string());
+            }
+        }
+    }
+
+    if text.is_none() {
+        for value in &values {
+            if let Some(candidate) = extract_text_candidate(value) {
+                text = Some(candidate);
+            }
+        }
+    }
+
+    let resume_id =
+        resume_id.ok_or_else(|| "codex JSON output did not include thread_id".to_string())?;
+    Ok(ExecResult {
+        text: text.unwrap_or_default(),
+        resume_id,
+    })
+}
+
+fn parse_claude_exec_result(raw_stdout: &str) -> Result<ExecResult, String> {
+    let values = parse_json_values(raw_stdout);
+    if values.is_empty() {
+        return Err("claude returned no parseable JSON output".to_string());
+    }
+
+    let mut resume_id = None;
+    let mut text = None;
+    for value in &values {
+        if resume_id.is_none() {
+            resume_id = value
+                .get("session_id")
+                .and_then(Value::as_str)
+                .map(|id| id.to_string());
+        }
+        if text.is_none() {
+            text = extract_text_candidate(value);
+        } else if let Some(candidate) = extract_text_candidate(value) {
+            text = Some(candidate);
+        }
+    }
+
+    let resume_id =
+        resume_id.ok_or_else(|| "claude JSON output did not include session_id".to_string())?;
+    Ok(ExecResult {
+        text: text.unwrap_or_default(),
+        resume_id,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::ffi::OsStr;
+
+    fn command_args(command: &Command) -> Vec<String> {
+        command
+            .get_args()
+            .map(|arg| arg.to_string_lossy().into_owned())
+            .collect()
+    }
+
+    fn command_env<'a>(command: &'a Command, key: &str) -> Option<&'a OsStr> {
+        command
+            .get_envs()
+            .find(|(name, _)| *name == OsStr::new(key))
+            .and_then(|(_, value)| value)
+    }
+
+    #[test]
+    fn builtin_engines_resolve_by_name() {
+        for name in BUILTIN_ENGINE_NAMES {
+            let engine = builtin_engine(name).expect("builtin engine should resolve");
+            assert_eq!(engine.name(), name);
+        }
+
+        assert!(builtin_engine("gpt4").is_none());
+    }
+
+    #[test]
+    fn codex_commands_use_bypass_flag_and_resume_syntax() {
+        let launch = CodexEngine.launch_command("prompt");
+        assert_eq!(launch.get_program(), "codex");
+        assert_eq!(
+            command_args(&launch),
+            vec!["--dangerously-bypass-approvals-and-sandbox", "prompt"]
+        );
+
+        let exec = CodexEngine.exec_command("prompt", Some("thread-1"));
+        assert_eq!(
+            command_args(&exec),
+            vec![
+                "--dangerously-bypass-approvals-and-sandbox",
+                "exec",
+                "resume",
+                "thread-1",
+                "prompt",
+                "--json",
+            ]
+        );
+    }
+
+    #[test]
+    fn claude_commands_use_print_mode_resume_flag_and_sandbox_env() {
+        let launch = ClaudeEngine.launch_command("prompt");
+        assert_eq!(launch.get_program(), "claude");
+        assert_eq!(
+            command_args(&launch),
+            vec!["--dangerously-skip-permissions", "prompt"]
+        );
+        assert_eq!(command_env(&launch, "IS_SANDBOX"), Some(OsStr::new("1")));
+
+        let exec = ClaudeEngine.exec_command("prompt", Some("session-1"));
+        assert_eq!(
+            command_args(&exec),
+            vec![
+                "--dangerously-skip-permissions",
+                "--resume",
+                "session-1",
+                "-p",
+                "prompt",
+                "--output-format",
+                "json",
+            ]
+        );
+        assert_eq!(command_env(&exec, "IS_SANDBOX"), Some(OsStr::new("1")));
+    }
+
+    #[test]
+    fn codex_parser_uses_thread_id_and_latest_completed_item() {
+        let parsed = CodexEngine
+            .parse_exec_output(
+                "{\"thread_id\":\"thread-1\"}\n{\"type\":\"item.completed\",\"item\":{\"text\":\"first\"}}\n{\"type\":\"item.completed\",\"item\":{\"text\":\"second\"}}\n",
+            )
+            .expect("codex output should parse");
+        assert_eq!(
+            parsed,
+            ExecResult {
+                text: "second".to_string(),
+                resume_id: "thread-1".to_string(),
+            }
+        );
+
+        assert_eq!(
+            CodexEngine.parse_exec_output("{\"type\":\"turn.completed\"}"),
+            Err("codex JSON output did not include thread_id".to_string())
+        );
+        assert_eq!(
+            CodexEngine.parse_exec_output("not json"),
+            Err("codex returned no parseable JSON output".to_string())
+        );
+    }
+
+    #[test]
+    fn claude_parser_uses_session_id_and_result_text() {
+        let parsed = ClaudeEngine
+            .parse_exec_output("{\"session_id\":\"session-1\",\"result\":\"done\"}")
+            .expect("claude output should parse");
+        assert_eq!(
+            parsed,
+            ExecResult {
+                text: "done".to_string(),
+                resume_id: "session-1".to_string(),
+            }
+        );
+
+        assert_eq!(
+            ClaudeEngine.parse_exec_output("{\"result\":\"done\"}"),
+            Err("claude JSON output did not include session_id".to_string())
+        );
+    }
+
+    #[test]
+    fn claude_stream_command_uses_stream_json_and_normalizes_events() {
+        let stream = ClaudeEngine.stream_exec_command("prompt", None);
+        assert_eq!(
+            command_args(&stream),
+            vec![
+                "--dangerously-skip-permissions",
+                "-p",
+                "prompt",
+                "--output-format",
+                "stream-json",
+                "--verbose",
+            ]
+        );
+
+        assert_eq!(
+            ClaudeEngine.parse_stream_line(
+                r#"{"type":"assistant","message":{"content":[{"type":"text","text":"checking runs"},{"type":"tool_use","name":"Bash","input":{"command":"ps -p 42"}}]}}"#
+            ),
+            vec![
+                StreamEvent::MessageDelta {
+                    text: "checking runs".to_string(),
+                },
+                StreamEvent::ToolCall {
+                    name: "Bash".to_string(),
+                    detail: Some("ps -p 42".to_string()),
+                },
+            ]
+        );
+        assert!(ClaudeEngine
+            .parse_stream_line(r#"{"type":"system","session_id":"session-1"}"#)
+            .is_empty());
+        assert!(ClaudeEngine.parse_stream_line("not json").is_empty());
+
+        let parsed = ClaudeEngine
+            .parse_exec_output(
+                "{\"type\":\"system\",\"session_id\":\"session-1\"}\n{\"type\":\"assistant\",\"message\":{\"content\":[{\"type\":\"text\",\"text\":\"partial\"}]}}\n{\"type\":\"result\",\"result\":\"done\",\"session_id\":\"session-1\"}\n",
+            )
+            .expect("stream output should parse as a final result");
+        assert_eq!(
+            parsed,
+            ExecResult {
+                text: "done".to_string(),
+                resume_id: "session-1".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn codex_stream_lines_normalize_tool_calls_and_messages() {
+        assert_eq!(
+            command_args(&CodexEngine.stream_exec_command("prompt", None)),
+            command_args(&CodexEngine.exec_command("prompt", None))
+        );
+        assert_eq!(
+            CodexEngine.parse_stream_line(
+                r#"{"type":"item.started","item":{"type":"command_execution","command":"bash -lc 'ps -p 42'"}}"#
+            ),
+            vec![StreamEvent::ToolCall {
+                name: "command_execution".to_string(),
+                detail: Some("bash -lc 'ps -p 42'".to_string()),
+            }]
+        );
+        assert_eq!(
+            CodexEngine.parse_stream_line(
+                r#"{"type":"item.completed","item":{"type":"agent_message","text":"all good"}}"#
+            ),
+            vec![StreamEvent::MessageDelta {
+                text: "all good".to_string(),
+            }]
+        );
+        assert!(CodexEngine
+            .parse_stream_line(
+                r#"{"type":"item.completed","item":{"type":"reasoning","text":"thinking"}}"#
+            )
+            .is_empty());
+        assert!(CodexEngine
+            .parse_stream_line(r#"{"thread_id":"thread-1"}"#)
+            .is_empty());
+    }
+}
@@ -0,0 +1,529 @@
+use crate::engine::{self, Engine, ExecResult};
+use crate::role_name;
+use crate::role_state;
+use serde_json::Value;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::process::Command;
+use toml::Table;
+
+const PROMPT_PLACEHOLDER: &str = "{prompt}";
+const RESUME_ID_PLACEHOLDER: &str = "{resume_id}";
+
+/// An engine declared in `.juliet/engines.toml`, driven through argv templates.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CustomEngine {
+    pub name: String,
+    pub launch: Vec<String>,
+    pub exec: Vec<String>,
+    pub exec_resume: Option<Vec<String>>,
+    pub env: Vec<(String, String)>,
+    pub text_pointer: String,
+    pub resume_id_pointer: String,
+}
+
+impl CustomEngine {
+    fn render(&self, template: &[String], prompt: &str, resume_id: Option<&str>) -> Command {
+        let mut argv = template
+            .iter()
+            .map(|arg| substitute_placeholders(arg, prompt, resume_id));
+
+        let mut command = Command::new(argv.next().unwrap_or_default());
+        command.args(argv);
+        for (key, value) in &self.env {
+            command.env(key, value);
+        }
+        command
+    }
+}
+
+/// Replaces placeholders in one left-to-right pass, so text inserted for `{prompt}` is never
+/// scanned again for `{resume_id}`.
+fn substitute_placeholders(arg: &str, prompt: &str, resume_id: Option<&str>) -> String {
+    let mut output = String::with_capacity(arg.len());
+    let mut rest = arg;
+    while let Some(start) = rest.find('{') {
+        output.push_str(&rest[..start]);
+        rest = &rest[start..];
+        if let Some(after) = rest.strip_prefix(PROMPT_PLACEHOLDER) {
+            output.push_str(prompt);
+            rest = after;
+        } else if let (Some(id), Some(after)) =
+            (resume_id, rest.strip_prefix(RESUME_ID_PLACEHOLDER))
+        {
+            output.push_str(id);
+            rest = after;
+        } else {
+            output.push('{');
+            rest = &rest[1..];
+        }
+    }
+    output.push_str(rest);
+    output
+}
+
+impl Engine for CustomEngine {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn launch_command(&self, prompt: &str) -> Command {
+        self.render(&self.launch, prompt, None)
+    }
+
+    fn exec_command(&self, prompt: &str, continue_id: Option<&str>) -> Command {
+        match (continue_id, &self.exec_resume) {
+            (Some(resume_id), Some(template)) => self.render(template, prompt, Some(resume_id)),
+            _ => self.render(&self.exec, prompt, None),
+        }
+    }
+
+    fn supports_resume(&self) -> bool {
+        self.exec_resume.is_some()
+    }
+
+    fn parse_exec_output(&self, raw_stdout: &str) -> Result<ExecResult, String> {
+        let values = engine::parse_json_values(raw_stdout);
+        if values.is_empty() {
+            return Err(format!("{} returned no parseable JSON output", self.name));
+        }
+
+        let mut resume_id = None;
+        let mut text = None;
+        for value in &values {
+            if resume_id.is_none() {
+                resume_id = value
+                    .pointer(&self.resume_id_pointer)
+                    .and_then(Value::as_str)
+                    .map(|id| id.to_string());
+            }
+            if let Some(candidate) = value.pointer(&self.text_pointer).and_then(Value::as_str) {
+                text = Some(candidate.to_string());
+            }
+        }
+
+        let resume_id = resume_id.ok_or_else(|| {
+            format!(
+                "{} JSON output did not include {}",
+                self.name, self.resume_id_pointer
+            )
+        })?;
+        Ok(ExecResult {
+            text: text.unwrap_or_default(),
+            resume_id,
+        })
+    }
+}
+
+pub fn resolve_engine(project_root: &Path, name: &str) -> Result<Box<dyn Engine>, String> {
+    if let Some(builtin) = engine::builtin_engine(name) {
+        return Ok(builtin);
+    }
+
+    let custom_engines = load_custom_engines(project_root)?;
+    let available = engine::BUILTIN_ENGINE_NAMES
+        .iter()
+        .map(|builtin| builtin.to_string())
+        .chain(custom_engines.iter().map(|custom| custom.name.clone()))
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    match custom_engines
+        .into_iter()
+        .find(|custom| custom.name == name)
+    {
+        Some(custom) => Ok(Box::new(custom)),
+        None => Err(format!(
+            "Unknown engine: {name}. Available engines: {available}"
+        )),
+    }
+}
+
+pub fn load_custom_engines(project_root: &Path) -> Result<Vec<CustomEngine>, String> {
+    let config_path = role_state::engines_config_path(project_root);
+    let contents = match fs::read_to_string(&config_path) {
+        Ok(contents) => contents,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => return Err(format!("failed to read {}: {err}", config_path.display())),
+    };
+
+    parse_engine_config(&contents)
+        .map_err(|err| format!("invalid engine config at {}: {err}", config_path.display()))
+}
+
+pub fn parse_engine_config(contents: &str) -> Result<Vec<CustomEngine>, String> {
+    let table = contents.parse::<Table>().map_err(|err| err.to_string())?;
+    let Some(engines) = table.get("engines") else {
+        return Ok(Vec::new());
+    };
+    let engines = engines
+        .as_table()
+        .ok_or_else(|| "engines must be a table".to_string())?;
+
+    let mut custom_engines = Vec::new();
+    for (name, definition) in engines {
+        if !role_name::is_valid_role_name(name) {
+            return Err(format!(
+                "invalid engine name: {name}. Use lowercase letters, numbers, and hyphens."
+            ));
+        }
+        if engine::builtin_engine(name).is_some() {
+            return Err(format!(
+                "engine name {name} is reserved for the built-in engine"
+            ));
+        }
+
+        let definition = definition
+            .as_table()
+            .ok_or_else(|| format!("engines.{name} must be a table"))?;
+        custom_engines.push(parse_engine_definition(name, definition)?);
+    }
+
+    Ok(custom_engines)
+}
+
+/// Default `exec` fallback chain from `[exec] engine_fallback` in `.juliet/engines.toml`.
+pub fn load_exec_fallback(project_root: &Path) -> Result<Vec<String>, String> {
+    let config_path = role_state::engines_config_path(project_root);
+    let contents = match fs::read_to_string(&config_path) {
+        Ok(contents) => contents,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => return Err(format!("failed to read {}: {err}", config_path.display())),
+    };
+
+    parse_exec_fallback(&contents)
+        .map_err(|err| format!("invalid engine config at {}: {err}", config_path.display()))
+}
+
+pub fn parse_exec_fallback(contents: &str) -> Result<Vec<String>, String> {
+    let table = contents.parse::<Table>().map_err(|err| err.to_string())?;
+    let Some(fallback) = table
+        .get("exec")
+        .and_then(|exec| exec.as_table())
+        .and_then(|exec| exec.get("engine_fallback"))
+    else {
+        return Ok(Vec::new());
+    };
+
+    let error = || "exec.engine_fallback must be an array of engine names".to_string();
+    fallback
+        .as_array()
+        .ok_or_else(error)?
+        .iter()
+        .map(|name| match name.as_str() {
+            Some(name) if role_name::is_valid_role_name(name) => Ok(name.to_string()),
+            _ => Err(error()),
+        })
+        .collect()
+}
+
+fn parse_engine_definition(name: &str, definition: &Table) -> Result<CustomEngine, String> {
+    let launch = required_argv(name, definition, "launch")?;
+    require_placeholder(name, "launch", &launch, PROMPT_PLACEHOLDER)?;
+    let exec = required_argv(name, definition, "exec")?;
+    require_placeholder(name, "exec", &exec, PROMPT_PLACEHOLDER)?;
+
+    let exec_resume = match definition.get("exec_resume") {
+        Some(_) => {
+            let template = required_argv(name, definition, "exec_resume")?;
+            require_placeholder(name, "exec_resume", &template, PROMPT_PLACEHOLDER)?;
+            require_placeholder(name, "exec_resume", &template, RESUME_ID_PLACEHOLDER)?;
+            Some(template)
+        }
+        None => None,
+    };
+
+    let env = match definition.get("env") {
+        Some(value) => {
+            let table = value
+                .as_table()
+                .ok_or_else(|| format!("engines.{name}.env must be a table of strings"))?;
+            table
+                .iter()
+                .map(|(key, value)| {
+                    value
+                        .as_str()
+                        .map(|value| (key.clone(), value.to_string()))
+                        .ok_or_else(|| format!("engines.{name}.env.{key} must be a string"))
+                })
+                .collect::<Result<Vec<_>, _>>()?
+        }
+        None => Vec::new(),
+    };
+
+    Ok(CustomEngine {
+        name: name.to_string(),
+        launch,
+        exec,
+        exec_resume,
+        env,
+        text_pointer: required_pointer(name, definition, "text_pointer")?,
+        resume_id_pointer: required_pointer(name, definition, "resume_id_pointer")?,
+    })
+}
+
+fn required_argv(name: &str, definition: &Table, key: &str) -> Result<Vec<String>, String> {
+    let error = || format!("engines.{name}.{key} must be a non-empty array of strings");
+    let items = definition
+        .get(key)
+        .and_then(|value| value.as_array())
+        .ok_or_else(error)?;
+    let argv = items
+        .iter()
+        .map(|item| item.as_str().map(|arg| arg.to_string()))
+        .collect::<Option<Vec<_>>>()
+        .ok_or_else(error)?;
+
+    if argv.first().is_none_or(|program| program.is_empty()) {
+        return Err(error());
+    }
+    Ok(argv)
+}
+
+fn require_placeholder(
+    name: &str,
+    key: &str,
+    argv: &[String],
+    placeholder: &str,
+) -> Result<(), String> {
+    if argv.iter().skip(1).any(|arg| arg.contains(placeholder)) {
+        Ok(())
+    } else {
+        Err(format!("engines.{name}.{key} must include {placeholder}"))
+    }
+}
+
+fn required_pointer(name: &str, definition: &Table, key: &str) -> Result<String, String> {
+    let pointer = definition
+        .get(key)
+        .and_then(|value| value.as_str())
+        .ok_or_else(|| format!("engines.{name}.{key} must be a JSON pointer string"))?;
+
+    if !pointer.starts_with('/') {
+        return Err(format!(
+            "engines.{name}.{key} must be a JSON pointer starting with '/'"
+        ));
+    }
+    Ok(pointer.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::TestDir;
+
+    const SAMPLE_CONFIG: &str = r#"
+[engines.mytool]
+launch = ["mytool", "--interactive", "{prompt}"]
+exec = ["mytool", "run", "--json", "{prompt}"]
+exec_resume = ["mytool", "run", "--json", "--resume", "{resume_id}", "{prompt}"]
+text_pointer = "/output/text"
+resume_id_pointer = "/conversation"
+
+[engines.mytool.env]
+MYTOOL_SANDBOX = "1"
+"#;
+
+    fn command_args(command: &Command) -> Vec<String> {
+        command
+            .get_args()
+            .map(|arg| arg.to_string_lossy().into_owned())
+            .collect()
+    }
+
+    fn sample_engine() -> CustomEngine {
+        parse_engine_config(SAMPLE_CONFIG)
+            .expect("sample config should parse")
+            .remove(0)
+    }
+
+    #[test]
+    fn parses_engine_definitions() {
+        let engines = parse_engine_config(SAMPLE_CONFIG).expect("sample config should parse");
+        assert_eq!(
+            engines,
+            vec![CustomEngine {
+                name: "mytool".to_string(),
+                launch: vec![
+                    "mytool".to_string(),
+                    "--interactive".to_string(),
+                    "{prompt}".to_string(),
+                ],
+                exec: vec![
+                    "mytool".to_string(),
+                    "run".to_string(),
+                    "--json".to_string(),
+                    "{prompt}".to_string(),
+                ],
+                exec_resume: Some(vec![
+                    "mytool".to_string(),
+                    "run".to_string(),
+                    "--json".to_string(),
+                    "--resume".to_string(),
+                    "{resume_id}".to_string(),
+                    "{prompt}".to_string(),
+                ]),
+                env: vec![("MYTOOL_SANDBOX".to_string(), "1".to_string())],
+                text_pointer: "/output/text".to_string(),
+                resume_id_pointer: "/conversation".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn resolve_engine_prefers_builtins_and_loads_config_engines() {
+        let temp = TestDir::new("resolve");
+        fs::create_dir_all(temp.path().join(".juliet")).expect("state root should be created");
+
+        assert_eq!(
+            resolve_engine(temp.path(), "codex")
+                .expect("builtin should resolve without config")
+                .name(),
+            "codex"
+        );
+        assert_eq!(
+            resolve_engine(temp.path(), "mytool").err(),
+            Some("Unknown engine: mytool. Available engines: claude, codex".to_string())
+        );
+
+        fs::write(role_state::engines_config_path(temp.path()), SAMPLE_CONFIG)
+            .expect("engine config should be written");
+        assert_eq!(
+            resolve_engine(temp.path(), "mytool")
+                .expect("config engine should resolve")
+                .name(),
+            "mytool"
+        );
+        assert_eq!(
+            resolve_engine(temp.path(), "other").err(),
+            Some("Unknown engine: other. Available engines: claude, codex, mytool".to_string())
+        );
+    }
+
+    #[test]
+    fn empty_config_declares_no_engines() {
+        assert_eq!(parse_engine_config(""), Ok(Vec::new()));
+    }
+
+    #[test]
+    fn renders_templates_with_prompt_resume_id_and_env() {
+        let engine = sample_engine();
+
+        let launch = engine.launch_command("hello world");
+        assert_eq!(launch.get_program(), "mytool");
+        assert_eq!(command_args(&launch), vec!["--interactive", "hello world"]);
+        assert!(launch
+            .get_envs()
+            .any(|(key, value)| key == "MYTOOL_SANDBOX" && value == Some("1".as_ref())));
+
+        let exec = engine.exec_command("hello", None);
+        assert_eq!(command_args(&exec), vec!["run", "--json", "hello"]);
+
+        let resumed = engine.exec_command("hello", Some("conv-9"));
+        assert_eq!(
+            command_args(&resumed),
+            vec!["run", "--json", "--resume", "conv-9", "hello"]
+        );
+    }
+
+    #[test]
+    fn does_not_substitute_placeholders_inside_the_prompt() {
+        let engine = sample_engine();
+
+        let resumed = engine.exec_command("keep {resume_id} and {prompt} literal", Some("conv-9"));
+        assert_eq!(
+            command_args(&resumed),
+            vec![
+                "run",
+                "--json",
+                "--resume",
+                "conv-9",
+                "keep {resume_id} and {prompt} literal"
+            ]
+        );
+        assert_eq!(
+            substitute_placeholders("--id={resume_id}:{prompt}{", "p", Some("r")),
+            "--id=r:p{"
+        );
+        assert_eq!(
+            substitute_placeholders("{resume_id}", "p", None),
+            "{resume_id}"
+        );
+    }
+
+    #[test]
+    fn parses_exec_output_with_configured_pointers() {
+        let engine = sample_engine();
+        let parsed = engine
+            .parse_exec_output(
+                "{\"conversation\":\"conv-1\"}\n{\"output\":{\"text\":\"partial\"}}\n{\"output\":{\"text\":\"final\"}}\n",
+            )
+            .expect("output should parse");
+        assert_eq!(
+            parsed,
+            ExecResult {
+                text: "final".to_string(),
+                resume_id: "conv-1".to_string(),
+            }
+        );
+
+        assert_eq!(
+            engine.parse_exec_output("{\"output\":{\"text\":\"final\"}}"),
+            Err("mytool JSON output did not include /conversation".to_string())
+        );
+        assert_eq!(
+            engine.parse_exec_output("plain text"),
+            Err("mytool returned no parseable JSON output".to_string())
+        );
+    }
+
+    #[test]
+    fn parses_exec_fallback_default() {
+        assert_eq!(parse_exec_fallback(SAMPLE_CONFIG), Ok(Vec::new()));
+        assert_eq!(
+            parse_exec_fallback("[exec]\nengine_fallback = [\"codex\", \"claude\"]\n"),
+            Ok(vec!["codex".to_string(), "claude".to_string()])
+        );
+        assert_eq!(
+            parse_exec_fallback("[exec]\nengine_fallback = \"codex\"\n"),
+            Err("exec.engine_fallback must be an array of engine names".to_string())
+        );
+        assert_eq!(
+            parse_exec_fallback("[exec]\nengine_fallback = [\"Codex\"]\n"),
+            Err("exec.engine_fallback must be an array of engine names".to_string())
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_definitions() {
+        for (config, expected) in [
+            (
+                "[engines.claude]\nlaunch = [\"x\", \"{prompt}\"]",
+                "engine name claude is reserved for the built-in engine",
+            ),
+            (
+                "[engines.My_Tool]\nlaunch = [\"x\", \"{prompt}\"]",
+                "invalid engine name: My_Tool. Use lowercase letters, numbers, and hyphens.",
+            ),
+            (
+                "[engines.mytool]\nexec = [\"x\", \"{prompt}\"]",
+                "engines.mytool.launch must be a non-empty array of strings",
+            ),
+            (
+                "[engines.mytool]\nlaunch = [\"x\"]",
+                "engines.mytool.launch must include {prompt}",
+            ),
+            (
+                "[engines.mytool]\nlaunch = [\"x\", \"{prompt}\"]\nexec = [\"x\", \"{prompt}\"]\nexec_resume = [\"x\", \"{prompt}\"]",
+                "engines.mytool.exec_resume must include {resume_id}",
+            ),
+            (
+                "[engines.mytool]\nlaunch = [\"x\", \"{prompt}\"]\nexec = [\"x\", \"{prompt}\"]\ntext_pointer = \"result\"",
+                "engines.mytool.text_pointer must be a JSON pointer starting with '/'",
+            ),
+        ] {
+            assert_eq!(parse_engine_config(config), Err(expected.to_string()));
+        }
+    }
+}
@@ -1,64 +1,153 @@
-use clap::{error::ErrorKind, Args, CommandFactory, Parser, Subcommand, ValueEnum};
-use serde_json::{json, Value};
+use clap::{error::ErrorKind, Args, CommandFactory, Parser, Subcommand};
+use engine::{Engine, ExecResult, StreamEvent};
+use serde_json::json;
+use std::collections::HashMap;
 use std::env;
 use std::fs;
-use std::io;
-use std::path::Path;
-use std::process::{Command, Output};
-
+use std::io::{self, IsTerminal, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::thread;
+use std::time::Duration;
+
+mod archive;
+mod digest;
+mod doctor;
+mod engine;
+mod engine_config;
+mod process_check;
+mod prompt_layers;
+mod prompt_lint;
+mod prompt_merge;
+mod prompt_vars;
 mod role_name;
 mod role_state;
+mod role_status;
+mod roles;
+mod sessions;
+mod state_files;
+mod supervisor;
+mod swarm_run;
+mod swarm_sprints;
+mod swarm_tasks;
+mod table;
+mod templates;
+#[cfg(test)]
+mod test_support;
+mod timestamp;
+mod watch;
+mod worktrees;
 
-const DEFAULT_PROMPT_SEED: &str = include_str!("prompts/juliet.md");
+const DEFAULT_PROMPT_SEED: &str = templates::DEFAULT_SEED;
 const NO_ROLES_CONFIGURED_ERROR: &str = "No roles configured. Run: juliet init --project <name>";
 const MULTIPLE_ROLES_FOUND_ERROR: &str = "Multiple roles found. Specify one with --project <name>:";
+const PROCESSES_CHANGED_EXIT_CODE: i32 = 2;
 const OPERATOR_PLACEHOLDER: &str =
     "<!-- TODO: Replace with role-specific instructions and expected operator input. -->";
 
-#[derive(Clone, Copy, Debug, Eq, PartialEq, ValueEnum)]
-enum Engine {
-    Claude,
-    Codex,
-}
-
-impl Engine {
-    fn as_str(self) -> &'static str {
-        match self {
-            Engine::Claude => "claude",
-            Engine::Codex => "codex",
-        }
-    }
-}
-
 #[derive(Debug, Eq, PartialEq)]
 enum CliCommand {
     Init {
         role_name: String,
+        template: Option<String>,
+        from: Option<PathBuf>,
     },
     Launch {
         role_name: Option<String>,
-        engine: Engine,
+        engine: String,
         operator_input: Option<String>,
+        timeout: Option<Duration>,
     },
     ResetPrompt {
         role_name: String,
     },
+    UpgradePrompt {
+        role_name: String,
+    },
+    SyncTemplate {
+        role_name: String,
+    },
+    Run {
+        role_name: String,
+        request: swarm_run::RunRequest,
+    },
+    LintPrompt {
+        role_name: String,
+    },
     ClearHistory {
         role_name: String,
+        undo: bool,
     },
     Exec {
         role_name: Option<String>,
-        engine: Engine,
+        engine: String,
         message: String,
         continue_id: Option<String>,
+        continue_last: bool,
         json_output: bool,
+        stream_output: bool,
+        timeout: Option<Duration>,
+        engine_fallback: Vec<String>,
+    },
+    Status {
+        role_name: Option<String>,
+        json_output: bool,
+    },
+    ProcessesCheck {
+        role_name: Option<String>,
+    },
+    Tasks {
+        swarm_project: String,
+        json_output: bool,
+    },
+    TasksCheck {
+        swarm_project: String,
+    },
+    Sprints {
+        swarm_project: Option<String>,
+    },
+    WorktreesList,
+    WorktreesPrune {
+        dry_run: bool,
+    },
+    Watch {
+        role_name: Option<String>,
+        engine: String,
+        interval_secs: u64,
+        once: bool,
+    },
+    SessionsList {
+        role_name: Option<String>,
+    },
+    SessionsShow {
+        role_name: Option<String>,
+        resume_id: String,
+    },
+    Doctor {
+        role_name: Option<String>,
+    },
+    Archive {
+        role_name: String,
+        keep: bool,
+    },
+    Restore {
+        role_name: String,
+        archive_name: Option<String>,
+    },
+    RolesList,
+    TemplatesList,
+    RolesRename {
+        from: String,
+        to: String,
+    },
+    RolesClone {
+        source: String,
+        target: String,
+    },
+    RolesDelete {
+        role_name: String,
+        force: bool,
     },
-}
-
-#[derive(Debug, Eq, PartialEq)]
-struct ExecResult {
-    text: String,
-    resume_id: String,
 }
 
 #[derive(Debug)]
@@ -66,6 +155,36 @@ struct EngineOutput {
     status_code: i32,
     stdout: String,
     stderr: String,
+    timed_out: bool,
+}
+
+/// How `juliet exec` reports a turn on stdout.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum ExecOutputMode {
+    Text,
+    Json,
+    Stream,
+}
+
+/// A `juliet exec` request as given on the command line.
+#[derive(Clone, Copy, Debug)]
+struct ExecRequest<'a> {
+    role_name: Option<&'a str>,
+    message: &'a str,
+    continue_id: Option<&'a str>,
+    continue_last: bool,
+    output_mode: ExecOutputMode,
+}
+
+/// One exec turn for a resolved role: the staged prompt, the operator's message, and how the
+/// result is reported.
+#[derive(Clone, Copy, Debug)]
+struct ExecTurn<'a> {
+    role_name: &'a str,
+    prompt: &'a str,
+    message: &'a str,
+    continue_id: Option<&'a str>,
+    output_mode: ExecOutputMode,
 }
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
@@ -86,6 +205,39 @@ struct ProjectArgs {
     role_name: String,
 }
 
+#[derive(Debug, Args)]
+struct InitArgs {
+    /// Role name to target.
+    #[arg(
+        long = "project",
+        visible_alias = "role",
+        value_name = "ROLE_NAME",
+        allow_hyphen_values = true
+    )]
+    role_name: String,
+    /// Template to seed the prompt from (see `juliet templates list`).
+    #[arg(long = "template", value_name = "TEMPLATE")]
+    template: Option<String>,
+    /// Copy the prompt from a prompt file or role directory and remember it for sync-template.
+    #[arg(long = "from", value_name = "PATH", conflicts_with = "template")]
+    from: Option<PathBuf>,
+}
+
+#[derive(Debug, Args)]
+struct ClearHistoryArgs {
+    /// Role name to target.
+    #[arg(
+        long = "project",
+        visible_alias = "role",
+        value_name = "ROLE_NAME",
+        allow_hyphen_values = true
+    )]
+    role_name: String,
+    /// Restore the snapshot taken by the most recent clear-history.
+    #[arg(long = "undo")]
+    undo: bool,
+}
+
 #[derive(Debug, Args)]
 struct ExecArgs {
     /// Role name to target. If omitted, Juliet auto-selects when exactly one role exists.
@@ -99,11 +251,30 @@ struct ExecArgs {
     /// Continue a prior non-interactive thread/session id.
     #[arg(long = "continue", value_name = "RESUME_ID")]
     continue_id: Option<String>,
+    /// Continue the most recent recorded session for this role and engine.
+    #[arg(long = "continue-last", conflicts_with = "continue_id")]
+    continue_last: bool,
     /// Emit normalized JSON output for this exec turn.
     #[arg(long = "json")]
     json_output: bool,
-    /// Engine to execute.
-    engine: Engine,
+    /// Stream normalized NDJSON events while the turn runs, ending with the final result.
+    #[arg(long = "stream", conflicts_with = "json_output")]
+    stream_output: bool,
+    /// Stop the engine after this long (e.g. 90s, 5m, 1h30m) and exit 124.
+    #[arg(long = "timeout", value_name = "DURATION", value_parser = supervisor::parse_duration)]
+    timeout: Option<Duration>,
+    /// Engines to retry, in order, when the engine is missing, fails to start, or returns
+    /// unparseable output. Defaults to `[exec] engine_fallback` in .juliet/engines.toml.
+    #[arg(
+        long = "engine-fallback",
+        value_name = "ENGINES",
+        value_delimiter = ',',
+        value_parser = parse_engine_name
+    )]
+    engine_fallback: Vec<String>,
+    /// Engine to execute (claude, codex, or one declared in .juliet/engines.toml).
+    #[arg(value_parser = parse_engine_name)]
+    engine: String,
     /// Message text appended to the prompt as user input.
     #[arg(
         required = true,
@@ -113,6 +284,297 @@ struct ExecArgs {
     message: Vec<String>,
 }
 
+#[derive(Debug, Args)]
+struct OptionalProjectArgs {
+    /// Role name to target. If omitted, Juliet auto-selects when exactly one role exists.
+    #[arg(
+        long = "project",
+        visible_alias = "role",
+        value_name = "ROLE_NAME",
+        allow_hyphen_values = true
+    )]
+    role_name: Option<String>,
+}
+
+#[derive(Debug, Args)]
+struct RunArgs {
+    /// Role name to target.
+    #[arg(
+        long = "project",
+        visible_alias = "role",
+        value_name = "ROLE_NAME",
+        allow_hyphen_values = true
+    )]
+    role_name: String,
+    /// Swarm project under .swarm-hug/ to run.
+    #[arg(long = "swarm-project", value_name = "PROJECT")]
+    swarm_project: String,
+    /// Number of runs to launch, each on its own -tryN target branch when more than one.
+    #[arg(
+        long = "variations",
+        value_name = "N",
+        default_value_t = 1,
+        value_parser = clap::value_parser!(u32).range(1..)
+    )]
+    variations: u32,
+    /// Maximum sprints per run.
+    #[arg(
+        long = "sprints",
+        value_name = "M",
+        value_parser = clap::value_parser!(u32).range(1..)
+    )]
+    sprints: u32,
+    /// Engine for swarm to use. Defaults to default_engine in session.md.
+    #[arg(long = "engine", value_name = "ENGINE")]
+    engine: Option<String>,
+    /// Branch to fork from. Defaults to the checked-out branch.
+    #[arg(long = "source-branch", value_name = "BRANCH")]
+    source_branch: Option<String>,
+    /// Target branch. Defaults to feature/<PROJECT>.
+    #[arg(long = "target-branch", value_name = "BRANCH")]
+    target_branch: Option<String>,
+}
+
+#[derive(Debug, Args)]
+#[command(args_conflicts_with_subcommands = true)]
+struct TasksArgs {
+    #[command(subcommand)]
+    command: Option<TasksSubcommand>,
+    /// Swarm project under .swarm-hug/ to report.
+    #[arg(long = "swarm-project", value_name = "PROJECT")]
+    swarm_project: Option<String>,
+    /// Emit machine-readable JSON instead of a summary.
+    #[arg(long = "json")]
+    json_output: bool,
+}
+
+#[derive(Debug, Subcommand)]
+enum TasksSubcommand {
+    /// Flag a scaffold tasks.md, placeholder tasks, empty sections, and missing point estimates.
+    #[command(
+        about = "Flag a scaffold tasks.md, placeholder tasks, empty sections, and missing point estimates",
+        long_about = None
+    )]
+    Check(SwarmProjectArgs),
+}
+
+#[derive(Debug, Args)]
+struct SwarmProjectArgs {
+    /// Swarm project under .swarm-hug/ to check.
+    #[arg(long = "swarm-project", value_name = "PROJECT")]
+    swarm_project: String,
+}
+
+#[derive(Debug, Args)]
+struct SprintsArgs {
+    /// Swarm project under .swarm-hug/ to report. If omitted, Juliet reports every project.
+    #[arg(long = "swarm-project", value_name = "PROJECT")]
+    swarm_project: Option<String>,
+}
+
+#[derive(Debug, Args)]
+struct ProcessesArgs {
+    #[command(subcommand)]
+    command: ProcessesSubcommand,
+}
+
+#[derive(Debug, Subcommand)]
+enum ProcessesSubcommand {
+    /// Probe active swarm runs and move exited ones to Completed.
+    #[command(
+        about = "Probe active swarm runs and move exited ones to Completed",
+        long_about = None
+    )]
+    Check(OptionalProjectArgs),
+}
+
+#[derive(Debug, Args)]
+struct SessionsArgs {
+    #[command(subcommand)]
+    command: SessionsSubcommand,
+}
+
+#[derive(Debug, Args)]
+struct SessionsShowArgs {
+    /// Role name to target. If omitted, Juliet auto-selects when exactly one role exists.
+    #[arg(
+        long = "project",
+        visible_alias = "role",
+        value_name = "ROLE_NAME",
+        allow_hyphen_values = true
+    )]
+    role_name: Option<String>,
+    /// Resume id to show.
+    #[arg(value_name = "RESUME_ID")]
+    resume_id: String,
+}
+
+#[derive(Debug, Subcommand)]
+enum SessionsSubcommand {
+    /// List recorded exec sessions for a role.
+    #[command(about = "List recorded exec sessions for a role", long_about = None)]
+    List(OptionalProjectArgs),
+    /// Show every recorded turn of one exec session.
+    #[command(about = "Show every recorded turn of one exec session", long_about = None)]
+    Show(SessionsShowArgs),
+}
+
+#[derive(Debug, Args)]
+struct ArchiveArgs {
+    /// Role name to archive.
+    #[arg(
+        long = "project",
+        visible_alias = "role",
+        value_name = "ROLE_NAME",
+        allow_hyphen_values = true
+    )]
+    role_name: String,
+    /// Keep the role in place after writing the archive.
+    #[arg(long = "keep")]
+    keep: bool,
+}
+
+#[derive(Debug, Args)]
+struct RestoreArgs {
+    /// Role name to restore.
+    #[arg(
+        long = "project",
+        visible_alias = "role",
+        value_name = "ROLE_NAME",
+        allow_hyphen_values = true
+    )]
+    role_name: String,
+    /// Archive file name in .juliet/.archive. Defaults to the role's most recent archive.
+    #[arg(value_name = "ARCHIVE")]
+    archive_name: Option<String>,
+}
+
+#[derive(Debug, Args)]
+struct TemplatesArgs {
+    #[command(subcommand)]
+    command: TemplatesSubcommand,
+}
+
+#[derive(Debug, Subcommand)]
+enum TemplatesSubcommand {
+    /// List built-in and user prompt templates.
+    #[command(about = "List built-in and user prompt templates", long_about = None)]
+    List,
+}
+
+#[derive(Debug, Args)]
+struct WorktreesArgs {
+    #[command(subcommand)]
+    command: WorktreesSubcommand,
+}
+
+#[derive(Debug, Subcommand)]
+enum WorktreesSubcommand {
+    /// List swarm worktrees with their branch, role, process, and orphan status.
+    #[command(
+        about = "List swarm worktrees with their branch, role, process, and orphan status",
+        long_about = None
+    )]
+    List,
+    /// Remove worktrees whose branch was merged or deleted. Squash merges are not detected.
+    #[command(
+        about = "Remove worktrees whose branch was merged or deleted. Squash merges are not detected",
+        long_about = None
+    )]
+    Prune {
+        /// Print what would be removed without removing anything.
+        #[arg(long = "dry-run")]
+        dry_run: bool,
+    },
+}
+
+#[derive(Debug, Args)]
+struct RolesArgs {
+    #[command(subcommand)]
+    command: RolesSubcommand,
+}
+
+#[derive(Debug, Subcommand)]
+enum RolesSubcommand {
+    /// List configured roles with prompt, project, and process counts.
+    #[command(
+        about = "List configured roles with prompt, project, and process counts",
+        long_about = None
+    )]
+    List,
+    /// Rename a role, moving its state and retitling its prompt.
+    #[command(about = "Rename a role, moving its state and retitling its prompt", long_about = None)]
+    Rename {
+        /// Existing role name.
+        #[arg(value_name = "ROLE_NAME")]
+        from: String,
+        /// New role name.
+        #[arg(value_name = "NEW_NAME")]
+        to: String,
+    },
+    /// Create a role from another role's prompt with fresh state.
+    #[command(about = "Create a role from another role's prompt with fresh state", long_about = None)]
+    Clone {
+        /// Role whose prompt is copied.
+        #[arg(value_name = "ROLE_NAME")]
+        source: String,
+        /// Name of the new role.
+        #[arg(value_name = "NEW_NAME")]
+        target: String,
+    },
+    /// Delete a role and all of its state.
+    #[command(about = "Delete a role and all of its state", long_about = None)]
+    Delete {
+        /// Role to delete.
+        #[arg(value_name = "ROLE_NAME")]
+        role_name: String,
+        /// Skip the confirmation prompt.
+        #[arg(long = "force")]
+        force: bool,
+    },
+}
+
+#[derive(Debug, Args)]
+struct WatchArgs {
+    /// Role name to watch. If omitted, Juliet auto-selects when exactly one role exists.
+    #[arg(
+        long = "project",
+        visible_alias = "role",
+        value_name = "ROLE_NAME",
+        allow_hyphen_values = true
+    )]
+    role_name: Option<String>,
+    /// Seconds to wait between polls.
+    #[arg(
+        long = "interval",
+        value_name = "SECONDS",
+        default_value_t = 30,
+        value_parser = clap::value_parser!(u64).range(1..)
+    )]
+    interval_secs: u64,
+    /// Poll once, run a turn if anything changed, and exit.
+    #[arg(long = "once")]
+    once: bool,
+    /// Engine to wake (claude, codex, or one declared in .juliet/engines.toml).
+    #[arg(value_parser = parse_engine_name)]
+    engine: String,
+}
+
+#[derive(Debug, Args)]
+struct StatusArgs {
+    /// Role name to report. If omitted, Juliet reports every configured role.
+    #[arg(
+        long = "project",
+        visible_alias = "role",
+        value_name = "ROLE_NAME",
+        allow_hyphen_values = true
+    )]
+    role_name: Option<String>,
+    /// Emit machine-readable JSON instead of a table.
+    #[arg(long = "json")]
+    json_output: bool,
+}
+
 #[derive(Debug, Parser)]
 #[command(
     name = "juliet",
@@ -132,8 +594,13 @@ struct JulietCli {
         allow_hyphen_values = true
     )]
     role_name: Option<String>,
-    /// Engine to launch in interactive mode.
-    engine: Option<Engine>,
+    /// Stop the launched engine and the processes it started after this long (e.g. 90s, 5m,
+    /// 1h30m) and exit 124.
+    #[arg(long = "timeout", value_name = "DURATION", value_parser = supervisor::parse_duration)]
+    timeout: Option<Duration>,
+    /// Engine to launch in interactive mode (claude, codex, or one declared in .juliet/engines.toml).
+    #[arg(value_parser = parse_engine_name)]
+    engine: Option<String>,
     /// Optional operator input appended to the launch prompt.
     #[arg(
         num_args = 0..,
@@ -148,51 +615,240 @@ struct JulietCli {
 enum JulietSubcommand {
     /// Initialize a new role scaffold.
     #[command(about = "Initialize a new role scaffold", long_about = None)]
-    Init(ProjectArgs),
+    Init(InitArgs),
     /// Reset a role prompt to the default template.
     #[command(name = "reset-prompt")]
     #[command(about = "Reset a role prompt to the default template", long_about = None)]
     ResetPrompt(ProjectArgs),
+    /// Merge a newer default seed into a role prompt, keeping local edits.
+    #[command(name = "upgrade-prompt")]
+    #[command(
+        about = "Merge a newer default seed into a role prompt, keeping local edits",
+        long_about = None
+    )]
+    UpgradePrompt(ProjectArgs),
+    /// Check a role prompt against the heading, state file, and exact phrase contracts.
+    #[command(name = "lint-prompt")]
+    #[command(
+        about = "Check a role prompt against the heading, state file, and exact phrase contracts",
+        long_about = None
+    )]
+    LintPrompt(ProjectArgs),
+    /// Merge changes from the path a role was initialized --from into its prompt.
+    #[command(name = "sync-template")]
+    #[command(
+        about = "Merge changes from the path a role was initialized --from into its prompt",
+        long_about = None
+    )]
+    SyncTemplate(ProjectArgs),
     /// Clear role state/history while preserving prompt customization.
     #[command(name = "clear-history")]
     #[command(
         about = "Clear role state/history while preserving prompt customization",
         long_about = None
     )]
-    ClearHistory(ProjectArgs),
+    ClearHistory(ClearHistoryArgs),
     /// Execute a single non-interactive turn.
     #[command(about = "Execute a single non-interactive turn", long_about = None)]
     Exec(ExecArgs),
-}
-
-fn parse_with_clap<P>(args: &[String]) -> Result<P, clap::Error>
-where
-    P: Parser,
-{
-    let mut argv = Vec::with_capacity(args.len() + 1);
-    argv.push("juliet".to_string());
-    argv.extend(args.iter().cloned());
-    P::try_parse_from(argv)
-}
-
-fn parse_cli_command(args: &[String]) -> Result<CliCommand, clap::Error> {
-    let parsed = parse_with_clap::<JulietCli>(args)?;
-    match parsed.command {
-        Some(JulietSubcommand::Init(project)) => Ok(CliCommand::Init {
-            role_name: project.role_name,
-        }),
-        Some(JulietSubcommand::ResetPrompt(project)) => Ok(CliCommand::ResetPrompt {
+    /// Report pending needs, processes, projects, and session state for roles.
+    #[command(
+        about = "Report pending needs, processes, projects, and session state for roles",
+        long_about = None
+    )]
+    Status(StatusArgs),
+    /// Launch swarm sprints in the background and record them in processes.md.
+    #[command(
+        about = "Launch swarm sprints in the background and record them in processes.md",
+        long_about = None
+    )]
+    Run(RunArgs),
+    /// Report points, blockers, assignees, and dependencies from a swarm project's tasks.md.
+    #[command(
+        about = "Report points, blockers, assignees, and dependencies from a swarm project's tasks.md",
+        long_about = None
+    )]
+    Tasks(TasksArgs),
+    /// Report sprint counts, feature branches, launching roles, and task progress for swarm projects.
+    #[command(
+        about = "Report sprint counts, feature branches, launching roles, and task progress for swarm projects",
+        long_about = None
+    )]
+    Sprints(SprintsArgs),
+    /// List swarm worktrees under .swarm-hug/.shared/worktrees and prune orphaned ones.
+    #[command(
+        about = "List swarm worktrees under .swarm-hug/.shared/worktrees and prune orphaned ones",
+        long_about = None
+    )]
+    Worktrees(WorktreesArgs),
+    /// Inspect and update swarm runs recorded in processes.md.
+    #[command(about = "Inspect and update swarm runs recorded in processes.md", long_about = None)]
+    Processes(ProcessesArgs),
+    /// Poll a role and run an exec turn only when something changed.
+    #[command(
+        about = "Poll a role and run an exec turn only when something changed",
+        long_about = None
+    )]
+    Watch(WatchArgs),
+    /// Inspect exec sessions recorded for a role.
+    #[command(about = "Inspect exec sessions recorded for a role", long_about = None)]
+    Sessions(SessionsArgs),
+    /// Probe engines, swarm, and repository state, then cache the results in session.md.
+    #[command(
+        about = "Probe engines, swarm, and repository state, then cache the results in session.md",
+        long_about = None
+    )]
+    Doctor(OptionalProjectArgs),
+    /// Pack a role's prompt, state, and artifacts into .juliet/.archive and remove the role.
+    #[command(
+        about = "Pack a role's prompt, state, and artifacts into .juliet/.archive and remove the role",
+        long_about = None
+    )]
+    Archive(ArchiveArgs),
+    /// Re-create a role from an archive in .juliet/.archive.
+    #[command(about = "Re-create a role from an archive in .juliet/.archive", long_about = None)]
+    Restore(RestoreArgs),
+    /// List, rename, clone, or delete roles.
+    #[command(about = "List, rename, clone, or delete roles", long_about = None)]
+    Roles(RolesArgs),
+    /// List prompt templates available to init --template.
+    #[command(about = "List prompt templates available to init --template", long_about = None)]
+    Templates(TemplatesArgs),
+}
+
+fn parse_engine_name(value: &str) -> Result<String, String> {
+    if role_name::is_valid_role_name(value) {
+        Ok(value.to_string())
+    } else {
+        Err("engine names use lowercase letters, numbers, and hyphens".to_string())
+    }
+}
+
+fn parse_with_clap<P>(args: &[String]) -> Result<P, clap::Error>
+where
+    P: Parser,
+{
+    let mut argv = Vec::with_capacity(args.len() + 1);
+    argv.push("juliet".to_string());
+    argv.extend(args.iter().cloned());
+    P::try_parse_from(argv)
+}
+
+fn parse_cli_command(args: &[String]) -> Result<CliCommand, clap::Error> {
+    let parsed = parse_with_clap::<JulietCli>(args)?;
+    match parsed.command {
+        Some(JulietSubcommand::Init(init)) => Ok(CliCommand::Init {
+            role_name: init.role_name,
+            template: init.template,
+            from: init.from,
+        }),
+        Some(JulietSubcommand::ResetPrompt(project)) => Ok(CliCommand::ResetPrompt {
+            role_name: project.role_name,
+        }),
+        Some(JulietSubcommand::UpgradePrompt(project)) => Ok(CliCommand::UpgradePrompt {
+            role_name: project.role_name,
+        }),
+        Some(JulietSubcommand::LintPrompt(project)) => Ok(CliCommand::LintPrompt {
             role_name: project.role_name,
         }),
-        Some(JulietSubcommand::ClearHistory(project)) => Ok(CliCommand::ClearHistory {
+        Some(JulietSubcommand::SyncTemplate(project)) => Ok(CliCommand::SyncTemplate {
             role_name: project.role_name,
         }),
+        Some(JulietSubcommand::Run(run)) => Ok(CliCommand::Run {
+            role_name: run.role_name,
+            request: swarm_run::RunRequest {
+                swarm_project: run.swarm_project,
+                variations: run.variations,
+                sprints: run.sprints,
+                engine: run.engine,
+                source_branch: run.source_branch,
+                target_branch: run.target_branch,
+            },
+        }),
+        Some(JulietSubcommand::ClearHistory(clear)) => Ok(CliCommand::ClearHistory {
+            role_name: clear.role_name,
+            undo: clear.undo,
+        }),
         Some(JulietSubcommand::Exec(exec)) => Ok(CliCommand::Exec {
             role_name: exec.role_name,
             engine: exec.engine,
             message: exec.message.join(" "),
             continue_id: exec.continue_id,
+            continue_last: exec.continue_last,
             json_output: exec.json_output,
+            stream_output: exec.stream_output,
+            timeout: exec.timeout,
+            engine_fallback: exec.engine_fallback,
+        }),
+        Some(JulietSubcommand::Status(status)) => Ok(CliCommand::Status {
+            role_name: status.role_name,
+            json_output: status.json_output,
+        }),
+        Some(JulietSubcommand::Tasks(tasks)) => match tasks.command {
+            Some(TasksSubcommand::Check(check)) => Ok(CliCommand::TasksCheck {
+                swarm_project: check.swarm_project,
+            }),
+            None => {
+                let Some(swarm_project) = tasks.swarm_project else {
+                    return Err(JulietCli::command().error(
+                        ErrorKind::MissingRequiredArgument,
+                        "the following required arguments were not provided:\n  --swarm-project <PROJECT>",
+                    ));
+                };
+                Ok(CliCommand::Tasks {
+                    swarm_project,
+                    json_output: tasks.json_output,
+                })
+            }
+        },
+        Some(JulietSubcommand::Sprints(sprints)) => Ok(CliCommand::Sprints {
+            swarm_project: sprints.swarm_project,
+        }),
+        Some(JulietSubcommand::Processes(processes)) => match processes.command {
+            ProcessesSubcommand::Check(project) => Ok(CliCommand::ProcessesCheck {
+                role_name: project.role_name,
+            }),
+        },
+        Some(JulietSubcommand::Watch(watch)) => Ok(CliCommand::Watch {
+            role_name: watch.role_name,
+            engine: watch.engine,
+            interval_secs: watch.interval_secs,
+            once: watch.once,
+        }),
+        Some(JulietSubcommand::Sessions(sessions)) => match sessions.command {
+            SessionsSubcommand::List(project) => Ok(CliCommand::SessionsList {
+                role_name: project.role_name,
+            }),
+            SessionsSubcommand::Show(show) => Ok(CliCommand::SessionsShow {
+                role_name: show.role_name,
+                resume_id: show.resume_id,
+            }),
+        },
+        Some(JulietSubcommand::Doctor(project)) => Ok(CliCommand::Doctor {
+            role_name: project.role_name,
+        }),
+        Some(JulietSubcommand::Archive(archive)) => Ok(CliCommand::Archive {
+            role_name: archive.role_name,
+            keep: archive.keep,
+        }),
+        Some(JulietSubcommand::Restore(restore)) => Ok(CliCommand::Restore {
+            role_name: restore.role_name,
+            archive_name: restore.archive_name,
+        }),
+        Some(JulietSubcommand::Worktrees(worktrees)) => Ok(match worktrees.command {
+            WorktreesSubcommand::List => CliCommand::WorktreesList,
+            WorktreesSubcommand::Prune { dry_run } => CliCommand::WorktreesPrune { dry_run },
+        }),
+        Some(JulietSubcommand::Templates(templates)) => Ok(match templates.command {
+            TemplatesSubcommand::List => CliCommand::TemplatesList,
+        }),
+        Some(JulietSubcommand::Roles(roles)) => Ok(match roles.command {
+            RolesSubcommand::List => CliCommand::RolesList,
+            RolesSubcommand::Rename { from, to } => CliCommand::RolesRename { from, to },
+            RolesSubcommand::Clone { source, target } => CliCommand::RolesClone { source, target },
+            RolesSubcommand::Delete { role_name, force } => {
+                CliCommand::RolesDelete { role_name, force }
+            }
         }),
         None => {
             let Some(engine) = parsed.engine else {
@@ -205,6 +861,7 @@ fn parse_cli_command(args: &[String]) -> Result<CliCommand, clap::Error> {
                 role_name: parsed.role_name,
                 engine,
                 operator_input: parse_operator_input(&parsed.operator_input),
+                timeout: parsed.timeout,
             })
         }
     }
@@ -218,233 +875,130 @@ fn parse_operator_input(args: &[String]) -> Option<String> {
     }
 }
 
-fn run_codex(prompt: &str, cwd: &Path) -> io::Result<i32> {
-    let status = Command::new("codex")
-        .arg("--dangerously-bypass-approvals-and-sandbox")
-        .arg(prompt)
-        .current_dir(cwd)
-        .status()?;
-
-    Ok(status.code().unwrap_or(1))
-}
-
-fn run_claude(prompt: &str, cwd: &Path) -> io::Result<i32> {
-    let status = Command::new("claude")
-        .arg("--dangerously-skip-permissions")
-        .arg(prompt)
-        .env("IS_SANDBOX", "1")
-        .current_dir(cwd)
-        .status()?;
-
-    Ok(status.code().unwrap_or(1))
-}
+fn run_engine(
+    engine: &dyn Engine,
+    prompt: &str,
+    cwd: &Path,
+    timeout: Option<Duration>,
+) -> io::Result<i32> {
+    let status =
+        supervisor::run_interactive(engine.launch_command(prompt).current_dir(cwd), timeout)?;
 
-fn run_engine(engine: Engine, prompt: &str, cwd: &Path) -> io::Result<i32> {
-    match engine {
-        Engine::Claude => run_claude(prompt, cwd),
-        Engine::Codex => run_codex(prompt, cwd),
+    match status {
+        Some(status) => Ok(status.code().unwrap_or(1)),
+        None => {
+            eprintln!(
+                "engine {} timed out after {}",
+                engine.name(),
+                supervisor::format_duration(timeout.unwrap_or_default())
+            );
+            Ok(supervisor::TIMEOUT_EXIT_CODE)
+        }
     }
 }
 
-fn command_output_to_engine_output(output: Output) -> EngineOutput {
+fn captured_run_to_engine_output(run: supervisor::CapturedRun) -> EngineOutput {
     EngineOutput {
-        status_code: output.status.code().unwrap_or(1),
-        stdout: String::from_utf8_lossy(&output.stdout).into_owned(),
-        stderr: String::from_utf8_lossy(&output.stderr).into_owned(),
-    }
-}
-
-fn run_codex_exec_json(
-    prompt: &str,
-    continue_id: Option<&str>,
-    cwd: &Path,
-) -> io::Result<EngineOutput> {
-    let mut command = Command::new("codex");
-    command
-        .arg("--dangerously-bypass-approvals-and-sandbox")
-        .arg("exec");
-
-    if let Some(resume_id) = continue_id {
-        command.arg("resume").arg(resume_id);
+        status_code: run
+            .status
+            .map(|status| status.code().unwrap_or(1))
+            .unwrap_or(supervisor::TIMEOUT_EXIT_CODE),
+        stdout: run.stdout,
+        stderr: run.stderr,
+        timed_out: run.status.is_none(),
     }
-
-    let output = command
-        .arg(prompt)
-        .arg("--json")
-        .current_dir(cwd)
-        .output()?;
-    Ok(command_output_to_engine_output(output))
 }
 
-fn run_claude_exec_json(
+fn run_exec_engine(
+    engine: &dyn Engine,
     prompt: &str,
     continue_id: Option<&str>,
     cwd: &Path,
+    timeout: Option<Duration>,
 ) -> io::Result<EngineOutput> {
-    let mut command = Command::new("claude");
-    command.arg("--dangerously-skip-permissions");
-
-    if let Some(resume_id) = continue_id {
-        command.arg("--resume").arg(resume_id);
-    }
-
-    let output = command
-        .arg("-p")
-        .arg(prompt)
-        .arg("--output-format")
-        .arg("json")
-        .env("IS_SANDBOX", "1")
-        .current_dir(cwd)
-        .output()?;
-    Ok(command_output_to_engine_output(output))
+    let run = supervisor::run_captured(
+        engine.exec_command(prompt, continue_id).current_dir(cwd),
+        timeout,
+        |_| {},
+    )?;
+    Ok(captured_run_to_engine_output(run))
 }
 
-fn run_exec_engine(
-    engine: Engine,
+/// Runs `stream_exec_command`, handing each stdout line to `on_line` as soon as it arrives.
+/// The full stdout is still collected so the final result can be parsed as usual.
+fn run_stream_exec_engine<S>(
+    engine: &dyn Engine,
     prompt: &str,
     continue_id: Option<&str>,
     cwd: &Path,
-) -> io::Result<EngineOutput> {
-    match engine {
-        Engine::Claude => run_claude_exec_json(prompt, continue_id, cwd),
-        Engine::Codex => run_codex_exec_json(prompt, continue_id, cwd),
-    }
-}
-
-fn parse_json_values(raw: &str) -> Vec<Value> {
-    let mut values = Vec::new();
-    for line in raw.lines() {
-        let trimmed = line.trim();
-        if trimmed.is_empty() {
-            continue;
-        }
-        if let Ok(value) = serde_json::from_str::<Value>(trimmed) {
-            values.push(value);
-        }
-    }
-
-    if values.is_empty() {
-        let trimmed = raw.trim();
-        if !trimmed.is_empty() {
-            if let Ok(value) = serde_json::from_str::<Value>(trimmed) {
-                values.push(value);
-            }
-        }
-    }
-
-    values
-}
-
-fn extract_text_candidate(value: &Value) -> Option<String> {
-    for pointer in [
-        "/item/text",
-        "/text",
-        "/result",
-        "/output_text",
-        "/message/text",
-        "/content/0/text",
-        "/message/content/0/text",
-    ] {
-        if let Some(text) = value.pointer(pointer).and_then(Value::as_str) {
-            return Some(text.to_string());
-        }
-    }
-
-    if let Some(content) = value.get("content").and_then(Value::as_array) {
-        for item in content {
-            if let Some(text) = item.as_str() {
-                return Some(text.to_string());
-            }
-            if let Some(text) = item.get("text").and_then(Value::as_str) {
-                return Some(text.to_string());
-            }
-        }
-    }
-
-    None
+    timeout: Option<Duration>,
+    on_line: S,
+) -> io::Result<EngineOutput>
+where
+    S: FnMut(&str),
+{
+    let run = supervisor::run_captured(
+        engine
+            .stream_exec_command(prompt, continue_id)
+            .current_dir(cwd),
+        timeout,
+        on_line,
+    )?;
+    Ok(captured_run_to_engine_output(run))
 }
 
-fn parse_codex_exec_result(raw_stdout: &str) -> Result<ExecResult, String> {
-    let values = parse_json_values(raw_stdout);
-    if values.is_empty() {
-        return Err("codex returned no parseable JSON output".to_string());
-    }
-
-    let mut resume_id = None;
-    let mut text = None;
-    for value in &values {
-        if resume_id.is_none() {
-            resume_id = value
-                .get("thread_id")
-                .and_then(Value::as_str)
-                .map(|id| id.to_string());
-        }
-
-        if value.get("type").and_then(Value::as_str) == Some("item.completed") {
-            if let Some(item_text) = value.pointer("/item/text").and_then(Value::as_str) {
-                text = Some(item_text.to_string());
-            }
-        }
-    }
-
-    if text.is_none() {
-        for value in &values {
-            if let Some(candidate) = extract_text_candidate(value) {
-                text = Some(candidate);
-            }
-        }
-    }
-
-    let resume_id =
-        resume_id.ok_or_else(|| "codex JSON output did not include thread_id".to_string())?;
-    Ok(ExecResult {
-        text: text.unwrap_or_default(),
-        resume_id,
-    })
+/// Saves whatever a timed-out exec turn printed so the hang can be diagnosed later.
+fn save_timeout_artifact(
+    project_root: &Path,
+    role_name: &str,
+    engine_name: &str,
+    engine_output: &EngineOutput,
+) -> io::Result<PathBuf> {
+    let artifacts_dir = role_state::artifacts_dir(project_root, role_name);
+    fs::create_dir_all(&artifacts_dir)?;
+
+    let stamp = timestamp::now_utc().replace(['-', ':'], "");
+    let path = artifacts_dir.join(format!("exec-timeout-{engine_name}-{stamp}.log"));
+    fs::write(
+        &path,
+        format!(
+            "# {engine_name} exec timed out\n\n## stdout\n{}\n## stderr\n{}",
+            engine_output.stdout, engine_output.stderr
+        ),
+    )?;
+    Ok(path)
 }
 
-fn parse_claude_exec_result(raw_stdout: &str) -> Result<ExecResult, String> {
-    let values = parse_json_values(raw_stdout);
-    if values.is_empty() {
-        return Err("claude returned no parseable JSON output".to_string());
-    }
-
-    let mut resume_id = None;
-    let mut text = None;
-    for value in &values {
-        if resume_id.is_none() {
-            resume_id = value
-                .get("session_id")
-                .and_then(Value::as_str)
-                .map(|id| id.to_string());
-        }
-        if text.is_none() {
-            text = extract_text_candidate(value);
-        } else if let Some(candidate) = extract_text_candidate(value) {
-            text = Some(candidate);
-        }
-    }
-
-    let resume_id =
-        resume_id.ok_or_else(|| "claude JSON output did not include session_id".to_string())?;
-    Ok(ExecResult {
-        text: text.unwrap_or_default(),
-        resume_id,
+fn format_exec_result_json(engine: &dyn Engine, result: &ExecResult) -> String {
+    json!({
+        "text": result.text,
+        "resume_id": result.resume_id,
+        "engine": engine.name(),
     })
+    .to_string()
 }
 
-fn parse_exec_result(engine: Engine, raw_stdout: &str) -> Result<ExecResult, String> {
-    match engine {
-        Engine::Claude => parse_claude_exec_result(raw_stdout),
-        Engine::Codex => parse_codex_exec_result(raw_stdout),
+fn format_stream_event_json(event: &StreamEvent) -> String {
+    match event {
+        StreamEvent::ToolCall { name, detail } => json!({
+            "type": "tool_call",
+            "name": name,
+            "detail": detail,
+        }),
+        StreamEvent::MessageDelta { text } => json!({
+            "type": "message_delta",
+            "text": text,
+        }),
     }
+    .to_string()
 }
 
-fn format_exec_result_json(engine: Engine, result: &ExecResult) -> String {
+fn format_stream_result_json(engine: &dyn Engine, result: &ExecResult) -> String {
     json!({
+        "type": "result",
         "text": result.text,
         "resume_id": result.resume_id,
-        "engine": engine.as_str(),
+        "engine": engine.name(),
     })
     .to_string()
 }
@@ -457,12 +1011,21 @@ fn build_launch_prompt(base: &str, operator_input: Option<&str>) -> String {
     }
 }
 
-fn role_prompt_template(role_name: &str, default_prompt_seed: &str) -> String {
+/// The prompt `init` and `reset-prompt` write. The seed is pulled in at runtime, so the prompt
+/// picks up seed changes without `upgrade-prompt`.
+fn role_prompt_template(role_name: &str) -> String {
     format!(
-        "# {role_name}\n\n{OPERATOR_PLACEHOLDER}\n\n## Default Prompt Seed\n\n{default_prompt_seed}"
+        "# {role_name}\n\n{OPERATOR_PLACEHOLDER}\n\n## Default Prompt Seed\n\n{}\n",
+        prompt_layers::SEED_INCLUDE
     )
 }
 
+/// The prompt `init` wrote before prompts included the seed. `upgrade-prompt` merges prompts
+/// that still hold a copy of the seed against it.
+fn copied_seed_prompt_template(role_name: &str, seed: &str) -> String {
+    format!("# {role_name}\n\n{OPERATOR_PLACEHOLDER}\n\n## Default Prompt Seed\n\n{seed}")
+}
+
 fn ensure_role_prompt_exists(
     project_root: &Path,
     role_name: &str,
@@ -487,9 +1050,10 @@ fn ensure_role_prompt_exists(
         fs::create_dir_all(parent_dir)?;
     }
 
+    fs::write(prompt_path, role_prompt_template(role_name))?;
     fs::write(
-        prompt_path,
-        role_prompt_template(role_name, default_prompt_seed),
+        role_state::prompt_seed_path(project_root, role_name),
+        default_prompt_seed,
     )
 }
 
@@ -546,31 +1110,168 @@ fn reset_prompt(
     }
 
     let prompt_path = role_state::role_prompt_path(project_root, role_name);
-    let content = role_prompt_template(role_name, default_prompt_seed);
+    let content = role_prompt_template(role_name);
     fs::write(&prompt_path, content).map_err(|err| {
         format!(
             "failed to write prompt for role {role_name} at {}: {err}",
             prompt_path.display()
         )
     })?;
+    write_prompt_seed_record(project_root, role_name, default_prompt_seed)
+}
 
-    Ok(())
+#[derive(Debug, Eq, PartialEq)]
+enum PromptUpgrade {
+    AlreadyCurrent {
+        version: String,
+    },
+    /// The prompt pulls the seed in with `{{include seed}}`, so it never goes stale.
+    IncludesSeed,
+    Upgraded {
+        from: String,
+        to: String,
+        conflicts: usize,
+    },
+}
+
+/// Short identifier for a seed text, shown to operators and in conflict markers.
+fn seed_version(seed: &str) -> String {
+    digest::fnv1a64(seed)
+}
+
+fn write_prompt_seed_record(
+    project_root: &Path,
+    role_name: &str,
+    default_prompt_seed: &str,
+) -> Result<(), String> {
+    let seed_path = role_state::prompt_seed_path(project_root, role_name);
+    fs::write(&seed_path, default_prompt_seed)
+        .map_err(|err| format!("failed to write {}: {err}", seed_path.display()))
 }
 
-fn clear_history(project_root: &Path, role_name: &str) -> Result<(), String> {
+/// Three-way merges the role's prompt with the template for `default_prompt_seed`, using the
+/// template for the recorded seed as the base. Prompts created before seeds were recorded use
+/// the text under their `## Default Prompt Seed` heading as the base seed.
+fn upgrade_prompt(
+    project_root: &Path,
+    role_name: &str,
+    default_prompt_seed: &str,
+) -> Result<PromptUpgrade, String> {
     role_name::validate_role_name(role_name)?;
 
     if !role_state::role_state_exists(project_root, role_name) {
         return Err(format!("Role '{role_name}' is not initialized."));
     }
 
-    role_state::clear_role_history(project_root, role_name)
-        .map_err(|err| format!("failed to clear history for role {role_name}: {err}"))?;
+    let prompt_path = role_state::role_prompt_path(project_root, role_name);
+    let prompt = fs::read_to_string(&prompt_path)
+        .map_err(|err| format!("failed to read {}: {err}", prompt_path.display()))?;
+    if prompt_layers::has_directive(&prompt, prompt_layers::SEED_INCLUDE) {
+        return Ok(PromptUpgrade::IncludesSeed);
+    }
+    require_no_conflict_markers(project_root, &prompt_path, &prompt)?;
+    let seed_path = role_state::prompt_seed_path(project_root, role_name);
+    let base_seed = match fs::read_to_string(&seed_path) {
+        Ok(seed) => seed,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => prompt
+            .split_once("\n## Default Prompt Seed\n\n")
+            .map(|(_, seed)| seed.to_string())
+            .ok_or_else(|| {
+                format!(
+                    "Role '{role_name}' has no recorded seed and its prompt has no '## Default Prompt Seed' section. Run: juliet reset-prompt --project {role_name}"
+                )
+            })?,
+        Err(err) => return Err(format!("failed to read {}: {err}", seed_path.display())),
+    };
 
-    Ok(())
+    let to = seed_version(default_prompt_seed);
+    if base_seed == default_prompt_seed {
+        write_prompt_seed_record(project_root, role_name, default_prompt_seed)?;
+        return Ok(PromptUpgrade::AlreadyCurrent { version: to });
+    }
+
+    let merged = prompt_merge::merge3(
+        &copied_seed_prompt_template(role_name, &base_seed),
+        &prompt,
+        &copied_seed_prompt_template(role_name, default_prompt_seed),
+        "prompt.md",
+        &format!("default seed {to}"),
+    );
+    fs::write(&prompt_path, &merged.text).map_err(|err| {
+        format!(
+            "failed to write prompt for role {role_name} at {}: {err}",
+            prompt_path.display()
+        )
+    })?;
+    write_prompt_seed_record(project_root, role_name, default_prompt_seed)?;
+
+    Ok(PromptUpgrade::Upgraded {
+        from: seed_version(&base_seed),
+        to,
+        conflicts: merged.conflicts,
+    })
+}
+
+#[derive(Debug, Eq, PartialEq)]
+enum TemplateSync {
+    AlreadyCurrent { from: PathBuf },
+    Synced { from: PathBuf, conflicts: usize },
+}
+
+/// Three-way merges what changed at the role's `init --from` path since the last sync into its
+/// prompt, using `template-base.md` as the base, and refreshes the seed record when the source
+/// has one.
+fn sync_template(project_root: &Path, role_name: &str) -> Result<TemplateSync, String> {
+    role_name::validate_role_name(role_name)?;
+
+    if !role_state::role_state_exists(project_root, role_name) {
+        return Err(format!("Role '{role_name}' is not initialized."));
+    }
+
+    let from = templates::read_template_record(project_root, role_name)?
+        .and_then(|record| record.from)
+        .ok_or_else(|| {
+            format!("Role '{role_name}' was not initialized with --from; nothing to sync.")
+        })?;
+    let label = from.display().to_string();
+    let source = templates::read_prompt_source(&project_root.join(&from))?;
+    let theirs = templates::adopt_heading(&source.prompt, role_name, &label)?;
+
+    let base_path = role_state::template_base_path(project_root, role_name);
+    let base = fs::read_to_string(&base_path)
+        .map_err(|err| format!("failed to read {}: {err}", base_path.display()))?;
+    let seed_path = role_state::prompt_seed_path(project_root, role_name);
+    let seed_changed = source
+        .seed
+        .as_ref()
+        .is_some_and(|seed| fs::read_to_string(&seed_path).ok().as_ref() != Some(seed));
+    if base == theirs && !seed_changed {
+        return Ok(TemplateSync::AlreadyCurrent { from });
+    }
+
+    let prompt_path = role_state::role_prompt_path(project_root, role_name);
+    let prompt = fs::read_to_string(&prompt_path)
+        .map_err(|err| format!("failed to read {}: {err}", prompt_path.display()))?;
+    let merged = prompt_merge::merge3(&base, &prompt, &theirs, "prompt.md", &label);
+    fs::write(&prompt_path, &merged.text).map_err(|err| {
+        format!(
+            "failed to write prompt for role {role_name} at {}: {err}",
+            prompt_path.display()
+        )
+    })?;
+    fs::write(&base_path, &theirs)
+        .map_err(|err| format!("failed to write {}: {err}", base_path.display()))?;
+    if let Some(seed) = &source.seed {
+        write_prompt_seed_record(project_root, role_name, seed)?;
+    }
+
+    Ok(TemplateSync::Synced {
+        from,
+        conflicts: merged.conflicts,
+    })
 }
 
-fn run_clear_history_command(role_name: &str) -> i32 {
+fn run_sync_template_command(role_name: &str) -> i32 {
     let cwd = match env::current_dir() {
         Ok(dir) => dir,
         Err(err) => {
@@ -579,11 +1280,29 @@ fn run_clear_history_command(role_name: &str) -> i32 {
         }
     };
 
-    match clear_history(&cwd, role_name) {
-        Ok(()) => {
-            println!("history cleared for role '{role_name}'");
+    match sync_template(&cwd, role_name) {
+        Ok(TemplateSync::AlreadyCurrent { from }) => {
+            println!(
+                "prompt for role '{role_name}' is already in sync with {}",
+                from.display()
+            );
             0
         }
+        Ok(TemplateSync::Synced { from, conflicts }) => {
+            println!(
+                "prompt for role '{role_name}' synced from {}",
+                from.display()
+            );
+            if conflicts == 0 {
+                return 0;
+            }
+            let prompt_path = role_state::role_prompt_path(&cwd, role_name);
+            eprintln!(
+                "{conflicts} conflict(s) left in {}; resolve the <<<<<<< markers before the next launch",
+                role_state::display_relative(&cwd, &prompt_path)
+            );
+            1
+        }
         Err(err) => {
             eprintln!("{err}");
             1
@@ -591,7 +1310,46 @@ fn run_clear_history_command(role_name: &str) -> i32 {
     }
 }
 
-fn run_reset_prompt_command(role_name: &str) -> i32 {
+/// Runs `git` in the project root and returns its stdout.
+fn git_output(project_root: &Path, args: &[&str]) -> Result<String, String> {
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(project_root)
+        .output()
+        .map_err(|err| format!("failed to run git {}: {err}", args.join(" ")))?;
+    if !output.status.success() {
+        return Err(format!(
+            "git {} failed: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Preflights and launches the requested swarm runs, then records them in `processes.md` and
+/// `projects.md`.
+fn launch_swarm_runs(
+    project_root: &Path,
+    role_name: &str,
+    request: &swarm_run::RunRequest,
+) -> Result<Vec<state_files::ProcessEntry>, String> {
+    resolve_role_name(project_root, Some(role_name))?;
+    let session = state_files::load_session(project_root, role_name)
+        .map_err(|err| format!("failed to read session for role {role_name}: {err}"))?;
+
+    let plan = swarm_run::plan_runs(project_root, request, &session, |args| {
+        git_output(project_root, args)
+    })?;
+    let launched = swarm_run::launch_runs(project_root, role_name, &plan, |args, log_path| {
+        swarm_run::spawn_swarm(project_root, args, log_path)
+    })?;
+    swarm_run::record_project(project_root, role_name, &plan)
+        .map_err(|err| format!("failed to write projects for role {role_name}: {err}"))?;
+    Ok(launched)
+}
+
+fn run_swarm_command(role_name: &str, request: &swarm_run::RunRequest) -> i32 {
     let cwd = match env::current_dir() {
         Ok(dir) => dir,
         Err(err) => {
@@ -600,9 +1358,17 @@ fn run_reset_prompt_command(role_name: &str) -> i32 {
         }
     };
 
-    match reset_prompt(&cwd, role_name, DEFAULT_PROMPT_SEED) {
-        Ok(()) => {
-            println!("prompt reset to default for role '{role_name}'");
+    match launch_swarm_runs(&cwd, role_name, request) {
+        Ok(launched) => {
+            for entry in &launched {
+                println!(
+                    "started {} (pid {}), log: {}",
+                    entry.target_branch.as_deref().unwrap_or_default(),
+                    entry.pid.unwrap_or_default(),
+                    entry.log_path.as_deref().unwrap_or_default()
+                );
+            }
+            println!("check on them with: juliet processes check --project {role_name}");
             0
         }
         Err(err) => {
@@ -612,7 +1378,7 @@ fn run_reset_prompt_command(role_name: &str) -> i32 {
     }
 }
 
-fn run_init_command(role_name: &str) -> i32 {
+fn run_tasks_command(swarm_project: &str, json_output: bool) -> i32 {
     let cwd = match env::current_dir() {
         Ok(dir) => dir,
         Err(err) => {
@@ -621,15 +1387,50 @@ fn run_init_command(role_name: &str) -> i32 {
         }
     };
 
-    match initialize_role(&cwd, role_name, DEFAULT_PROMPT_SEED) {
-        Ok(InitOutcome::Initialized) => {
-            println!("Initialized role: {role_name}");
+    match swarm_tasks::load_report(&cwd, swarm_project) {
+        Ok(report) => {
+            let display_path = role_state::display_relative(&cwd, &report.tasks_path);
+            if json_output {
+                println!(
+                    "{}",
+                    swarm_tasks::format_report_json(&report, &display_path)
+                );
+            } else {
+                print!("{}", swarm_tasks::format_report(&report, &display_path));
+            }
             0
         }
-        Ok(InitOutcome::AlreadyExists) => {
-            println!("Role already exists: {role_name}");
+        Err(err) => {
+            eprintln!("{err}");
+            1
+        }
+    }
+}
+
+fn run_tasks_check_command(swarm_project: &str) -> i32 {
+    let cwd = match env::current_dir() {
+        Ok(dir) => dir,
+        Err(err) => {
+            eprintln!("failed to get current directory: {err}");
+            return 1;
+        }
+    };
+
+    match swarm_tasks::load_check(&cwd, swarm_project) {
+        Ok((_, problems)) if problems.is_empty() => {
+            println!("tasks for swarm project '{swarm_project}' passed check");
             0
         }
+        Ok((tasks_path, problems)) => {
+            print!(
+                "{}",
+                swarm_tasks::format_problems(
+                    &role_state::display_relative(&cwd, &tasks_path),
+                    &problems
+                )
+            );
+            1
+        }
         Err(err) => {
             eprintln!("{err}");
             1
@@ -637,89 +1438,53 @@ fn run_init_command(role_name: &str) -> i32 {
     }
 }
 
-fn stage_explicit_role_prompt(project_root: &Path, role_name: &str) -> Result<String, String> {
-    role_name::validate_role_name(role_name)?;
-
-    if !role_state::role_state_exists(project_root, role_name) {
-        return Err(format!(
-            "Role not found: {role_name}. Run: juliet init --project {role_name}"
-        ));
-    }
-
-    let prompt_path = role_state::role_prompt_path(project_root, role_name);
-    let prompt = fs::read_to_string(&prompt_path)
-        .map_err(|err| format!("failed to read {}: {err}", prompt_path.display()))?;
-
-    let runtime_prompt_path = role_state::runtime_prompt_path(project_root, role_name);
-    role_state::write_runtime_prompt(project_root, role_name, &prompt).map_err(|err| {
-        format!(
-            "failed to write runtime prompt for role {role_name} at {}: {err}",
-            runtime_prompt_path.display()
-        )
-    })?;
-
-    Ok(prompt)
-}
-
-fn stage_implicit_role_prompt(project_root: &Path) -> Result<String, String> {
-    let roles = role_state::discover_configured_roles(project_root)
-        .map_err(|err| format!("failed to discover configured roles: {err}"))?;
-
-    match roles.as_slice() {
-        [] => Err(NO_ROLES_CONFIGURED_ERROR.to_string()),
-        [role] => stage_explicit_role_prompt(project_root, &role.name),
-        _ => {
-            let role_names = roles
-                .iter()
-                .map(|role| role.name.as_str())
-                .collect::<Vec<_>>()
-                .join("\n");
-            Err(format!("{MULTIPLE_ROLES_FOUND_ERROR}\n{role_names}"))
+fn run_sprints_command(swarm_project: Option<&str>) -> i32 {
+    let cwd = match env::current_dir() {
+        Ok(dir) => dir,
+        Err(err) => {
+            eprintln!("failed to get current directory: {err}");
+            return 1;
         }
-    }
-}
+    };
 
-fn prepare_launch_prompt(project_root: &Path, role_name: Option<&str>) -> Result<String, String> {
-    match role_name {
-        Some(name) => stage_explicit_role_prompt(project_root, name),
-        None => stage_implicit_role_prompt(project_root),
+    match swarm_sprints::collect_summaries(&cwd, swarm_project) {
+        Ok(summaries) => {
+            print!("{}", swarm_sprints::format_sprints(&summaries));
+            if summaries.iter().any(|summary| !summary.errors.is_empty()) {
+                1
+            } else {
+                0
+            }
+        }
+        Err(err) => {
+            eprintln!("{err}");
+            1
+        }
     }
 }
 
-fn run_launch_command_in_dir<F>(
-    project_root: &Path,
-    role_name: Option<&str>,
-    engine: Engine,
-    operator_input: Option<&str>,
-    engine_runner: F,
-) -> i32
-where
-    F: FnOnce(Engine, &str, &Path) -> io::Result<i32>,
-{
-    let prompt = match prepare_launch_prompt(project_root, role_name) {
-        Ok(contents) => contents,
+fn run_worktrees_list_command() -> i32 {
+    let cwd = match env::current_dir() {
+        Ok(dir) => dir,
         Err(err) => {
-            eprintln!("{err}");
+            eprintln!("failed to get current directory: {err}");
             return 1;
         }
     };
 
-    let prompt = build_launch_prompt(&prompt, operator_input);
-
-    match engine_runner(engine, &prompt, project_root) {
-        Ok(code) => code,
+    match worktrees::list_worktrees(&cwd, |args| git_output(&cwd, args)) {
+        Ok(listed) => {
+            print!("{}", worktrees::format_worktrees(&cwd, &listed));
+            0
+        }
         Err(err) => {
-            eprintln!("failed to run engine: {err}");
+            eprintln!("{err}");
             1
         }
     }
 }
 
-fn run_launch_command(
-    role_name: Option<&str>,
-    engine: Engine,
-    operator_input: Option<&str>,
-) -> i32 {
+fn run_worktrees_prune_command(dry_run: bool) -> i32 {
     let cwd = match env::current_dir() {
         Ok(dir) => dir,
         Err(err) => {
@@ -728,72 +1493,78 @@ fn run_launch_command(
         }
     };
 
-    run_launch_command_in_dir(&cwd, role_name, engine, operator_input, run_engine)
-}
-
-fn run_exec_command_in_dir<F>(
-    project_root: &Path,
-    role_name: Option<&str>,
-    engine: Engine,
-    message: &str,
-    continue_id: Option<&str>,
-    json_output: bool,
-    engine_runner: F,
-) -> i32
-where
-    F: FnOnce(Engine, &str, Option<&str>, &Path) -> io::Result<EngineOutput>,
-{
-    let base_prompt = match prepare_launch_prompt(project_root, role_name) {
-        Ok(contents) => contents,
+    let listed = match worktrees::list_worktrees(&cwd, |args| git_output(&cwd, args)) {
+        Ok(listed) => listed,
         Err(err) => {
             eprintln!("{err}");
             return 1;
         }
     };
+    let results = worktrees::prune_worktrees(&listed, dry_run, |args| git_output(&cwd, args));
+    print!("{}", worktrees::format_prune(&cwd, &results));
+    if results
+        .iter()
+        .any(|(_, action)| matches!(action, worktrees::PruneAction::Failed(_)))
+    {
+        1
+    } else {
+        0
+    }
+}
 
-    let prompt = build_launch_prompt(&base_prompt, Some(message));
+fn lint_role_prompt(
+    project_root: &Path,
+    role_name: &str,
+) -> Result<Vec<prompt_lint::LintProblem>, String> {
+    resolve_role_name(project_root, Some(role_name))?;
 
-    match engine_runner(engine, &prompt, continue_id, project_root) {
-        Ok(engine_output) => {
-            if engine_output.status_code != 0 {
-                if !engine_output.stderr.is_empty() {
-                    eprint!("{}", engine_output.stderr);
-                } else if !engine_output.stdout.is_empty() {
-                    eprint!("{}", engine_output.stdout);
-                }
-                return engine_output.status_code;
-            }
+    let prompt_path = role_state::role_prompt_path(project_root, role_name);
+    let prompt = fs::read_to_string(&prompt_path)
+        .map_err(|err| format!("failed to read {}: {err}", prompt_path.display()))?;
 
-            let exec_result = match parse_exec_result(engine, &engine_output.stdout) {
-                Ok(parsed) => parsed,
-                Err(err) => {
-                    eprintln!("failed to parse {} exec output: {err}", engine.as_str());
-                    return 1;
-                }
-            };
+    let seed = current_role_seed(project_root, role_name)?;
 
-            if json_output {
-                println!("{}", format_exec_result_json(engine, &exec_result));
-            } else if !exec_result.text.is_empty() {
-                println!("{}", exec_result.text);
-            }
+    Ok(prompt_lint::lint_prompt(
+        role_name,
+        &prompt,
+        &seed,
+        OPERATOR_PLACEHOLDER,
+    ))
+}
+
+fn run_lint_prompt_command(role_name: &str) -> i32 {
+    let cwd = match env::current_dir() {
+        Ok(dir) => dir,
+        Err(err) => {
+            eprintln!("failed to get current directory: {err}");
+            return 1;
+        }
+    };
 
-            engine_output.status_code
+    match lint_role_prompt(&cwd, role_name) {
+        Ok(problems) if problems.is_empty() => {
+            println!("prompt for role '{role_name}' passed lint");
+            0
+        }
+        Ok(problems) => {
+            let prompt_path = role_state::role_prompt_path(&cwd, role_name);
+            print!(
+                "{}",
+                prompt_lint::format_problems(
+                    &role_state::display_relative(&cwd, &prompt_path),
+                    &problems
+                )
+            );
+            1
         }
         Err(err) => {
-            eprintln!("failed to run engine: {err}");
+            eprintln!("{err}");
             1
         }
     }
 }
 
-fn run_exec_command(
-    role_name: Option<&str>,
-    engine: Engine,
-    message: &str,
-    continue_id: Option<&str>,
-    json_output: bool,
-) -> i32 {
+fn run_upgrade_prompt_command(role_name: &str) -> i32 {
     let cwd = match env::current_dir() {
         Ok(dir) => dir,
         Err(err) => {
@@ -802,2129 +1573,5328 @@ fn run_exec_command(
         }
     };
 
-    run_exec_command_in_dir(
-        &cwd,
-        role_name,
-        engine,
-        message,
-        continue_id,
-        json_output,
-        run_exec_engine,
-    )
-}
-
-fn main() {
-    let args: Vec<String> = env::args().skip(1).collect();
-    let command = match parse_cli_command(&args) {
-        Ok(parsed) => parsed,
+    match current_role_seed(&cwd, role_name).and_then(|seed| upgrade_prompt(&cwd, role_name, &seed))
+    {
+        Ok(PromptUpgrade::AlreadyCurrent { version }) => {
+            println!("prompt for role '{role_name}' is already on default seed {version}");
+            0
+        }
+        Ok(PromptUpgrade::IncludesSeed) => {
+            println!(
+                "prompt for role '{role_name}' uses {}; nothing to upgrade",
+                prompt_layers::SEED_INCLUDE
+            );
+            0
+        }
+        Ok(PromptUpgrade::Upgraded {
+            from,
+            to,
+            conflicts,
+        }) => {
+            println!("prompt for role '{role_name}' upgraded from default seed {from} to {to}");
+            if conflicts == 0 {
+                return 0;
+            }
+            let prompt_path = role_state::role_prompt_path(&cwd, role_name);
+            eprintln!(
+                "{conflicts} conflict(s) left in {}; resolve the <<<<<<< markers before the next launch",
+                prompt_path
+                    .strip_prefix(&cwd)
+                    .unwrap_or(&prompt_path)
+                    .display()
+            );
+            1
+        }
         Err(err) => {
-            let exit_code = err.exit_code();
-            let _ = err.print();
-            std::process::exit(exit_code);
+            eprintln!("{err}");
+            1
         }
-    };
-
-    let exit_code = match command {
-        CliCommand::Init { role_name } => run_init_command(&role_name),
-        CliCommand::Launch {
-            role_name,
-            engine,
-            operator_input,
-        } => run_launch_command(role_name.as_deref(), engine, operator_input.as_deref()),
-        CliCommand::ResetPrompt { role_name } => run_reset_prompt_command(&role_name),
-        CliCommand::ClearHistory { role_name } => run_clear_history_command(&role_name),
-        CliCommand::Exec {
-            role_name,
-            engine,
-            message,
-            continue_id,
-            json_output,
-        } => run_exec_command(
-            role_name.as_deref(),
-            engine,
-            &message,
-            continue_id.as_deref(),
-            json_output,
-        ),
-    };
+    }
+}
 
-    std::process::exit(exit_code);
+/// Where `clear_history` saved the previous state, and the runs that were still active.
+#[derive(Debug)]
+struct ClearedHistory {
+    snapshot_dir: PathBuf,
+    active_pids: Vec<String>,
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use std::env;
-    use std::fs;
-    use std::path::{Path, PathBuf};
-    use std::process;
-    use std::time::{SystemTime, UNIX_EPOCH};
+fn clear_history(project_root: &Path, role_name: &str) -> Result<ClearedHistory, String> {
+    role_name::validate_role_name(role_name)?;
 
-    struct TestDir {
-        path: PathBuf,
+    if !role_state::role_state_exists(project_root, role_name) {
+        return Err(format!("Role '{role_name}' is not initialized."));
     }
 
-    impl TestDir {
-        fn new(name: &str) -> Self {
-            let timestamp = SystemTime::now()
-                .duration_since(UNIX_EPOCH)
-                .expect("time drift should not occur in tests")
-                .as_nanos();
-            let path =
-                env::temp_dir().join(format!("juliet-cli-{name}-{}-{timestamp}", process::id()));
-            fs::create_dir_all(&path).expect("test directory should be created");
+    let active_pids = state_files::load_processes(project_root, role_name)
+        .map_err(|err| format!("failed to read processes for role {role_name}: {err}"))?
+        .active
+        .iter()
+        .map(|entry| {
+            entry
+                .pid
+                .map(|pid| pid.to_string())
+                .unwrap_or_else(|| "?".to_string())
+        })
+        .collect();
+
+    let snapshot_dir = role_state::clear_role_history(project_root, role_name)
+        .map_err(|err| format!("failed to clear history for role {role_name}: {err}"))?;
 
-            Self { path }
-        }
+    Ok(ClearedHistory {
+        snapshot_dir,
+        active_pids,
+    })
+}
 
-        fn path(&self) -> &Path {
-            &self.path
-        }
-    }
+fn undo_clear_history(project_root: &Path, role_name: &str) -> Result<PathBuf, String> {
+    role_name::validate_role_name(role_name)?;
 
-    impl Drop for TestDir {
-        fn drop(&mut self) {
-            let _ = fs::remove_dir_all(&self.path);
-        }
+    if !role_state::role_state_exists(project_root, role_name) {
+        return Err(format!("Role '{role_name}' is not initialized."));
     }
 
-    fn to_args(values: &[&str]) -> Vec<String> {
-        values.iter().map(|value| (*value).to_string()).collect()
-    }
+    role_state::restore_latest_history_snapshot(project_root, role_name)
+        .map_err(|err| format!("failed to restore history for role {role_name}: {err}"))?
+        .ok_or_else(|| format!("No clear-history snapshot found for role '{role_name}'."))
+}
 
-    #[test]
-    fn parses_init_with_project() {
-        let parsed = parse_cli_command(&to_args(&["init", "--project", "director-of-engineering"]))
-            .expect("init parse should succeed");
-        assert_eq!(
-            parsed,
-            CliCommand::Init {
-                role_name: "director-of-engineering".to_string()
-            }
-        );
-    }
+fn run_clear_history_command(role_name: &str, undo: bool) -> i32 {
+    let cwd = match env::current_dir() {
+        Ok(dir) => dir,
+        Err(err) => {
+            eprintln!("failed to get current directory: {err}");
+            return 1;
+        }
+    };
 
-    #[test]
-    fn parses_init_with_role_alias() {
-        let parsed = parse_cli_command(&to_args(&["init", "--role", "director-of-engineering"]))
-            .expect("init alias parse should succeed");
-        assert_eq!(
-            parsed,
-            CliCommand::Init {
-                role_name: "director-of-engineering".to_string()
+    if undo {
+        return match undo_clear_history(&cwd, role_name) {
+            Ok(snapshot_dir) => {
+                println!(
+                    "history restored for role '{role_name}' from {}",
+                    snapshot_dir
+                        .strip_prefix(&cwd)
+                        .unwrap_or(&snapshot_dir)
+                        .display()
+                );
+                0
             }
-        );
+            Err(err) => {
+                eprintln!("{err}");
+                1
+            }
+        };
     }
 
-    #[test]
-    fn parses_explicit_role_launch_with_alias_and_operator_input() {
-        let parsed = parse_cli_command(&to_args(&[
-            "--role",
-            "director-of-engineering",
-            "codex",
-            "continue",
-            "project",
-            "alpha",
-        ]))
-        .expect("explicit launch parse should succeed");
-        assert_eq!(
-            parsed,
-            CliCommand::Launch {
-                role_name: Some("director-of-engineering".to_string()),
-                engine: Engine::Codex,
-                operator_input: Some("continue project alpha".to_string()),
+    match clear_history(&cwd, role_name) {
+        Ok(cleared) => {
+            if !cleared.active_pids.is_empty() {
+                eprintln!(
+                    "warning: processes.md listed active runs (pid {}); they are no longer tracked. A snapshot was saved to {}. Undo with: juliet clear-history --undo --project {role_name}",
+                    cleared.active_pids.join(", "),
+                    cleared
+                        .snapshot_dir
+                        .strip_prefix(&cwd)
+                        .unwrap_or(&cleared.snapshot_dir)
+                        .display()
+                );
             }
-        );
+            println!("history cleared for role '{role_name}'");
+            0
+        }
+        Err(err) => {
+            eprintln!("{err}");
+            1
+        }
     }
+}
 
-    #[test]
-    fn parses_implicit_role_launch() {
-        let parsed =
-            parse_cli_command(&to_args(&["claude"])).expect("implicit launch parse should succeed");
-        assert_eq!(
-            parsed,
-            CliCommand::Launch {
-                role_name: None,
-                engine: Engine::Claude,
-                operator_input: None,
-            }
-        );
-    }
+fn run_reset_prompt_command(role_name: &str) -> i32 {
+    let cwd = match env::current_dir() {
+        Ok(dir) => dir,
+        Err(err) => {
+            eprintln!("failed to get current directory: {err}");
+            return 1;
+        }
+    };
 
-    #[test]
-    fn parses_reset_prompt_with_alias() {
-        let parsed = parse_cli_command(&to_args(&["reset-prompt", "--role", "ops"]))
-            .expect("reset-prompt parse should succeed");
-        assert_eq!(
-            parsed,
-            CliCommand::ResetPrompt {
-                role_name: "ops".to_string()
-            }
-        );
+    match current_role_seed(&cwd, role_name).and_then(|seed| reset_prompt(&cwd, role_name, &seed)) {
+        Ok(()) => {
+            println!("prompt reset to default for role '{role_name}'");
+            0
+        }
+        Err(err) => {
+            eprintln!("{err}");
+            1
+        }
     }
+}
 
-    #[test]
-    fn parses_clear_history_with_project() {
-        let parsed = parse_cli_command(&to_args(&["clear-history", "--project", "qa-team"]))
-            .expect("clear-history parse should succeed");
-        assert_eq!(
-            parsed,
-            CliCommand::ClearHistory {
-                role_name: "qa-team".to_string()
-            }
-        );
+/// Initializes `role_name` from a named template, recording any template other than the
+/// default so later resets, upgrades, and `{{include seed}}` follow it.
+fn initialize_role_from_template(
+    project_root: &Path,
+    role_name: &str,
+    template_name: Option<&str>,
+) -> Result<InitOutcome, String> {
+    let Some(template_name) = template_name else {
+        return initialize_role(project_root, role_name, DEFAULT_PROMPT_SEED);
+    };
+    require_new_prompt(project_root, role_name, "--template")?;
+
+    let template =
+        templates::find_template(template_name, templates::user_templates_dir().as_deref())?;
+    let outcome = initialize_role(project_root, role_name, &template.seed)?;
+    if template.name != templates::DEFAULT_TEMPLATE {
+        templates::write_template_record(
+            project_root,
+            role_name,
+            &templates::TemplateRecord {
+                template: Some(template.name),
+                from: None,
+            },
+        )?;
     }
+    Ok(outcome)
+}
 
-    #[test]
-    fn parses_exec_implicit_and_explicit() {
-        let implicit = parse_cli_command(&to_args(&["exec", "claude", "do", "the", "thing"]))
-            .expect("implicit exec parse should succeed");
-        assert_eq!(
-            implicit,
-            CliCommand::Exec {
-                role_name: None,
-                engine: Engine::Claude,
-                message: "do the thing".to_string(),
-                continue_id: None,
-                json_output: false,
-            }
-        );
+/// Initializes `role_name` with the prompt at `from`, a prompt file or a role directory, and
+/// records the path so `sync-template` can merge later changes. A source `prompt-seed.md`
+/// becomes the role's seed.
+fn initialize_role_from_path(
+    project_root: &Path,
+    role_name: &str,
+    from: &Path,
+) -> Result<InitOutcome, String> {
+    require_new_prompt(project_root, role_name, "--from")?;
 
-        let explicit = parse_cli_command(&to_args(&[
-            "exec", "--role", "my-role", "codex", "fix", "the", "bug",
-        ]))
-        .expect("explicit exec parse should succeed");
-        assert_eq!(
-            explicit,
-            CliCommand::Exec {
-                role_name: Some("my-role".to_string()),
-                engine: Engine::Codex,
-                message: "fix the bug".to_string(),
-                continue_id: None,
-                json_output: false,
-            }
-        );
-    }
+    let source = templates::read_prompt_source(&project_root.join(from))?;
+    let prompt = templates::adopt_heading(&source.prompt, role_name, &from.display().to_string())?;
+    let seed = source.seed.as_deref().unwrap_or(DEFAULT_PROMPT_SEED);
+    let outcome = initialize_role(project_root, role_name, seed)?;
 
-    #[test]
-    fn parses_exec_with_hyphen_prefixed_role_value() {
-        let parsed = parse_cli_command(&to_args(&[
-            "exec",
-            "--project",
-            "-leading",
-            "claude",
-            "hello",
-        ]))
-        .expect("exec parse should allow hyphen-prefixed role values");
-        assert_eq!(
-            parsed,
-            CliCommand::Exec {
-                role_name: Some("-leading".to_string()),
-                engine: Engine::Claude,
-                message: "hello".to_string(),
-                continue_id: None,
-                json_output: false,
-            }
-        );
+    for path in [
+        role_state::role_prompt_path(project_root, role_name),
+        role_state::template_base_path(project_root, role_name),
+    ] {
+        fs::write(&path, &prompt)
+            .map_err(|err| format!("failed to write {}: {err}", path.display()))?;
     }
+    templates::write_template_record(
+        project_root,
+        role_name,
+        &templates::TemplateRecord {
+            template: None,
+            from: Some(from.to_path_buf()),
+        },
+    )?;
+    Ok(outcome)
+}
 
-    #[test]
-    fn parses_exec_continue_and_json_options() {
-        let parsed = parse_cli_command(&to_args(&[
-            "exec",
-            "--project",
-            "my-role",
-            "--continue",
-            "session-123",
-            "--json",
-            "codex",
-            "ship",
-            "it",
-        ]))
-        .expect("exec parse with continue/json options should succeed");
-        assert_eq!(
-            parsed,
-            CliCommand::Exec {
-                role_name: Some("my-role".to_string()),
-                engine: Engine::Codex,
-                message: "ship it".to_string(),
-                continue_id: Some("session-123".to_string()),
-                json_output: true,
-            }
-        );
+fn require_new_prompt(project_root: &Path, role_name: &str, flag: &str) -> Result<(), String> {
+    role_name::validate_role_name(role_name)?;
+    if role_state::role_prompt_path(project_root, role_name).is_file() {
+        return Err(format!(
+            "Role '{role_name}' already has a prompt; {flag} only applies to new roles."
+        ));
     }
+    Ok(())
+}
 
-    #[test]
-    fn parses_exec_json_option_after_message() {
-        let parsed = parse_cli_command(&to_args(&["exec", "codex", "hello", "--json"]))
-            .expect("exec parse should allow --json after message");
-        assert_eq!(
-            parsed,
-            CliCommand::Exec {
-                role_name: None,
-                engine: Engine::Codex,
-                message: "hello".to_string(),
-                continue_id: None,
-                json_output: true,
-            }
-        );
-    }
+/// Current seed of the template `role_name` was created from.
+fn current_role_seed(project_root: &Path, role_name: &str) -> Result<String, String> {
+    role_name::validate_role_name(role_name)?;
+    templates::role_seed(
+        project_root,
+        role_name,
+        templates::user_templates_dir().as_deref(),
+    )
+}
 
-    #[test]
-    fn parser_errors_are_clap_native_for_invalid_shapes() {
-        for args in [
-            vec!["reset-prompt"],
-            vec!["clear-history", "--role"],
-            vec!["exec", "claude"],
-            vec!["exec", "--role", "my-role", "claude"],
-            vec!["exec", "--role"],
-            vec!["exec", "--continue", "claude", "hello"],
-            vec!["exec", "--continue"],
-        ] {
-            assert!(
-                parse_cli_command(&to_args(&args)).is_err(),
-                "expected parse error for args: {:?}",
-                args
-            );
+fn run_templates_list_command() -> i32 {
+    match templates::list_templates(templates::user_templates_dir().as_deref()) {
+        Ok(templates) => {
+            print!("{}", templates::format_template_list(&templates));
+            0
+        }
+        Err(err) => {
+            eprintln!("{err}");
+            1
         }
     }
+}
 
-    #[test]
-    fn parser_requires_engine_for_launch_mode() {
-        let error = parse_cli_command(&to_args(&[])).expect_err("no args should fail");
-        assert_eq!(
-            error.kind(),
-            clap::error::ErrorKind::MissingRequiredArgument
-        );
+fn run_init_command(role_name: &str, template_name: Option<&str>, from: Option<&Path>) -> i32 {
+    let cwd = match env::current_dir() {
+        Ok(dir) => dir,
+        Err(err) => {
+            eprintln!("failed to get current directory: {err}");
+            return 1;
+        }
+    };
 
-        let explicit_missing_engine = parse_cli_command(&to_args(&["--role", "director"]))
-            .expect_err("explicit launch without engine should fail");
-        assert_eq!(
-            explicit_missing_engine.kind(),
-            clap::error::ErrorKind::MissingRequiredArgument
-        );
+    let initialized = match from {
+        Some(from) => initialize_role_from_path(&cwd, role_name, from),
+        None => initialize_role_from_template(&cwd, role_name, template_name),
+    };
+    match initialized {
+        Ok(InitOutcome::Initialized) => {
+            println!("Initialized role: {role_name}");
+            0
+        }
+        Ok(InitOutcome::AlreadyExists) => {
+            println!("Role already exists: {role_name}");
+            0
+        }
+        Err(err) => {
+            eprintln!("{err}");
+            1
+        }
     }
+}
 
-    #[test]
-    fn parser_rejects_invalid_exec_engine_values() {
-        for invalid_engine in ["Claude", "CLAUDE", "Codex", "CODEX", "Claude3", "gpt4"] {
-            let error = parse_cli_command(&to_args(&["exec", invalid_engine, "hello"]))
-                .expect_err("invalid engine should fail");
-            assert_eq!(error.kind(), clap::error::ErrorKind::InvalidValue);
+fn resolve_role_name(project_root: &Path, role_name: Option<&str>) -> Result<String, String> {
+    if let Some(role_name) = role_name {
+        role_name::validate_role_name(role_name)?;
+
+        if !role_state::role_state_exists(project_root, role_name) {
+            return Err(format!(
+                "Role not found: {role_name}. Run: juliet init --project {role_name}"
+            ));
         }
+
+        return Ok(role_name.to_string());
     }
 
-    #[test]
-    fn reset_prompt_bad_role_name_rejected_by_validation() {
-        // Role name validation rejects names that the parser passes through.
-        for bad_name in ["Invalid_Name", "../traversal", "", "-leading", "UPPER"] {
-            let err = role_name::validate_role_name(bad_name)
-                .expect_err(&format!("role name '{bad_name}' should be rejected"));
-            assert!(
-                err.contains("Invalid role name"),
-                "validation error for '{bad_name}' should contain 'Invalid role name': {err}"
-            );
+    let roles = role_state::discover_configured_roles(project_root)
+        .map_err(|err| format!("failed to discover configured roles: {err}"))?;
+
+    match roles.as_slice() {
+        [] => Err(NO_ROLES_CONFIGURED_ERROR.to_string()),
+        [role] => Ok(role.name.clone()),
+        _ => {
+            let role_names = roles
+                .iter()
+                .map(|role| role.name.as_str())
+                .collect::<Vec<_>>()
+                .join("\n");
+            Err(format!("{MULTIPLE_ROLES_FOUND_ERROR}\n{role_names}"))
         }
     }
+}
 
-    #[test]
-    fn clear_history_bad_role_name_rejected_by_validation() {
-        // Role name validation rejects names that the parser passes through.
-        for bad_name in ["Invalid_Name", "../traversal", "", "-leading", "UPPER"] {
-            let err = role_name::validate_role_name(bad_name)
-                .expect_err(&format!("role name '{bad_name}' should be rejected"));
-            assert!(
-                err.contains("Invalid role name"),
-                "validation error for '{bad_name}' should contain 'Invalid role name': {err}"
-            );
+fn stage_explicit_role_prompt(project_root: &Path, role_name: &str) -> Result<String, String> {
+    resolve_role_name(project_root, Some(role_name))?;
+
+    let prompt_path = role_state::role_prompt_path(project_root, role_name);
+    let role_prompt = fs::read_to_string(&prompt_path)
+        .map_err(|err| format!("failed to read {}: {err}", prompt_path.display()))?;
+    require_no_conflict_markers(project_root, &prompt_path, &role_prompt)?;
+    let shared_prompt_path = role_state::shared_prompt_path(project_root);
+    let shared_prompt = match fs::read_to_string(&shared_prompt_path) {
+        Ok(shared_prompt) => Some(shared_prompt),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => None,
+        Err(err) => {
+            return Err(format!(
+                "failed to read {}: {err}",
+                shared_prompt_path.display()
+            ))
         }
-    }
+    };
 
-    #[test]
-    fn exec_bad_role_name_rejected_by_validation() {
-        // Role name validation rejects names that the parser passes through.
-        for bad_name in ["Invalid_Name", "../traversal", "", "-leading", "UPPER"] {
-            let err = role_name::validate_role_name(bad_name)
-                .expect_err(&format!("role name '{bad_name}' should be rejected"));
-            assert!(
-                err.contains("Invalid role name"),
-                "validation error for '{bad_name}' should contain 'Invalid role name': {err}"
-            );
-        }
-    }
+    // Variables are expanded per layer so errors point at the file the operator edits.
+    let mut values = HashMap::new();
+    let mut resolve = |name: &str| {
+        values
+            .entry(name.to_string())
+            .or_insert_with(|| prompt_variable(project_root, role_name, name))
+            .clone()
+    };
+    let role_prompt = prompt_vars::expand_variables(
+        &role_prompt,
+        &role_state::display_relative(project_root, &prompt_path),
+        &mut resolve,
+    )?;
+    let shared_prompt = shared_prompt
+        .map(|shared_prompt| {
+            prompt_vars::expand_variables(
+                &shared_prompt,
+                &role_state::display_relative(project_root, &shared_prompt_path),
+                &mut resolve,
+            )
+        })
+        .transpose()?;
 
-    #[test]
-    fn prepare_launch_prompt_fails_when_explicit_role_is_missing() {
-        let temp = TestDir::new("launch-missing-role");
+    let seed = current_role_seed(project_root, role_name)?;
+    let prompt = prompt_layers::compose_prompt(&role_prompt, &seed, shared_prompt.as_deref())
+        .map_err(|err| format!("failed to compose prompt for role {role_name}: {err}"))?;
 
-        let err = prepare_launch_prompt(temp.path(), Some("missing-role"))
-            .expect_err("missing role should fail");
+    let runtime_prompt_path = role_state::runtime_prompt_path(project_root, role_name);
+    role_state::write_runtime_prompt(project_root, role_name, &prompt).map_err(|err| {
+        format!(
+            "failed to write runtime prompt for role {role_name} at {}: {err}",
+            runtime_prompt_path.display()
+        )
+    })?;
 
-        assert_eq!(
-            err,
-            "Role not found: missing-role. Run: juliet init --project missing-role"
-        );
-    }
+    Ok(prompt)
+}
 
-    #[test]
-    fn prepare_launch_prompt_rejects_explicit_role_traversal_name() {
-        let temp = TestDir::new("launch-explicit-invalid-role-name");
-        let escaped_role_name = "../escaped-role";
-        let escaped_role_dir = temp.path().join("escaped-role");
+/// Value of a `prompt_vars::VARIABLES` entry for `role_name`. Lookups that fail degrade to a
+/// placeholder rather than blocking the turn.
+fn prompt_variable(project_root: &Path, role_name: &str, name: &str) -> String {
+    match name {
+        "role" => role_name.to_string(),
+        "now_utc" => timestamp::now_utc(),
+        "git_branch" => Command::new("git")
+            .args(["rev-parse", "--abbrev-ref", "HEAD"])
+            .current_dir(project_root)
+            .stderr(Stdio::null())
+            .output()
+            .ok()
+            .filter(|output| output.status.success())
+            .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+            .unwrap_or_else(|| "unknown".to_string()),
+        "available_engines" => state_files::load_session(project_root, role_name)
+            .ok()
+            .map(|session| session.available_engines.join(", "))
+            .filter(|engines| !engines.is_empty())
+            .unwrap_or_else(|| "unknown (run juliet doctor)".to_string()),
+        "pending_needs" => {
+            let needs = state_files::load_needs(project_root, role_name).unwrap_or_default();
+            if needs.is_empty() {
+                "none".to_string()
+            } else {
+                needs
+                    .iter()
+                    .map(|need| format!("- {}", need.text))
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            }
+        }
+        _ => unreachable!("prompt_vars only resolves known variables"),
+    }
+}
 
-        fs::create_dir_all(temp.path().join(".juliet"))
-            .expect("state root should exist for traversal regression test");
-        fs::create_dir_all(&escaped_role_dir)
-            .expect("escaped role directory should exist outside .juliet");
-        fs::write(escaped_role_dir.join("prompt.md"), "# escaped prompt")
-            .expect("escaped prompt file should exist outside .juliet");
+/// Refuses prompts still holding conflict markers from `upgrade-prompt` or `sync-template`, so
+/// an engine never sees both sides of a conflict.
+fn require_no_conflict_markers(project_root: &Path, path: &Path, text: &str) -> Result<(), String> {
+    match prompt_merge::conflict_marker_lines(text).first() {
+        Some(line) => Err(format!(
+            "{}:{line}: unresolved merge conflict marker; resolve the <<<<<<< blocks first",
+            role_state::display_relative(project_root, path)
+        )),
+        None => Ok(()),
+    }
+}
 
-        let err = prepare_launch_prompt(temp.path(), Some(escaped_role_name))
-            .expect_err("invalid explicit role name should fail before path traversal");
+fn stage_implicit_role_prompt(project_root: &Path) -> Result<String, String> {
+    let role_name = resolve_role_name(project_root, None)?;
+    stage_explicit_role_prompt(project_root, &role_name)
+}
 
-        assert_eq!(
-            err,
-            "Invalid role name: ../escaped-role. Use lowercase letters, numbers, and hyphens."
-        );
-        assert!(
-            !escaped_role_dir.join("juliet-prompt.md").exists(),
-            "runtime prompt should not be written outside .juliet/<role>/"
-        );
+fn prepare_launch_prompt(project_root: &Path, role_name: Option<&str>) -> Result<String, String> {
+    match role_name {
+        Some(name) => stage_explicit_role_prompt(project_root, name),
+        None => stage_implicit_role_prompt(project_root),
     }
+}
 
-    #[test]
-    fn prepare_launch_prompt_reads_and_stages_explicit_role_prompt() {
-        let temp = TestDir::new("launch-explicit-role");
-        let role_name = "director-of-engineering";
-        role_state::create_role_state(temp.path(), role_name).expect("role state should exist");
-
-        let prompt_path = role_state::role_prompt_path(temp.path(), role_name);
-        fs::write(&prompt_path, "# Explicit prompt\n\nDo role work.")
-            .expect("role prompt should be written");
+fn run_launch_command_in_dir<F>(
+    project_root: &Path,
+    role_name: Option<&str>,
+    engine: &dyn Engine,
+    operator_input: Option<&str>,
+    engine_runner: F,
+) -> i32
+where
+    F: FnOnce(&dyn Engine, &str, &Path) -> io::Result<i32>,
+{
+    let prompt = match prepare_launch_prompt(project_root, role_name) {
+        Ok(contents) => contents,
+        Err(err) => {
+            eprintln!("{err}");
+            return 1;
+        }
+    };
 
-        let prompt = prepare_launch_prompt(temp.path(), Some(role_name))
-            .expect("explicit role prompt should be loaded");
-        assert_eq!(prompt, "# Explicit prompt\n\nDo role work.");
+    let prompt = build_launch_prompt(&prompt, operator_input);
 
-        let runtime_prompt =
-            fs::read_to_string(role_state::runtime_prompt_path(temp.path(), role_name))
-                .expect("runtime prompt should be written");
-        assert_eq!(runtime_prompt, prompt);
+    match engine_runner(engine, &prompt, project_root) {
+        Ok(code) => code,
+        Err(err) => {
+            eprintln!("failed to run engine: {err}");
+            1
+        }
     }
+}
 
-    #[test]
-    fn run_launch_command_in_dir_returns_engine_exit_code_for_explicit_role() {
-        let temp = TestDir::new("launch-explicit-engine-exit");
-        let role_name = "director-of-engineering";
-        role_state::create_role_state(temp.path(), role_name).expect("role state should exist");
+fn run_launch_command(
+    role_name: Option<&str>,
+    engine_name: &str,
+    operator_input: Option<&str>,
+    timeout: Option<Duration>,
+) -> i32 {
+    let cwd = match env::current_dir() {
+        Ok(dir) => dir,
+        Err(err) => {
+            eprintln!("failed to get current directory: {err}");
+            return 1;
+        }
+    };
+    let engine = match engine_config::resolve_engine(&cwd, engine_name) {
+        Ok(engine) => engine,
+        Err(err) => {
+            eprintln!("{err}");
+            return 1;
+        }
+    };
 
-        let prompt_path = role_state::role_prompt_path(temp.path(), role_name);
-        fs::write(&prompt_path, "# Explicit prompt\n\nDo role work.")
-            .expect("role prompt should be written");
+    run_launch_command_in_dir(
+        &cwd,
+        role_name,
+        engine.as_ref(),
+        operator_input,
+        |engine, prompt, cwd| run_engine(engine, prompt, cwd, timeout),
+    )
+}
 
-        let mut captured_engine = None;
-        let mut captured_prompt = String::new();
-        let exit_code = run_launch_command_in_dir(
-            temp.path(),
-            Some(role_name),
-            Engine::Codex,
-            None,
-            |engine, prompt, cwd| {
-                captured_engine = Some(engine);
-                captured_prompt = prompt.to_string();
-                assert_eq!(cwd, temp.path());
-                Ok(5)
-            },
-        );
+fn run_exec_command_in_dir<F>(
+    project_root: &Path,
+    role_name: Option<&str>,
+    engine: &dyn Engine,
+    message: &str,
+    continue_id: Option<&str>,
+    output_mode: ExecOutputMode,
+    engine_runner: F,
+) -> i32
+where
+    F: FnMut(&dyn Engine, &str, Option<&str>, &Path) -> io::Result<EngineOutput>,
+{
+    run_exec_chain_in_dir(
+        project_root,
+        role_name,
+        &[engine],
+        message,
+        continue_id,
+        output_mode,
+        engine_runner,
+    )
+}
 
-        assert_eq!(exit_code, 5);
-        assert_eq!(captured_engine, Some(Engine::Codex));
-        assert_eq!(captured_prompt, "# Explicit prompt\n\nDo role work.");
+/// Runs the turn on the first engine, moving down the chain only for failures another engine
+/// could avoid. `continue_id` belongs to the first engine, so fallbacks start fresh sessions.
+fn run_exec_chain_in_dir<F>(
+    project_root: &Path,
+    role_name: Option<&str>,
+    engines: &[&dyn Engine],
+    message: &str,
+    continue_id: Option<&str>,
+    output_mode: ExecOutputMode,
+    mut engine_runner: F,
+) -> i32
+where
+    F: FnMut(&dyn Engine, &str, Option<&str>, &Path) -> io::Result<EngineOutput>,
+{
+    let primary = engines[0];
+    if continue_id.is_some() && !primary.supports_resume() {
+        eprintln!(
+            "engine {} does not support --continue. Add exec_resume to {}",
+            primary.name(),
+            role_state::engines_config_path(project_root).display()
+        );
+        return 1;
     }
 
-    #[test]
-    fn prepare_launch_prompt_fails_when_implicit_launch_has_no_roles() {
-        let temp = TestDir::new("launch-implicit-no-roles");
-        let prompts_dir = temp.path().join("prompts");
-        fs::create_dir_all(&prompts_dir).expect("prompts directory should be created");
-        fs::write(prompts_dir.join("juliet.md"), "# legacy prompt")
-            .expect("legacy prompt should not affect role discovery");
+    let role_name = match resolve_role_name(project_root, role_name) {
+        Ok(role_name) => role_name,
+        Err(err) => {
+            eprintln!("{err}");
+            return 1;
+        }
+    };
+    let base_prompt = match stage_explicit_role_prompt(project_root, &role_name) {
+        Ok(contents) => contents,
+        Err(err) => {
+            eprintln!("{err}");
+            return 1;
+        }
+    };
 
-        let err =
-            prepare_launch_prompt(temp.path(), None).expect_err("missing roles should fail launch");
-        assert_eq!(err, NO_ROLES_CONFIGURED_ERROR);
+    let prompt = build_launch_prompt(&base_prompt, Some(message));
+
+    for (index, engine) in engines.iter().enumerate() {
+        let turn = ExecTurn {
+            role_name: &role_name,
+            prompt: &prompt,
+            message,
+            continue_id: if index == 0 { continue_id } else { None },
+            output_mode,
+        };
+        let failure = match run_exec_attempt(project_root, *engine, turn, &mut engine_runner) {
+            Ok(exit_code) => return exit_code,
+            Err(failure) => failure,
+        };
+        eprintln!("{failure}");
+
+        if let Some(next) = engines.get(index + 1) {
+            eprintln!("falling back to engine {}", next.name());
+            if let Err(err) = record_fallback_learning(
+                project_root,
+                &role_name,
+                engine.name(),
+                next.name(),
+                &failure,
+            ) {
+                eprintln!("failed to record engine fallback in shared learnings: {err}");
+            }
+        }
     }
+    1
+}
 
-    #[test]
-    fn prepare_launch_prompt_auto_selects_single_configured_role() {
-        let temp = TestDir::new("launch-implicit-single-role");
-        let role_name = "director-of-engineering";
-        role_state::create_role_state(temp.path(), role_name).expect("role state should exist");
+/// Runs one exec turn on `engine`. `Err` carries a failure worth retrying on another engine:
+/// the binary is missing, the process could not be spawned, or its output did not parse.
+fn run_exec_attempt<F>(
+    project_root: &Path,
+    engine: &dyn Engine,
+    turn: ExecTurn,
+    engine_runner: &mut F,
+) -> Result<i32, String>
+where
+    F: FnMut(&dyn Engine, &str, Option<&str>, &Path) -> io::Result<EngineOutput>,
+{
+    let ExecTurn {
+        role_name,
+        prompt,
+        message,
+        continue_id,
+        output_mode,
+    } = turn;
+    let engine_output = engine_runner(engine, prompt, continue_id, project_root)
+        .map_err(|err| format!("failed to run engine: {err}"))?;
+
+    if engine_output.timed_out {
+        match save_timeout_artifact(project_root, role_name, engine.name(), &engine_output) {
+            Ok(path) => eprintln!(
+                "engine {} timed out; partial output saved to {}",
+                engine.name(),
+                path.display()
+            ),
+            Err(err) => eprintln!(
+                "engine {} timed out; failed to save partial output: {err}",
+                engine.name()
+            ),
+        }
+        return Ok(supervisor::TIMEOUT_EXIT_CODE);
+    }
 
-        let prompt_path = role_state::role_prompt_path(temp.path(), role_name);
-        fs::write(&prompt_path, "# Implicit prompt\n\nDo role work.")
-            .expect("role prompt should be written");
+    if engine_output.status_code != 0 {
+        if !engine_output.stderr.is_empty() {
+            eprint!("{}", engine_output.stderr);
+        } else if !engine_output.stdout.is_empty() {
+            eprint!("{}", engine_output.stdout);
+        }
+        return Ok(engine_output.status_code);
+    }
 
-        let prompt = prepare_launch_prompt(temp.path(), None)
-            .expect("single role should be selected implicitly");
-        assert_eq!(prompt, "# Implicit prompt\n\nDo role work.");
+    let exec_result = engine
+        .parse_exec_output(&engine_output.stdout)
+        .map_err(|err| format!("failed to parse {} exec output: {err}", engine.name()))?;
 
-        let runtime_prompt =
-            fs::read_to_string(role_state::runtime_prompt_path(temp.path(), role_name))
-                .expect("runtime prompt should be written");
-        assert_eq!(runtime_prompt, prompt);
+    let record = sessions::SessionRecord::new(
+        &timestamp::now_utc(),
+        engine.name(),
+        &exec_result.resume_id,
+        message,
+    );
+    if let Err(err) = sessions::record_session(project_root, role_name, &record) {
+        eprintln!("failed to record session for role {role_name}: {err}");
     }
 
-    #[test]
-    fn prepare_launch_prompt_auto_selects_single_juliet_role() {
-        let temp = TestDir::new("launch-implicit-juliet-role");
-        let role_name = "juliet";
-        role_state::create_role_state(temp.path(), role_name).expect("role state should exist");
+    match output_mode {
+        ExecOutputMode::Json => println!("{}", format_exec_result_json(engine, &exec_result)),
+        ExecOutputMode::Stream => {
+            println!("{}", format_stream_result_json(engine, &exec_result))
+        }
+        ExecOutputMode::Text if !exec_result.text.is_empty() => println!("{}", exec_result.text),
+        ExecOutputMode::Text => {}
+    }
 
-        let prompt_path = role_state::role_prompt_path(temp.path(), role_name);
-        fs::write(&prompt_path, "# Juliet role prompt\n\nDo role work.")
-            .expect("role prompt should be written");
+    Ok(engine_output.status_code)
+}
 
-        let prompt = prepare_launch_prompt(temp.path(), None)
-            .expect("single juliet role should be selected implicitly");
-        assert_eq!(prompt, "# Juliet role prompt\n\nDo role work.");
+fn record_fallback_learning(
+    project_root: &Path,
+    role_name: &str,
+    failed_engine: &str,
+    next_engine: &str,
+    failure: &str,
+) -> io::Result<()> {
+    role_state::append_shared_learning(
+        project_root,
+        &format!(
+            "- {} [exec-fallback] role {role_name}: {failed_engine} failed ({failure}); retried the turn with {next_engine}.",
+            timestamp::now_utc()
+        ),
+    )
+}
 
-        let runtime_prompt =
-            fs::read_to_string(role_state::runtime_prompt_path(temp.path(), role_name))
-                .expect("runtime prompt should be written");
-        assert_eq!(runtime_prompt, prompt);
-    }
+fn latest_resume_id(
+    project_root: &Path,
+    role_name: Option<&str>,
+    engine_name: &str,
+) -> Result<String, String> {
+    let role_name = resolve_role_name(project_root, role_name)?;
+    let records = sessions::load_sessions(project_root, &role_name)
+        .map_err(|err| format!("failed to read sessions for role {role_name}: {err}"))?;
+
+    sessions::latest_session(&records, engine_name)
+        .map(|record| record.resume_id)
+        .ok_or_else(|| {
+            format!(
+                "No recorded {engine_name} sessions for role {role_name}. Run juliet exec without --continue-last first."
+            )
+        })
+}
 
-    #[test]
-    fn prepare_launch_prompt_auto_selects_single_artifacts_role() {
-        let temp = TestDir::new("launch-implicit-artifacts-role");
-        let role_name = "artifacts";
-        role_state::create_role_state(temp.path(), role_name).expect("role state should exist");
-
-        let prompt_path = role_state::role_prompt_path(temp.path(), role_name);
-        fs::write(&prompt_path, "# Artifacts role prompt\n\nDo role work.")
-            .expect("role prompt should be written");
-
-        let prompt = prepare_launch_prompt(temp.path(), None)
-            .expect("single artifacts role should be selected implicitly");
-        assert_eq!(prompt, "# Artifacts role prompt\n\nDo role work.");
+/// Resolves the exec engine followed by its fallbacks. An empty `engine_fallback` uses the
+/// default from `.juliet/engines.toml`; repeated engines are only tried once.
+fn resolve_engine_chain(
+    project_root: &Path,
+    engine_name: &str,
+    engine_fallback: &[String],
+) -> Result<Vec<Box<dyn Engine>>, String> {
+    let fallback_names = if engine_fallback.is_empty() {
+        engine_config::load_exec_fallback(project_root)?
+    } else {
+        engine_fallback.to_vec()
+    };
 
-        let runtime_prompt =
-            fs::read_to_string(role_state::runtime_prompt_path(temp.path(), role_name))
-                .expect("runtime prompt should be written");
-        assert_eq!(runtime_prompt, prompt);
+    let mut engines = vec![engine_config::resolve_engine(project_root, engine_name)?];
+    for name in fallback_names {
+        if engines.iter().all(|engine| engine.name() != name) {
+            engines.push(engine_config::resolve_engine(project_root, &name)?);
+        }
     }
+    Ok(engines)
+}
 
-    #[test]
-    fn run_launch_command_in_dir_returns_engine_exit_code_for_implicit_single_role_launch() {
-        let temp = TestDir::new("launch-implicit-engine-exit");
-        let role_name = "director-of-engineering";
-        role_state::create_role_state(temp.path(), role_name).expect("role state should exist");
-
-        let prompt_path = role_state::role_prompt_path(temp.path(), role_name);
-        fs::write(&prompt_path, "# Implicit prompt\n\nDo role work.")
-            .expect("role prompt should be written");
+fn run_exec_command(
+    request: ExecRequest,
+    engine_name: &str,
+    timeout: Option<Duration>,
+    engine_fallback: &[String],
+) -> i32 {
+    let ExecRequest {
+        role_name,
+        message,
+        continue_id,
+        continue_last,
+        output_mode,
+    } = request;
+    let cwd = match env::current_dir() {
+        Ok(dir) => dir,
+        Err(err) => {
+            eprintln!("failed to get current directory: {err}");
+            return 1;
+        }
+    };
+    let engines = match resolve_engine_chain(&cwd, engine_name, engine_fallback) {
+        Ok(engines) => engines,
+        Err(err) => {
+            eprintln!("{err}");
+            return 1;
+        }
+    };
+    let engines = engines.iter().map(Box::as_ref).collect::<Vec<_>>();
+
+    let continue_id = if continue_last {
+        match latest_resume_id(&cwd, role_name, engines[0].name()) {
+            Ok(resume_id) => Some(resume_id),
+            Err(err) => {
+                eprintln!("{err}");
+                return 1;
+            }
+        }
+    } else {
+        continue_id.map(str::to_string)
+    };
 
-        let mut captured_engine = None;
-        let mut captured_prompt = String::new();
-        let exit_code = run_launch_command_in_dir(
-            temp.path(),
-            None,
-            Engine::Claude,
-            None,
-            |engine, prompt, cwd| {
-                captured_engine = Some(engine);
-                captured_prompt = prompt.to_string();
-                assert_eq!(cwd, temp.path());
-                Ok(5)
+    if output_mode == ExecOutputMode::Stream {
+        return run_exec_chain_in_dir(
+            &cwd,
+            role_name,
+            &engines,
+            message,
+            continue_id.as_deref(),
+            output_mode,
+            |engine, prompt, continue_id, cwd| {
+                run_stream_exec_engine(engine, prompt, continue_id, cwd, timeout, |line| {
+                    for event in engine.parse_stream_line(line) {
+                        println!("{}", format_stream_event_json(&event));
+                    }
+                })
             },
         );
-
-        assert_eq!(exit_code, 5);
-        assert_eq!(captured_engine, Some(Engine::Claude));
-        assert_eq!(captured_prompt, "# Implicit prompt\n\nDo role work.");
     }
 
-    #[test]
-    fn prepare_launch_prompt_fails_when_multiple_roles_are_configured() {
-        let temp = TestDir::new("launch-implicit-multiple-roles");
-        role_state::create_role_state(temp.path(), "zeta-team")
-            .expect("zeta team role state should exist");
-        role_state::create_role_state(temp.path(), "alpha-team")
-            .expect("alpha team role state should exist");
-
-        let err = prepare_launch_prompt(temp.path(), None)
-            .expect_err("multiple configured roles should require explicit selection");
-        assert_eq!(
-            err,
-            "Multiple roles found. Specify one with --project <name>:\nalpha-team\nzeta-team"
-        );
-    }
+    run_exec_chain_in_dir(
+        &cwd,
+        role_name,
+        &engines,
+        message,
+        continue_id.as_deref(),
+        output_mode,
+        |engine, prompt, continue_id, cwd| {
+            run_exec_engine(engine, prompt, continue_id, cwd, timeout)
+        },
+    )
+}
 
-    #[test]
-    fn initialize_role_rejects_invalid_role_name() {
-        let temp = TestDir::new("invalid-role");
-        let err = initialize_role(temp.path(), "Invalid_Name", "seed prompt")
-            .expect_err("invalid role name should fail");
+fn collect_statuses(
+    project_root: &Path,
+    role_name: Option<&str>,
+) -> Result<Vec<role_status::RoleStatus>, String> {
+    let role_names = match role_name {
+        Some(name) => vec![resolve_role_name(project_root, Some(name))?],
+        None => {
+            let roles = role_state::discover_configured_roles(project_root)
+                .map_err(|err| format!("failed to discover configured roles: {err}"))?;
+            if roles.is_empty() {
+                return Err(NO_ROLES_CONFIGURED_ERROR.to_string());
+            }
+            roles.into_iter().map(|role| role.name).collect()
+        }
+    };
 
-        assert_eq!(
-            err,
-            "Invalid role name: Invalid_Name. Use lowercase letters, numbers, and hyphens."
-        );
-    }
+    role_names
+        .iter()
+        .map(|name| {
+            role_status::collect_role_status(project_root, name)
+                .map_err(|err| format!("failed to read state for role {name}: {err}"))
+        })
+        .collect()
+}
 
-    #[test]
-    fn initialize_role_creates_prompt_template_and_state_structure() {
-        let temp = TestDir::new("fresh-init");
-        let role_name = "director-of-engineering";
+fn run_status_command(role_name: Option<&str>, json_output: bool) -> i32 {
+    let cwd = match env::current_dir() {
+        Ok(dir) => dir,
+        Err(err) => {
+            eprintln!("failed to get current directory: {err}");
+            return 1;
+        }
+    };
 
-        let outcome =
-            initialize_role(temp.path(), role_name, "## Seeded prompt").expect("init should work");
-        assert_eq!(outcome, InitOutcome::Initialized);
+    match collect_statuses(&cwd, role_name) {
+        Ok(statuses) => {
+            if json_output {
+                println!("{}", role_status::format_status_json(&statuses));
+            } else {
+                print!("{}", role_status::format_status_table(&statuses));
+            }
+            0
+        }
+        Err(err) => {
+            eprintln!("{err}");
+            1
+        }
+    }
+}
 
-        let prompt_path = role_state::role_prompt_path(temp.path(), role_name);
-        let prompt_contents =
-            fs::read_to_string(prompt_path).expect("role prompt should be readable");
-        assert!(prompt_contents.contains("# director-of-engineering"));
-        assert!(prompt_contents.contains(OPERATOR_PLACEHOLDER));
-        assert!(prompt_contents.contains("## Seeded prompt"));
+fn run_processes_check_command(role_name: Option<&str>) -> i32 {
+    let cwd = match env::current_dir() {
+        Ok(dir) => dir,
+        Err(err) => {
+            eprintln!("failed to get current directory: {err}");
+            return 1;
+        }
+    };
 
-        let role_dir = role_state::role_state_dir(temp.path(), role_name);
-        assert!(role_dir.is_dir());
-        assert!(role_dir.join("session.md").is_file());
-        assert!(role_dir.join("needs-from-operator.md").is_file());
-        assert!(role_dir.join("projects.md").is_file());
-        assert!(role_dir.join("processes.md").is_file());
-        assert!(role_dir.join("artifacts").is_dir());
-        assert!(role_state::shared_learnings_path(temp.path()).is_file());
-        let state_gitignore_path = role_state::state_gitignore_path(temp.path());
-        let state_gitignore =
-            fs::read_to_string(state_gitignore_path).expect("state gitignore should be readable");
-        assert!(
-            state_gitignore.contains("!*/prompt.md"),
-            "state gitignore should keep role prompt files tracked"
-        );
+    let check = resolve_role_name(&cwd, role_name).and_then(|role_name| {
+        process_check::check_processes(&cwd, &role_name, process_check::pid_is_running)
+            .map_err(|err| format!("failed to check processes for role {role_name}: {err}"))
+    });
+
+    match check {
+        Ok(check) => {
+            print!("{}", process_check::format_process_check(&check));
+            if check.changed() {
+                PROCESSES_CHANGED_EXIT_CODE
+            } else {
+                0
+            }
+        }
+        Err(err) => {
+            eprintln!("{err}");
+            1
+        }
     }
+}
 
-    #[test]
-    fn initialize_role_is_idempotent_when_prompt_and_state_both_exist() {
-        let temp = TestDir::new("already-exists");
-        let role_name = "director-of-marketing";
+fn run_doctor_probe(
+    project_root: &Path,
+    program: &str,
+    args: &[&str],
+) -> io::Result<doctor::ProbeOutput> {
+    let run = supervisor::run_captured(
+        Command::new(program)
+            .args(args)
+            .current_dir(project_root)
+            .stdin(Stdio::null()),
+        Some(doctor::PROBE_TIMEOUT),
+        |_| {},
+    )?;
+    Ok(doctor::ProbeOutput {
+        success: run.status.is_some_and(|status| status.success()),
+        stdout: run.stdout,
+        stderr: run.stderr,
+    })
+}
 
-        let first = initialize_role(temp.path(), role_name, "seed prompt one")
-            .expect("first init should succeed");
-        assert_eq!(first, InitOutcome::Initialized);
+fn run_doctor_command(role_name: Option<&str>) -> i32 {
+    let cwd = match env::current_dir() {
+        Ok(dir) => dir,
+        Err(err) => {
+            eprintln!("failed to get current directory: {err}");
+            return 1;
+        }
+    };
+    let role_name = match resolve_role_name(&cwd, role_name) {
+        Ok(role_name) => role_name,
+        Err(err) => {
+            eprintln!("{err}");
+            return 1;
+        }
+    };
 
-        let prompt_path = role_state::role_prompt_path(temp.path(), role_name);
-        let session_path = role_state::role_state_dir(temp.path(), role_name).join("session.md");
-        fs::write(&prompt_path, "custom prompt").expect("prompt should be mutable for test");
-        fs::write(&session_path, "custom session").expect("state file should be mutable for test");
+    let report = doctor::run_doctor(&cwd, |program, args| run_doctor_probe(&cwd, program, args));
+    print!("{}", doctor::format_report(&report));
+
+    let saved = state_files::load_session(&cwd, &role_name).and_then(|mut session| {
+        doctor::apply_to_session(&mut session, &report, &timestamp::now_utc());
+        state_files::save_session(&cwd, &role_name, &session)
+    });
+    match saved {
+        Ok(()) => println!(
+            "updated {}",
+            role_state::session_path(&cwd, &role_name).display()
+        ),
+        Err(err) => {
+            eprintln!("failed to update session for role {role_name}: {err}");
+            return 1;
+        }
+    }
 
-        let second = initialize_role(temp.path(), role_name, "seed prompt two")
-            .expect("second init should be idempotent");
-        assert_eq!(second, InitOutcome::AlreadyExists);
-        assert_eq!(
-            fs::read_to_string(&prompt_path).expect("prompt should still exist"),
-            "custom prompt"
-        );
-        assert_eq!(
-            fs::read_to_string(&session_path).expect("session should still exist"),
-            "custom session"
-        );
+    if report.has_failures() {
+        1
+    } else {
+        0
     }
+}
 
-    #[test]
-    fn initialize_role_repairs_missing_state_gitignore_even_when_role_already_exists() {
-        let temp = TestDir::new("repair-state-gitignore");
-        let role_name = "director-of-operations";
+fn watch_once_in_dir<G, F>(
+    project_root: &Path,
+    role_name: &str,
+    engine: &dyn Engine,
+    watcher: &mut watch::Watcher,
+    is_running: G,
+    engine_runner: F,
+) -> i32
+where
+    G: Fn(u32) -> bool,
+    F: FnMut(&dyn Engine, &str, Option<&str>, &Path) -> io::Result<EngineOutput>,
+{
+    let events = match watcher.poll(project_root, role_name, is_running) {
+        Ok(events) => events,
+        Err(err) => {
+            eprintln!("failed to poll role {role_name}: {err}");
+            return 1;
+        }
+    };
+    for event in &events {
+        println!("[watch] {}", event.describe());
+    }
 
-        let first = initialize_role(temp.path(), role_name, "seed prompt")
-            .expect("first init should succeed");
-        assert_eq!(first, InitOutcome::Initialized);
+    let Some(message) = watch::build_turn_message(&events) else {
+        return 0;
+    };
 
-        let state_gitignore_path = role_state::state_gitignore_path(temp.path());
-        fs::remove_file(&state_gitignore_path).expect("state gitignore should be removable");
+    let continue_id = if engine.supports_resume() {
+        match sessions::load_sessions(project_root, role_name) {
+            Ok(records) => {
+                sessions::latest_session(&records, engine.name()).map(|record| record.resume_id)
+            }
+            Err(err) => {
+                eprintln!("failed to read sessions for role {role_name}: {err}");
+                return 1;
+            }
+        }
+    } else {
+        None
+    };
 
-        let second = initialize_role(temp.path(), role_name, "seed prompt")
-            .expect("second init should still succeed");
-        assert_eq!(second, InitOutcome::AlreadyExists);
-        let state_gitignore =
-            fs::read_to_string(state_gitignore_path).expect("state gitignore should be recreated");
-        assert!(
-            state_gitignore.contains("!*/prompt.md"),
-            "state gitignore should keep role prompt files tracked"
-        );
+    let status_code = run_exec_command_in_dir(
+        project_root,
+        Some(role_name),
+        engine,
+        &message,
+        continue_id.as_deref(),
+        ExecOutputMode::Text,
+        engine_runner,
+    );
+    if status_code != 0 {
+        eprintln!("[watch] turn failed; keeping events for the next poll");
+        return status_code;
     }
+    if let Err(err) = watcher.acknowledge(project_root, role_name, &events) {
+        eprintln!("failed to acknowledge events for role {role_name}: {err}");
+        return 1;
+    }
+    0
+}
 
-    #[test]
-    fn initialize_role_repairs_missing_shared_learnings_even_when_role_already_exists() {
-        let temp = TestDir::new("repair-shared-learnings");
-        let role_name = "director-of-operations";
-
-        let first = initialize_role(temp.path(), role_name, "seed prompt")
-            .expect("first init should succeed");
-        assert_eq!(first, InitOutcome::Initialized);
-
-        let shared_learnings_path = role_state::shared_learnings_path(temp.path());
-        fs::remove_file(&shared_learnings_path).expect("shared learnings should be removable");
+fn run_watch_command(
+    role_name: Option<&str>,
+    engine_name: &str,
+    interval_secs: u64,
+    once: bool,
+) -> i32 {
+    let cwd = match env::current_dir() {
+        Ok(dir) => dir,
+        Err(err) => {
+            eprintln!("failed to get current directory: {err}");
+            return 1;
+        }
+    };
+    let role_name = match resolve_role_name(&cwd, role_name) {
+        Ok(role_name) => role_name,
+        Err(err) => {
+            eprintln!("{err}");
+            return 1;
+        }
+    };
+    let engine = match engine_config::resolve_engine(&cwd, engine_name) {
+        Ok(engine) => engine,
+        Err(err) => {
+            eprintln!("{err}");
+            return 1;
+        }
+    };
+    let mut watcher = match watch::Watcher::new(&cwd, &role_name) {
+        Ok(watcher) => watcher,
+        Err(err) => {
+            eprintln!("failed to read state for role {role_name}: {err}");
+            return 1;
+        }
+    };
 
-        let second = initialize_role(temp.path(), role_name, "seed prompt")
-            .expect("second init should still succeed");
-        assert_eq!(second, InitOutcome::AlreadyExists);
-        assert!(
-            shared_learnings_path.is_file(),
-            "shared learnings file should be recreated on repeated init"
+    if !once {
+        println!(
+            "[watch] watching {role_name} every {interval_secs}s; {} wakes only on changes",
+            engine.name()
         );
     }
 
-    #[test]
-    fn initialize_role_creates_missing_state_when_prompt_already_exists() {
-        let temp = TestDir::new("prompt-only");
-        let role_name = "operations";
-        let prompt_path = role_state::role_prompt_path(temp.path(), role_name);
-        fs::create_dir_all(
-            prompt_path
-                .parent()
-                .expect("prompt parent dir should exist"),
-        )
-        .expect("prompt parent directory should be created");
-        fs::write(&prompt_path, "# custom operations prompt").expect("prompt should be created");
-
-        let outcome = initialize_role(temp.path(), role_name, "seed prompt")
-            .expect("init should create missing state");
-        assert_eq!(outcome, InitOutcome::Initialized);
-        assert_eq!(
-            fs::read_to_string(&prompt_path).expect("prompt should remain unchanged"),
-            "# custom operations prompt"
+    loop {
+        let status_code = watch_once_in_dir(
+            &cwd,
+            &role_name,
+            engine.as_ref(),
+            &mut watcher,
+            process_check::pid_is_running,
+            |engine, prompt, continue_id, cwd| {
+                run_exec_engine(engine, prompt, continue_id, cwd, None)
+            },
         );
-
-        let role_dir = role_state::role_state_dir(temp.path(), role_name);
-        assert!(role_dir.is_dir());
-        assert!(role_dir.join("session.md").is_file());
-        assert!(role_dir.join("needs-from-operator.md").is_file());
-        assert!(role_dir.join("projects.md").is_file());
-        assert!(role_dir.join("processes.md").is_file());
-        assert!(role_dir.join("artifacts").is_dir());
-        assert!(role_state::shared_learnings_path(temp.path()).is_file());
+        if once {
+            return status_code;
+        }
+        thread::sleep(Duration::from_secs(interval_secs));
     }
+}
 
-    #[test]
-    fn initialize_role_scaffolds_missing_state_files_when_prompt_and_legacy_dir_exist() {
-        let temp = TestDir::new("artifacts-prompt-and-legacy-dir");
-        let role_name = "artifacts";
-        let role_dir = role_state::role_state_dir(temp.path(), role_name);
-        fs::create_dir_all(&role_dir).expect("legacy artifacts directory should be created");
-        fs::write(role_dir.join("legacy-note.txt"), "legacy artifact")
-            .expect("legacy artifacts file should be created");
-
-        let prompt_path = role_state::role_prompt_path(temp.path(), role_name);
-        fs::write(&prompt_path, "# legacy artifacts prompt").expect("prompt should be created");
+fn load_session_threads(
+    project_root: &Path,
+    role_name: Option<&str>,
+) -> Result<Vec<sessions::SessionThread>, String> {
+    let role_name = resolve_role_name(project_root, role_name)?;
+    let records = sessions::load_sessions(project_root, &role_name)
+        .map_err(|err| format!("failed to read sessions for role {role_name}: {err}"))?;
+    Ok(sessions::group_threads(&records))
+}
 
-        let outcome = initialize_role(temp.path(), role_name, "seed prompt")
-            .expect("init should scaffold missing state files");
-        assert_eq!(outcome, InitOutcome::Initialized);
-        assert_eq!(
-            fs::read_to_string(&prompt_path).expect("prompt should remain unchanged"),
-            "# legacy artifacts prompt"
-        );
+fn run_sessions_list_command(role_name: Option<&str>) -> i32 {
+    let cwd = match env::current_dir() {
+        Ok(dir) => dir,
+        Err(err) => {
+            eprintln!("failed to get current directory: {err}");
+            return 1;
+        }
+    };
 
-        assert!(role_state::role_state_is_scaffolded(temp.path(), role_name));
-        assert!(
-            role_state::discover_configured_roles(temp.path())
-                .expect("role discovery should succeed")
-                .iter()
-                .any(|role| role.name == role_name),
-            "artifacts role should be discoverable after scaffolding"
-        );
+    match load_session_threads(&cwd, role_name) {
+        Ok(threads) => {
+            print!("{}", sessions::format_thread_list(&threads));
+            0
+        }
+        Err(err) => {
+            eprintln!("{err}");
+            1
+        }
     }
+}
 
-    #[test]
-    fn initialize_role_creates_missing_prompt_when_state_already_exists() {
-        let temp = TestDir::new("state-only");
-        let role_name = "program-manager";
-        role_state::create_role_state(temp.path(), role_name)
-            .expect("state scaffold should be created");
+fn run_sessions_show_command(role_name: Option<&str>, resume_id: &str) -> i32 {
+    let cwd = match env::current_dir() {
+        Ok(dir) => dir,
+        Err(err) => {
+            eprintln!("failed to get current directory: {err}");
+            return 1;
+        }
+    };
 
-        let session_path = role_state::role_state_dir(temp.path(), role_name).join("session.md");
-        fs::write(&session_path, "existing session data").expect("session should be writable");
+    let thread = load_session_threads(&cwd, role_name).and_then(|threads| {
+        threads
+            .into_iter()
+            .rev()
+            .find(|thread| thread.resume_id == resume_id)
+            .ok_or_else(|| format!("Session not found: {resume_id}. Run: juliet sessions list"))
+    });
+
+    match thread {
+        Ok(thread) => {
+            print!("{}", sessions::format_thread(&thread));
+            0
+        }
+        Err(err) => {
+            eprintln!("{err}");
+            1
+        }
+    }
+}
 
-        let outcome =
-            initialize_role(temp.path(), role_name, "## Embedded seed").expect("init should work");
-        assert_eq!(outcome, InitOutcome::Initialized);
-        assert_eq!(
-            fs::read_to_string(&session_path).expect("session should remain unchanged"),
-            "existing session data"
-        );
+fn run_archive_command(role_name: &str, keep: bool) -> i32 {
+    let cwd = match env::current_dir() {
+        Ok(dir) => dir,
+        Err(err) => {
+            eprintln!("failed to get current directory: {err}");
+            return 1;
+        }
+    };
 
-        let prompt_path = role_state::role_prompt_path(temp.path(), role_name);
-        let prompt_contents = fs::read_to_string(prompt_path).expect("prompt should be readable");
-        assert!(prompt_contents.contains("# program-manager"));
-        assert!(prompt_contents.contains(OPERATOR_PLACEHOLDER));
-        assert!(prompt_contents.contains("## Embedded seed"));
+    match archive::archive_role(&cwd, role_name, keep) {
+        Ok(archive_path) => {
+            println!(
+                "archived role {role_name} to {}",
+                archive_path
+                    .strip_prefix(&cwd)
+                    .unwrap_or(&archive_path)
+                    .display()
+            );
+            0
+        }
+        Err(err) => {
+            eprintln!("{err}");
+            1
+        }
     }
+}
 
-    #[test]
-    fn build_launch_prompt_appends_operator_input() {
-        let base = "# Role prompt\n\nDo role work.";
-        assert_eq!(build_launch_prompt(base, None), base.to_string());
-        assert_eq!(
-            build_launch_prompt(base, Some("please continue from yesterday")),
-            "# Role prompt\n\nDo role work.\n\nUser input:\nplease continue from yesterday"
-                .to_string()
-        );
-    }
+fn run_restore_command(role_name: &str, archive_name: Option<&str>) -> i32 {
+    let cwd = match env::current_dir() {
+        Ok(dir) => dir,
+        Err(err) => {
+            eprintln!("failed to get current directory: {err}");
+            return 1;
+        }
+    };
 
-    // reset_prompt unit tests
+    match archive::restore_role(&cwd, role_name, archive_name) {
+        Ok(archive) => {
+            println!(
+                "restored role {role_name} from {}",
+                archive
+                    .path
+                    .strip_prefix(&cwd)
+                    .unwrap_or(&archive.path)
+                    .display()
+            );
+            0
+        }
+        Err(err) => {
+            eprintln!("{err}");
+            1
+        }
+    }
+}
 
-    #[test]
-    fn reset_prompt_rejects_invalid_role_name() {
-        let temp = TestDir::new("reset-prompt-invalid-name");
-        let err = reset_prompt(temp.path(), "Invalid_Name", "seed prompt")
-            .expect_err("invalid role name should fail");
+fn run_roles_list_command() -> i32 {
+    let cwd = match env::current_dir() {
+        Ok(dir) => dir,
+        Err(err) => {
+            eprintln!("failed to get current directory: {err}");
+            return 1;
+        }
+    };
 
-        assert_eq!(
-            err,
-            "Invalid role name: Invalid_Name. Use lowercase letters, numbers, and hyphens."
-        );
+    match roles::list_roles(&cwd, OPERATOR_PLACEHOLDER) {
+        Ok(roles) => {
+            print!("{}", roles::format_role_list(&roles));
+            0
+        }
+        Err(err) => {
+            eprintln!("failed to list roles: {err}");
+            1
+        }
     }
+}
 
-    #[test]
-    fn reset_prompt_fails_when_role_not_initialized() {
-        let temp = TestDir::new("reset-prompt-not-initialized");
-        let err = reset_prompt(temp.path(), "missing-role", "seed prompt")
-            .expect_err("uninitialized role should fail");
+fn run_roles_rename_command(from: &str, to: &str) -> i32 {
+    let cwd = match env::current_dir() {
+        Ok(dir) => dir,
+        Err(err) => {
+            eprintln!("failed to get current directory: {err}");
+            return 1;
+        }
+    };
 
-        assert_eq!(err, "Role 'missing-role' is not initialized.");
+    match roles::rename_role(&cwd, from, to) {
+        Ok(()) => {
+            println!("renamed role {from} to {to}");
+            0
+        }
+        Err(err) => {
+            eprintln!("{err}");
+            1
+        }
     }
+}
 
-    #[test]
-    fn reset_prompt_overwrites_prompt_with_default_template() {
-        let temp = TestDir::new("reset-prompt-overwrite");
-        let role_name = "director-of-engineering";
-        let seed = "## Seeded prompt content";
-
-        initialize_role(temp.path(), role_name, seed).expect("init should succeed");
+fn run_roles_clone_command(source: &str, target: &str) -> i32 {
+    let cwd = match env::current_dir() {
+        Ok(dir) => dir,
+        Err(err) => {
+            eprintln!("failed to get current directory: {err}");
+            return 1;
+        }
+    };
 
-        let prompt_path = role_state::role_prompt_path(temp.path(), role_name);
-        fs::write(
-            &prompt_path,
-            "# Custom modified prompt\n\nUser changes here.",
-        )
-        .expect("prompt should be writable");
+    match roles::clone_role(&cwd, source, target) {
+        Ok(()) => {
+            println!("cloned role {source} to {target}");
+            0
+        }
+        Err(err) => {
+            eprintln!("{err}");
+            1
+        }
+    }
+}
 
-        reset_prompt(temp.path(), role_name, seed).expect("reset_prompt should succeed");
+fn run_roles_delete_command(role_name: &str, force: bool) -> i32 {
+    let cwd = match env::current_dir() {
+        Ok(dir) => dir,
+        Err(err) => {
+            eprintln!("failed to get current directory: {err}");
+            return 1;
+        }
+    };
 
-        let prompt_contents =
-            fs::read_to_string(&prompt_path).expect("prompt should be readable after reset");
-        let expected = role_prompt_template(role_name, seed);
-        assert_eq!(prompt_contents, expected);
-        assert!(prompt_contents.contains(&format!("# {role_name}")));
-        assert!(prompt_contents.contains(OPERATOR_PLACEHOLDER));
-        assert!(prompt_contents.contains(seed));
+    let confirm = |role_name: &str| {
+        if force {
+            return Ok(());
+        }
+        if !io::stdin().is_terminal() {
+            return Err(format!(
+                "Refusing to delete role {role_name} without confirmation. Re-run with --force."
+            ));
+        }
+
+        print!("Delete role {role_name} and all of its state? [y/N] ");
+        let _ = io::stdout().flush();
+        let mut answer = String::new();
+        io::stdin()
+            .read_line(&mut answer)
+            .map_err(|err| format!("failed to read confirmation: {err}"))?;
+        if matches!(answer.trim(), "y" | "Y" | "yes") {
+            Ok(())
+        } else {
+            Err("Deletion cancelled.".to_string())
+        }
+    };
+
+    match roles::delete_role(&cwd, role_name, confirm) {
+        Ok(()) => {
+            println!("deleted role {role_name}");
+            0
+        }
+        Err(err) => {
+            eprintln!("{err}");
+            1
+        }
     }
+}
 
-    #[test]
-    fn reset_prompt_preserves_state_files() {
-        let temp = TestDir::new("reset-prompt-preserves-state");
-        let role_name = "operations";
-        let seed = "## Seed";
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let command = match parse_cli_command(&args) {
+        Ok(parsed) => parsed,
+        Err(err) => {
+            let exit_code = err.exit_code();
+            let _ = err.print();
+            std::process::exit(exit_code);
+        }
+    };
 
-        initialize_role(temp.path(), role_name, seed).expect("init should succeed");
+    let exit_code = match command {
+        CliCommand::Init {
+            role_name,
+            template,
+            from,
+        } => run_init_command(&role_name, template.as_deref(), from.as_deref()),
+        CliCommand::Launch {
+            role_name,
+            engine,
+            operator_input,
+            timeout,
+        } => run_launch_command(
+            role_name.as_deref(),
+            &engine,
+            operator_input.as_deref(),
+            timeout,
+        ),
+        CliCommand::ResetPrompt { role_name } => run_reset_prompt_command(&role_name),
+        CliCommand::UpgradePrompt { role_name } => run_upgrade_prompt_command(&role_name),
+        CliCommand::LintPrompt { role_name } => run_lint_prompt_command(&role_name),
+        CliCommand::SyncTemplate { role_name } => run_sync_template_command(&role_name),
+        CliCommand::Run { role_name, request } => run_swarm_command(&role_name, &request),
+        CliCommand::ClearHistory { role_name, undo } => run_clear_history_command(&role_name, undo),
+        CliCommand::Exec {
+            role_name,
+            engine,
+            message,
+            continue_id,
+            continue_last,
+            json_output,
+            stream_output,
+            timeout,
+            engine_fallback,
+        } => {
+            let output_mode = if stream_output {
+                ExecOutputMode::Stream
+            } else if json_output {
+                ExecOutputMode::Json
+            } else {
+                ExecOutputMode::Text
+            };
+            let request = ExecRequest {
+                role_name: role_name.as_deref(),
+                message: &message,
+                continue_id: continue_id.as_deref(),
+                continue_last,
+                output_mode,
+            };
+            run_exec_command(request, &engine, timeout, &engine_fallback)
+        }
+        CliCommand::Status {
+            role_name,
+            json_output,
+        } => run_status_command(role_name.as_deref(), json_output),
+        CliCommand::Doctor { role_name } => run_doctor_command(role_name.as_deref()),
+        CliCommand::ProcessesCheck { role_name } => {
+            run_processes_check_command(role_name.as_deref())
+        }
+        CliCommand::Tasks {
+            swarm_project,
+            json_output,
+        } => run_tasks_command(&swarm_project, json_output),
+        CliCommand::TasksCheck { swarm_project } => run_tasks_check_command(&swarm_project),
+        CliCommand::Sprints { swarm_project } => run_sprints_command(swarm_project.as_deref()),
+        CliCommand::WorktreesList => run_worktrees_list_command(),
+        CliCommand::WorktreesPrune { dry_run } => run_worktrees_prune_command(dry_run),
+        CliCommand::Watch {
+            role_name,
+            engine,
+            interval_secs,
+            once,
+        } => run_watch_command(role_name.as_deref(), &engine, interval_secs, once),
+        CliCommand::SessionsList { role_name } => run_sessions_list_command(role_name.as_deref()),
+        CliCommand::SessionsShow {
+            role_name,
+            resume_id,
+        } => run_sessions_show_command(role_name.as_deref(), &resume_id),
+        CliCommand::Archive { role_name, keep } => run_archive_command(&role_name, keep),
+        CliCommand::Restore {
+            role_name,
+            archive_name,
+        } => run_restore_command(&role_name, archive_name.as_deref()),
+        CliCommand::RolesList => run_roles_list_command(),
+        CliCommand::TemplatesList => run_templates_list_command(),
+        CliCommand::RolesRename { from, to } => run_roles_rename_command(&from, &to),
+        CliCommand::RolesClone { source, target } => run_roles_clone_command(&source, &target),
+        CliCommand::RolesDelete { role_name, force } => run_roles_delete_command(&role_name, force),
+    };
 
-        let session_path = role_state::role_state_dir(temp.path(), role_name).join("session.md");
-        fs::write(&session_path, "important session data").expect("session should be writable");
+    std::process::exit(exit_code);
+}
 
-        reset_prompt(temp.path(), role_name, seed).expect("reset_prompt should succeed");
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::TestDir;
+    use std::fs;
+    use std::path::{Path, PathBuf};
 
-        assert_eq!(
-            fs::read_to_string(&session_path).expect("session should still exist"),
-            "important session data"
-        );
+    fn to_args(values: &[&str]) -> Vec<String> {
+        values.iter().map(|value| (*value).to_string()).collect()
     }
 
-    // clear_history unit tests
-
     #[test]
-    fn clear_history_rejects_invalid_role_name() {
-        let temp = TestDir::new("clear-history-invalid-name");
-        let err =
-            clear_history(temp.path(), "Invalid_Name").expect_err("invalid role name should fail");
-
+    fn parses_init_with_project() {
+        let parsed = parse_cli_command(&to_args(&["init", "--project", "director-of-engineering"]))
+            .expect("init parse should succeed");
         assert_eq!(
-            err,
-            "Invalid role name: Invalid_Name. Use lowercase letters, numbers, and hyphens."
+            parsed,
+            CliCommand::Init {
+                role_name: "director-of-engineering".to_string(),
+                template: None,
+                from: None,
+            }
         );
     }
 
     #[test]
-    fn clear_history_fails_when_role_not_initialized() {
-        let temp = TestDir::new("clear-history-not-initialized");
-        let err =
-            clear_history(temp.path(), "missing-role").expect_err("uninitialized role should fail");
-
-        assert_eq!(err, "Role 'missing-role' is not initialized.");
+    fn parses_init_with_role_alias() {
+        let parsed = parse_cli_command(&to_args(&["init", "--role", "director-of-engineering"]))
+            .expect("init alias parse should succeed");
+        assert_eq!(
+            parsed,
+            CliCommand::Init {
+                role_name: "director-of-engineering".to_string(),
+                template: None,
+                from: None,
+            }
+        );
     }
 
     #[test]
-    fn clear_history_empties_state_files() {
-        let temp = TestDir::new("clear-history-empties-state");
-        let role_name = "director-of-engineering";
-
-        initialize_role(temp.path(), role_name, "seed").expect("init should succeed");
-
-        let role_dir = role_state::role_state_dir(temp.path(), role_name);
-        fs::write(role_dir.join("session.md"), "session data").expect("write session");
-        fs::write(role_dir.join("needs-from-operator.md"), "operator needs").expect("write needs");
-        fs::write(role_dir.join("projects.md"), "project data").expect("write projects");
-        fs::write(role_dir.join("processes.md"), "process data").expect("write processes");
-        let shared_learnings_path = role_state::shared_learnings_path(temp.path());
-        fs::write(&shared_learnings_path, "learning data").expect("write shared learnings");
-
-        clear_history(temp.path(), role_name).expect("clear_history should succeed");
-
-        assert_eq!(fs::read_to_string(role_dir.join("session.md")).unwrap(), "");
+    fn parses_init_with_template_and_templates_list() {
         assert_eq!(
-            fs::read_to_string(role_dir.join("needs-from-operator.md")).unwrap(),
-            ""
+            parse_cli_command(&to_args(&[
+                "init",
+                "--project",
+                "qa",
+                "--template",
+                "reviewer"
+            ]))
+            .expect("init template parse should succeed"),
+            CliCommand::Init {
+                role_name: "qa".to_string(),
+                template: Some("reviewer".to_string()),
+                from: None,
+            }
         );
         assert_eq!(
-            fs::read_to_string(role_dir.join("projects.md")).unwrap(),
-            ""
+            parse_cli_command(&to_args(&["templates", "list"]))
+                .expect("templates list parse should succeed"),
+            CliCommand::TemplatesList
         );
+    }
+
+    #[test]
+    fn parses_init_from_path_and_sync_template() {
         assert_eq!(
-            fs::read_to_string(role_dir.join("processes.md")).unwrap(),
-            ""
+            parse_cli_command(&to_args(&[
+                "init",
+                "--project",
+                "qa",
+                "--from",
+                "../team/qa"
+            ]))
+            .expect("init from parse should succeed"),
+            CliCommand::Init {
+                role_name: "qa".to_string(),
+                template: None,
+                from: Some(PathBuf::from("../team/qa")),
+            }
         );
+        assert!(parse_cli_command(&to_args(&[
+            "init",
+            "--project",
+            "qa",
+            "--from",
+            "../team/qa",
+            "--template",
+            "reviewer"
+        ]))
+        .is_err());
         assert_eq!(
-            fs::read_to_string(shared_learnings_path).unwrap(),
-            "learning data"
+            parse_cli_command(&to_args(&["sync-template", "--project", "qa"]))
+                .expect("sync-template parse should succeed"),
+            CliCommand::SyncTemplate {
+                role_name: "qa".to_string()
+            }
         );
     }
 
     #[test]
-    fn clear_history_deletes_juliet_prompt_md() {
-        let temp = TestDir::new("clear-history-deletes-runtime-prompt");
-        let role_name = "operations";
-
-        initialize_role(temp.path(), role_name, "seed").expect("init should succeed");
-
-        let runtime_path = role_state::runtime_prompt_path(temp.path(), role_name);
-        fs::write(&runtime_path, "runtime prompt content").expect("write runtime prompt");
-        assert!(runtime_path.exists());
-
-        clear_history(temp.path(), role_name).expect("clear_history should succeed");
-
-        assert!(!runtime_path.exists(), "juliet-prompt.md should be deleted");
+    fn parses_explicit_role_launch_with_alias_and_operator_input() {
+        let parsed = parse_cli_command(&to_args(&[
+            "--role",
+            "director-of-engineering",
+            "codex",
+            "continue",
+            "project",
+            "alpha",
+        ]))
+        .expect("explicit launch parse should succeed");
+        assert_eq!(
+            parsed,
+            CliCommand::Launch {
+                role_name: Some("director-of-engineering".to_string()),
+                engine: "codex".to_string(),
+                operator_input: Some("continue project alpha".to_string()),
+                timeout: None,
+            }
+        );
     }
 
     #[test]
-    fn clear_history_succeeds_when_juliet_prompt_md_absent() {
-        let temp = TestDir::new("clear-history-no-runtime-prompt");
-        let role_name = "qa";
-
-        initialize_role(temp.path(), role_name, "seed").expect("init should succeed");
-
-        let runtime_path = role_state::runtime_prompt_path(temp.path(), role_name);
-        assert!(!runtime_path.exists());
-
-        clear_history(temp.path(), role_name)
-            .expect("clear_history should succeed without runtime prompt");
+    fn parses_implicit_role_launch() {
+        let parsed =
+            parse_cli_command(&to_args(&["claude"])).expect("implicit launch parse should succeed");
+        assert_eq!(
+            parsed,
+            CliCommand::Launch {
+                role_name: None,
+                engine: "claude".to_string(),
+                operator_input: None,
+                timeout: None,
+            }
+        );
     }
 
     #[test]
-    fn clear_history_clears_artifacts_directory_contents() {
-        let temp = TestDir::new("clear-history-clears-artifacts");
-        let role_name = "engineering";
-
-        initialize_role(temp.path(), role_name, "seed").expect("init should succeed");
-
-        let artifacts_dir = role_state::role_state_dir(temp.path(), role_name).join("artifacts");
-        fs::write(artifacts_dir.join("report.txt"), "report content").expect("write artifact file");
-        fs::create_dir_all(artifacts_dir.join("subdir")).expect("create artifact subdir");
-        fs::write(
-            artifacts_dir.join("subdir").join("nested.md"),
-            "nested content",
-        )
-        .expect("write nested artifact");
-
-        clear_history(temp.path(), role_name).expect("clear_history should succeed");
-
-        assert!(
-            artifacts_dir.is_dir(),
-            "artifacts directory should be preserved"
-        );
+    fn parses_reset_prompt_with_alias() {
+        let parsed = parse_cli_command(&to_args(&["reset-prompt", "--role", "ops"]))
+            .expect("reset-prompt parse should succeed");
         assert_eq!(
-            fs::read_dir(&artifacts_dir).unwrap().count(),
-            0,
-            "artifacts directory should be empty"
+            parsed,
+            CliCommand::ResetPrompt {
+                role_name: "ops".to_string()
+            }
         );
     }
 
     #[test]
-    fn clear_history_preserves_prompt_md() {
-        let temp = TestDir::new("clear-history-preserves-prompt");
-        let role_name = "director-of-marketing";
-
-        initialize_role(temp.path(), role_name, "seed").expect("init should succeed");
-
-        let prompt_path = role_state::role_prompt_path(temp.path(), role_name);
-        fs::write(&prompt_path, "# Custom prompt\n\nKeep this intact.")
-            .expect("write custom prompt");
-
-        clear_history(temp.path(), role_name).expect("clear_history should succeed");
-
+    fn parses_clear_history_with_project() {
+        let parsed = parse_cli_command(&to_args(&["clear-history", "--project", "qa-team"]))
+            .expect("clear-history parse should succeed");
         assert_eq!(
-            fs::read_to_string(&prompt_path).unwrap(),
-            "# Custom prompt\n\nKeep this intact."
+            parsed,
+            CliCommand::ClearHistory {
+                role_name: "qa-team".to_string(),
+                undo: false,
+            }
         );
     }
 
-    // exec command unit tests
-
     #[test]
-    fn exec_explicit_role_stages_prompt_and_appends_message() {
-        let temp = TestDir::new("exec-explicit-role");
-        let role_name = "director-of-engineering";
-        let role_prompt = "# Exec prompt\n\nDo role work.";
-        role_state::create_role_state(temp.path(), role_name).expect("role state should exist");
-        fs::write(
-            role_state::role_prompt_path(temp.path(), role_name),
-            role_prompt,
-        )
-        .expect("role prompt should be written");
-
-        let mut captured_engine = None;
-        let mut captured_prompt = String::new();
-        let exit_code = run_exec_command_in_dir(
-            temp.path(),
-            Some(role_name),
-            Engine::Codex,
-            "fix the bug",
-            None,
-            false,
-            |engine, prompt, continue_id, cwd| {
-                captured_engine = Some(engine);
-                captured_prompt = prompt.to_string();
-                assert_eq!(continue_id, None);
-                assert_eq!(cwd, temp.path());
-                Ok(EngineOutput {
-                    status_code: 0,
-                    stdout:
-                        "{\"thread_id\":\"thread-1\"}\n{\"type\":\"item.completed\",\"item\":{\"text\":\"done\"}}\n"
-                            .to_string(),
-                    stderr: String::new(),
-                })
-            },
+    fn parses_exec_implicit_and_explicit() {
+        let implicit = parse_cli_command(&to_args(&["exec", "claude", "do", "the", "thing"]))
+            .expect("implicit exec parse should succeed");
+        assert_eq!(
+            implicit,
+            CliCommand::Exec {
+                role_name: None,
+                engine: "claude".to_string(),
+                message: "do the thing".to_string(),
+                continue_id: None,
+                continue_last: false,
+                json_output: false,
+                stream_output: false,
+                timeout: None,
+                engine_fallback: Vec::new(),
+            }
         );
 
-        assert_eq!(exit_code, 0);
-        assert_eq!(captured_engine, Some(Engine::Codex));
+        let explicit = parse_cli_command(&to_args(&[
+            "exec", "--role", "my-role", "codex", "fix", "the", "bug",
+        ]))
+        .expect("explicit exec parse should succeed");
         assert_eq!(
-            captured_prompt,
-            "# Exec prompt\n\nDo role work.\n\nUser input:\nfix the bug"
+            explicit,
+            CliCommand::Exec {
+                role_name: Some("my-role".to_string()),
+                engine: "codex".to_string(),
+                message: "fix the bug".to_string(),
+                continue_id: None,
+                continue_last: false,
+                json_output: false,
+                stream_output: false,
+                timeout: None,
+                engine_fallback: Vec::new(),
+            }
         );
-
-        let runtime_prompt =
-            fs::read_to_string(role_state::runtime_prompt_path(temp.path(), role_name))
-                .expect("runtime prompt should be written");
-        assert_eq!(runtime_prompt, role_prompt);
     }
 
     #[test]
-    fn exec_implicit_single_role_stages_prompt_and_appends_message() {
-        let temp = TestDir::new("exec-implicit-single-role");
-        let role_name = "director-of-engineering";
-        let role_prompt = "# Implicit exec prompt\n\nDo role work.";
-        role_state::create_role_state(temp.path(), role_name).expect("role state should exist");
-        fs::write(
-            role_state::role_prompt_path(temp.path(), role_name),
-            role_prompt,
-        )
-        .expect("role prompt should be written");
-
-        let mut captured_engine = None;
-        let mut captured_prompt = String::new();
-        let exit_code = run_exec_command_in_dir(
-            temp.path(),
-            None,
-            Engine::Claude,
-            "deploy the app",
-            None,
-            false,
-            |engine, prompt, continue_id, cwd| {
-                captured_engine = Some(engine);
-                captured_prompt = prompt.to_string();
-                assert_eq!(continue_id, None);
-                assert_eq!(cwd, temp.path());
-                Ok(EngineOutput {
-                    status_code: 0,
-                    stdout: "{\"session_id\":\"session-1\",\"result\":\"done\"}\n".to_string(),
-                    stderr: String::new(),
-                })
-            },
+    fn parses_exec_with_hyphen_prefixed_role_value() {
+        let parsed = parse_cli_command(&to_args(&[
+            "exec",
+            "--project",
+            "-leading",
+            "claude",
+            "hello",
+        ]))
+        .expect("exec parse should allow hyphen-prefixed role values");
+        assert_eq!(
+            parsed,
+            CliCommand::Exec {
+                role_name: Some("-leading".to_string()),
+                engine: "claude".to_string(),
+                message: "hello".to_string(),
+                continue_id: None,
+                continue_last: false,
+                json_output: false,
+                stream_output: false,
+                timeout: None,
+                engine_fallback: Vec::new(),
+            }
         );
+    }
 
-        assert_eq!(exit_code, 0);
-        assert_eq!(captured_engine, Some(Engine::Claude));
+    #[test]
+    fn parses_exec_continue_and_json_options() {
+        let parsed = parse_cli_command(&to_args(&[
+            "exec",
+            "--project",
+            "my-role",
+            "--continue",
+            "session-123",
+            "--json",
+            "codex",
+            "ship",
+            "it",
+        ]))
+        .expect("exec parse with continue/json options should succeed");
         assert_eq!(
-            captured_prompt,
-            "# Implicit exec prompt\n\nDo role work.\n\nUser input:\ndeploy the app"
+            parsed,
+            CliCommand::Exec {
+                role_name: Some("my-role".to_string()),
+                engine: "codex".to_string(),
+                message: "ship it".to_string(),
+                continue_id: Some("session-123".to_string()),
+                continue_last: false,
+                json_output: true,
+                stream_output: false,
+                timeout: None,
+                engine_fallback: Vec::new(),
+            }
         );
-
-        let runtime_prompt =
-            fs::read_to_string(role_state::runtime_prompt_path(temp.path(), role_name))
-                .expect("runtime prompt should be written");
-        assert_eq!(runtime_prompt, role_prompt);
     }
 
     #[test]
-    fn exec_returns_engine_exit_code() {
-        let temp = TestDir::new("exec-engine-exit-code");
-        let role_name = "director-of-engineering";
-        role_state::create_role_state(temp.path(), role_name).expect("role state should exist");
-        fs::write(
-            role_state::role_prompt_path(temp.path(), role_name),
-            "# prompt",
-        )
-        .expect("role prompt should be written");
+    fn parses_exec_json_option_after_message() {
+        let parsed = parse_cli_command(&to_args(&["exec", "codex", "hello", "--json"]))
+            .expect("exec parse should allow --json after message");
+        assert_eq!(
+            parsed,
+            CliCommand::Exec {
+                role_name: None,
+                engine: "codex".to_string(),
+                message: "hello".to_string(),
+                continue_id: None,
+                continue_last: false,
+                json_output: true,
+                stream_output: false,
+                timeout: None,
+                engine_fallback: Vec::new(),
+            }
+        );
+    }
 
-        let exit_code = run_exec_command_in_dir(
-            temp.path(),
-            Some(role_name),
-            Engine::Claude,
-            "hello",
-            None,
-            false,
-            |_engine, _prompt, _continue_id, _cwd| {
-                Ok(EngineOutput {
-                    status_code: 42,
-                    stdout: String::new(),
-                    stderr: String::new(),
-                })
-            },
+    #[test]
+    fn parses_exec_stream_option_and_rejects_json_with_stream() {
+        let parsed = parse_cli_command(&to_args(&["exec", "--stream", "claude", "hello"]))
+            .expect("exec parse with --stream should succeed");
+        assert_eq!(
+            parsed,
+            CliCommand::Exec {
+                role_name: None,
+                engine: "claude".to_string(),
+                message: "hello".to_string(),
+                continue_id: None,
+                continue_last: false,
+                json_output: false,
+                stream_output: true,
+                timeout: None,
+                engine_fallback: Vec::new(),
+            }
         );
 
-        assert_eq!(exit_code, 42);
+        let err = parse_cli_command(&to_args(&["exec", "--stream", "--json", "claude", "hello"]))
+            .expect_err("--stream and --json should conflict");
+        assert_eq!(err.kind(), ErrorKind::ArgumentConflict);
     }
 
     #[test]
-    fn exec_fails_when_explicit_role_is_missing() {
-        let temp = TestDir::new("exec-missing-role");
-
-        let exit_code = run_exec_command_in_dir(
-            temp.path(),
-            Some("missing-role"),
-            Engine::Codex,
+    fn parses_engine_fallback_list() {
+        let parsed = parse_cli_command(&to_args(&[
+            "exec",
+            "--engine-fallback",
+            "codex,claude",
+            "claude",
             "hello",
-            None,
-            false,
-            |_engine, _prompt, _continue_id, _cwd| unreachable!("runner should not be called"),
+        ]))
+        .expect("exec parse with --engine-fallback should succeed");
+        assert_eq!(
+            parsed,
+            CliCommand::Exec {
+                role_name: None,
+                engine: "claude".to_string(),
+                message: "hello".to_string(),
+                continue_id: None,
+                continue_last: false,
+                json_output: false,
+                stream_output: false,
+                timeout: None,
+                engine_fallback: vec!["codex".to_string(), "claude".to_string()],
+            }
         );
 
-        assert_eq!(exit_code, 1);
+        let err = parse_cli_command(&to_args(&[
+            "exec",
+            "--engine-fallback",
+            "codex,Bad",
+            "claude",
+            "hello",
+        ]))
+        .expect_err("invalid fallback engine names should fail");
+        assert_eq!(err.kind(), ErrorKind::ValueValidation);
     }
 
     #[test]
-    fn exec_fails_when_no_roles_configured_for_implicit_discovery() {
-        let temp = TestDir::new("exec-no-roles");
+    fn parses_timeout_for_exec_and_launch() {
+        let exec = parse_cli_command(&to_args(&["exec", "--timeout", "5m", "codex", "hello"]))
+            .expect("exec parse with --timeout should succeed");
+        assert_eq!(
+            exec,
+            CliCommand::Exec {
+                role_name: None,
+                engine: "codex".to_string(),
+                message: "hello".to_string(),
+                continue_id: None,
+                continue_last: false,
+                json_output: false,
+                stream_output: false,
+                timeout: Some(Duration::from_secs(300)),
+                engine_fallback: Vec::new(),
+            }
+        );
 
-        let exit_code = run_exec_command_in_dir(
-            temp.path(),
-            None,
-            Engine::Claude,
-            "hello",
-            None,
-            false,
-            |_engine, _prompt, _continue_id, _cwd| unreachable!("runner should not be called"),
+        let launch = parse_cli_command(&to_args(&["--timeout", "90", "claude"]))
+            .expect("launch parse with --timeout should succeed");
+        assert_eq!(
+            launch,
+            CliCommand::Launch {
+                role_name: None,
+                engine: "claude".to_string(),
+                operator_input: None,
+                timeout: Some(Duration::from_secs(90)),
+            }
         );
 
-        assert_eq!(exit_code, 1);
+        let err = parse_cli_command(&to_args(&["exec", "--timeout", "soon", "codex", "hi"]))
+            .expect_err("invalid duration should fail");
+        assert_eq!(err.kind(), ErrorKind::ValueValidation);
     }
 
     #[test]
-    fn exec_fails_when_multiple_roles_configured_for_implicit_discovery() {
-        let temp = TestDir::new("exec-multiple-roles");
-        role_state::create_role_state(temp.path(), "alpha-team")
-            .expect("alpha role state should exist");
-        role_state::create_role_state(temp.path(), "zeta-team")
-            .expect("zeta role state should exist");
+    fn parses_status_with_optional_role_and_json() {
+        let all_roles =
+            parse_cli_command(&to_args(&["status"])).expect("status parse should succeed");
+        assert_eq!(
+            all_roles,
+            CliCommand::Status {
+                role_name: None,
+                json_output: false,
+            }
+        );
 
-        let exit_code = run_exec_command_in_dir(
-            temp.path(),
-            None,
-            Engine::Codex,
-            "hello",
-            None,
-            false,
-            |_engine, _prompt, _continue_id, _cwd| unreachable!("runner should not be called"),
+        let single_role = parse_cli_command(&to_args(&["status", "--role", "eng", "--json"]))
+            .expect("status parse with role and json should succeed");
+        assert_eq!(
+            single_role,
+            CliCommand::Status {
+                role_name: Some("eng".to_string()),
+                json_output: true,
+            }
         );
+    }
 
-        assert_eq!(exit_code, 1);
+    #[test]
+    fn parses_doctor_with_optional_role() {
+        assert_eq!(
+            parse_cli_command(&to_args(&["doctor"])).expect("doctor parse should succeed"),
+            CliCommand::Doctor { role_name: None }
+        );
+        assert_eq!(
+            parse_cli_command(&to_args(&["doctor", "--role", "eng"]))
+                .expect("doctor parse with role should succeed"),
+            CliCommand::Doctor {
+                role_name: Some("eng".to_string()),
+            }
+        );
     }
 
     #[test]
-    fn exec_fails_when_engine_runner_returns_error() {
-        let temp = TestDir::new("exec-engine-error");
-        let role_name = "director-of-engineering";
-        role_state::create_role_state(temp.path(), role_name).expect("role state should exist");
-        fs::write(
-            role_state::role_prompt_path(temp.path(), role_name),
-            "# prompt",
-        )
-        .expect("role prompt should be written");
-
-        let exit_code = run_exec_command_in_dir(
-            temp.path(),
-            Some(role_name),
-            Engine::Claude,
-            "hello",
-            None,
-            false,
-            |_engine, _prompt, _continue_id, _cwd| {
-                Err(io::Error::new(io::ErrorKind::NotFound, "engine not found"))
-            },
+    fn parses_run_with_defaults_and_rejects_zero_sprints() {
+        assert_eq!(
+            parse_cli_command(&to_args(&[
+                "run",
+                "--project",
+                "eng",
+                "--swarm-project",
+                "wind",
+                "--sprints",
+                "3",
+                "--engine",
+                "codex"
+            ]))
+            .expect("run parse should succeed"),
+            CliCommand::Run {
+                role_name: "eng".to_string(),
+                request: swarm_run::RunRequest {
+                    swarm_project: "wind".to_string(),
+                    variations: 1,
+                    sprints: 3,
+                    engine: Some("codex".to_string()),
+                    source_branch: None,
+                    target_branch: None,
+                },
+            }
         );
-
-        assert_eq!(exit_code, 1);
+        assert!(parse_cli_command(&to_args(&[
+            "run",
+            "--project",
+            "eng",
+            "--swarm-project",
+            "wind",
+            "--sprints",
+            "0"
+        ]))
+        .is_err());
     }
 
     #[test]
-    fn exec_rejects_invalid_explicit_role_name() {
-        let temp = TestDir::new("exec-invalid-role-name");
-
-        let exit_code = run_exec_command_in_dir(
-            temp.path(),
-            Some("../escaped-role"),
-            Engine::Codex,
-            "hello",
-            None,
-            false,
-            |_engine, _prompt, _continue_id, _cwd| unreachable!("runner should not be called"),
+    fn parses_tasks_report_and_check() {
+        assert_eq!(
+            parse_cli_command(&to_args(&["tasks", "--swarm-project", "wind", "--json"]))
+                .expect("tasks parse should succeed"),
+            CliCommand::Tasks {
+                swarm_project: "wind".to_string(),
+                json_output: true,
+            }
+        );
+        assert_eq!(
+            parse_cli_command(&to_args(&["tasks", "check", "--swarm-project", "wind"]))
+                .expect("tasks check parse should succeed"),
+            CliCommand::TasksCheck {
+                swarm_project: "wind".to_string(),
+            }
         );
+        assert!(parse_cli_command(&to_args(&["tasks"])).is_err());
+        assert!(parse_cli_command(&to_args(&["tasks", "check"])).is_err());
+    }
 
-        assert_eq!(exit_code, 1);
+    #[test]
+    fn parses_sprints_with_optional_swarm_project() {
+        assert_eq!(
+            parse_cli_command(&to_args(&["sprints"])).expect("sprints parse should succeed"),
+            CliCommand::Sprints {
+                swarm_project: None
+            }
+        );
+        assert_eq!(
+            parse_cli_command(&to_args(&["sprints", "--swarm-project", "wind"]))
+                .expect("sprints parse with project should succeed"),
+            CliCommand::Sprints {
+                swarm_project: Some("wind".to_string()),
+            }
+        );
     }
 
-    #[cfg(unix)]
-    mod cli_integration_tests {
-        use super::*;
-        use std::env;
-        use std::fs;
-        use std::os::unix::fs::PermissionsExt;
-        use std::path::{Path, PathBuf};
-        use std::process::Command;
-        use std::sync::OnceLock;
+    #[test]
+    fn parses_worktrees_list_and_prune() {
+        assert_eq!(
+            parse_cli_command(&to_args(&["worktrees", "list"]))
+                .expect("worktrees list parse should succeed"),
+            CliCommand::WorktreesList
+        );
+        assert_eq!(
+            parse_cli_command(&to_args(&["worktrees", "prune", "--dry-run"]))
+                .expect("worktrees prune parse should succeed"),
+            CliCommand::WorktreesPrune { dry_run: true }
+        );
+    }
 
-        struct CliOutput {
-            exit_code: i32,
-            stdout: String,
-            stderr: String,
-        }
+    #[test]
+    fn parses_processes_check_with_optional_role() {
+        assert_eq!(
+            parse_cli_command(&to_args(&["processes", "check"]))
+                .expect("processes check parse should succeed"),
+            CliCommand::ProcessesCheck { role_name: None }
+        );
+        assert_eq!(
+            parse_cli_command(&to_args(&["processes", "check", "--project", "eng"]))
+                .expect("processes check parse with role should succeed"),
+            CliCommand::ProcessesCheck {
+                role_name: Some("eng".to_string()),
+            }
+        );
 
-        struct MockCodex {
-            bin_dir: PathBuf,
-            args_file: PathBuf,
-            exit_code: i32,
-        }
+        let err = parse_cli_command(&to_args(&["processes"]))
+            .expect_err("processes without a subcommand should fail");
+        assert_eq!(
+            err.kind(),
+            ErrorKind::DisplayHelpOnMissingArgumentOrSubcommand
+        );
+    }
 
-        impl MockCodex {
-            fn new(root: &Path, exit_code: i32) -> Self {
-                let bin_dir = root.join("mock-bin");
-                fs::create_dir_all(&bin_dir).expect("mock bin directory should exist");
+    #[test]
+    fn parses_watch_with_defaults_and_overrides() {
+        assert_eq!(
+            parse_cli_command(&to_args(&["watch", "codex"])).expect("watch parse should succeed"),
+            CliCommand::Watch {
+                role_name: None,
+                engine: "codex".to_string(),
+                interval_secs: 30,
+                once: false,
+            }
+        );
+        assert_eq!(
+            parse_cli_command(&to_args(&[
+                "watch",
+                "--project",
+                "eng",
+                "--interval",
+                "5",
+                "--once",
+                "claude",
+            ]))
+            .expect("watch parse with overrides should succeed"),
+            CliCommand::Watch {
+                role_name: Some("eng".to_string()),
+                engine: "claude".to_string(),
+                interval_secs: 5,
+                once: true,
+            }
+        );
 
-                let args_file = root.join("mock-codex-args.txt");
-                let codex_path = bin_dir.join("codex");
+        let err = parse_cli_command(&to_args(&["watch", "--interval", "0", "codex"]))
+            .expect_err("zero interval should fail");
+        assert_eq!(err.kind(), ErrorKind::ValueValidation);
+    }
 
-                fs::write(
-                    &codex_path,
-                    r#"#!/usr/bin/env bash
-set -eu
-printf '%s\0' "$@" > "${JULIET_TEST_CODEX_ARGS_FILE:?}"
-if [ "${2:-}" = "exec" ]; then
-  if [ "${JULIET_TEST_CODEX_STDOUT:-}" != "" ]; then
-    printf '%s' "${JULIET_TEST_CODEX_STDOUT}"
-  else
-    printf '%s\n' '{"thread_id":"codex-thread-id"}'
-    printf '%s\n' '{"type":"item.completed","item":{"text":"codex mock response"}}'
-  fi
-fi
-if [ "${JULIET_TEST_CODEX_STDERR:-}" != "" ]; then
-  printf '%s' "${JULIET_TEST_CODEX_STDERR}" >&2
-fi
-exit "${JULIET_TEST_CODEX_EXIT_CODE:-0}"
-"#,
-                )
-                .expect("mock codex script should be writable");
+    #[test]
+    fn parses_exec_continue_last_and_rejects_conflicting_continue() {
+        assert_eq!(
+            parse_cli_command(&to_args(&["exec", "--continue-last", "codex", "next"]))
+                .expect("continue-last parse should succeed"),
+            CliCommand::Exec {
+                role_name: None,
+                engine: "codex".to_string(),
+                message: "next".to_string(),
+                continue_id: None,
+                continue_last: true,
+                json_output: false,
+                stream_output: false,
+                timeout: None,
+                engine_fallback: Vec::new(),
+            }
+        );
 
-                let mut permissions = fs::metadata(&codex_path)
-                    .expect("mock codex script metadata should be readable")
-                    .permissions();
-                permissions.set_mode(0o755);
-                fs::set_permissions(&codex_path, permissions)
-                    .expect("mock codex script should be executable");
+        let err = parse_cli_command(&to_args(&[
+            "exec",
+            "--continue",
+            "thread-1",
+            "--continue-last",
+            "codex",
+            "next",
+        ]))
+        .expect_err("continue and continue-last should conflict");
+        assert_eq!(err.kind(), ErrorKind::ArgumentConflict);
+    }
 
-                Self {
-                    bin_dir,
-                    args_file,
-                    exit_code,
-                }
+    #[test]
+    fn parses_sessions_list_and_show() {
+        assert_eq!(
+            parse_cli_command(&to_args(&["sessions", "list", "--project", "eng"]))
+                .expect("sessions list parse should succeed"),
+            CliCommand::SessionsList {
+                role_name: Some("eng".to_string()),
+            }
+        );
+        assert_eq!(
+            parse_cli_command(&to_args(&["sessions", "show", "thread-1"]))
+                .expect("sessions show parse should succeed"),
+            CliCommand::SessionsShow {
+                role_name: None,
+                resume_id: "thread-1".to_string(),
             }
+        );
+    }
 
-            fn recorded_args(&self) -> Vec<String> {
-                fs::read_to_string(&self.args_file)
-                    .expect("mock codex args capture should be readable")
-                    .split('\0')
-                    .filter(|s| !s.is_empty())
-                    .map(|s| s.to_string())
-                    .collect()
+    #[test]
+    fn parses_archive_and_restore() {
+        assert_eq!(
+            parse_cli_command(&to_args(&["archive", "--project", "eng", "--keep"]))
+                .expect("archive should parse"),
+            CliCommand::Archive {
+                role_name: "eng".to_string(),
+                keep: true,
+            }
+        );
+        assert_eq!(
+            parse_cli_command(&to_args(&[
+                "restore",
+                "--role",
+                "eng",
+                "eng-20260301T090000Z.tar.gz"
+            ]))
+            .expect("restore should parse"),
+            CliCommand::Restore {
+                role_name: "eng".to_string(),
+                archive_name: Some("eng-20260301T090000Z.tar.gz".to_string()),
             }
+        );
+    }
 
-            fn recorded_prompt(&self) -> String {
-                let args = self.recorded_args();
-                args.last()
-                    .expect("mock codex should have received at least one argument")
-                    .clone()
+    #[test]
+    fn parses_roles_subcommands() {
+        assert_eq!(
+            parse_cli_command(&to_args(&["roles", "list"])).expect("roles list should parse"),
+            CliCommand::RolesList
+        );
+        assert_eq!(
+            parse_cli_command(&to_args(&["roles", "rename", "eng", "platform"]))
+                .expect("roles rename should parse"),
+            CliCommand::RolesRename {
+                from: "eng".to_string(),
+                to: "platform".to_string(),
             }
-        }
+        );
+        assert_eq!(
+            parse_cli_command(&to_args(&["roles", "clone", "eng", "eng-2"]))
+                .expect("roles clone should parse"),
+            CliCommand::RolesClone {
+                source: "eng".to_string(),
+                target: "eng-2".to_string(),
+            }
+        );
+        assert_eq!(
+            parse_cli_command(&to_args(&["roles", "delete", "eng", "--force"]))
+                .expect("roles delete should parse"),
+            CliCommand::RolesDelete {
+                role_name: "eng".to_string(),
+                force: true,
+            }
+        );
+    }
 
-        struct MockClaude {
-            bin_dir: PathBuf,
-            args_file: PathBuf,
-            env_file: PathBuf,
-            exit_code: i32,
+    #[test]
+    fn parser_errors_are_clap_native_for_invalid_shapes() {
+        for args in [
+            vec!["reset-prompt"],
+            vec!["clear-history", "--role"],
+            vec!["exec", "claude"],
+            vec!["exec", "--role", "my-role", "claude"],
+            vec!["exec", "--role"],
+            vec!["exec", "--continue", "claude", "hello"],
+            vec!["exec", "--continue"],
+        ] {
+            assert!(
+                parse_cli_command(&to_args(&args)).is_err(),
+                "expected parse error for args: {:?}",
+                args
+            );
         }
+    }
 
-        impl MockClaude {
-            fn new(root: &Path, exit_code: i32) -> Self {
-                let bin_dir = root.join("mock-bin");
-                fs::create_dir_all(&bin_dir).expect("mock bin directory should exist");
+    #[test]
+    fn parser_requires_engine_for_launch_mode() {
+        let error = parse_cli_command(&to_args(&[])).expect_err("no args should fail");
+        assert_eq!(
+            error.kind(),
+            clap::error::ErrorKind::MissingRequiredArgument
+        );
 
-                let args_file = root.join("mock-claude-args.txt");
-                let env_file = root.join("mock-claude-env.txt");
-                let claude_path = bin_dir.join("claude");
+        let explicit_missing_engine = parse_cli_command(&to_args(&["--role", "director"]))
+            .expect_err("explicit launch without engine should fail");
+        assert_eq!(
+            explicit_missing_engine.kind(),
+            clap::error::ErrorKind::MissingRequiredArgument
+        );
+    }
 
-                fs::write(
-                    &claude_path,
-                    r#"#!/usr/bin/env bash
-set -eu
-printf '%s\0' "$@" > "${JULIET_TEST_CLAUDE_ARGS_FILE:?}"
-printf 'IS_SANDBOX=%s\n' "${IS_SANDBOX:-}" > "${JULIET_TEST_CLAUDE_ENV_FILE:?}"
-emit_json="0"
-for arg in "$@"; do
-  if [ "$arg" = "--output-format" ]; then
-    emit_json="1"
-    break
-  fi
-done
-if [ "$emit_json" = "1" ]; then
-  if [ "${JULIET_TEST_CLAUDE_STDOUT:-}" != "" ]; then
-    printf '%s' "${JULIET_TEST_CLAUDE_STDOUT}"
-  else
-    printf '%s\n' '{"session_id":"claude-session-id","result":"claude mock response"}'
-  fi
-fi
-if [ "${JULIET_TEST_CLAUDE_STDERR:-}" != "" ]; then
-  printf '%s' "${JULIET_TEST_CLAUDE_STDERR}" >&2
-fi
-exit "${JULIET_TEST_CLAUDE_EXIT_CODE:-0}"
-"#,
-                )
-                .expect("mock claude script should be writable");
+    #[test]
+    fn parser_rejects_invalid_exec_engine_values() {
+        for invalid_engine in ["Claude", "CLAUDE", "Codex", "CODEX", "Claude3", "my_tool"] {
+            let error = parse_cli_command(&to_args(&["exec", invalid_engine, "hello"]))
+                .expect_err("invalid engine should fail");
+            assert_eq!(error.kind(), clap::error::ErrorKind::ValueValidation);
+        }
+    }
 
-                let mut permissions = fs::metadata(&claude_path)
-                    .expect("mock claude script metadata should be readable")
+    #[test]
+    fn parser_accepts_custom_engine_names() {
+        let parsed = parse_cli_command(&to_args(&["exec", "mytool", "hello"]))
+            .expect("custom engine names should parse");
+        assert_eq!(
+            parsed,
+            CliCommand::Exec {
+                role_name: None,
+                engine: "mytool".to_string(),
+                message: "hello".to_string(),
+                continue_id: None,
+                continue_last: false,
+                json_output: false,
+                stream_output: false,
+                timeout: None,
+                engine_fallback: Vec::new(),
+            }
+        );
+    }
+
+    #[test]
+    fn reset_prompt_bad_role_name_rejected_by_validation() {
+        // Role name validation rejects names that the parser passes through.
+        for bad_name in ["Invalid_Name", "../traversal", "", "-leading", "UPPER"] {
+            let err = role_name::validate_role_name(bad_name)
+                .expect_err(&format!("role name '{bad_name}' should be rejected"));
+            assert!(
+                err.contains("Invalid role name"),
+                "validation error for '{bad_name}' should contain 'Invalid role name': {err}"
+            );
+        }
+    }
+
+    #[test]
+    fn clear_history_bad_role_name_rejected_by_validation() {
+        // Role name validation rejects names that the parser passes through.
+        for bad_name in ["Invalid_Name", "../traversal", "", "-leading", "UPPER"] {
+            let err = role_name::validate_role_name(bad_name)
+                .expect_err(&format!("role name '{bad_name}' should be rejected"));
+            assert!(
+                err.contains("Invalid role name"),
+                "validation error for '{bad_name}' should contain 'Invalid role name': {err}"
+            );
+        }
+    }
+
+    #[test]
+    fn exec_bad_role_name_rejected_by_validation() {
+        // Role name validation rejects names that the parser passes through.
+        for bad_name in ["Invalid_Name", "../traversal", "", "-leading", "UPPER"] {
+            let err = role_name::validate_role_name(bad_name)
+                .expect_err(&format!("role name '{bad_name}' should be rejected"));
+            assert!(
+                err.contains("Invalid role name"),
+                "validation error for '{bad_name}' should contain 'Invalid role name': {err}"
+            );
+        }
+    }
+
+    #[test]
+    fn prepare_launch_prompt_fails_when_explicit_role_is_missing() {
+        let temp = TestDir::new("launch-missing-role");
+
+        let err = prepare_launch_prompt(temp.path(), Some("missing-role"))
+            .expect_err("missing role should fail");
+
+        assert_eq!(
+            err,
+            "Role not found: missing-role. Run: juliet init --project missing-role"
+        );
+    }
+
+    #[test]
+    fn prepare_launch_prompt_rejects_explicit_role_traversal_name() {
+        let temp = TestDir::new("launch-explicit-invalid-role-name");
+        let escaped_role_name = "../escaped-role";
+        let escaped_role_dir = temp.path().join("escaped-role");
+
+        fs::create_dir_all(temp.path().join(".juliet"))
+            .expect("state root should exist for traversal regression test");
+        fs::create_dir_all(&escaped_role_dir)
+            .expect("escaped role directory should exist outside .juliet");
+        fs::write(escaped_role_dir.join("prompt.md"), "# escaped prompt")
+            .expect("escaped prompt file should exist outside .juliet");
+
+        let err = prepare_launch_prompt(temp.path(), Some(escaped_role_name))
+            .expect_err("invalid explicit role name should fail before path traversal");
+
+        assert_eq!(
+            err,
+            "Invalid role name: ../escaped-role. Use lowercase letters, numbers, and hyphens."
+        );
+        assert!(
+            !escaped_role_dir.join("juliet-prompt.md").exists(),
+            "runtime prompt should not be written outside .juliet/<role>/"
+        );
+    }
+
+    #[test]
+    fn prepare_launch_prompt_reads_and_stages_explicit_role_prompt() {
+        let temp = TestDir::new("launch-explicit-role");
+        let role_name = "director-of-engineering";
+        role_state::create_role_state(temp.path(), role_name).expect("role state should exist");
+
+        let prompt_path = role_state::role_prompt_path(temp.path(), role_name);
+        fs::write(&prompt_path, "# Explicit prompt\n\nDo role work.")
+            .expect("role prompt should be written");
+
+        let prompt = prepare_launch_prompt(temp.path(), Some(role_name))
+            .expect("explicit role prompt should be loaded");
+        assert_eq!(prompt, "# Explicit prompt\n\nDo role work.");
+
+        let runtime_prompt =
+            fs::read_to_string(role_state::runtime_prompt_path(temp.path(), role_name))
+                .expect("runtime prompt should be written");
+        assert_eq!(runtime_prompt, prompt);
+    }
+
+    #[test]
+    fn prepare_launch_prompt_composes_seed_shared_and_role_layers() {
+        let temp = TestDir::new("launch-layered-prompt");
+        let role_name = "eng";
+        role_state::create_role_state(temp.path(), role_name).expect("role state should exist");
+        fs::write(
+            role_state::role_prompt_path(temp.path(), role_name),
+            "# eng\n\nOwn the wind project.\n\n{{include seed}}\n",
+        )
+        .expect("role prompt should be written");
+        fs::write(
+            role_state::shared_prompt_path(temp.path()),
+            "Always branch from main.\n",
+        )
+        .expect("shared prompt should be written");
+
+        let prompt = prepare_launch_prompt(temp.path(), Some(role_name))
+            .expect("layered prompt should be composed");
+
+        assert_eq!(
+            prompt,
+            format!(
+                "# eng\n\nOwn the wind project.\n\n{}\n\nAlways branch from main.\n",
+                DEFAULT_PROMPT_SEED.trim_end()
+            )
+        );
+        assert_eq!(
+            fs::read_to_string(role_state::runtime_prompt_path(temp.path(), role_name))
+                .expect("runtime prompt should be written"),
+            prompt
+        );
+
+        fs::write(
+            role_state::role_prompt_path(temp.path(), role_name),
+            "{{include sed}}\n",
+        )
+        .expect("role prompt should be written");
+        assert_eq!(
+            prepare_launch_prompt(temp.path(), Some(role_name)),
+            Err("failed to compose prompt for role eng: unknown include `{{include sed}}` in the role prompt; use {{include seed}} or {{include shared}}".to_string())
+        );
+    }
+
+    #[test]
+    fn prepare_launch_prompt_expands_variables_in_role_and_shared_layers() {
+        let temp = TestDir::new("launch-prompt-variables");
+        let role_name = "eng";
+        role_state::create_role_state(temp.path(), role_name).expect("role state should exist");
+        fs::write(
+            role_state::role_prompt_path(temp.path(), role_name),
+            "# {{role}}\n\nNeeds:\n{{pending_needs}}\nEngines: {{available_engines}}\n{{include shared}}\n",
+        )
+        .expect("role prompt should be written");
+        fs::write(
+            role_state::shared_prompt_path(temp.path()),
+            "Shared for {{role}}.\n",
+        )
+        .expect("shared prompt should be written");
+        fs::write(
+            role_state::needs_path(temp.path(), role_name),
+            "# Needs from operator\n\n- pick a winner branch\n",
+        )
+        .expect("needs should be written");
+
+        let prompt =
+            prepare_launch_prompt(temp.path(), Some(role_name)).expect("variables should expand");
+        assert_eq!(
+            prompt,
+            "# eng\n\nNeeds:\n- pick a winner branch\nEngines: unknown (run juliet doctor)\nShared for eng.\n"
+        );
+
+        fs::write(
+            role_state::shared_prompt_path(temp.path()),
+            "Today: {{today}}\n",
+        )
+        .expect("shared prompt should be written");
+        assert_eq!(
+            prepare_launch_prompt(temp.path(), Some(role_name)),
+            Err("unknown prompt variables in .juliet/.shared/prompt.md: `{{today}}` (line 1). Known variables: role, now_utc, git_branch, available_engines, pending_needs".to_string())
+        );
+    }
+
+    #[test]
+    fn run_launch_command_in_dir_returns_engine_exit_code_for_explicit_role() {
+        let temp = TestDir::new("launch-explicit-engine-exit");
+        let role_name = "director-of-engineering";
+        role_state::create_role_state(temp.path(), role_name).expect("role state should exist");
+
+        let prompt_path = role_state::role_prompt_path(temp.path(), role_name);
+        fs::write(&prompt_path, "# Explicit prompt\n\nDo role work.")
+            .expect("role prompt should be written");
+
+        let mut captured_engine = None;
+        let mut captured_prompt = String::new();
+        let exit_code = run_launch_command_in_dir(
+            temp.path(),
+            Some(role_name),
+            &engine::CodexEngine,
+            None,
+            |engine, prompt, cwd| {
+                captured_engine = Some(engine.name().to_string());
+                captured_prompt = prompt.to_string();
+                assert_eq!(cwd, temp.path());
+                Ok(5)
+            },
+        );
+
+        assert_eq!(exit_code, 5);
+        assert_eq!(captured_engine.as_deref(), Some("codex"));
+        assert_eq!(captured_prompt, "# Explicit prompt\n\nDo role work.");
+    }
+
+    #[test]
+    fn prepare_launch_prompt_fails_when_implicit_launch_has_no_roles() {
+        let temp = TestDir::new("launch-implicit-no-roles");
+        let prompts_dir = temp.path().join("prompts");
+        fs::create_dir_all(&prompts_dir).expect("prompts directory should be created");
+        fs::write(prompts_dir.join("juliet.md"), "# legacy prompt")
+            .expect("legacy prompt should not affect role discovery");
+
+        let err =
+            prepare_launch_prompt(temp.path(), None).expect_err("missing roles should fail launch");
+        assert_eq!(err, NO_ROLES_CONFIGURED_ERROR);
+    }
+
+    #[test]
+    fn prepare_launch_prompt_auto_selects_single_configured_role() {
+        let temp = TestDir::new("launch-implicit-single-role");
+        let role_name = "director-of-engineering";
+        role_state::create_role_state(temp.path(), role_name).expect("role state should exist");
+
+        let prompt_path = role_state::role_prompt_path(temp.path(), role_name);
+        fs::write(&prompt_path, "# Implicit prompt\n\nDo role work.")
+            .expect("role prompt should be written");
+
+        let prompt = prepare_launch_prompt(temp.path(), None)
+            .expect("single role should be selected implicitly");
+        assert_eq!(prompt, "# Implicit prompt\n\nDo role work.");
+
+        let runtime_prompt =
+            fs::read_to_string(role_state::runtime_prompt_path(temp.path(), role_name))
+                .expect("runtime prompt should be written");
+        assert_eq!(runtime_prompt, prompt);
+    }
+
+    #[test]
+    fn prepare_launch_prompt_auto_selects_single_juliet_role() {
+        let temp = TestDir::new("launch-implicit-juliet-role");
+        let role_name = "juliet";
+        role_state::create_role_state(temp.path(), role_name).expect("role state should exist");
+
+        let prompt_path = role_state::role_prompt_path(temp.path(), role_name);
+        fs::write(&prompt_path, "# Juliet role prompt\n\nDo role work.")
+            .expect("role prompt should be written");
+
+        let prompt = prepare_launch_prompt(temp.path(), None)
+            .expect("single juliet role should be selected implicitly");
+        assert_eq!(prompt, "# Juliet role prompt\n\nDo role work.");
+
+        let runtime_prompt =
+            fs::read_to_string(role_state::runtime_prompt_path(temp.path(), role_name))
+                .expect("runtime prompt should be written");
+        assert_eq!(runtime_prompt, prompt);
+    }
+
+    #[test]
+    fn prepare_launch_prompt_auto_selects_single_artifacts_role() {
+        let temp = TestDir::new("launch-implicit-artifacts-role");
+        let role_name = "artifacts";
+        role_state::create_role_state(temp.path(), role_name).expect("role state should exist");
+
+        let prompt_path = role_state::role_prompt_path(temp.path(), role_name);
+        fs::write(&prompt_path, "# Artifacts role prompt\n\nDo role work.")
+            .expect("role prompt should be written");
+
+        let prompt = prepare_launch_prompt(temp.path(), None)
+            .expect("single artifacts role should be selected implicitly");
+        assert_eq!(prompt, "# Artifacts role prompt\n\nDo role work.");
+
+        let runtime_prompt =
+            fs::read_to_string(role_state::runtime_prompt_path(temp.path(), role_name))
+                .expect("runtime prompt should be written");
+        assert_eq!(runtime_prompt, prompt);
+    }
+
+    #[test]
+    fn run_launch_command_in_dir_returns_engine_exit_code_for_implicit_single_role_launch() {
+        let temp = TestDir::new("launch-implicit-engine-exit");
+        let role_name = "director-of-engineering";
+        role_state::create_role_state(temp.path(), role_name).expect("role state should exist");
+
+        let prompt_path = role_state::role_prompt_path(temp.path(), role_name);
+        fs::write(&prompt_path, "# Implicit prompt\n\nDo role work.")
+            .expect("role prompt should be written");
+
+        let mut captured_engine = None;
+        let mut captured_prompt = String::new();
+        let exit_code = run_launch_command_in_dir(
+            temp.path(),
+            None,
+            &engine::ClaudeEngine,
+            None,
+            |engine, prompt, cwd| {
+                captured_engine = Some(engine.name().to_string());
+                captured_prompt = prompt.to_string();
+                assert_eq!(cwd, temp.path());
+                Ok(5)
+            },
+        );
+
+        assert_eq!(exit_code, 5);
+        assert_eq!(captured_engine.as_deref(), Some("claude"));
+        assert_eq!(captured_prompt, "# Implicit prompt\n\nDo role work.");
+    }
+
+    #[test]
+    fn prepare_launch_prompt_fails_when_multiple_roles_are_configured() {
+        let temp = TestDir::new("launch-implicit-multiple-roles");
+        role_state::create_role_state(temp.path(), "zeta-team")
+            .expect("zeta team role state should exist");
+        role_state::create_role_state(temp.path(), "alpha-team")
+            .expect("alpha team role state should exist");
+
+        let err = prepare_launch_prompt(temp.path(), None)
+            .expect_err("multiple configured roles should require explicit selection");
+        assert_eq!(
+            err,
+            "Multiple roles found. Specify one with --project <name>:\nalpha-team\nzeta-team"
+        );
+    }
+
+    #[test]
+    fn initialize_role_rejects_invalid_role_name() {
+        let temp = TestDir::new("invalid-role");
+        let err = initialize_role(temp.path(), "Invalid_Name", "seed prompt")
+            .expect_err("invalid role name should fail");
+
+        assert_eq!(
+            err,
+            "Invalid role name: Invalid_Name. Use lowercase letters, numbers, and hyphens."
+        );
+    }
+
+    #[test]
+    fn initialize_role_creates_prompt_template_and_state_structure() {
+        let temp = TestDir::new("fresh-init");
+        let role_name = "director-of-engineering";
+
+        let outcome =
+            initialize_role(temp.path(), role_name, "## Seeded prompt").expect("init should work");
+        assert_eq!(outcome, InitOutcome::Initialized);
+
+        let prompt_path = role_state::role_prompt_path(temp.path(), role_name);
+        let prompt_contents =
+            fs::read_to_string(prompt_path).expect("role prompt should be readable");
+        assert!(prompt_contents.contains("# director-of-engineering"));
+        assert!(prompt_contents.contains(OPERATOR_PLACEHOLDER));
+        assert!(prompt_contents.contains(prompt_layers::SEED_INCLUDE));
+
+        let role_dir = role_state::role_state_dir(temp.path(), role_name);
+        assert!(role_dir.is_dir());
+        assert!(role_dir.join("session.md").is_file());
+        assert!(role_dir.join("needs-from-operator.md").is_file());
+        assert!(role_dir.join("projects.md").is_file());
+        assert!(role_dir.join("processes.md").is_file());
+        assert!(role_dir.join("artifacts").is_dir());
+        assert!(role_state::shared_learnings_path(temp.path()).is_file());
+        let state_gitignore_path = role_state::state_gitignore_path(temp.path());
+        let state_gitignore =
+            fs::read_to_string(state_gitignore_path).expect("state gitignore should be readable");
+        assert!(
+            state_gitignore.contains("!*/prompt.md"),
+            "state gitignore should keep role prompt files tracked"
+        );
+    }
+
+    #[test]
+    fn initialize_role_is_idempotent_when_prompt_and_state_both_exist() {
+        let temp = TestDir::new("already-exists");
+        let role_name = "director-of-marketing";
+
+        let first = initialize_role(temp.path(), role_name, "seed prompt one")
+            .expect("first init should succeed");
+        assert_eq!(first, InitOutcome::Initialized);
+
+        let prompt_path = role_state::role_prompt_path(temp.path(), role_name);
+        let session_path = role_state::role_state_dir(temp.path(), role_name).join("session.md");
+        fs::write(&prompt_path, "custom prompt").expect("prompt should be mutable for test");
+        fs::write(&session_path, "custom session").expect("state file should be mutable for test");
+
+        let second = initialize_role(temp.path(), role_name, "seed prompt two")
+            .expect("second init should be idempotent");
+        assert_eq!(second, InitOutcome::AlreadyExists);
+        assert_eq!(
+            fs::read_to_string(&prompt_path).expect("prompt should still exist"),
+            "custom prompt"
+        );
+        assert_eq!(
+            fs::read_to_string(&session_path).expect("session should still exist"),
+            "custom session"
+        );
+    }
+
+    #[test]
+    fn initialize_role_repairs_missing_state_gitignore_even_when_role_already_exists() {
+        let temp = TestDir::new("repair-state-gitignore");
+        let role_name = "director-of-operations";
+
+        let first = initialize_role(temp.path(), role_name, "seed prompt")
+            .expect("first init should succeed");
+        assert_eq!(first, InitOutcome::Initialized);
+
+        let state_gitignore_path = role_state::state_gitignore_path(temp.path());
+        fs::remove_file(&state_gitignore_path).expect("state gitignore should be removable");
+
+        let second = initialize_role(temp.path(), role_name, "seed prompt")
+            .expect("second init should still succeed");
+        assert_eq!(second, InitOutcome::AlreadyExists);
+        let state_gitignore =
+            fs::read_to_string(state_gitignore_path).expect("state gitignore should be recreated");
+        assert!(
+            state_gitignore.contains("!*/prompt.md"),
+            "state gitignore should keep role prompt files tracked"
+        );
+    }
+
+    #[test]
+    fn initialize_role_repairs_missing_shared_learnings_even_when_role_already_exists() {
+        let temp = TestDir::new("repair-shared-learnings");
+        let role_name = "director-of-operations";
+
+        let first = initialize_role(temp.path(), role_name, "seed prompt")
+            .expect("first init should succeed");
+        assert_eq!(first, InitOutcome::Initialized);
+
+        let shared_learnings_path = role_state::shared_learnings_path(temp.path());
+        fs::remove_file(&shared_learnings_path).expect("shared learnings should be removable");
+
+        let second = initialize_role(temp.path(), role_name, "seed prompt")
+            .expect("second init should still succeed");
+        assert_eq!(second, InitOutcome::AlreadyExists);
+        assert!(
+            shared_learnings_path.is_file(),
+            "shared learnings file should be recreated on repeated init"
+        );
+    }
+
+    #[test]
+    fn initialize_role_creates_missing_state_when_prompt_already_exists() {
+        let temp = TestDir::new("prompt-only");
+        let role_name = "operations";
+        let prompt_path = role_state::role_prompt_path(temp.path(), role_name);
+        fs::create_dir_all(
+            prompt_path
+                .parent()
+                .expect("prompt parent dir should exist"),
+        )
+        .expect("prompt parent directory should be created");
+        fs::write(&prompt_path, "# custom operations prompt").expect("prompt should be created");
+
+        let outcome = initialize_role(temp.path(), role_name, "seed prompt")
+            .expect("init should create missing state");
+        assert_eq!(outcome, InitOutcome::Initialized);
+        assert_eq!(
+            fs::read_to_string(&prompt_path).expect("prompt should remain unchanged"),
+            "# custom operations prompt"
+        );
+
+        let role_dir = role_state::role_state_dir(temp.path(), role_name);
+        assert!(role_dir.is_dir());
+        assert!(role_dir.join("session.md").is_file());
+        assert!(role_dir.join("needs-from-operator.md").is_file());
+        assert!(role_dir.join("projects.md").is_file());
+        assert!(role_dir.join("processes.md").is_file());
+        assert!(role_dir.join("artifacts").is_dir());
+        assert!(role_state::shared_learnings_path(temp.path()).is_file());
+    }
+
+    #[test]
+    fn initialize_role_scaffolds_missing_state_files_when_prompt_and_legacy_dir_exist() {
+        let temp = TestDir::new("artifacts-prompt-and-legacy-dir");
+        let role_name = "artifacts";
+        let role_dir = role_state::role_state_dir(temp.path(), role_name);
+        fs::create_dir_all(&role_dir).expect("legacy artifacts directory should be created");
+        fs::write(role_dir.join("legacy-note.txt"), "legacy artifact")
+            .expect("legacy artifacts file should be created");
+
+        let prompt_path = role_state::role_prompt_path(temp.path(), role_name);
+        fs::write(&prompt_path, "# legacy artifacts prompt").expect("prompt should be created");
+
+        let outcome = initialize_role(temp.path(), role_name, "seed prompt")
+            .expect("init should scaffold missing state files");
+        assert_eq!(outcome, InitOutcome::Initialized);
+        assert_eq!(
+            fs::read_to_string(&prompt_path).expect("prompt should remain unchanged"),
+            "# legacy artifacts prompt"
+        );
+
+        assert!(role_state::role_state_is_scaffolded(temp.path(), role_name));
+        assert!(
+            role_state::discover_configured_roles(temp.path())
+                .expect("role discovery should succeed")
+                .iter()
+                .any(|role| role.name == role_name),
+            "artifacts role should be discoverable after scaffolding"
+        );
+    }
+
+    #[test]
+    fn initialize_role_creates_missing_prompt_when_state_already_exists() {
+        let temp = TestDir::new("state-only");
+        let role_name = "program-manager";
+        role_state::create_role_state(temp.path(), role_name)
+            .expect("state scaffold should be created");
+
+        let session_path = role_state::role_state_dir(temp.path(), role_name).join("session.md");
+        fs::write(&session_path, "existing session data").expect("session should be writable");
+
+        let outcome =
+            initialize_role(temp.path(), role_name, "## Embedded seed").expect("init should work");
+        assert_eq!(outcome, InitOutcome::Initialized);
+        assert_eq!(
+            fs::read_to_string(&session_path).expect("session should remain unchanged"),
+            "existing session data"
+        );
+
+        let prompt_path = role_state::role_prompt_path(temp.path(), role_name);
+        let prompt_contents = fs::read_to_string(prompt_path).expect("prompt should be readable");
+        assert!(prompt_contents.contains("# program-manager"));
+        assert!(prompt_contents.contains(OPERATOR_PLACEHOLDER));
+        assert!(prompt_contents.contains(prompt_layers::SEED_INCLUDE));
+    }
+
+    #[test]
+    fn build_launch_prompt_appends_operator_input() {
+        let base = "# Role prompt\n\nDo role work.";
+        assert_eq!(build_launch_prompt(base, None), base.to_string());
+        assert_eq!(
+            build_launch_prompt(base, Some("please continue from yesterday")),
+            "# Role prompt\n\nDo role work.\n\nUser input:\nplease continue from yesterday"
+                .to_string()
+        );
+    }
+
+    // reset_prompt unit tests
+
+    #[test]
+    fn reset_prompt_rejects_invalid_role_name() {
+        let temp = TestDir::new("reset-prompt-invalid-name");
+        let err = reset_prompt(temp.path(), "Invalid_Name", "seed prompt")
+            .expect_err("invalid role name should fail");
+
+        assert_eq!(
+            err,
+            "Invalid role name: Invalid_Name. Use lowercase letters, numbers, and hyphens."
+        );
+    }
+
+    #[test]
+    fn reset_prompt_fails_when_role_not_initialized() {
+        let temp = TestDir::new("reset-prompt-not-initialized");
+        let err = reset_prompt(temp.path(), "missing-role", "seed prompt")
+            .expect_err("uninitialized role should fail");
+
+        assert_eq!(err, "Role 'missing-role' is not initialized.");
+    }
+
+    #[test]
+    fn reset_prompt_overwrites_prompt_with_default_template() {
+        let temp = TestDir::new("reset-prompt-overwrite");
+        let role_name = "director-of-engineering";
+        let seed = "## Seeded prompt content";
+
+        initialize_role(temp.path(), role_name, seed).expect("init should succeed");
+
+        let prompt_path = role_state::role_prompt_path(temp.path(), role_name);
+        fs::write(
+            &prompt_path,
+            "# Custom modified prompt\n\nUser changes here.",
+        )
+        .expect("prompt should be writable");
+
+        reset_prompt(temp.path(), role_name, seed).expect("reset_prompt should succeed");
+
+        let prompt_contents =
+            fs::read_to_string(&prompt_path).expect("prompt should be readable after reset");
+        let expected = role_prompt_template(role_name);
+        assert_eq!(prompt_contents, expected);
+        assert!(prompt_contents.contains(&format!("# {role_name}")));
+        assert!(prompt_contents.contains(OPERATOR_PLACEHOLDER));
+        assert!(prompt_layers::has_directive(
+            &prompt_contents,
+            prompt_layers::SEED_INCLUDE
+        ));
+        assert_eq!(
+            fs::read_to_string(role_state::prompt_seed_path(temp.path(), role_name))
+                .expect("seed record should be readable"),
+            seed
+        );
+    }
+
+    #[test]
+    fn reset_prompt_preserves_state_files() {
+        let temp = TestDir::new("reset-prompt-preserves-state");
+        let role_name = "operations";
+        let seed = "## Seed";
+
+        initialize_role(temp.path(), role_name, seed).expect("init should succeed");
+
+        let session_path = role_state::role_state_dir(temp.path(), role_name).join("session.md");
+        fs::write(&session_path, "important session data").expect("session should be writable");
+
+        reset_prompt(temp.path(), role_name, seed).expect("reset_prompt should succeed");
+
+        assert_eq!(
+            fs::read_to_string(&session_path).expect("session should still exist"),
+            "important session data"
+        );
+    }
+
+    // upgrade_prompt unit tests
+
+    #[test]
+    fn upgrade_prompt_merges_new_seed_with_role_edits() {
+        let temp = TestDir::new("upgrade-prompt-merge");
+        let old_seed = "step one\nstep two\nstep three\n";
+        let new_seed = "step one\nstep two, faster\nstep three\nstep four\n";
+
+        initialize_role(temp.path(), "eng", old_seed).expect("init should succeed");
+        let prompt_path = role_state::role_prompt_path(temp.path(), "eng");
+        let customized = copied_seed_prompt_template("eng", old_seed)
+            .replace(OPERATOR_PLACEHOLDER, "Own the wind project.");
+        fs::write(&prompt_path, customized).expect("prompt should be writable");
+
+        let upgrade = upgrade_prompt(temp.path(), "eng", new_seed).expect("upgrade should work");
+
+        assert_eq!(
+            upgrade,
+            PromptUpgrade::Upgraded {
+                from: seed_version(old_seed),
+                to: seed_version(new_seed),
+                conflicts: 0,
+            }
+        );
+        assert_eq!(
+            fs::read_to_string(&prompt_path).expect("prompt should be readable"),
+            "# eng\n\nOwn the wind project.\n\n## Default Prompt Seed\n\nstep one\nstep two, faster\nstep three\nstep four\n"
+        );
+        assert_eq!(
+            upgrade_prompt(temp.path(), "eng", new_seed).expect("second upgrade should work"),
+            PromptUpgrade::AlreadyCurrent {
+                version: seed_version(new_seed),
+            }
+        );
+    }
+
+    #[test]
+    fn upgrade_prompt_leaves_conflict_markers_for_overlapping_edits() {
+        let temp = TestDir::new("upgrade-prompt-conflict");
+        initialize_role(temp.path(), "eng", "step one\nstep two\n").expect("init should succeed");
+        let prompt_path = role_state::role_prompt_path(temp.path(), "eng");
+        let customized = copied_seed_prompt_template("eng", "step one\nstep two\n")
+            .replace("step two", "step two, our way");
+        fs::write(&prompt_path, customized).expect("prompt should be writable");
+
+        let new_seed = "step one\nstep two, the new way\n";
+        let upgrade = upgrade_prompt(temp.path(), "eng", new_seed).expect("upgrade should work");
+
+        assert!(matches!(
+            upgrade,
+            PromptUpgrade::Upgraded { conflicts: 1, .. }
+        ));
+        assert!(fs::read_to_string(&prompt_path)
+            .expect("prompt should be readable")
+            .ends_with(&format!(
+                "step one\n<<<<<<< prompt.md\nstep two, our way\n=======\nstep two, the new way\n>>>>>>> default seed {}\n",
+                seed_version(new_seed)
+            )));
+
+        let conflict =
+            "eng/prompt.md:8: unresolved merge conflict marker; resolve the <<<<<<< blocks first";
+        assert!(stage_explicit_role_prompt(temp.path(), "eng")
+            .expect_err("conflicted prompts must not be staged")
+            .ends_with(conflict));
+        assert!(
+            upgrade_prompt(temp.path(), "eng", "step one\nstep two, newer\n")
+                .expect_err("conflicted prompts must not be upgraded again")
+                .ends_with(conflict)
+        );
+    }
+
+    #[test]
+    fn upgrade_prompt_skips_prompts_that_include_the_seed() {
+        let temp = TestDir::new("upgrade-prompt-include");
+        initialize_role(temp.path(), "eng", "old seed\n").expect("init should succeed");
+        let prompt_path = role_state::role_prompt_path(temp.path(), "eng");
+        fs::write(&prompt_path, "# eng\n\n{{include seed}}\n").expect("prompt should be writable");
+
+        assert_eq!(
+            upgrade_prompt(temp.path(), "eng", "new seed\n"),
+            Ok(PromptUpgrade::IncludesSeed)
+        );
+        assert_eq!(
+            fs::read_to_string(&prompt_path).expect("prompt should be readable"),
+            "# eng\n\n{{include seed}}\n"
+        );
+    }
+
+    #[test]
+    fn upgrade_prompt_uses_embedded_seed_when_none_was_recorded() {
+        let temp = TestDir::new("upgrade-prompt-legacy");
+        initialize_role(temp.path(), "eng", "old seed\n").expect("init should succeed");
+        fs::write(
+            role_state::role_prompt_path(temp.path(), "eng"),
+            copied_seed_prompt_template("eng", "old seed\n"),
+        )
+        .expect("prompt should be writable");
+        fs::remove_file(role_state::prompt_seed_path(temp.path(), "eng"))
+            .expect("seed record should be removable");
+
+        upgrade_prompt(temp.path(), "eng", "new seed\n").expect("upgrade should work");
+
+        assert_eq!(
+            fs::read_to_string(role_state::role_prompt_path(temp.path(), "eng"))
+                .expect("prompt should be readable"),
+            copied_seed_prompt_template("eng", "new seed\n")
+        );
+        assert_eq!(
+            fs::read_to_string(role_state::prompt_seed_path(temp.path(), "eng"))
+                .expect("seed record should be written"),
+            "new seed\n"
+        );
+
+        fs::write(role_state::role_prompt_path(temp.path(), "eng"), "# eng\n")
+            .expect("prompt should be writable");
+        fs::remove_file(role_state::prompt_seed_path(temp.path(), "eng"))
+            .expect("seed record should be removable");
+        assert_eq!(
+            upgrade_prompt(temp.path(), "eng", "new seed\n"),
+            Err("Role 'eng' has no recorded seed and its prompt has no '## Default Prompt Seed' section. Run: juliet reset-prompt --project eng".to_string())
+        );
+    }
+
+    // clear_history unit tests
+
+    #[test]
+    fn clear_history_rejects_invalid_role_name() {
+        let temp = TestDir::new("clear-history-invalid-name");
+        let err =
+            clear_history(temp.path(), "Invalid_Name").expect_err("invalid role name should fail");
+
+        assert_eq!(
+            err,
+            "Invalid role name: Invalid_Name. Use lowercase letters, numbers, and hyphens."
+        );
+    }
+
+    #[test]
+    fn clear_history_fails_when_role_not_initialized() {
+        let temp = TestDir::new("clear-history-not-initialized");
+        let err =
+            clear_history(temp.path(), "missing-role").expect_err("uninitialized role should fail");
+
+        assert_eq!(err, "Role 'missing-role' is not initialized.");
+    }
+
+    #[test]
+    fn clear_history_empties_state_files() {
+        let temp = TestDir::new("clear-history-empties-state");
+        let role_name = "director-of-engineering";
+
+        initialize_role(temp.path(), role_name, "seed").expect("init should succeed");
+
+        let role_dir = role_state::role_state_dir(temp.path(), role_name);
+        fs::write(role_dir.join("session.md"), "session data").expect("write session");
+        fs::write(role_dir.join("needs-from-operator.md"), "operator needs").expect("write needs");
+        fs::write(role_dir.join("projects.md"), "project data").expect("write projects");
+        fs::write(role_dir.join("processes.md"), "process data").expect("write processes");
+        let shared_learnings_path = role_state::shared_learnings_path(temp.path());
+        fs::write(&shared_learnings_path, "learning data").expect("write shared learnings");
+
+        clear_history(temp.path(), role_name).expect("clear_history should succeed");
+
+        assert_eq!(fs::read_to_string(role_dir.join("session.md")).unwrap(), "");
+        assert_eq!(
+            fs::read_to_string(role_dir.join("needs-from-operator.md")).unwrap(),
+            ""
+        );
+        assert_eq!(
+            fs::read_to_string(role_dir.join("projects.md")).unwrap(),
+            ""
+        );
+        assert_eq!(
+            fs::read_to_string(role_dir.join("processes.md")).unwrap(),
+            ""
+        );
+        assert_eq!(
+            fs::read_to_string(shared_learnings_path).unwrap(),
+            "learning data"
+        );
+    }
+
+    #[test]
+    fn clear_history_deletes_juliet_prompt_md() {
+        let temp = TestDir::new("clear-history-deletes-runtime-prompt");
+        let role_name = "operations";
+
+        initialize_role(temp.path(), role_name, "seed").expect("init should succeed");
+
+        let runtime_path = role_state::runtime_prompt_path(temp.path(), role_name);
+        fs::write(&runtime_path, "runtime prompt content").expect("write runtime prompt");
+        assert!(runtime_path.exists());
+
+        clear_history(temp.path(), role_name).expect("clear_history should succeed");
+
+        assert!(!runtime_path.exists(), "juliet-prompt.md should be deleted");
+    }
+
+    #[test]
+    fn clear_history_succeeds_when_juliet_prompt_md_absent() {
+        let temp = TestDir::new("clear-history-no-runtime-prompt");
+        let role_name = "qa";
+
+        initialize_role(temp.path(), role_name, "seed").expect("init should succeed");
+
+        let runtime_path = role_state::runtime_prompt_path(temp.path(), role_name);
+        assert!(!runtime_path.exists());
+
+        clear_history(temp.path(), role_name)
+            .expect("clear_history should succeed without runtime prompt");
+    }
+
+    #[test]
+    fn clear_history_clears_artifacts_directory_contents() {
+        let temp = TestDir::new("clear-history-clears-artifacts");
+        let role_name = "engineering";
+
+        initialize_role(temp.path(), role_name, "seed").expect("init should succeed");
+
+        let artifacts_dir = role_state::role_state_dir(temp.path(), role_name).join("artifacts");
+        fs::write(artifacts_dir.join("report.txt"), "report content").expect("write artifact file");
+        fs::create_dir_all(artifacts_dir.join("subdir")).expect("create artifact subdir");
+        fs::write(
+            artifacts_dir.join("subdir").join("nested.md"),
+            "nested content",
+        )
+        .expect("write nested artifact");
+
+        clear_history(temp.path(), role_name).expect("clear_history should succeed");
+
+        assert!(
+            artifacts_dir.is_dir(),
+            "artifacts directory should be preserved"
+        );
+        assert_eq!(
+            fs::read_dir(&artifacts_dir).unwrap().count(),
+            0,
+            "artifacts directory should be empty"
+        );
+    }
+
+    #[test]
+    fn clear_history_preserves_prompt_md() {
+        let temp = TestDir::new("clear-history-preserves-prompt");
+        let role_name = "director-of-marketing";
+
+        initialize_role(temp.path(), role_name, "seed").expect("init should succeed");
+
+        let prompt_path = role_state::role_prompt_path(temp.path(), role_name);
+        fs::write(&prompt_path, "# Custom prompt\n\nKeep this intact.")
+            .expect("write custom prompt");
+
+        clear_history(temp.path(), role_name).expect("clear_history should succeed");
+
+        assert_eq!(
+            fs::read_to_string(&prompt_path).unwrap(),
+            "# Custom prompt\n\nKeep this intact."
+        );
+    }
+
+    // status unit tests
+
+    #[test]
+    fn collect_statuses_reports_every_configured_role_in_order() {
+        let temp = TestDir::new("status-all-roles");
+        role_state::create_role_state(temp.path(), "zeta-team").expect("zeta role should exist");
+        role_state::create_role_state(temp.path(), "alpha-team").expect("alpha role should exist");
+
+        let statuses = collect_statuses(temp.path(), None).expect("statuses should load");
+        assert_eq!(
+            statuses
+                .iter()
+                .map(|status| status.role.as_str())
+                .collect::<Vec<_>>(),
+            vec!["alpha-team", "zeta-team"]
+        );
+    }
+
+    #[test]
+    fn collect_statuses_validates_explicit_role() {
+        let temp = TestDir::new("status-explicit-role");
+
+        assert_eq!(
+            collect_statuses(temp.path(), Some("../escaped")).expect_err("must fail"),
+            "Invalid role name: ../escaped. Use lowercase letters, numbers, and hyphens."
+        );
+        assert_eq!(
+            collect_statuses(temp.path(), Some("missing-role")).expect_err("must fail"),
+            "Role not found: missing-role. Run: juliet init --project missing-role"
+        );
+        assert_eq!(
+            collect_statuses(temp.path(), None).expect_err("must fail"),
+            NO_ROLES_CONFIGURED_ERROR
+        );
+    }
+
+    // sessions unit tests
+
+    #[test]
+    fn exec_records_resume_id_and_message_digest() {
+        let temp = TestDir::new("exec-records-session");
+        role_state::create_role_state(temp.path(), "eng").expect("role state should exist");
+        fs::write(role_state::role_prompt_path(temp.path(), "eng"), "# eng")
+            .expect("role prompt should be written");
+
+        let exit_code = run_exec_command_in_dir(
+            temp.path(),
+            None,
+            &engine::CodexEngine,
+            "fix the bug",
+            None,
+            ExecOutputMode::Text,
+            |_, _, _, _| {
+                Ok(EngineOutput {
+                    status_code: 0,
+                    stdout: "{\"thread_id\":\"thread-9\"}\n".to_string(),
+                    stderr: String::new(),
+                    timed_out: false,
+                })
+            },
+        );
+
+        assert_eq!(exit_code, 0);
+        let records = sessions::load_sessions(temp.path(), "eng").expect("sessions should load");
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].engine, "codex");
+        assert_eq!(records[0].resume_id, "thread-9");
+        assert_eq!(
+            records[0].message_digest,
+            sessions::message_digest("fix the bug")
+        );
+    }
+
+    #[test]
+    fn exec_chain_falls_back_on_spawn_and_parse_failures() {
+        let temp = TestDir::new("exec-engine-fallback");
+        role_state::create_role_state(temp.path(), "eng").expect("role state should exist");
+        fs::write(role_state::role_prompt_path(temp.path(), "eng"), "# eng")
+            .expect("role prompt should be written");
+
+        let mut attempts = Vec::new();
+        let exit_code = run_exec_chain_in_dir(
+            temp.path(),
+            None,
+            &[&engine::ClaudeEngine, &engine::CodexEngine],
+            "fix the bug",
+            Some("session-1"),
+            ExecOutputMode::Text,
+            |engine, _, continue_id, _| {
+                attempts.push((engine.name().to_string(), continue_id.map(str::to_string)));
+                match engine.name() {
+                    "claude" => Err(io::Error::new(io::ErrorKind::NotFound, "no claude")),
+                    _ => Ok(EngineOutput {
+                        status_code: 0,
+                        stdout: "{\"thread_id\":\"thread-9\"}\n".to_string(),
+                        stderr: String::new(),
+                        timed_out: false,
+                    }),
+                }
+            },
+        );
+
+        assert_eq!(exit_code, 0);
+        assert_eq!(
+            attempts,
+            vec![
+                ("claude".to_string(), Some("session-1".to_string())),
+                ("codex".to_string(), None),
+            ]
+        );
+        let records = sessions::load_sessions(temp.path(), "eng").expect("sessions should load");
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].engine, "codex");
+        let learnings = fs::read_to_string(role_state::shared_learnings_path(temp.path()))
+            .expect("learnings should be readable");
+        assert!(learnings.contains(
+            "[exec-fallback] role eng: claude failed (failed to run engine: no claude); retried the turn with codex."
+        ));
+
+        let mut attempts = 0;
+        let exit_code = run_exec_chain_in_dir(
+            temp.path(),
+            None,
+            &[&engine::CodexEngine, &engine::ClaudeEngine],
+            "fix the bug",
+            None,
+            ExecOutputMode::Text,
+            |_, _, _, _| {
+                attempts += 1;
+                Ok(EngineOutput {
+                    status_code: 0,
+                    stdout: "not json".to_string(),
+                    stderr: String::new(),
+                    timed_out: false,
+                })
+            },
+        );
+        assert_eq!(exit_code, 1);
+        assert_eq!(attempts, 2);
+        let learnings = fs::read_to_string(role_state::shared_learnings_path(temp.path()))
+            .expect("learnings should be readable");
+        assert_eq!(learnings.lines().count(), 2);
+    }
+
+    #[test]
+    fn exec_chain_does_not_fall_back_when_engine_exits_non_zero() {
+        let temp = TestDir::new("exec-engine-no-fallback");
+        role_state::create_role_state(temp.path(), "eng").expect("role state should exist");
+        fs::write(role_state::role_prompt_path(temp.path(), "eng"), "# eng")
+            .expect("role prompt should be written");
+
+        let mut attempts = 0;
+        let exit_code = run_exec_chain_in_dir(
+            temp.path(),
+            None,
+            &[&engine::ClaudeEngine, &engine::CodexEngine],
+            "fix the bug",
+            None,
+            ExecOutputMode::Text,
+            |_, _, _, _| {
+                attempts += 1;
+                Ok(EngineOutput {
+                    status_code: 4,
+                    stdout: String::new(),
+                    stderr: "rate limited\n".to_string(),
+                    timed_out: false,
+                })
+            },
+        );
+
+        assert_eq!(exit_code, 4);
+        assert_eq!(attempts, 1);
+        assert_eq!(
+            fs::read_to_string(role_state::shared_learnings_path(temp.path()))
+                .expect("learnings should be readable"),
+            ""
+        );
+    }
+
+    #[test]
+    fn exec_timeout_saves_partial_output_and_skips_session() {
+        let temp = TestDir::new("exec-timeout-artifact");
+        role_state::create_role_state(temp.path(), "eng").expect("role state should exist");
+        fs::write(role_state::role_prompt_path(temp.path(), "eng"), "# eng")
+            .expect("role prompt should be written");
+
+        let exit_code = run_exec_command_in_dir(
+            temp.path(),
+            None,
+            &engine::CodexEngine,
+            "fix the bug",
+            None,
+            ExecOutputMode::Text,
+            |_, _, _, _| {
+                Ok(EngineOutput {
+                    status_code: supervisor::TIMEOUT_EXIT_CODE,
+                    stdout: "{\"thread_id\":\"thread-9\"}\n".to_string(),
+                    stderr: "still thinking\n".to_string(),
+                    timed_out: true,
+                })
+            },
+        );
+
+        assert_eq!(exit_code, supervisor::TIMEOUT_EXIT_CODE);
+        assert!(sessions::load_sessions(temp.path(), "eng")
+            .expect("sessions should load")
+            .is_empty());
+
+        let artifacts = fs::read_dir(role_state::artifacts_dir(temp.path(), "eng"))
+            .expect("artifacts should be readable")
+            .map(|entry| entry.expect("artifact entry should be readable").path())
+            .collect::<Vec<_>>();
+        assert_eq!(artifacts.len(), 1);
+        let file_name = artifacts[0]
+            .file_name()
+            .and_then(|name| name.to_str())
+            .expect("artifact name should be UTF-8");
+        assert!(file_name.starts_with("exec-timeout-codex-"));
+        assert!(file_name.ends_with(".log"));
+        assert_eq!(
+            fs::read_to_string(&artifacts[0]).expect("artifact should be readable"),
+            "# codex exec timed out\n\n## stdout\n{\"thread_id\":\"thread-9\"}\n\n## stderr\nstill thinking\n"
+        );
+    }
+
+    #[test]
+    fn latest_resume_id_requires_a_recorded_session_for_the_engine() {
+        let temp = TestDir::new("latest-resume-id");
+        role_state::create_role_state(temp.path(), "eng").expect("role state should exist");
+
+        assert_eq!(
+            latest_resume_id(temp.path(), None, "codex").expect_err("must fail"),
+            "No recorded codex sessions for role eng. Run juliet exec without --continue-last first."
+        );
+
+        sessions::record_session(
+            temp.path(),
+            "eng",
+            &sessions::SessionRecord::new("2026-02-06T10:00:00Z", "codex", "thread-1", "hi"),
+        )
+        .expect("session should be recorded");
+        assert_eq!(
+            latest_resume_id(temp.path(), Some("eng"), "codex").expect("resume id should resolve"),
+            "thread-1"
+        );
+    }
+
+    // watch unit tests
+
+    #[test]
+    fn watch_skips_engine_when_nothing_changed() {
+        let temp = TestDir::new("watch-quiet");
+        role_state::create_role_state(temp.path(), "eng").expect("role state should exist");
+        let mut watcher = watch::Watcher::new(temp.path(), "eng").expect("watcher should start");
+
+        let exit_code = watch_once_in_dir(
+            temp.path(),
+            "eng",
+            &engine::CodexEngine,
+            &mut watcher,
+            |_| true,
+            |_, _, _, _| panic!("engine should not run without changes"),
+        );
+
+        assert_eq!(exit_code, 0);
+    }
+
+    #[test]
+    fn watch_runs_turn_on_inbox_input_and_resumes_saved_session() {
+        let temp = TestDir::new("watch-inbox");
+        role_state::create_role_state(temp.path(), "eng").expect("role state should exist");
+        fs::write(role_state::role_prompt_path(temp.path(), "eng"), "# eng")
+            .expect("role prompt should be written");
+        sessions::record_session(
+            temp.path(),
+            "eng",
+            &sessions::SessionRecord::new("2026-02-06T10:00:00Z", "codex", "thread-0", "earlier"),
+        )
+        .expect("prior session should be recorded");
+        let mut watcher = watch::Watcher::new(temp.path(), "eng").expect("watcher should start");
+        fs::write(
+            role_state::inbox_path(temp.path(), "eng"),
+            "start the next sprint\n",
+        )
+        .expect("inbox should be written");
+
+        let mut captured = None;
+        let exit_code = watch_once_in_dir(
+            temp.path(),
+            "eng",
+            &engine::CodexEngine,
+            &mut watcher,
+            |_| true,
+            |_, prompt, continue_id, _| {
+                captured = Some((prompt.to_string(), continue_id.map(str::to_string)));
+                Ok(EngineOutput {
+                    status_code: 0,
+                    stdout: "{\"thread_id\":\"thread-1\"}\n".to_string(),
+                    stderr: String::new(),
+                    timed_out: false,
+                })
+            },
+        );
+
+        assert_eq!(exit_code, 0);
+        assert_eq!(
+            captured,
+            Some((
+                "# eng\n\nUser input:\nstart the next sprint".to_string(),
+                Some("thread-0".to_string())
+            ))
+        );
+        let records = sessions::load_sessions(temp.path(), "eng").expect("sessions should load");
+        assert_eq!(
+            sessions::latest_session(&records, "codex").map(|record| record.resume_id),
+            Some("thread-1".to_string())
+        );
+    }
+
+    #[test]
+    fn watch_keeps_inbox_input_when_the_turn_fails() {
+        let temp = TestDir::new("watch-failed-turn");
+        role_state::create_role_state(temp.path(), "eng").expect("role state should exist");
+        fs::write(role_state::role_prompt_path(temp.path(), "eng"), "# eng")
+            .expect("role prompt should be written");
+        let mut watcher = watch::Watcher::new(temp.path(), "eng").expect("watcher should start");
+        let inbox_path = role_state::inbox_path(temp.path(), "eng");
+        fs::write(&inbox_path, "start the next sprint\n").expect("inbox should be written");
+        let failing_engine = |_: &dyn Engine, _: &str, _: Option<&str>, _: &Path| {
+            Ok(EngineOutput {
+                status_code: 1,
+                stdout: String::new(),
+                stderr: "rate limited\n".to_string(),
+                timed_out: false,
+            })
+        };
+
+        let exit_code = watch_once_in_dir(
+            temp.path(),
+            "eng",
+            &engine::CodexEngine,
+            &mut watcher,
+            |_| true,
+            failing_engine,
+        );
+
+        assert_eq!(exit_code, 1);
+        assert_eq!(
+            fs::read_to_string(&inbox_path).expect("inbox should be readable"),
+            "start the next sprint\n"
+        );
+
+        let mut prompts = Vec::new();
+        let exit_code = watch_once_in_dir(
+            temp.path(),
+            "eng",
+            &engine::CodexEngine,
+            &mut watcher,
+            |_| true,
+            |_, prompt, _, _| {
+                prompts.push(prompt.to_string());
+                Ok(EngineOutput {
+                    status_code: 0,
+                    stdout: "{\"thread_id\":\"thread-1\"}\n".to_string(),
+                    stderr: String::new(),
+                    timed_out: false,
+                })
+            },
+        );
+
+        assert_eq!(exit_code, 0);
+        assert_eq!(
+            prompts,
+            vec!["# eng\n\nUser input:\nstart the next sprint".to_string()]
+        );
+        assert_eq!(
+            fs::read_to_string(&inbox_path).expect("inbox should be readable"),
+            ""
+        );
+    }
+
+    // exec command unit tests
+
+    #[test]
+    fn exec_explicit_role_stages_prompt_and_appends_message() {
+        let temp = TestDir::new("exec-explicit-role");
+        let role_name = "director-of-engineering";
+        let role_prompt = "# Exec prompt\n\nDo role work.";
+        role_state::create_role_state(temp.path(), role_name).expect("role state should exist");
+        fs::write(
+            role_state::role_prompt_path(temp.path(), role_name),
+            role_prompt,
+        )
+        .expect("role prompt should be written");
+
+        let mut captured_engine = None;
+        let mut captured_prompt = String::new();
+        let exit_code = run_exec_command_in_dir(
+            temp.path(),
+            Some(role_name),
+            &engine::CodexEngine,
+            "fix the bug",
+            None,
+            ExecOutputMode::Text,
+            |engine, prompt, continue_id, cwd| {
+                captured_engine = Some(engine.name().to_string());
+                captured_prompt = prompt.to_string();
+                assert_eq!(continue_id, None);
+                assert_eq!(cwd, temp.path());
+                Ok(EngineOutput {
+                    status_code: 0,
+                    stdout:
+                        "{\"thread_id\":\"thread-1\"}\n{\"type\":\"item.completed\",\"item\":{\"text\":\"done\"}}\n"
+                            .to_string(),
+                    stderr: String::new(),
+                    timed_out: false,
+                })
+            },
+        );
+
+        assert_eq!(exit_code, 0);
+        assert_eq!(captured_engine.as_deref(), Some("codex"));
+        assert_eq!(
+            captured_prompt,
+            "# Exec prompt\n\nDo role work.\n\nUser input:\nfix the bug"
+        );
+
+        let runtime_prompt =
+            fs::read_to_string(role_state::runtime_prompt_path(temp.path(), role_name))
+                .expect("runtime prompt should be written");
+        assert_eq!(runtime_prompt, role_prompt);
+    }
+
+    #[test]
+    fn exec_implicit_single_role_stages_prompt_and_appends_message() {
+        let temp = TestDir::new("exec-implicit-single-role");
+        let role_name = "director-of-engineering";
+        let role_prompt = "# Implicit exec prompt\n\nDo role work.";
+        role_state::create_role_state(temp.path(), role_name).expect("role state should exist");
+        fs::write(
+            role_state::role_prompt_path(temp.path(), role_name),
+            role_prompt,
+        )
+        .expect("role prompt should be written");
+
+        let mut captured_engine = None;
+        let mut captured_prompt = String::new();
+        let exit_code = run_exec_command_in_dir(
+            temp.path(),
+            None,
+            &engine::ClaudeEngine,
+            "deploy the app",
+            None,
+            ExecOutputMode::Text,
+            |engine, prompt, continue_id, cwd| {
+                captured_engine = Some(engine.name().to_string());
+                captured_prompt = prompt.to_string();
+                assert_eq!(continue_id, None);
+                assert_eq!(cwd, temp.path());
+                Ok(EngineOutput {
+                    status_code: 0,
+                    stdout: "{\"session_id\":\"session-1\",\"result\":\"done\"}\n".to_string(),
+                    stderr: String::new(),
+                    timed_out: false,
+                })
+            },
+        );
+
+        assert_eq!(exit_code, 0);
+        assert_eq!(captured_engine.as_deref(), Some("claude"));
+        assert_eq!(
+            captured_prompt,
+            "# Implicit exec prompt\n\nDo role work.\n\nUser input:\ndeploy the app"
+        );
+
+        let runtime_prompt =
+            fs::read_to_string(role_state::runtime_prompt_path(temp.path(), role_name))
+                .expect("runtime prompt should be written");
+        assert_eq!(runtime_prompt, role_prompt);
+    }
+
+    #[test]
+    fn exec_returns_engine_exit_code() {
+        let temp = TestDir::new("exec-engine-exit-code");
+        let role_name = "director-of-engineering";
+        role_state::create_role_state(temp.path(), role_name).expect("role state should exist");
+        fs::write(
+            role_state::role_prompt_path(temp.path(), role_name),
+            "# prompt",
+        )
+        .expect("role prompt should be written");
+
+        let exit_code = run_exec_command_in_dir(
+            temp.path(),
+            Some(role_name),
+            &engine::ClaudeEngine,
+            "hello",
+            None,
+            ExecOutputMode::Text,
+            |_engine, _prompt, _continue_id, _cwd| {
+                Ok(EngineOutput {
+                    status_code: 42,
+                    stdout: String::new(),
+                    stderr: String::new(),
+                    timed_out: false,
+                })
+            },
+        );
+
+        assert_eq!(exit_code, 42);
+    }
+
+    #[test]
+    fn exec_fails_when_explicit_role_is_missing() {
+        let temp = TestDir::new("exec-missing-role");
+
+        let exit_code = run_exec_command_in_dir(
+            temp.path(),
+            Some("missing-role"),
+            &engine::CodexEngine,
+            "hello",
+            None,
+            ExecOutputMode::Text,
+            |_engine, _prompt, _continue_id, _cwd| unreachable!("runner should not be called"),
+        );
+
+        assert_eq!(exit_code, 1);
+    }
+
+    #[test]
+    fn exec_fails_when_no_roles_configured_for_implicit_discovery() {
+        let temp = TestDir::new("exec-no-roles");
+
+        let exit_code = run_exec_command_in_dir(
+            temp.path(),
+            None,
+            &engine::ClaudeEngine,
+            "hello",
+            None,
+            ExecOutputMode::Text,
+            |_engine, _prompt, _continue_id, _cwd| unreachable!("runner should not be called"),
+        );
+
+        assert_eq!(exit_code, 1);
+    }
+
+    #[test]
+    fn exec_fails_when_multiple_roles_configured_for_implicit_discovery() {
+        let temp = TestDir::new("exec-multiple-roles");
+        role_state::create_role_state(temp.path(), "alpha-team")
+            .expect("alpha role state should exist");
+        role_state::create_role_state(temp.path(), "zeta-team")
+            .expect("zeta role state should exist");
+
+        let exit_code = run_exec_command_in_dir(
+            temp.path(),
+            None,
+            &engine::CodexEngine,
+            "hello",
+            None,
+            ExecOutputMode::Text,
+            |_engine, _prompt, _continue_id, _cwd| unreachable!("runner should not be called"),
+        );
+
+        assert_eq!(exit_code, 1);
+    }
+
+    #[test]
+    fn exec_fails_when_engine_runner_returns_error() {
+        let temp = TestDir::new("exec-engine-error");
+        let role_name = "director-of-engineering";
+        role_state::create_role_state(temp.path(), role_name).expect("role state should exist");
+        fs::write(
+            role_state::role_prompt_path(temp.path(), role_name),
+            "# prompt",
+        )
+        .expect("role prompt should be written");
+
+        let exit_code = run_exec_command_in_dir(
+            temp.path(),
+            Some(role_name),
+            &engine::ClaudeEngine,
+            "hello",
+            None,
+            ExecOutputMode::Text,
+            |_engine, _prompt, _continue_id, _cwd| {
+                Err(io::Error::new(io::ErrorKind::NotFound, "engine not found"))
+            },
+        );
+
+        assert_eq!(exit_code, 1);
+    }
+
+    #[test]
+    fn exec_rejects_invalid_explicit_role_name() {
+        let temp = TestDir::new("exec-invalid-role-name");
+
+        let exit_code = run_exec_command_in_dir(
+            temp.path(),
+            Some("../escaped-role"),
+            &engine::CodexEngine,
+            "hello",
+            None,
+            ExecOutputMode::Text,
+            |_engine, _prompt, _continue_id, _cwd| unreachable!("runner should not be called"),
+        );
+
+        assert_eq!(exit_code, 1);
+    }
+
+    #[cfg(unix)]
+    mod cli_integration_tests {
+        use super::*;
+        use serde_json::Value;
+        use std::env;
+        use std::fs;
+        use std::os::unix::fs::PermissionsExt;
+        use std::path::{Path, PathBuf};
+        use std::process::Command;
+        use std::sync::OnceLock;
+
+        struct CliOutput {
+            exit_code: i32,
+            stdout: String,
+            stderr: String,
+        }
+
+        struct MockCodex {
+            bin_dir: PathBuf,
+            args_file: PathBuf,
+            exit_code: i32,
+        }
+
+        impl MockCodex {
+            fn new(root: &Path, exit_code: i32) -> Self {
+                let bin_dir = root.join("mock-bin");
+                fs::create_dir_all(&bin_dir).expect("mock bin directory should exist");
+
+                let args_file = root.join("mock-codex-args.txt");
+                let codex_path = bin_dir.join("codex");
+
+                fs::write(
+                    &codex_path,
+                    r#"#!/usr/bin/env bash
+set -eu
+printf '%s\0' "$@" > "${JULIET_TEST_CODEX_ARGS_FILE:?}"
+if [ "${2:-}" = "exec" ]; then
+  if [ "${JULIET_TEST_CODEX_STDOUT:-}" != "" ]; then
+    printf '%s' "${JULIET_TEST_CODEX_STDOUT}"
+  else
+    printf '%s\n' '{"thread_id":"codex-thread-id"}'
+    printf '%s\n' '{"type":"item.completed","item":{"text":"codex mock response"}}'
+  fi
+fi
+if [ "${JULIET_TEST_CODEX_STDERR:-}" != "" ]; then
+  printf '%s' "${JULIET_TEST_CODEX_STDERR}" >&2
+fi
+exit "${JULIET_TEST_CODEX_EXIT_CODE:-0}"
+"#,
+                )
+                .expect("mock codex script should be writable");
+
+                let mut permissions = fs::metadata(&codex_path)
+                    .expect("mock codex script metadata should be readable")
+                    .permissions();
+                permissions.set_mode(0o755);
+                fs::set_permissions(&codex_path, permissions)
+                    .expect("mock codex script should be executable");
+
+                Self {
+                    bin_dir,
+                    args_file,
+                    exit_code,
+                }
+            }
+
+            fn recorded_args(&self) -> Vec<String> {
+                fs::read_to_string(&self.args_file)
+                    .expect("mock codex args capture should be readable")
+                    .split('\0')
+                    .filter(|s| !s.is_empty())
+                    .map(|s| s.to_string())
+                    .collect()
+            }
+
+            fn recorded_prompt(&self) -> String {
+                let args = self.recorded_args();
+                args.last()
+                    .expect("mock codex should have received at least one argument")
+                    .clone()
+            }
+        }
+
+        struct MockClaude {
+            bin_dir: PathBuf,
+            args_file: PathBuf,
+            env_file: PathBuf,
+            exit_code: i32,
+        }
+
+        impl MockClaude {
+            fn new(root: &Path, exit_code: i32) -> Self {
+                let bin_dir = root.join("mock-bin");
+                fs::create_dir_all(&bin_dir).expect("mock bin directory should exist");
+
+                let args_file = root.join("mock-claude-args.txt");
+                let env_file = root.join("mock-claude-env.txt");
+                let claude_path = bin_dir.join("claude");
+
+                fs::write(
+                    &claude_path,
+                    r#"#!/usr/bin/env bash
+set -eu
+printf '%s\0' "$@" > "${JULIET_TEST_CLAUDE_ARGS_FILE:?}"
+printf 'IS_SANDBOX=%s\n' "${IS_SANDBOX:-}" > "${JULIET_TEST_CLAUDE_ENV_FILE:?}"
+emit_json="0"
+for arg in "$@"; do
+  if [ "$arg" = "--output-format" ]; then
+    emit_json="1"
+    break
+  fi
+done
+if [ "$emit_json" = "1" ]; then
+  if [ "${JULIET_TEST_CLAUDE_STDOUT:-}" != "" ]; then
+    printf '%s' "${JULIET_TEST_CLAUDE_STDOUT}"
+  else
+    printf '%s\n' '{"session_id":"claude-session-id","result":"claude mock response"}'
+  fi
+fi
+if [ "${JULIET_TEST_CLAUDE_STDERR:-}" != "" ]; then
+  printf '%s' "${JULIET_TEST_CLAUDE_STDERR}" >&2
+fi
+exit "${JULIET_TEST_CLAUDE_EXIT_CODE:-0}"
+"#,
+                )
+                .expect("mock claude script should be writable");
+
+                let mut permissions = fs::metadata(&claude_path)
+                    .expect("mock claude script metadata should be readable")
                     .permissions();
                 permissions.set_mode(0o755);
                 fs::set_permissions(&claude_path, permissions)
                     .expect("mock claude script should be executable");
 
-                Self {
-                    bin_dir,
-                    args_file,
-                    env_file,
-                    exit_code,
-                }
-            }
+                Self {
+                    bin_dir,
+                    args_file,
+                    env_file,
+                    exit_code,
+                }
+            }
+
+            fn recorded_args(&self) -> Vec<String> {
+                fs::read_to_string(&self.args_file)
+                    .expect("mock claude args capture should be readable")
+                    .split('\0')
+                    .filter(|s| !s.is_empty())
+                    .map(|s| s.to_string())
+                    .collect()
+            }
+
+            fn recorded_env(&self) -> String {
+                fs::read_to_string(&self.env_file)
+                    .expect("mock claude env capture should be readable")
+            }
+        }
+
+        fn cli_binary_path() -> &'static PathBuf {
+            static CLI_BINARY: OnceLock<PathBuf> = OnceLock::new();
+            CLI_BINARY.get_or_init(|| {
+                let manifest_dir =
+                    env::current_dir().expect("test process should have a current directory");
+                let output = Command::new("cargo")
+                    .arg("build")
+                    .arg("--bin")
+                    .arg("juliet")
+                    .arg("--quiet")
+                    .current_dir(&manifest_dir)
+                    .output()
+                    .expect("cargo should be invokable for cli integration tests");
+
+                if !output.status.success() {
+                    panic!(
+                        "failed to build CLI binary\nstatus: {:?}\nstdout:\n{}\nstderr:\n{}",
+                        output.status.code(),
+                        String::from_utf8_lossy(&output.stdout),
+                        String::from_utf8_lossy(&output.stderr)
+                    );
+                }
+
+                let target_dir = env::var_os("CARGO_TARGET_DIR")
+                    .map(PathBuf::from)
+                    .unwrap_or_else(|| manifest_dir.join("target"));
+                let binary_path = target_dir.join("debug").join("juliet");
+                assert!(
+                    binary_path.is_file(),
+                    "expected juliet binary at {}",
+                    binary_path.display()
+                );
+                binary_path
+            })
+        }
+
+        fn create_project_root(temp: &TestDir) -> PathBuf {
+            let project_root = temp.path().join("project");
+            fs::create_dir_all(&project_root).expect("project root should be created");
+            // Canonicalize to resolve symlinks (e.g. macOS /var -> /private/var)
+            // so path assertions match what the binary sees via current_dir().
+            project_root
+                .canonicalize()
+                .expect("project root should be canonicalizable")
+        }
+
+        fn run_cli(
+            project_root: &Path,
+            args: &[&str],
+            mock_codex: Option<&MockCodex>,
+        ) -> CliOutput {
+            run_cli_with_engines(project_root, args, mock_codex, None)
+        }
+
+        fn run_cli_with_engines(
+            project_root: &Path,
+            args: &[&str],
+            mock_codex: Option<&MockCodex>,
+            mock_claude: Option<&MockClaude>,
+        ) -> CliOutput {
+            let mut command = Command::new(cli_binary_path());
+            command.args(args).current_dir(project_root);
+
+            // Collect PATH components from mocks
+            let existing_path = env::var("PATH").unwrap_or_default();
+            let mut path_dirs: Vec<String> = Vec::new();
+
+            if let Some(mock) = mock_codex {
+                path_dirs.push(mock.bin_dir.display().to_string());
+                command.env("JULIET_TEST_CODEX_ARGS_FILE", &mock.args_file);
+                command.env("JULIET_TEST_CODEX_EXIT_CODE", mock.exit_code.to_string());
+            }
+
+            if let Some(mock) = mock_claude {
+                if !path_dirs
+                    .iter()
+                    .any(|d| d == &mock.bin_dir.display().to_string())
+                {
+                    path_dirs.push(mock.bin_dir.display().to_string());
+                }
+                command.env("JULIET_TEST_CLAUDE_ARGS_FILE", &mock.args_file);
+                command.env("JULIET_TEST_CLAUDE_ENV_FILE", &mock.env_file);
+                command.env("JULIET_TEST_CLAUDE_EXIT_CODE", mock.exit_code.to_string());
+            }
+
+            if !path_dirs.is_empty() {
+                if !existing_path.is_empty() {
+                    path_dirs.push(existing_path);
+                }
+                command.env("PATH", path_dirs.join(":"));
+            }
+
+            let output = command.output().expect("CLI command should execute");
+            let exit_code = output
+                .status
+                .code()
+                .expect("CLI process should exit with an exit code");
+            CliOutput {
+                exit_code,
+                stdout: String::from_utf8(output.stdout)
+                    .expect("stdout should be valid UTF-8 in tests"),
+                stderr: String::from_utf8(output.stderr)
+                    .expect("stderr should be valid UTF-8 in tests"),
+            }
+        }
+
+        #[test]
+        fn cli_help_uses_standard_clap_format() {
+            let temp = TestDir::new("integration-help");
+            let project_root = create_project_root(&temp);
+
+            let output = run_cli(&project_root, &["--help"], None);
+
+            assert_eq!(output.exit_code, 0);
+            assert!(output.stdout.contains("Usage: juliet"));
+            assert!(output.stdout.contains("Commands:"));
+            assert!(output.stdout.contains("init"));
+            assert!(output.stdout.contains("Initialize a new role scaffold"));
+            assert!(output
+                .stdout
+                .contains("Reset a role prompt to the default template"));
+            assert!(output.stdout.contains("Clear role state/history"));
+            assert!(output.stdout.contains("exec"));
+            assert!(output
+                .stdout
+                .contains("Execute a single non-interactive turn"));
+            assert!(output.stdout.contains("-h, --help"));
+            assert!(output.stdout.contains("-V, --version"));
+            assert!(output.stdout.contains("--project <ROLE_NAME>"));
+            assert_eq!(output.stderr, "");
+        }
+
+        #[test]
+        fn cli_subcommand_help_includes_argument_annotations() {
+            let temp = TestDir::new("integration-subcommand-help");
+            let project_root = create_project_root(&temp);
+
+            let exec_help = run_cli(&project_root, &["exec", "--help"], None);
+            assert_eq!(exec_help.exit_code, 0);
+            assert!(exec_help
+                .stdout
+                .contains("Execute a single non-interactive turn"));
+            assert!(exec_help.stdout.contains("--project <ROLE_NAME>"));
+            assert!(exec_help
+                .stdout
+                .contains("Message text appended to the prompt"));
+
+            let launch_help = run_cli(&project_root, &["--help"], None);
+            assert!(launch_help
+                .stdout
+                .contains("Engine to launch in interactive mode"));
+            assert!(launch_help
+                .stdout
+                .contains("Optional operator input appended to the launch prompt"));
+        }
+
+        #[test]
+        fn cli_no_args_prints_clap_error_and_usage() {
+            let temp = TestDir::new("integration-no-args");
+            let project_root = create_project_root(&temp);
+
+            let output = run_cli(&project_root, &[], None);
+
+            assert_eq!(output.exit_code, 2);
+            assert_eq!(output.stdout, "");
+            assert!(output.stderr.contains("error:"));
+            assert!(output
+                .stderr
+                .contains("required arguments were not provided"));
+            assert!(output.stderr.contains("<ENGINE>"));
+            assert!(output.stderr.contains("Usage: juliet"));
+        }
+
+        #[test]
+        fn cli_init_without_role_prints_clap_usage_and_exits_with_code_two() {
+            let temp = TestDir::new("integration-init-usage");
+            let project_root = create_project_root(&temp);
+
+            let output = run_cli(&project_root, &["init"], None);
+
+            assert_eq!(output.exit_code, 2);
+            assert_eq!(output.stdout, "");
+            assert!(output.stderr.contains("error:"));
+            assert!(output
+                .stderr
+                .contains("Usage: juliet init --project <ROLE_NAME>"));
+        }
+
+        #[test]
+        fn cli_init_with_empty_role_name_prints_invalid_name_and_exits_with_code_one() {
+            let temp = TestDir::new("integration-init-empty-role");
+            let project_root = create_project_root(&temp);
+
+            let output = run_cli(&project_root, &["init", "--project", ""], None);
+
+            assert_eq!(output.exit_code, 1);
+            assert_eq!(output.stdout, "");
+            assert_eq!(
+                output.stderr,
+                "Invalid role name: . Use lowercase letters, numbers, and hyphens.\n"
+            );
+        }
+
+        #[test]
+        fn cli_init_is_idempotent_with_exact_messages_and_exit_codes() {
+            let temp = TestDir::new("integration-init-idempotent");
+            let project_root = create_project_root(&temp);
+            let role_name = "director-of-engineering";
+
+            let first = run_cli(&project_root, &["init", "--project", role_name], None);
+            assert_eq!(first.exit_code, 0);
+            assert_eq!(first.stdout, format!("Initialized role: {role_name}\n"));
+            assert_eq!(first.stderr, "");
+            let state_gitignore_path = role_state::state_gitignore_path(&project_root);
+            let first_state_gitignore = fs::read_to_string(&state_gitignore_path)
+                .expect("state gitignore should exist after init");
+            assert!(
+                first_state_gitignore.contains("!*/prompt.md"),
+                "state gitignore should keep role prompt files tracked"
+            );
+
+            fs::remove_file(&state_gitignore_path)
+                .expect("state gitignore should be removable for repair test");
+
+            let second = run_cli(&project_root, &["init", "--project", role_name], None);
+            assert_eq!(second.exit_code, 0);
+            assert_eq!(second.stdout, format!("Role already exists: {role_name}\n"));
+            assert_eq!(second.stderr, "");
+            let second_state_gitignore = fs::read_to_string(state_gitignore_path)
+                .expect("state gitignore should be recreated on repeated init");
+            assert!(
+                second_state_gitignore.contains("!*/prompt.md"),
+                "state gitignore should keep role prompt files tracked"
+            );
+        }
+
+        #[test]
+        fn cli_init_artifacts_scaffolds_legacy_directory_and_allows_implicit_launch() {
+            let temp = TestDir::new("integration-init-artifacts-legacy-dir");
+            let project_root = create_project_root(&temp);
+            let role_name = "artifacts";
+            let role_prompt = "# Legacy artifacts prompt\n\nUse legacy artifacts role.";
+
+            let prompt_path = role_state::role_prompt_path(&project_root, role_name);
+            fs::create_dir_all(prompt_path.parent().expect("prompts dir should exist"))
+                .expect("prompts directory should be created");
+            fs::write(&prompt_path, role_prompt).expect("legacy prompt should be writable");
 
-            fn recorded_args(&self) -> Vec<String> {
-                fs::read_to_string(&self.args_file)
-                    .expect("mock claude args capture should be readable")
-                    .split('\0')
-                    .filter(|s| !s.is_empty())
-                    .map(|s| s.to_string())
-                    .collect()
-            }
+            let legacy_role_dir = role_state::role_state_dir(&project_root, role_name);
+            fs::create_dir_all(&legacy_role_dir).expect("legacy artifacts directory should exist");
+            fs::write(
+                legacy_role_dir.join("existing-artifact.md"),
+                "legacy artifact",
+            )
+            .expect("legacy artifact file should be writable");
 
-            fn recorded_env(&self) -> String {
-                fs::read_to_string(&self.env_file)
-                    .expect("mock claude env capture should be readable")
-            }
+            let init = run_cli(&project_root, &["init", "--project", role_name], None);
+            assert_eq!(init.exit_code, 0);
+            assert_eq!(init.stdout, format!("Initialized role: {role_name}\n"));
+            assert_eq!(init.stderr, "");
+            assert!(role_state::role_state_is_scaffolded(
+                &project_root,
+                role_name
+            ));
+
+            let mock_codex = MockCodex::new(temp.path(), 0);
+            let launch = run_cli(&project_root, &["codex"], Some(&mock_codex));
+
+            assert_eq!(launch.exit_code, 0);
+            assert_eq!(launch.stdout, "");
+            assert_eq!(launch.stderr, "");
+            assert_eq!(mock_codex.recorded_prompt(), role_prompt);
+            assert_eq!(
+                fs::read_to_string(role_state::runtime_prompt_path(&project_root, role_name))
+                    .expect("runtime prompt should be readable"),
+                role_prompt
+            );
         }
 
-        fn cli_binary_path() -> &'static PathBuf {
-            static CLI_BINARY: OnceLock<PathBuf> = OnceLock::new();
-            CLI_BINARY.get_or_init(|| {
-                let manifest_dir =
-                    env::current_dir().expect("test process should have a current directory");
-                let output = Command::new("cargo")
-                    .arg("build")
-                    .arg("--bin")
-                    .arg("juliet")
-                    .arg("--quiet")
-                    .current_dir(&manifest_dir)
-                    .output()
-                    .expect("cargo should be invokable for cli integration tests");
+        #[test]
+        fn cli_explicit_role_launch_stages_prompt_and_uses_engine_exit_code() {
+            let temp = TestDir::new("integration-explicit-launch");
+            let project_root = create_project_root(&temp);
+            let role_name = "director-of-marketing";
+            let role_prompt = "# Explicit role prompt\n\nRun the explicit role workflow.";
 
-                if !output.status.success() {
-                    panic!(
-                        "failed to build CLI binary\nstatus: {:?}\nstdout:\n{}\nstderr:\n{}",
-                        output.status.code(),
-                        String::from_utf8_lossy(&output.stdout),
-                        String::from_utf8_lossy(&output.stderr)
-                    );
-                }
+            let init = run_cli(&project_root, &["init", "--role", role_name], None);
+            assert_eq!(init.exit_code, 0);
 
-                let target_dir = env::var_os("CARGO_TARGET_DIR")
-                    .map(PathBuf::from)
-                    .unwrap_or_else(|| manifest_dir.join("target"));
-                let binary_path = target_dir.join("debug").join("juliet");
-                assert!(
-                    binary_path.is_file(),
-                    "expected juliet binary at {}",
-                    binary_path.display()
-                );
-                binary_path
-            })
+            let role_prompt_path = role_state::role_prompt_path(&project_root, role_name);
+            fs::write(&role_prompt_path, role_prompt).expect("role prompt should be writable");
+
+            let mock_codex = MockCodex::new(temp.path(), 0);
+            let launch = run_cli(
+                &project_root,
+                &["--project", role_name, "codex"],
+                Some(&mock_codex),
+            );
+
+            assert_eq!(launch.exit_code, 0);
+            assert_eq!(launch.stdout, "");
+            assert_eq!(launch.stderr, "");
+            assert_eq!(
+                mock_codex.recorded_args(),
+                vec![
+                    "--dangerously-bypass-approvals-and-sandbox".to_string(),
+                    role_prompt.to_string(),
+                ]
+            );
+            assert_eq!(
+                fs::read_to_string(role_state::runtime_prompt_path(&project_root, role_name))
+                    .expect("runtime prompt should be readable"),
+                role_prompt
+            );
         }
 
-        fn create_project_root(temp: &TestDir) -> PathBuf {
-            let project_root = temp.path().join("project");
-            fs::create_dir_all(&project_root).expect("project root should be created");
-            // Canonicalize to resolve symlinks (e.g. macOS /var -> /private/var)
-            // so path assertions match what the binary sees via current_dir().
-            project_root
-                .canonicalize()
-                .expect("project root should be canonicalizable")
+        #[test]
+        fn cli_explicit_role_launch_with_missing_role_prints_not_found_and_exits_with_code_one() {
+            let temp = TestDir::new("integration-explicit-missing");
+            let project_root = create_project_root(&temp);
+
+            let output = run_cli(&project_root, &["--role", "missing-role", "codex"], None);
+
+            assert_eq!(output.exit_code, 1);
+            assert_eq!(output.stdout, "");
+            assert_eq!(
+                output.stderr,
+                "Role not found: missing-role. Run: juliet init --project missing-role\n"
+            );
         }
 
-        fn run_cli(
-            project_root: &Path,
-            args: &[&str],
-            mock_codex: Option<&MockCodex>,
-        ) -> CliOutput {
-            run_cli_with_engines(project_root, args, mock_codex, None)
+        #[test]
+        fn cli_implicit_launch_with_no_roles_prints_no_roles_message_and_exits_with_code_one() {
+            let temp = TestDir::new("integration-implicit-no-roles");
+            let project_root = create_project_root(&temp);
+
+            let output = run_cli(&project_root, &["codex"], None);
+
+            assert_eq!(output.exit_code, 1);
+            assert_eq!(output.stdout, "");
+            assert_eq!(output.stderr, format!("{NO_ROLES_CONFIGURED_ERROR}\n"));
         }
 
-        fn run_cli_with_engines(
-            project_root: &Path,
-            args: &[&str],
-            mock_codex: Option<&MockCodex>,
-            mock_claude: Option<&MockClaude>,
-        ) -> CliOutput {
-            let mut command = Command::new(cli_binary_path());
-            command.args(args).current_dir(project_root);
+        #[test]
+        fn cli_implicit_launch_with_multiple_roles_prints_sorted_list_and_exits_with_code_one() {
+            let temp = TestDir::new("integration-implicit-multi-role");
+            let project_root = create_project_root(&temp);
+            let first_role = "zeta-team";
+            let second_role = "alpha-team";
 
-            // Collect PATH components from mocks
-            let existing_path = env::var("PATH").unwrap_or_default();
-            let mut path_dirs: Vec<String> = Vec::new();
+            let first_init = run_cli(&project_root, &["init", "--role", first_role], None);
+            assert_eq!(first_init.exit_code, 0);
+            let second_init = run_cli(&project_root, &["init", "--role", second_role], None);
+            assert_eq!(second_init.exit_code, 0);
 
-            if let Some(mock) = mock_codex {
-                path_dirs.push(mock.bin_dir.display().to_string());
-                command.env("JULIET_TEST_CODEX_ARGS_FILE", &mock.args_file);
-                command.env("JULIET_TEST_CODEX_EXIT_CODE", mock.exit_code.to_string());
-            }
+            let output = run_cli(&project_root, &["codex"], None);
 
-            if let Some(mock) = mock_claude {
-                if !path_dirs
-                    .iter()
-                    .any(|d| d == &mock.bin_dir.display().to_string())
-                {
-                    path_dirs.push(mock.bin_dir.display().to_string());
-                }
-                command.env("JULIET_TEST_CLAUDE_ARGS_FILE", &mock.args_file);
-                command.env("JULIET_TEST_CLAUDE_ENV_FILE", &mock.env_file);
-                command.env("JULIET_TEST_CLAUDE_EXIT_CODE", mock.exit_code.to_string());
-            }
+            assert_eq!(output.exit_code, 1);
+            assert_eq!(output.stdout, "");
+            assert_eq!(
+                output.stderr,
+                "Multiple roles found. Specify one with --project <name>:\nalpha-team\nzeta-team\n"
+            );
+        }
 
-            if !path_dirs.is_empty() {
-                if !existing_path.is_empty() {
-                    path_dirs.push(existing_path);
-                }
-                command.env("PATH", path_dirs.join(":"));
-            }
+        #[test]
+        fn cli_implicit_single_role_launch_auto_selects_role_and_exits_with_engine_code() {
+            let temp = TestDir::new("integration-implicit-single-role");
+            let project_root = create_project_root(&temp);
+            let role_name = "director-of-engineering";
+            let role_prompt = "# Implicit role prompt\n\nRun the implicit role workflow.";
+
+            let init = run_cli(&project_root, &["init", "--project", role_name], None);
+            assert_eq!(init.exit_code, 0);
+            fs::write(
+                role_state::role_prompt_path(&project_root, role_name),
+                role_prompt,
+            )
+            .expect("role prompt should be writable");
+
+            let mock_codex = MockCodex::new(temp.path(), 0);
+            let launch = run_cli(&project_root, &["codex"], Some(&mock_codex));
+
+            assert_eq!(launch.exit_code, 0);
+            assert_eq!(launch.stdout, "");
+            assert_eq!(launch.stderr, "");
+            assert_eq!(mock_codex.recorded_prompt(), role_prompt);
+            assert_eq!(
+                fs::read_to_string(role_state::runtime_prompt_path(&project_root, role_name))
+                    .expect("runtime prompt should be readable"),
+                role_prompt
+            );
+        }
+
+        #[test]
+        fn cli_implicit_single_artifacts_role_launch_auto_selects_role_and_exits_with_engine_code()
+        {
+            let temp = TestDir::new("integration-implicit-artifacts-role");
+            let project_root = create_project_root(&temp);
+            let role_name = "artifacts";
+            let role_prompt = "# Artifacts role prompt\n\nRun the implicit role workflow.";
+
+            let init = run_cli(&project_root, &["init", "--role", role_name], None);
+            assert_eq!(init.exit_code, 0);
+            fs::write(
+                role_state::role_prompt_path(&project_root, role_name),
+                role_prompt,
+            )
+            .expect("role prompt should be writable");
+
+            let mock_codex = MockCodex::new(temp.path(), 0);
+            let launch = run_cli(&project_root, &["codex"], Some(&mock_codex));
 
-            let output = command.output().expect("CLI command should execute");
-            let exit_code = output
-                .status
-                .code()
-                .expect("CLI process should exit with an exit code");
-            CliOutput {
-                exit_code,
-                stdout: String::from_utf8(output.stdout)
-                    .expect("stdout should be valid UTF-8 in tests"),
-                stderr: String::from_utf8(output.stderr)
-                    .expect("stderr should be valid UTF-8 in tests"),
-            }
+            assert_eq!(launch.exit_code, 0);
+            assert_eq!(launch.stdout, "");
+            assert_eq!(launch.stderr, "");
+            assert_eq!(mock_codex.recorded_prompt(), role_prompt);
+            assert_eq!(
+                fs::read_to_string(role_state::runtime_prompt_path(&project_root, role_name))
+                    .expect("runtime prompt should be readable"),
+                role_prompt
+            );
         }
 
+        // reset-prompt integration tests
+
         #[test]
-        fn cli_help_uses_standard_clap_format() {
-            let temp = TestDir::new("integration-help");
+        fn cli_reset_prompt_overwrites_prompt_with_default_template_and_prints_success() {
+            let temp = TestDir::new("integration-reset-prompt-success");
             let project_root = create_project_root(&temp);
+            let role_name = "director-of-engineering";
 
-            let output = run_cli(&project_root, &["--help"], None);
+            let init = run_cli(&project_root, &["init", "--role", role_name], None);
+            assert_eq!(init.exit_code, 0);
+
+            let prompt_path = role_state::role_prompt_path(&project_root, role_name);
+            fs::write(&prompt_path, "# Custom modified prompt\n\nUser edits here.")
+                .expect("prompt should be writable");
+
+            let output = run_cli(
+                &project_root,
+                &["reset-prompt", "--project", role_name],
+                None,
+            );
 
             assert_eq!(output.exit_code, 0);
-            assert!(output.stdout.contains("Usage: juliet"));
-            assert!(output.stdout.contains("Commands:"));
-            assert!(output.stdout.contains("init"));
-            assert!(output.stdout.contains("Initialize a new role scaffold"));
-            assert!(output
-                .stdout
-                .contains("Reset a role prompt to the default template"));
-            assert!(output.stdout.contains("Clear role state/history"));
-            assert!(output.stdout.contains("exec"));
-            assert!(output
-                .stdout
-                .contains("Execute a single non-interactive turn"));
-            assert!(output.stdout.contains("-h, --help"));
-            assert!(output.stdout.contains("-V, --version"));
-            assert!(output.stdout.contains("--project <ROLE_NAME>"));
+            assert_eq!(
+                output.stdout,
+                format!("prompt reset to default for role '{role_name}'\n")
+            );
             assert_eq!(output.stderr, "");
+
+            let prompt_contents =
+                fs::read_to_string(&prompt_path).expect("prompt should be readable after reset");
+            assert!(
+                prompt_contents.contains(&format!("# {role_name}")),
+                "prompt should contain role heading"
+            );
+            assert!(
+                prompt_contents.contains(OPERATOR_PLACEHOLDER),
+                "prompt should contain operator placeholder"
+            );
+            assert!(
+                prompt_contents.contains("## Default Prompt Seed"),
+                "prompt should contain default prompt seed heading"
+            );
+            assert!(
+                prompt_contents.contains(prompt_layers::SEED_INCLUDE),
+                "prompt should include the default prompt seed"
+            );
         }
 
         #[test]
-        fn cli_subcommand_help_includes_argument_annotations() {
-            let temp = TestDir::new("integration-subcommand-help");
+        fn cli_reset_prompt_fails_when_role_not_initialized() {
+            let temp = TestDir::new("integration-reset-prompt-not-initialized");
             let project_root = create_project_root(&temp);
 
-            let exec_help = run_cli(&project_root, &["exec", "--help"], None);
-            assert_eq!(exec_help.exit_code, 0);
-            assert!(exec_help
-                .stdout
-                .contains("Execute a single non-interactive turn"));
-            assert!(exec_help.stdout.contains("--project <ROLE_NAME>"));
-            assert!(exec_help
-                .stdout
-                .contains("Message text appended to the prompt"));
+            let output = run_cli(
+                &project_root,
+                &["reset-prompt", "--role", "missing-role"],
+                None,
+            );
 
-            let launch_help = run_cli(&project_root, &["--help"], None);
-            assert!(launch_help
-                .stdout
-                .contains("Engine to launch in interactive mode"));
-            assert!(launch_help
-                .stdout
-                .contains("Optional operator input appended to the launch prompt"));
+            assert_eq!(output.exit_code, 1);
+            assert_eq!(output.stdout, "");
+            assert_eq!(output.stderr, "Role 'missing-role' is not initialized.\n");
         }
 
         #[test]
-        fn cli_no_args_prints_clap_error_and_usage() {
-            let temp = TestDir::new("integration-no-args");
+        fn cli_reset_prompt_fails_with_invalid_role_name() {
+            let temp = TestDir::new("integration-reset-prompt-invalid-name");
             let project_root = create_project_root(&temp);
 
-            let output = run_cli(&project_root, &[], None);
+            let output = run_cli(
+                &project_root,
+                &["reset-prompt", "--role", "Invalid_Name"],
+                None,
+            );
 
-            assert_eq!(output.exit_code, 2);
+            assert_eq!(output.exit_code, 1);
             assert_eq!(output.stdout, "");
-            assert!(output.stderr.contains("error:"));
-            assert!(output
-                .stderr
-                .contains("required arguments were not provided"));
-            assert!(output.stderr.contains("<ENGINE>"));
-            assert!(output.stderr.contains("Usage: juliet"));
+            assert_eq!(
+                output.stderr,
+                "Invalid role name: Invalid_Name. Use lowercase letters, numbers, and hyphens.\n"
+            );
         }
 
         #[test]
-        fn cli_init_without_role_prints_clap_usage_and_exits_with_code_two() {
-            let temp = TestDir::new("integration-init-usage");
+        fn cli_reset_prompt_without_role_prints_clap_usage_and_exits_with_code_two() {
+            let temp = TestDir::new("integration-reset-prompt-usage");
             let project_root = create_project_root(&temp);
 
-            let output = run_cli(&project_root, &["init"], None);
+            let output = run_cli(&project_root, &["reset-prompt"], None);
 
             assert_eq!(output.exit_code, 2);
             assert_eq!(output.stdout, "");
             assert!(output.stderr.contains("error:"));
             assert!(output
                 .stderr
-                .contains("Usage: juliet init --project <ROLE_NAME>"));
+                .contains("Usage: juliet reset-prompt --project <ROLE_NAME>"));
         }
 
         #[test]
-        fn cli_init_with_empty_role_name_prints_invalid_name_and_exits_with_code_one() {
-            let temp = TestDir::new("integration-init-empty-role");
+        fn cli_reset_prompt_preserves_state_files() {
+            let temp = TestDir::new("integration-reset-prompt-preserves-state");
             let project_root = create_project_root(&temp);
+            let role_name = "operations";
 
-            let output = run_cli(&project_root, &["init", "--project", ""], None);
+            let init = run_cli(&project_root, &["init", "--project", role_name], None);
+            assert_eq!(init.exit_code, 0);
 
-            assert_eq!(output.exit_code, 1);
-            assert_eq!(output.stdout, "");
+            let session_path =
+                role_state::role_state_dir(&project_root, role_name).join("session.md");
+            fs::write(&session_path, "important session data").expect("session should be writable");
+
+            let output = run_cli(&project_root, &["reset-prompt", "--role", role_name], None);
+
+            assert_eq!(output.exit_code, 0);
             assert_eq!(
-                output.stderr,
-                "Invalid role name: . Use lowercase letters, numbers, and hyphens.\n"
+                fs::read_to_string(&session_path).expect("session should still exist"),
+                "important session data"
             );
         }
 
         #[test]
-        fn cli_init_is_idempotent_with_exact_messages_and_exit_codes() {
-            let temp = TestDir::new("integration-init-idempotent");
+        fn cli_reset_prompt_restores_exact_init_template() {
+            let temp = TestDir::new("integration-reset-prompt-exact-template");
             let project_root = create_project_root(&temp);
             let role_name = "director-of-engineering";
 
-            let first = run_cli(&project_root, &["init", "--project", role_name], None);
-            assert_eq!(first.exit_code, 0);
-            assert_eq!(first.stdout, format!("Initialized role: {role_name}\n"));
-            assert_eq!(first.stderr, "");
-            let state_gitignore_path = role_state::state_gitignore_path(&project_root);
-            let first_state_gitignore = fs::read_to_string(&state_gitignore_path)
-                .expect("state gitignore should exist after init");
-            assert!(
-                first_state_gitignore.contains("!*/prompt.md"),
-                "state gitignore should keep role prompt files tracked"
-            );
+            let init = run_cli(&project_root, &["init", "--role", role_name], None);
+            assert_eq!(init.exit_code, 0);
 
-            fs::remove_file(&state_gitignore_path)
-                .expect("state gitignore should be removable for repair test");
+            let prompt_path = role_state::role_prompt_path(&project_root, role_name);
+            let original_prompt =
+                fs::read_to_string(&prompt_path).expect("init should create prompt.md");
 
-            let second = run_cli(&project_root, &["init", "--project", role_name], None);
-            assert_eq!(second.exit_code, 0);
-            assert_eq!(second.stdout, format!("Role already exists: {role_name}\n"));
-            assert_eq!(second.stderr, "");
-            let second_state_gitignore = fs::read_to_string(state_gitignore_path)
-                .expect("state gitignore should be recreated on repeated init");
-            assert!(
-                second_state_gitignore.contains("!*/prompt.md"),
-                "state gitignore should keep role prompt files tracked"
+            fs::write(
+                &prompt_path,
+                "# Completely replaced prompt\n\nNothing from before.",
+            )
+            .expect("prompt should be writable");
+
+            let output = run_cli(&project_root, &["reset-prompt", "--role", role_name], None);
+
+            assert_eq!(output.exit_code, 0);
+            assert_eq!(
+                output.stdout,
+                format!("prompt reset to default for role '{role_name}'\n")
+            );
+            assert_eq!(output.stderr, "");
+
+            let reset_prompt =
+                fs::read_to_string(&prompt_path).expect("prompt should be readable after reset");
+            assert_eq!(
+                reset_prompt, original_prompt,
+                "reset-prompt should restore the exact same content as init"
             );
         }
 
+        // clear-history integration tests
+
         #[test]
-        fn cli_init_artifacts_scaffolds_legacy_directory_and_allows_implicit_launch() {
-            let temp = TestDir::new("integration-init-artifacts-legacy-dir");
+        fn cli_clear_history_empties_state_and_prints_success() {
+            let temp = TestDir::new("integration-clear-history-success");
             let project_root = create_project_root(&temp);
-            let role_name = "artifacts";
-            let role_prompt = "# Legacy artifacts prompt\n\nUse legacy artifacts role.";
+            let role_name = "director-of-engineering";
 
-            let prompt_path = role_state::role_prompt_path(&project_root, role_name);
-            fs::create_dir_all(prompt_path.parent().expect("prompts dir should exist"))
-                .expect("prompts directory should be created");
-            fs::write(&prompt_path, role_prompt).expect("legacy prompt should be writable");
+            let init = run_cli(&project_root, &["init", "--role", role_name], None);
+            assert_eq!(init.exit_code, 0);
 
-            let legacy_role_dir = role_state::role_state_dir(&project_root, role_name);
-            fs::create_dir_all(&legacy_role_dir).expect("legacy artifacts directory should exist");
-            fs::write(
-                legacy_role_dir.join("existing-artifact.md"),
-                "legacy artifact",
-            )
-            .expect("legacy artifact file should be writable");
+            let role_dir = role_state::role_state_dir(&project_root, role_name);
+            fs::write(role_dir.join("session.md"), "session data")
+                .expect("session should be writable");
+            fs::write(role_dir.join("needs-from-operator.md"), "operator needs")
+                .expect("needs should be writable");
+            fs::write(role_dir.join("projects.md"), "project data")
+                .expect("projects should be writable");
+            fs::write(role_dir.join("processes.md"), "process data")
+                .expect("processes should be writable");
+            let shared_learnings_path = role_state::shared_learnings_path(&project_root);
+            fs::write(&shared_learnings_path, "learning data")
+                .expect("shared learnings should be writable");
+
+            let runtime_path = role_state::runtime_prompt_path(&project_root, role_name);
+            fs::write(&runtime_path, "runtime prompt").expect("runtime prompt should be writable");
+
+            let artifacts_dir = role_dir.join("artifacts");
+            fs::write(artifacts_dir.join("report.txt"), "report content")
+                .expect("artifact should be writable");
+            fs::create_dir_all(artifacts_dir.join("subdir"))
+                .expect("artifact subdir should be created");
+            fs::write(artifacts_dir.join("subdir").join("nested.md"), "nested")
+                .expect("nested artifact should be writable");
+
+            let prompt_path = role_state::role_prompt_path(&project_root, role_name);
+            fs::write(&prompt_path, "# Custom prompt\n\nKeep this.")
+                .expect("prompt should be writable");
 
-            let init = run_cli(&project_root, &["init", "--project", role_name], None);
-            assert_eq!(init.exit_code, 0);
-            assert_eq!(init.stdout, format!("Initialized role: {role_name}\n"));
-            assert_eq!(init.stderr, "");
-            assert!(role_state::role_state_is_scaffolded(
+            let output = run_cli(
                 &project_root,
-                role_name
-            ));
+                &["clear-history", "--project", role_name],
+                None,
+            );
 
-            let mock_codex = MockCodex::new(temp.path(), 0);
-            let launch = run_cli(&project_root, &["codex"], Some(&mock_codex));
+            assert_eq!(output.exit_code, 0);
+            assert_eq!(
+                output.stdout,
+                format!("history cleared for role '{role_name}'\n")
+            );
+            assert_eq!(output.stderr, "");
 
-            assert_eq!(launch.exit_code, 0);
-            assert_eq!(launch.stdout, "");
-            assert_eq!(launch.stderr, "");
-            assert_eq!(mock_codex.recorded_prompt(), role_prompt);
+            // State files should be empty
+            assert_eq!(fs::read_to_string(role_dir.join("session.md")).unwrap(), "");
             assert_eq!(
-                fs::read_to_string(role_state::runtime_prompt_path(&project_root, role_name))
-                    .expect("runtime prompt should be readable"),
-                role_prompt
+                fs::read_to_string(role_dir.join("needs-from-operator.md")).unwrap(),
+                ""
+            );
+            assert_eq!(
+                fs::read_to_string(role_dir.join("projects.md")).unwrap(),
+                ""
+            );
+            assert_eq!(
+                fs::read_to_string(role_dir.join("processes.md")).unwrap(),
+                ""
+            );
+            assert_eq!(
+                fs::read_to_string(shared_learnings_path).unwrap(),
+                "learning data"
+            );
+
+            // Runtime prompt should be deleted
+            assert!(!runtime_path.exists(), "juliet-prompt.md should be deleted");
+
+            // Artifacts directory should be empty but still exist
+            assert!(
+                artifacts_dir.is_dir(),
+                "artifacts directory should be preserved"
+            );
+            assert_eq!(
+                fs::read_dir(&artifacts_dir).unwrap().count(),
+                0,
+                "artifacts directory should be empty"
+            );
+
+            // prompt.md should be preserved
+            assert_eq!(
+                fs::read_to_string(&prompt_path).unwrap(),
+                "# Custom prompt\n\nKeep this."
             );
         }
 
         #[test]
-        fn cli_explicit_role_launch_stages_prompt_and_uses_engine_exit_code() {
-            let temp = TestDir::new("integration-explicit-launch");
+        fn cli_clear_history_fails_when_role_not_initialized() {
+            let temp = TestDir::new("integration-clear-history-not-initialized");
             let project_root = create_project_root(&temp);
-            let role_name = "director-of-marketing";
-            let role_prompt = "# Explicit role prompt\n\nRun the explicit role workflow.";
-
-            let init = run_cli(&project_root, &["init", "--role", role_name], None);
-            assert_eq!(init.exit_code, 0);
-
-            let role_prompt_path = role_state::role_prompt_path(&project_root, role_name);
-            fs::write(&role_prompt_path, role_prompt).expect("role prompt should be writable");
 
-            let mock_codex = MockCodex::new(temp.path(), 0);
-            let launch = run_cli(
+            let output = run_cli(
                 &project_root,
-                &["--project", role_name, "codex"],
-                Some(&mock_codex),
+                &["clear-history", "--role", "missing-role"],
+                None,
             );
 
-            assert_eq!(launch.exit_code, 0);
-            assert_eq!(launch.stdout, "");
-            assert_eq!(launch.stderr, "");
-            assert_eq!(
-                mock_codex.recorded_args(),
-                vec![
-                    "--dangerously-bypass-approvals-and-sandbox".to_string(),
-                    role_prompt.to_string(),
-                ]
-            );
-            assert_eq!(
-                fs::read_to_string(role_state::runtime_prompt_path(&project_root, role_name))
-                    .expect("runtime prompt should be readable"),
-                role_prompt
-            );
+            assert_eq!(output.exit_code, 1);
+            assert_eq!(output.stdout, "");
+            assert_eq!(output.stderr, "Role 'missing-role' is not initialized.\n");
         }
 
         #[test]
-        fn cli_explicit_role_launch_with_missing_role_prints_not_found_and_exits_with_code_one() {
-            let temp = TestDir::new("integration-explicit-missing");
+        fn cli_clear_history_fails_with_invalid_role_name() {
+            let temp = TestDir::new("integration-clear-history-invalid-name");
             let project_root = create_project_root(&temp);
 
-            let output = run_cli(&project_root, &["--role", "missing-role", "codex"], None);
+            let output = run_cli(
+                &project_root,
+                &["clear-history", "--role", "Invalid_Name"],
+                None,
+            );
 
             assert_eq!(output.exit_code, 1);
             assert_eq!(output.stdout, "");
             assert_eq!(
                 output.stderr,
-                "Role not found: missing-role. Run: juliet init --project missing-role\n"
+                "Invalid role name: Invalid_Name. Use lowercase letters, numbers, and hyphens.\n"
             );
         }
 
         #[test]
-        fn cli_implicit_launch_with_no_roles_prints_no_roles_message_and_exits_with_code_one() {
-            let temp = TestDir::new("integration-implicit-no-roles");
+        fn cli_clear_history_without_role_prints_clap_usage_and_exits_with_code_two() {
+            let temp = TestDir::new("integration-clear-history-usage");
             let project_root = create_project_root(&temp);
 
-            let output = run_cli(&project_root, &["codex"], None);
+            let output = run_cli(&project_root, &["clear-history"], None);
 
-            assert_eq!(output.exit_code, 1);
+            assert_eq!(output.exit_code, 2);
             assert_eq!(output.stdout, "");
-            assert_eq!(output.stderr, format!("{NO_ROLES_CONFIGURED_ERROR}\n"));
+            assert!(output.stderr.contains("error:"));
+            assert!(output
+                .stderr
+                .contains("Usage: juliet clear-history --project <ROLE_NAME>"));
         }
 
         #[test]
-        fn cli_implicit_launch_with_multiple_roles_prints_sorted_list_and_exits_with_code_one() {
-            let temp = TestDir::new("integration-implicit-multi-role");
+        fn cli_clear_history_warns_on_active_runs_and_undo_restores_snapshot() {
+            let temp = TestDir::new("integration-clear-history-undo");
             let project_root = create_project_root(&temp);
-            let first_role = "zeta-team";
-            let second_role = "alpha-team";
 
-            let first_init = run_cli(&project_root, &["init", "--role", first_role], None);
-            assert_eq!(first_init.exit_code, 0);
-            let second_init = run_cli(&project_root, &["init", "--role", second_role], None);
-            assert_eq!(second_init.exit_code, 0);
+            let init = run_cli(&project_root, &["init", "--role", "eng"], None);
+            assert_eq!(init.exit_code, 0);
+            let processes =
+                "## Active\n- pid: 4242\n  target_branch: feature/wind\n\n## Completed\n";
+            fs::write(role_state::processes_path(&project_root, "eng"), processes)
+                .expect("processes should be writable");
 
-            let output = run_cli(&project_root, &["codex"], None);
+            let clear = run_cli(&project_root, &["clear-history", "--project", "eng"], None);
+            assert_eq!(clear.exit_code, 0);
+            assert_eq!(clear.stdout, "history cleared for role 'eng'\n");
+            assert!(clear
+                .stderr
+                .starts_with("warning: processes.md listed active runs (pid 4242)"));
+            assert!(clear
+                .stderr
+                .contains("Undo with: juliet clear-history --undo --project eng"));
+            assert_eq!(
+                fs::read_to_string(role_state::processes_path(&project_root, "eng"))
+                    .expect("processes should be readable"),
+                ""
+            );
 
-            assert_eq!(output.exit_code, 1);
-            assert_eq!(output.stdout, "");
+            let undo = run_cli(
+                &project_root,
+                &["clear-history", "--undo", "--project", "eng"],
+                None,
+            );
+            assert_eq!(undo.exit_code, 0, "stderr: {}", undo.stderr);
+            assert!(undo
+                .stdout
+                .starts_with("history restored for role 'eng' from .juliet/eng/history/"));
             assert_eq!(
-                output.stderr,
-                "Multiple roles found. Specify one with --project <name>:\nalpha-team\nzeta-team\n"
+                fs::read_to_string(role_state::processes_path(&project_root, "eng"))
+                    .expect("processes should be readable"),
+                processes
+            );
+
+            let again = run_cli(
+                &project_root,
+                &["clear-history", "--undo", "--project", "eng"],
+                None,
+            );
+            assert_eq!(again.exit_code, 1);
+            assert_eq!(
+                again.stderr,
+                "No clear-history snapshot found for role 'eng'.\n"
             );
         }
 
         #[test]
-        fn cli_implicit_single_role_launch_auto_selects_role_and_exits_with_engine_code() {
-            let temp = TestDir::new("integration-implicit-single-role");
+        fn cli_clear_history_preserves_prompt_md() {
+            let temp = TestDir::new("integration-clear-history-preserves-prompt");
             let project_root = create_project_root(&temp);
-            let role_name = "director-of-engineering";
-            let role_prompt = "# Implicit role prompt\n\nRun the implicit role workflow.";
+            let role_name = "operations";
 
             let init = run_cli(&project_root, &["init", "--project", role_name], None);
             assert_eq!(init.exit_code, 0);
-            fs::write(
-                role_state::role_prompt_path(&project_root, role_name),
-                role_prompt,
-            )
-            .expect("role prompt should be writable");
 
-            let mock_codex = MockCodex::new(temp.path(), 0);
-            let launch = run_cli(&project_root, &["codex"], Some(&mock_codex));
+            let prompt_path = role_state::role_prompt_path(&project_root, role_name);
+            fs::write(&prompt_path, "# Custom operations prompt\n\nPreserve me.")
+                .expect("prompt should be writable");
 
-            assert_eq!(launch.exit_code, 0);
-            assert_eq!(launch.stdout, "");
-            assert_eq!(launch.stderr, "");
-            assert_eq!(mock_codex.recorded_prompt(), role_prompt);
+            let output = run_cli(&project_root, &["clear-history", "--role", role_name], None);
+
+            assert_eq!(output.exit_code, 0);
             assert_eq!(
-                fs::read_to_string(role_state::runtime_prompt_path(&project_root, role_name))
-                    .expect("runtime prompt should be readable"),
-                role_prompt
+                fs::read_to_string(&prompt_path).unwrap(),
+                "# Custom operations prompt\n\nPreserve me."
             );
         }
 
         #[test]
-        fn cli_implicit_single_artifacts_role_launch_auto_selects_role_and_exits_with_engine_code()
-        {
-            let temp = TestDir::new("integration-implicit-artifacts-role");
+        fn cli_clear_history_succeeds_when_no_runtime_prompt_exists() {
+            let temp = TestDir::new("integration-clear-history-no-runtime-prompt");
             let project_root = create_project_root(&temp);
-            let role_name = "artifacts";
-            let role_prompt = "# Artifacts role prompt\n\nRun the implicit role workflow.";
+            let role_name = "qa";
 
             let init = run_cli(&project_root, &["init", "--role", role_name], None);
             assert_eq!(init.exit_code, 0);
-            fs::write(
-                role_state::role_prompt_path(&project_root, role_name),
-                role_prompt,
-            )
-            .expect("role prompt should be writable");
 
-            let mock_codex = MockCodex::new(temp.path(), 0);
-            let launch = run_cli(&project_root, &["codex"], Some(&mock_codex));
+            let runtime_path = role_state::runtime_prompt_path(&project_root, role_name);
+            assert!(!runtime_path.exists());
 
-            assert_eq!(launch.exit_code, 0);
-            assert_eq!(launch.stdout, "");
-            assert_eq!(launch.stderr, "");
-            assert_eq!(mock_codex.recorded_prompt(), role_prompt);
+            let output = run_cli(&project_root, &["clear-history", "--role", role_name], None);
+
+            assert_eq!(output.exit_code, 0);
             assert_eq!(
-                fs::read_to_string(role_state::runtime_prompt_path(&project_root, role_name))
-                    .expect("runtime prompt should be readable"),
-                role_prompt
+                output.stdout,
+                format!("history cleared for role '{role_name}'\n")
             );
+            assert_eq!(output.stderr, "");
         }
 
-        // reset-prompt integration tests
+        // status integration tests
 
         #[test]
-        fn cli_reset_prompt_overwrites_prompt_with_default_template_and_prints_success() {
-            let temp = TestDir::new("integration-reset-prompt-success");
+        fn cli_status_json_reports_role_state_without_running_engines() {
+            let temp = TestDir::new("integration-status-json");
             let project_root = create_project_root(&temp);
             let role_name = "director-of-engineering";
 
             let init = run_cli(&project_root, &["init", "--role", role_name], None);
             assert_eq!(init.exit_code, 0);
+            let role_dir = role_state::role_state_dir(&project_root, role_name);
+            fs::write(
+                role_dir.join("needs-from-operator.md"),
+                "- review tasks for wind-sonnets\n",
+            )
+            .expect("needs should be writable");
+            fs::write(
+                role_dir.join("processes.md"),
+                "## Active\n- pid: 777\n  target_branch: feature/wind\n\n## Completed\n",
+            )
+            .expect("processes should be writable");
 
-            let prompt_path = role_state::role_prompt_path(&project_root, role_name);
-            fs::write(&prompt_path, "# Custom modified prompt\n\nUser edits here.")
-                .expect("prompt should be writable");
-
-            let output = run_cli(
-                &project_root,
-                &["reset-prompt", "--project", role_name],
-                None,
-            );
+            let output = run_cli(&project_root, &["status", "--json"], None);
 
             assert_eq!(output.exit_code, 0);
+            assert_eq!(output.stderr, "");
+            let payload: Value =
+                serde_json::from_str(output.stdout.trim()).expect("stdout should be valid JSON");
+            assert_eq!(payload["roles"][0]["role"], role_name);
             assert_eq!(
-                output.stdout,
-                format!("prompt reset to default for role '{role_name}'\n")
+                payload["roles"][0]["needs"][0],
+                "review tasks for wind-sonnets"
             );
-            assert_eq!(output.stderr, "");
+            assert_eq!(payload["roles"][0]["processes"]["active"][0]["pid"], 777);
+        }
 
-            let prompt_contents =
-                fs::read_to_string(&prompt_path).expect("prompt should be readable after reset");
-            assert!(
-                prompt_contents.contains(&format!("# {role_name}")),
-                "prompt should contain role heading"
-            );
-            assert!(
-                prompt_contents.contains(OPERATOR_PLACEHOLDER),
-                "prompt should contain operator placeholder"
-            );
-            assert!(
-                prompt_contents.contains("## Default Prompt Seed"),
-                "prompt should contain default prompt seed heading"
-            );
-            assert!(
-                prompt_contents.contains(DEFAULT_PROMPT_SEED),
-                "prompt should contain the default prompt seed content"
-            );
+        #[test]
+        fn cli_status_prints_table_header() {
+            let temp = TestDir::new("integration-status-table");
+            let project_root = create_project_root(&temp);
+
+            let init = run_cli(&project_root, &["init", "--role", "qa"], None);
+            assert_eq!(init.exit_code, 0);
+
+            let output = run_cli(&project_root, &["status", "--project", "qa"], None);
+
+            assert_eq!(output.exit_code, 0);
+            assert_eq!(output.stderr, "");
+            assert!(output
+                .stdout
+                .starts_with("ROLE  SESSION  ENGINE  NEEDS  ACTIVE  COMPLETED  PROJECTS\nqa"));
         }
 
+        // tasks integration tests
+
         #[test]
-        fn cli_reset_prompt_fails_when_role_not_initialized() {
-            let temp = TestDir::new("integration-reset-prompt-not-initialized");
+        fn cli_tasks_reports_progress_from_swarm_tasks_file() {
+            let temp = TestDir::new("integration-tasks");
             let project_root = create_project_root(&temp);
+            let swarm_dir = project_root.join(".swarm-hug").join("wind");
+            fs::create_dir_all(&swarm_dir).expect("swarm dir should be created");
+            fs::write(
+                swarm_dir.join("tasks.md"),
+                "# Tasks\n\n## Poems\n- [x] (#1) Draft sonnets [5 pts] (A)\n- [ ] (#2) Edit sonnets [3 pts] (blocked by #1) (B)\n",
+            )
+            .expect("tasks should be writable");
 
-            let output = run_cli(
+            let output = run_cli(&project_root, &["tasks", "--swarm-project", "wind"], None);
+
+            assert_eq!(output.stderr, "");
+            assert_eq!(output.exit_code, 0);
+            assert!(output
+                .stdout
+                .starts_with("project: wind (.swarm-hug/wind/tasks.md)\ntasks: 1/2 done\npoints: 5/8 done, 3 remaining\nstatus: more sprints remain\n"));
+            assert!(output.stdout.contains("dependencies:\n  #1 -> #2\n"));
+
+            let json = run_cli(
                 &project_root,
-                &["reset-prompt", "--role", "missing-role"],
+                &["tasks", "--swarm-project", "wind", "--json"],
                 None,
             );
+            assert_eq!(json.exit_code, 0);
+            let payload: Value =
+                serde_json::from_str(json.stdout.trim()).expect("stdout should be valid JSON");
+            assert_eq!(payload["summary"]["remaining_points"], 3);
+            assert_eq!(payload["blocked"], serde_json::json!([]));
 
-            assert_eq!(output.exit_code, 1);
-            assert_eq!(output.stdout, "");
-            assert_eq!(output.stderr, "Role 'missing-role' is not initialized.\n");
+            let missing = run_cli(&project_root, &["tasks", "--swarm-project", "fog"], None);
+            assert_eq!(missing.exit_code, 1);
+            assert!(missing
+                .stderr
+                .contains("Swarm project 'fog' has no tasks.md"));
         }
 
         #[test]
-        fn cli_reset_prompt_fails_with_invalid_role_name() {
-            let temp = TestDir::new("integration-reset-prompt-invalid-name");
+        fn cli_tasks_check_flags_scaffold_and_status_reports_it() {
+            let temp = TestDir::new("integration-tasks-check");
             let project_root = create_project_root(&temp);
+            let init = run_cli(&project_root, &["init", "--role", "eng"], None);
+            assert_eq!(init.exit_code, 0);
+            fs::write(
+                role_state::role_state_dir(&project_root, "eng").join("projects.md"),
+                "- name: wind\n",
+            )
+            .expect("projects should be writable");
+            let swarm_dir = project_root.join(".swarm-hug").join("wind");
+            fs::create_dir_all(&swarm_dir).expect("swarm dir should be created");
+            fs::write(swarm_dir.join("tasks.md"), "# Tasks\n\n## Backlog\n")
+                .expect("tasks should be writable");
 
-            let output = run_cli(
+            let check = run_cli(
                 &project_root,
-                &["reset-prompt", "--role", "Invalid_Name"],
+                &["tasks", "check", "--swarm-project", "wind"],
                 None,
             );
+            assert_eq!(check.exit_code, 1);
+            assert_eq!(
+                check.stdout,
+                ".swarm-hug/wind/tasks.md: no tasks found; this looks like the `swarm project init` scaffold\n"
+            );
 
-            assert_eq!(output.exit_code, 1);
-            assert_eq!(output.stdout, "");
+            let status = run_cli(&project_root, &["status", "--project", "eng"], None);
+            assert_eq!(status.exit_code, 0);
+            assert!(status
+                .stdout
+                .contains("eng blocked: wind tasks.md is not ready for swarm run"));
+
+            fs::write(
+                swarm_dir.join("tasks.md"),
+                "# Tasks\n\n## Backlog\n- [ ] (#1) Write a wind sonnet [3 pts]\n",
+            )
+            .expect("tasks should be writable");
+            let check = run_cli(
+                &project_root,
+                &["tasks", "check", "--swarm-project", "wind"],
+                None,
+            );
+            assert_eq!(check.exit_code, 0);
             assert_eq!(
-                output.stderr,
-                "Invalid role name: Invalid_Name. Use lowercase letters, numbers, and hyphens.\n"
+                check.stdout,
+                "tasks for swarm project 'wind' passed check\n"
             );
         }
 
+        // sprints integration tests
+
         #[test]
-        fn cli_reset_prompt_without_role_prints_clap_usage_and_exits_with_code_two() {
-            let temp = TestDir::new("integration-reset-prompt-usage");
+        fn cli_sprints_reports_swarm_state_and_launching_role() {
+            let temp = TestDir::new("integration-sprints");
             let project_root = create_project_root(&temp);
+            let init = run_cli(&project_root, &["init", "--role", "eng"], None);
+            assert_eq!(init.exit_code, 0);
+            fs::write(
+                role_state::role_state_dir(&project_root, "eng").join("projects.md"),
+                "- name: wind\n",
+            )
+            .expect("projects should be writable");
+            let swarm_dir = project_root.join(".swarm-hug").join("wind");
+            fs::create_dir_all(&swarm_dir).expect("swarm dir should be created");
+            fs::write(
+                swarm_dir.join("sprint-history.json"),
+                r#"{"team": "wind", "total_sprints": 2}"#,
+            )
+            .expect("sprint history should be writable");
+            fs::write(
+                swarm_dir.join("team-state.json"),
+                r#"{"team": "wind", "feature_branch": "wind-sprint-2-x1"}"#,
+            )
+            .expect("team state should be writable");
 
-            let output = run_cli(&project_root, &["reset-prompt"], None);
+            let output = run_cli(&project_root, &["sprints", "--swarm-project", "wind"], None);
 
-            assert_eq!(output.exit_code, 2);
-            assert_eq!(output.stdout, "");
-            assert!(output.stderr.contains("error:"));
-            assert!(output
-                .stderr
-                .contains("Usage: juliet reset-prompt --project <ROLE_NAME>"));
+            assert_eq!(output.stderr, "");
+            assert_eq!(output.exit_code, 0);
+            assert_eq!(
+                output.stdout,
+                "PROJECT  SPRINTS  BRANCH            ROLE  TASKS  POINTS\nwind     2        wind-sprint-2-x1  eng   -      -\n"
+            );
         }
 
+        // worktrees integration tests
+
         #[test]
-        fn cli_reset_prompt_preserves_state_files() {
-            let temp = TestDir::new("integration-reset-prompt-preserves-state");
+        fn cli_worktrees_lists_and_prunes_merged_swarm_worktrees() {
+            let temp = TestDir::new("integration-worktrees");
             let project_root = create_project_root(&temp);
-            let role_name = "operations";
+            let git = |args: &[&str]| {
+                let status = Command::new("git")
+                    .args(["-c", "user.name=Test", "-c", "user.email=test@example.com"])
+                    .args(args)
+                    .current_dir(&project_root)
+                    .status()
+                    .expect("git should run");
+                assert!(status.success(), "git {args:?} should succeed");
+            };
 
-            let init = run_cli(&project_root, &["init", "--project", role_name], None);
+            let init = run_cli(&project_root, &["init", "--role", "eng"], None);
             assert_eq!(init.exit_code, 0);
+            fs::write(
+                role_state::role_state_dir(&project_root, "eng").join("processes.md"),
+                "## Active\n- pid: 42\n  target_branch: feature/wind-try2\n\n## Completed\n- pid: 7\n  target_branch: feature/wind-try1\n",
+            )
+            .expect("processes should be writable");
+            git(&["init", "-q", "-b", "main"]);
+            fs::write(project_root.join("README.md"), "wind\n").expect("readme should be writable");
+            git(&["add", "README.md"]);
+            git(&["commit", "-q", "-m", "start"]);
+            for branch in [
+                "feature/wind-try1",
+                "feature/wind-try2",
+                "feature/wind-try3",
+                "feature/wind-try4",
+            ] {
+                let path = format!(
+                    ".swarm-hug/.shared/worktrees/{}",
+                    branch.replace('/', "%2F")
+                );
+                git(&["worktree", "add", "-q", "-b", branch, &path]);
+            }
+            let try4_path = ".swarm-hug/.shared/worktrees/feature%2Fwind-try4";
+            fs::write(project_root.join(try4_path).join("haiku.md"), "calm\n")
+                .expect("haiku should be writable");
+            git(&["-C", try4_path, "add", "haiku.md"]);
+            git(&["-C", try4_path, "commit", "-q", "-m", "haiku"]);
+            git(&["merge", "-q", "--ff-only", "feature/wind-try4"]);
+            let try1_path = ".swarm-hug/.shared/worktrees/feature%2Fwind-try1";
+            fs::write(project_root.join(try1_path).join("poem.md"), "gust\n")
+                .expect("poem should be writable");
+            git(&["-C", try1_path, "add", "poem.md"]);
+            git(&["-C", try1_path, "commit", "-q", "-m", "poem"]);
+            git(&[
+                "merge",
+                "-q",
+                "--no-ff",
+                "-m",
+                "merge try1",
+                "feature/wind-try1",
+            ]);
+
+            let list = run_cli(&project_root, &["worktrees", "list"], None);
+            assert_eq!(list.stderr, "");
+            assert_eq!(list.exit_code, 0);
+            assert!(list.stdout.contains(
+                "feature/wind-try1  eng   7    orphaned (merged into main)  .swarm-hug/.shared/worktrees/feature%2Fwind-try1\n"
+            ));
+            assert!(list.stdout.contains("feature/wind-try2  eng   42   active"));
+            // try3 was branched from main but has no commits, so it is not merged work.
+            assert!(list.stdout.contains("feature/wind-try3  -     -    ok"));
+            // try4 was fast-forwarded into main, so there is no merge commit to find.
+            assert!(list.stdout.contains(
+                "feature/wind-try4  -     -    orphaned (merged into main)  .swarm-hug/.shared/worktrees/feature%2Fwind-try4\n"
+            ));
 
-            let session_path =
-                role_state::role_state_dir(&project_root, role_name).join("session.md");
-            fs::write(&session_path, "important session data").expect("session should be writable");
-
-            let output = run_cli(&project_root, &["reset-prompt", "--role", role_name], None);
+            let dry_run = run_cli(&project_root, &["worktrees", "prune", "--dry-run"], None);
+            assert_eq!(dry_run.exit_code, 0);
+            assert_eq!(
+                dry_run.stdout,
+                "would remove .swarm-hug/.shared/worktrees/feature%2Fwind-try1 (merged into main)\n\
+would remove .swarm-hug/.shared/worktrees/feature%2Fwind-try4 (merged into main)\n"
+            );
+            let try1 = project_root.join(".swarm-hug/.shared/worktrees/feature%2Fwind-try1");
+            assert!(try1.is_dir());
 
-            assert_eq!(output.exit_code, 0);
+            let prune = run_cli(&project_root, &["worktrees", "prune"], None);
+            assert_eq!(prune.stderr, "");
+            assert_eq!(prune.exit_code, 0);
             assert_eq!(
-                fs::read_to_string(&session_path).expect("session should still exist"),
-                "important session data"
+                prune.stdout,
+                "removed .swarm-hug/.shared/worktrees/feature%2Fwind-try1 (merged into main)\n\
+removed .swarm-hug/.shared/worktrees/feature%2Fwind-try4 (merged into main)\n"
             );
+            assert!(!try1.exists());
+            assert!(project_root
+                .join(".swarm-hug/.shared/worktrees/feature%2Fwind-try2")
+                .is_dir());
         }
 
+        // processes check integration tests
+
+        #[cfg(target_os = "linux")]
         #[test]
-        fn cli_reset_prompt_restores_exact_init_template() {
-            let temp = TestDir::new("integration-reset-prompt-exact-template");
+        fn cli_processes_check_moves_exited_runs_and_signals_change() {
+            let temp = TestDir::new("integration-processes-check");
             let project_root = create_project_root(&temp);
-            let role_name = "director-of-engineering";
 
-            let init = run_cli(&project_root, &["init", "--role", role_name], None);
+            let init = run_cli(&project_root, &["init", "--role", "eng"], None);
             assert_eq!(init.exit_code, 0);
 
-            let prompt_path = role_state::role_prompt_path(&project_root, role_name);
-            let original_prompt =
-                fs::read_to_string(&prompt_path).expect("init should create prompt.md");
+            let mut child = Command::new("true").spawn().expect("true should spawn");
+            let exited_pid = child.id();
+            child.wait().expect("child should exit");
+            let live_pid = std::process::id();
 
+            fs::write(project_root.join("exited.log"), "sprint done\n")
+                .expect("log should be writable");
             fs::write(
-                &prompt_path,
-                "# Completely replaced prompt\n\nNothing from before.",
+                role_state::processes_path(&project_root, "eng"),
+                format!(
+                    "## Active\n- pid: {exited_pid}\n  target_branch: feature/done\n  log_path: exited.log\n- pid: {live_pid}\n  target_branch: feature/live\n\n## Completed\n"
+                ),
             )
-            .expect("prompt should be writable");
+            .expect("processes should be writable");
 
-            let output = run_cli(&project_root, &["reset-prompt", "--role", role_name], None);
-
-            assert_eq!(output.exit_code, 0);
+            let first = run_cli(&project_root, &["processes", "check"], None);
+            assert_eq!(first.exit_code, PROCESSES_CHANGED_EXIT_CODE);
+            assert_eq!(first.stderr, "");
             assert_eq!(
-                output.stdout,
-                format!("prompt reset to default for role '{role_name}'\n")
+                first.stdout,
+                format!(
+                    "finished: pid {exited_pid} -> feature/done (TODO: review log and summarize outcome; exited; no obvious errors in last 50 lines)\nrunning: pid {live_pid} -> feature/live\n"
+                )
             );
-            assert_eq!(output.stderr, "");
 
-            let reset_prompt =
-                fs::read_to_string(&prompt_path).expect("prompt should be readable after reset");
+            let second = run_cli(&project_root, &["processes", "check"], None);
+            assert_eq!(second.exit_code, 0);
             assert_eq!(
-                reset_prompt, original_prompt,
-                "reset-prompt should restore the exact same content as init"
+                second.stdout,
+                format!("running: pid {live_pid} -> feature/live\n")
             );
         }
 
-        // clear-history integration tests
+        #[test]
+        fn cli_processes_check_requires_known_role() {
+            let temp = TestDir::new("integration-processes-missing-role");
+            let project_root = create_project_root(&temp);
+
+            let output = run_cli(
+                &project_root,
+                &["processes", "check", "--project", "ghost"],
+                None,
+            );
+
+            assert_eq!(output.exit_code, 1);
+            assert_eq!(output.stdout, "");
+            assert_eq!(
+                output.stderr,
+                "Role not found: ghost. Run: juliet init --project ghost\n"
+            );
+        }
 
         #[test]
-        fn cli_clear_history_empties_state_and_prints_success() {
-            let temp = TestDir::new("integration-clear-history-success");
+        fn cli_upgrade_prompt_reports_current_seed() {
+            let temp = TestDir::new("integration-upgrade-prompt");
             let project_root = create_project_root(&temp);
-            let role_name = "director-of-engineering";
 
-            let init = run_cli(&project_root, &["init", "--role", role_name], None);
+            let init = run_cli(&project_root, &["init", "--role", "eng"], None);
             assert_eq!(init.exit_code, 0);
+            assert_eq!(
+                fs::read_to_string(role_state::prompt_seed_path(&project_root, "eng"))
+                    .expect("seed record should be written by init"),
+                DEFAULT_PROMPT_SEED
+            );
+            let included = run_cli(&project_root, &["upgrade-prompt", "--project", "eng"], None);
+            assert_eq!(
+                included.stdout,
+                "prompt for role 'eng' uses {{include seed}}; nothing to upgrade\n"
+            );
+            fs::write(
+                role_state::role_prompt_path(&project_root, "eng"),
+                copied_seed_prompt_template("eng", DEFAULT_PROMPT_SEED),
+            )
+            .expect("prompt should be writable");
 
-            let role_dir = role_state::role_state_dir(&project_root, role_name);
-            fs::write(role_dir.join("session.md"), "session data")
-                .expect("session should be writable");
-            fs::write(role_dir.join("needs-from-operator.md"), "operator needs")
-                .expect("needs should be writable");
-            fs::write(role_dir.join("projects.md"), "project data")
-                .expect("projects should be writable");
-            fs::write(role_dir.join("processes.md"), "process data")
-                .expect("processes should be writable");
-            let shared_learnings_path = role_state::shared_learnings_path(&project_root);
-            fs::write(&shared_learnings_path, "learning data")
-                .expect("shared learnings should be writable");
+            let upgrade = run_cli(&project_root, &["upgrade-prompt", "--project", "eng"], None);
+            assert_eq!(upgrade.exit_code, 0, "stderr: {}", upgrade.stderr);
+            assert_eq!(
+                upgrade.stdout,
+                format!(
+                    "prompt for role 'eng' is already on default seed {}\n",
+                    seed_version(DEFAULT_PROMPT_SEED)
+                )
+            );
+        }
 
-            let runtime_path = role_state::runtime_prompt_path(&project_root, role_name);
-            fs::write(&runtime_path, "runtime prompt").expect("runtime prompt should be writable");
+        #[test]
+        fn cli_init_from_template_records_it_for_reset_and_lint() {
+            let temp = TestDir::new("integration-init-template");
+            let project_root = create_project_root(&temp);
 
-            let artifacts_dir = role_dir.join("artifacts");
-            fs::write(artifacts_dir.join("report.txt"), "report content")
-                .expect("artifact should be writable");
-            fs::create_dir_all(artifacts_dir.join("subdir"))
-                .expect("artifact subdir should be created");
-            fs::write(artifacts_dir.join("subdir").join("nested.md"), "nested")
-                .expect("nested artifact should be writable");
+            let listed = run_cli(&project_root, &["templates", "list"], None);
+            assert_eq!(listed.exit_code, 0);
+            assert!(listed.stdout.starts_with("TEMPLATE"));
+            assert!(listed
+                .stdout
+                .contains("reviewer         built-in  Reviews branches"));
 
-            let prompt_path = role_state::role_prompt_path(&project_root, role_name);
-            fs::write(&prompt_path, "# Custom prompt\n\nKeep this.")
-                .expect("prompt should be writable");
+            let unknown = run_cli(
+                &project_root,
+                &["init", "--project", "qa", "--template", "qa-lead"],
+                None,
+            );
+            assert_eq!(unknown.exit_code, 1);
+            assert!(unknown.stderr.contains("Unknown template 'qa-lead'"));
+            assert!(!role_state::role_state_exists(&project_root, "qa"));
 
-            let output = run_cli(
+            let init = run_cli(
                 &project_root,
-                &["clear-history", "--project", role_name],
+                &["init", "--project", "qa", "--template", "reviewer"],
                 None,
             );
+            assert_eq!(init.exit_code, 0, "stderr: {}", init.stderr);
+            let reviewer_seed = include_str!("prompts/reviewer.md");
+            let prompt_path = role_state::role_prompt_path(&project_root, "qa");
+            assert_eq!(
+                fs::read_to_string(&prompt_path).expect("prompt should exist"),
+                role_prompt_template("qa")
+            );
+            assert_eq!(
+                fs::read_to_string(role_state::prompt_seed_path(&project_root, "qa"))
+                    .expect("seed record should exist"),
+                reviewer_seed
+            );
+            assert_eq!(
+                fs::read_to_string(role_state::template_record_path(&project_root, "qa"))
+                    .expect("template record should exist"),
+                "template = \"reviewer\"\n"
+            );
 
-            assert_eq!(output.exit_code, 0);
+            let again = run_cli(
+                &project_root,
+                &["init", "--project", "qa", "--template", "triage"],
+                None,
+            );
+            assert_eq!(again.exit_code, 1);
+            assert!(again
+                .stderr
+                .contains("--template only applies to new roles"));
+
+            fs::write(&prompt_path, "# qa\n\nEdited.\n").expect("prompt should be writable");
+            let reset = run_cli(&project_root, &["reset-prompt", "--project", "qa"], None);
+            assert_eq!(reset.exit_code, 0);
+            let customized = fs::read_to_string(&prompt_path)
+                .expect("prompt should exist")
+                .replace(OPERATOR_PLACEHOLDER, "Review the wind project.");
+            assert!(customized.ends_with("{{include seed}}\n"));
             assert_eq!(
-                output.stdout,
-                format!("history cleared for role '{role_name}'\n")
+                fs::read_to_string(role_state::prompt_seed_path(&project_root, "qa"))
+                    .expect("seed record should exist"),
+                reviewer_seed
             );
-            assert_eq!(output.stderr, "");
+            fs::write(&prompt_path, customized).expect("prompt should be writable");
 
-            // State files should be empty
-            assert_eq!(fs::read_to_string(role_dir.join("session.md")).unwrap(), "");
+            let lint = run_cli(&project_root, &["lint-prompt", "--project", "qa"], None);
+            assert_eq!(lint.exit_code, 0, "stdout: {}", lint.stdout);
+        }
+
+        #[test]
+        fn cli_init_from_path_and_sync_template_merge_upstream_changes() {
+            let temp = TestDir::new("integration-init-from");
+            let project_root = create_project_root(&temp);
+            let source_dir = project_root.join("team/eng-lead");
+            fs::create_dir_all(&source_dir).expect("source dir should be created");
+            fs::write(
+                source_dir.join("prompt.md"),
+                "# eng-lead\n\nLine A\nMiddle\nLine B\n",
+            )
+            .expect("source prompt should be written");
+            fs::write(source_dir.join("prompt-seed.md"), "Team seed.\n")
+                .expect("source seed should be written");
+            fs::write(project_root.join("team/bad.md"), "No heading.\n")
+                .expect("bad prompt should be written");
+
+            let bad = run_cli(
+                &project_root,
+                &["init", "--project", "bad", "--from", "team/bad.md"],
+                None,
+            );
+            assert_eq!(bad.exit_code, 1);
             assert_eq!(
-                fs::read_to_string(role_dir.join("needs-from-operator.md")).unwrap(),
-                ""
+                bad.stderr,
+                "team/bad.md must start with a `# <role>` heading, found `No heading.`\n"
+            );
+            assert!(!role_state::role_state_exists(&project_root, "bad"));
+
+            let init = run_cli(
+                &project_root,
+                &["init", "--project", "platform", "--from", "team/eng-lead"],
+                None,
             );
+            assert_eq!(init.exit_code, 0, "stderr: {}", init.stderr);
+            let prompt_path = role_state::role_prompt_path(&project_root, "platform");
+            let read_prompt =
+                || fs::read_to_string(&prompt_path).expect("prompt should be readable");
+            assert_eq!(read_prompt(), "# platform\n\nLine A\nMiddle\nLine B\n");
             assert_eq!(
-                fs::read_to_string(role_dir.join("projects.md")).unwrap(),
-                ""
+                fs::read_to_string(role_state::prompt_seed_path(&project_root, "platform"))
+                    .expect("seed should be recorded"),
+                "Team seed.\n"
             );
             assert_eq!(
-                fs::read_to_string(role_dir.join("processes.md")).unwrap(),
-                ""
+                fs::read_to_string(role_state::template_record_path(&project_root, "platform"))
+                    .expect("template record should exist"),
+                "from = \"team/eng-lead\"\n"
+            );
+
+            let current = run_cli(
+                &project_root,
+                &["sync-template", "--project", "platform"],
+                None,
             );
+            assert_eq!(current.exit_code, 0);
             assert_eq!(
-                fs::read_to_string(shared_learnings_path).unwrap(),
-                "learning data"
+                current.stdout,
+                "prompt for role 'platform' is already in sync with team/eng-lead\n"
             );
 
-            // Runtime prompt should be deleted
-            assert!(!runtime_path.exists(), "juliet-prompt.md should be deleted");
+            fs::write(
+                &prompt_path,
+                "# platform\n\nLine A, locally\nMiddle\nLine B\n",
+            )
+            .expect("prompt should be writable");
+            fs::write(
+                source_dir.join("prompt.md"),
+                "# eng-lead\n\nLine A\nMiddle\nLine B, upstream\n",
+            )
+            .expect("source prompt should be writable");
+            let merged = run_cli(
+                &project_root,
+                &["sync-template", "--project", "platform"],
+                None,
+            );
+            assert_eq!(merged.exit_code, 0, "stderr: {}", merged.stderr);
+            assert_eq!(
+                merged.stdout,
+                "prompt for role 'platform' synced from team/eng-lead\n"
+            );
+            assert_eq!(
+                read_prompt(),
+                "# platform\n\nLine A, locally\nMiddle\nLine B, upstream\n"
+            );
 
-            // Artifacts directory should be empty but still exist
-            assert!(
-                artifacts_dir.is_dir(),
-                "artifacts directory should be preserved"
+            fs::write(
+                source_dir.join("prompt.md"),
+                "# eng-lead\n\nLine A, upstream\nMiddle\nLine B, upstream\n",
+            )
+            .expect("source prompt should be writable");
+            let conflicted = run_cli(
+                &project_root,
+                &["sync-template", "--project", "platform"],
+                None,
             );
+            assert_eq!(conflicted.exit_code, 1);
             assert_eq!(
-                fs::read_dir(&artifacts_dir).unwrap().count(),
-                0,
-                "artifacts directory should be empty"
+                read_prompt(),
+                "# platform\n\n<<<<<<< prompt.md\nLine A, locally\n=======\nLine A, upstream\n>>>>>>> team/eng-lead\nMiddle\nLine B, upstream\n"
             );
 
-            // prompt.md should be preserved
+            let run_init = run_cli(&project_root, &["init", "--project", "eng"], None);
+            assert_eq!(run_init.exit_code, 0);
+            let not_from = run_cli(&project_root, &["sync-template", "--project", "eng"], None);
+            assert_eq!(not_from.exit_code, 1);
             assert_eq!(
-                fs::read_to_string(&prompt_path).unwrap(),
-                "# Custom prompt\n\nKeep this."
+                not_from.stderr,
+                "Role 'eng' was not initialized with --from; nothing to sync.\n"
             );
         }
 
         #[test]
-        fn cli_clear_history_fails_when_role_not_initialized() {
-            let temp = TestDir::new("integration-clear-history-not-initialized");
+        fn cli_lint_prompt_flags_placeholder_until_customized() {
+            let temp = TestDir::new("integration-lint-prompt");
             let project_root = create_project_root(&temp);
 
-            let output = run_cli(
-                &project_root,
-                &["clear-history", "--role", "missing-role"],
-                None,
+            let init = run_cli(&project_root, &["init", "--role", "eng"], None);
+            assert_eq!(init.exit_code, 0);
+
+            let fresh = run_cli(&project_root, &["lint-prompt", "--project", "eng"], None);
+            assert_eq!(fresh.exit_code, 1);
+            assert_eq!(
+                fresh.stdout,
+                ".juliet/eng/prompt.md:3: operator placeholder is still present; replace it with role-specific instructions\n"
             );
 
-            assert_eq!(output.exit_code, 1);
-            assert_eq!(output.stdout, "");
-            assert_eq!(output.stderr, "Role 'missing-role' is not initialized.\n");
+            let prompt_path = role_state::role_prompt_path(&project_root, "eng");
+            let customized = fs::read_to_string(&prompt_path)
+                .expect("prompt should be readable")
+                .replace(OPERATOR_PLACEHOLDER, "Own the wind project.");
+            fs::write(&prompt_path, customized).expect("prompt should be writable");
+
+            let customized = run_cli(&project_root, &["lint-prompt", "--project", "eng"], None);
+            assert_eq!(customized.exit_code, 0, "stdout: {}", customized.stdout);
+            assert_eq!(customized.stdout, "prompt for role 'eng' passed lint\n");
         }
 
         #[test]
-        fn cli_clear_history_fails_with_invalid_role_name() {
-            let temp = TestDir::new("integration-clear-history-invalid-name");
+        fn cli_roles_rename_clone_list_and_delete() {
+            let temp = TestDir::new("integration-roles");
             let project_root = create_project_root(&temp);
 
-            let output = run_cli(
-                &project_root,
-                &["clear-history", "--role", "Invalid_Name"],
-                None,
+            let init = run_cli(&project_root, &["init", "--role", "eng"], None);
+            assert_eq!(init.exit_code, 0);
+
+            let rename = run_cli(&project_root, &["roles", "rename", "eng", "platform"], None);
+            assert_eq!(rename.exit_code, 0, "stderr: {}", rename.stderr);
+            assert_eq!(rename.stdout, "renamed role eng to platform\n");
+            assert!(
+                fs::read_to_string(role_state::role_prompt_path(&project_root, "platform"))
+                    .expect("renamed prompt should be readable")
+                    .starts_with("# platform\n")
             );
 
-            assert_eq!(output.exit_code, 1);
-            assert_eq!(output.stdout, "");
+            let clone = run_cli(&project_root, &["roles", "clone", "platform", "qa"], None);
+            assert_eq!(clone.exit_code, 0, "stderr: {}", clone.stderr);
+
+            let list = run_cli(&project_root, &["roles", "list"], None);
+            assert_eq!(list.exit_code, 0);
             assert_eq!(
-                output.stderr,
-                "Invalid role name: Invalid_Name. Use lowercase letters, numbers, and hyphens.\n"
+                list.stdout,
+                "ROLE      PROMPT    PROJECTS  ACTIVE  LAST_TURN\n\
+platform  template  0         0       -\n\
+qa        template  0         0       -\n"
+            );
+
+            let unconfirmed = run_cli(&project_root, &["roles", "delete", "qa"], None);
+            assert_eq!(unconfirmed.exit_code, 1);
+            assert_eq!(
+                unconfirmed.stderr,
+                "Refusing to delete role qa without confirmation. Re-run with --force.\n"
             );
+            assert!(role_state::role_state_exists(&project_root, "qa"));
+
+            let forced = run_cli(&project_root, &["roles", "delete", "qa", "--force"], None);
+            assert_eq!(forced.exit_code, 0, "stderr: {}", forced.stderr);
+            assert_eq!(forced.stdout, "deleted role qa\n");
+            assert!(!role_state::role_state_exists(&project_root, "qa"));
         }
 
         #[test]
-        fn cli_clear_history_without_role_prints_clap_usage_and_exits_with_code_two() {
-            let temp = TestDir::new("integration-clear-history-usage");
+        fn cli_archive_shelves_role_and_restore_brings_it_back() {
+            let temp = TestDir::new("integration-archive");
             let project_root = create_project_root(&temp);
 
-            let output = run_cli(&project_root, &["clear-history"], None);
+            let init = run_cli(&project_root, &["init", "--role", "eng"], None);
+            assert_eq!(init.exit_code, 0);
+            fs::write(
+                role_state::needs_path(&project_root, "eng"),
+                "- pick a winner branch\n",
+            )
+            .expect("needs should be writable");
 
-            assert_eq!(output.exit_code, 2);
-            assert_eq!(output.stdout, "");
-            assert!(output.stderr.contains("error:"));
-            assert!(output
-                .stderr
-                .contains("Usage: juliet clear-history --project <ROLE_NAME>"));
+            let archive = run_cli(&project_root, &["archive", "--project", "eng"], None);
+            assert_eq!(archive.exit_code, 0, "stderr: {}", archive.stderr);
+            assert!(archive
+                .stdout
+                .starts_with("archived role eng to .juliet/.archive/eng-"));
+            assert!(!role_state::role_state_exists(&project_root, "eng"));
+
+            let status = run_cli(&project_root, &["status"], None);
+            assert_eq!(status.exit_code, 1);
+            assert_eq!(status.stderr, format!("{NO_ROLES_CONFIGURED_ERROR}\n"));
+
+            let restore = run_cli(&project_root, &["restore", "--project", "eng"], None);
+            assert_eq!(restore.exit_code, 0, "stderr: {}", restore.stderr);
+            assert!(restore
+                .stdout
+                .starts_with("restored role eng from .juliet/.archive/eng-"));
+            assert_eq!(
+                fs::read_to_string(role_state::needs_path(&project_root, "eng"))
+                    .expect("needs should be restored"),
+                "- pick a winner branch\n"
+            );
         }
 
+        // sessions integration tests
+
         #[test]
-        fn cli_clear_history_preserves_prompt_md() {
-            let temp = TestDir::new("integration-clear-history-preserves-prompt");
+        fn cli_exec_continue_last_resumes_recorded_thread() {
+            let temp = TestDir::new("integration-continue-last");
             let project_root = create_project_root(&temp);
-            let role_name = "operations";
+            let mock_codex = MockCodex::new(temp.path(), 0);
 
-            let init = run_cli(&project_root, &["init", "--project", role_name], None);
+            let init = run_cli(&project_root, &["init", "--role", "eng"], None);
             assert_eq!(init.exit_code, 0);
 
-            let prompt_path = role_state::role_prompt_path(&project_root, role_name);
-            fs::write(&prompt_path, "# Custom operations prompt\n\nPreserve me.")
-                .expect("prompt should be writable");
+            let missing = run_cli(
+                &project_root,
+                &["exec", "--continue-last", "codex", "hello"],
+                Some(&mock_codex),
+            );
+            assert_eq!(missing.exit_code, 1);
+            assert_eq!(
+                missing.stderr,
+                "No recorded codex sessions for role eng. Run juliet exec without --continue-last first.\n"
+            );
 
-            let output = run_cli(&project_root, &["clear-history", "--role", role_name], None);
+            let first = run_cli(
+                &project_root,
+                &["exec", "codex", "hello"],
+                Some(&mock_codex),
+            );
+            assert_eq!(first.exit_code, 0);
 
-            assert_eq!(output.exit_code, 0);
+            let second = run_cli(
+                &project_root,
+                &["exec", "--continue-last", "codex", "again"],
+                Some(&mock_codex),
+            );
+            assert_eq!(second.exit_code, 0);
+            assert_eq!(second.stderr, "");
+            let args = mock_codex.recorded_args();
+            assert_eq!(args[2], "resume");
+            assert_eq!(args[3], "codex-thread-id");
+
+            let list = run_cli(&project_root, &["sessions", "list"], None);
+            assert_eq!(list.exit_code, 0);
+            let rows = list.stdout.lines().collect::<Vec<_>>();
+            assert_eq!(rows[0], "ENGINE  RESUME_ID        TURNS  LAST_TURN");
+            assert!(rows[1].starts_with("codex   codex-thread-id  2      "));
+
+            let show = run_cli(
+                &project_root,
+                &["sessions", "show", "codex-thread-id"],
+                None,
+            );
+            assert_eq!(show.exit_code, 0);
+            assert!(show
+                .stdout
+                .starts_with("engine: codex\nresume_id: codex-thread-id\nturns: 2\n"));
+
+            let unknown = run_cli(&project_root, &["sessions", "show", "nope"], None);
+            assert_eq!(unknown.exit_code, 1);
             assert_eq!(
-                fs::read_to_string(&prompt_path).unwrap(),
-                "# Custom operations prompt\n\nPreserve me."
+                unknown.stderr,
+                "Session not found: nope. Run: juliet sessions list\n"
             );
         }
 
+        // watch integration tests
+
         #[test]
-        fn cli_clear_history_succeeds_when_no_runtime_prompt_exists() {
-            let temp = TestDir::new("integration-clear-history-no-runtime-prompt");
+        fn cli_watch_once_wakes_engine_for_inbox_input() {
+            let temp = TestDir::new("integration-watch-once");
             let project_root = create_project_root(&temp);
-            let role_name = "qa";
+            let mock = MockCodex::new(temp.path(), 0);
 
-            let init = run_cli(&project_root, &["init", "--role", role_name], None);
+            let init = run_cli(&project_root, &["init", "--role", "eng"], None);
             assert_eq!(init.exit_code, 0);
+            fs::write(
+                role_state::inbox_path(&project_root, "eng"),
+                "what is the status?\n",
+            )
+            .expect("inbox should be writable");
 
-            let runtime_path = role_state::runtime_prompt_path(&project_root, role_name);
-            assert!(!runtime_path.exists());
-
-            let output = run_cli(&project_root, &["clear-history", "--role", role_name], None);
+            let output = run_cli(&project_root, &["watch", "--once", "codex"], Some(&mock));
 
             assert_eq!(output.exit_code, 0);
+            assert_eq!(output.stderr, "");
             assert_eq!(
                 output.stdout,
-                format!("history cleared for role '{role_name}'\n")
+                "[watch] operator input received\ncodex mock response\n"
             );
-            assert_eq!(output.stderr, "");
+            let args = mock.recorded_args();
+            assert_eq!(args[1], "exec");
+            assert!(args[2].ends_with("User input:\nwhat is the status?"));
+            let records =
+                sessions::load_sessions(&project_root, "eng").expect("sessions should load");
+            assert_eq!(records.len(), 1);
+            assert_eq!(records[0].resume_id, "codex-thread-id");
+
+            let quiet = run_cli(&project_root, &["watch", "--once", "codex"], Some(&mock));
+            assert_eq!(quiet.exit_code, 0);
+            assert_eq!(quiet.stdout, "");
         }
 
         // exec integration tests
@@ -3265,8 +7235,11 @@ exit "${JULIET_TEST_CLAUDE_EXIT_CODE:-0}"
                     "thread-123".to_string(),
                     format!(
                         "{}\n\nUser input:\nhello",
-                        fs::read_to_string(role_state::role_prompt_path(&project_root, role_name))
-                            .expect("role prompt should be readable")
+                        fs::read_to_string(role_state::runtime_prompt_path(
+                            &project_root,
+                            role_name
+                        ))
+                        .expect("runtime prompt should be readable")
                     ),
                     "--json".to_string(),
                 ]
@@ -3310,8 +7283,11 @@ exit "${JULIET_TEST_CLAUDE_EXIT_CODE:-0}"
                     "-p".to_string(),
                     format!(
                         "{}\n\nUser input:\nhello",
-                        fs::read_to_string(role_state::role_prompt_path(&project_root, role_name))
-                            .expect("role prompt should be readable")
+                        fs::read_to_string(role_state::runtime_prompt_path(
+                            &project_root,
+                            role_name
+                        ))
+                        .expect("runtime prompt should be readable")
                     ),
                     "--output-format".to_string(),
                     "json".to_string(),
@@ -3319,6 +7295,436 @@ exit "${JULIET_TEST_CLAUDE_EXIT_CODE:-0}"
             );
         }
 
+        #[test]
+        fn cli_exec_runs_custom_engine_from_config() {
+            let temp = TestDir::new("integration-exec-custom-engine");
+            let project_root = create_project_root(&temp);
+            let role_name = "director-of-engineering";
+            let role_prompt = "# Custom engine prompt\n\nRun custom exec.";
+
+            let init = run_cli(&project_root, &["init", "--role", role_name], None);
+            assert_eq!(init.exit_code, 0);
+            fs::write(
+                role_state::role_prompt_path(&project_root, role_name),
+                role_prompt,
+            )
+            .expect("role prompt should be writable");
+
+            let bin_dir = temp.path().join("mock-bin");
+            fs::create_dir_all(&bin_dir).expect("mock bin directory should exist");
+            let args_file = temp.path().join("mock-mytool-args.txt");
+            let tool_path = bin_dir.join("mytool");
+            fs::write(
+                &tool_path,
+                format!(
+                    r#"#!/usr/bin/env bash
+set -eu
+printf '%s\0' "$@" > "{}"
+printf '%s\n' "{{\"conversation\":\"conv-1\",\"answer\":\"mytool says $MYTOOL_MODE\"}}"
+"#,
+                    args_file.display()
+                ),
+            )
+            .expect("mock tool should be writable");
+            let mut permissions = fs::metadata(&tool_path)
+                .expect("mock tool metadata should be readable")
+                .permissions();
+            permissions.set_mode(0o755);
+            fs::set_permissions(&tool_path, permissions).expect("mock tool should be executable");
+
+            fs::write(
+                role_state::engines_config_path(&project_root),
+                format!(
+                    r#"[engines.mytool]
+launch = ["{tool}", "{{prompt}}"]
+exec = ["{tool}", "run", "{{prompt}}"]
+text_pointer = "/answer"
+resume_id_pointer = "/conversation"
+
+[engines.mytool.env]
+MYTOOL_MODE = "wrapped"
+"#,
+                    tool = tool_path.display()
+                ),
+            )
+            .expect("engine config should be writable");
+
+            let output = run_cli(
+                &project_root,
+                &["exec", "--json", "--role", role_name, "mytool", "hello"],
+                None,
+            );
+
+            assert_eq!(output.stderr, "");
+            assert_eq!(output.exit_code, 0);
+            let payload: Value =
+                serde_json::from_str(output.stdout.trim()).expect("stdout should be valid JSON");
+            assert_eq!(payload["text"], "mytool says wrapped");
+            assert_eq!(payload["resume_id"], "conv-1");
+            assert_eq!(payload["engine"], "mytool");
+
+            let recorded_args = fs::read_to_string(&args_file)
+                .expect("mock tool args should be readable")
+                .split('\0')
+                .filter(|arg| !arg.is_empty())
+                .map(|arg| arg.to_string())
+                .collect::<Vec<_>>();
+            assert_eq!(
+                recorded_args,
+                vec![
+                    "run".to_string(),
+                    format!("{role_prompt}\n\nUser input:\nhello"),
+                ]
+            );
+
+            let resume = run_cli(
+                &project_root,
+                &["exec", "--continue", "conv-1", "mytool", "hello"],
+                None,
+            );
+            assert_eq!(resume.exit_code, 1);
+            assert!(resume
+                .stderr
+                .contains("engine mytool does not support --continue"));
+        }
+
+        #[test]
+        fn cli_exec_unknown_engine_lists_available_engines() {
+            let temp = TestDir::new("integration-exec-unknown-engine");
+            let project_root = create_project_root(&temp);
+
+            let init = run_cli(&project_root, &["init", "--role", "qa"], None);
+            assert_eq!(init.exit_code, 0);
+
+            let output = run_cli(&project_root, &["exec", "gpt4", "hello"], None);
+
+            assert_eq!(output.exit_code, 1);
+            assert_eq!(output.stdout, "");
+            assert_eq!(
+                output.stderr,
+                "Unknown engine: gpt4. Available engines: claude, codex\n"
+            );
+        }
+
+        #[test]
+        fn cli_exec_stream_emits_ndjson_events_then_result() {
+            let temp = TestDir::new("integration-exec-stream-codex");
+            let project_root = create_project_root(&temp);
+            let role_name = "eng";
+
+            let init = run_cli(&project_root, &["init", "--role", role_name], None);
+            assert_eq!(init.exit_code, 0);
+
+            let mock_codex = MockCodex::new(temp.path(), 0);
+            let output = run_cli(
+                &project_root,
+                &["exec", "--stream", "codex", "hello"],
+                Some(&mock_codex),
+            );
+
+            assert_eq!(output.exit_code, 0);
+            assert_eq!(output.stderr, "");
+            let events = output
+                .stdout
+                .lines()
+                .map(|line| serde_json::from_str::<Value>(line).expect("line should be JSON"))
+                .collect::<Vec<_>>();
+            assert_eq!(events.len(), 2);
+            assert_eq!(events[0]["type"], "message_delta");
+            assert_eq!(events[0]["text"], "codex mock response");
+            assert_eq!(events[1]["type"], "result");
+            assert_eq!(events[1]["text"], "codex mock response");
+            assert_eq!(events[1]["resume_id"], "codex-thread-id");
+            assert_eq!(events[1]["engine"], "codex");
+
+            let records =
+                sessions::load_sessions(&project_root, role_name).expect("sessions should load");
+            assert_eq!(records.len(), 1);
+            assert_eq!(records[0].resume_id, "codex-thread-id");
+        }
+
+        #[test]
+        fn cli_exec_stream_uses_claude_stream_json() {
+            let temp = TestDir::new("integration-exec-stream-claude");
+            let project_root = create_project_root(&temp);
+
+            let init = run_cli(&project_root, &["init", "--role", "eng"], None);
+            assert_eq!(init.exit_code, 0);
+
+            let mock_claude = MockClaude::new(temp.path(), 0);
+            let output = run_cli_with_engines(
+                &project_root,
+                &["exec", "--stream", "claude", "hello"],
+                None,
+                Some(&mock_claude),
+            );
+
+            assert_eq!(output.exit_code, 0);
+            let args = mock_claude.recorded_args();
+            assert_eq!(
+                args[args.len() - 3..].to_vec(),
+                vec![
+                    "--output-format".to_string(),
+                    "stream-json".to_string(),
+                    "--verbose".to_string(),
+                ]
+            );
+            let result: Value =
+                serde_json::from_str(output.stdout.trim()).expect("stdout should be JSON");
+            assert_eq!(result["type"], "result");
+            assert_eq!(result["text"], "claude mock response");
+            assert_eq!(result["resume_id"], "claude-session-id");
+        }
+
+        #[test]
+        fn cli_exec_falls_back_from_missing_binary_using_config_default() {
+            let temp = TestDir::new("integration-engine-fallback");
+            let project_root = create_project_root(&temp);
+
+            let init = run_cli(&project_root, &["init", "--role", "eng"], None);
+            assert_eq!(init.exit_code, 0);
+            fs::write(
+                role_state::engines_config_path(&project_root),
+                r#"[exec]
+engine_fallback = ["codex"]
+
+[engines.missing]
+launch = ["juliet-test-missing-engine", "{prompt}"]
+exec = ["juliet-test-missing-engine", "{prompt}"]
+text_pointer = "/text"
+resume_id_pointer = "/id"
+"#,
+            )
+            .expect("engine config should be writable");
+
+            let mock_codex = MockCodex::new(temp.path(), 0);
+            let output = run_cli(
+                &project_root,
+                &["exec", "missing", "hello"],
+                Some(&mock_codex),
+            );
+
+            assert_eq!(output.exit_code, 0);
+            assert_eq!(output.stdout, "codex mock response\n");
+            assert!(output.stderr.starts_with("failed to run engine: "));
+            assert!(output.stderr.ends_with("falling back to engine codex\n"));
+            let learnings = fs::read_to_string(role_state::shared_learnings_path(&project_root))
+                .expect("learnings should be readable");
+            assert!(learnings.contains("[exec-fallback] role eng: missing failed"));
+            assert!(learnings.contains("retried the turn with codex."));
+        }
+
+        #[test]
+        fn cli_exec_and_launch_timeout_stop_hung_engines() {
+            let temp = TestDir::new("integration-engine-timeout");
+            let project_root = create_project_root(&temp);
+
+            let init = run_cli(&project_root, &["init", "--role", "eng"], None);
+            assert_eq!(init.exit_code, 0);
+            fs::write(
+                role_state::engines_config_path(&project_root),
+                r#"[engines.hang]
+launch = ["sh", "-c", "exec sleep 30", "{prompt}"]
+exec = ["sh", "-c", "echo partial; echo stuck >&2; sleep 30", "{prompt}"]
+text_pointer = "/text"
+resume_id_pointer = "/id"
+"#,
+            )
+            .expect("engine config should be writable");
+
+            let exec = run_cli(
+                &project_root,
+                &["exec", "--timeout", "1s", "hang", "hello"],
+                None,
+            );
+            assert_eq!(exec.exit_code, supervisor::TIMEOUT_EXIT_CODE);
+            assert_eq!(exec.stdout, "");
+            assert!(exec
+                .stderr
+                .starts_with("engine hang timed out; partial output saved to "));
+            let artifact = fs::read_dir(role_state::artifacts_dir(&project_root, "eng"))
+                .expect("artifacts should be readable")
+                .next()
+                .expect("timeout artifact should exist")
+                .expect("artifact entry should be readable")
+                .path();
+            let contents = fs::read_to_string(artifact).expect("artifact should be readable");
+            assert!(contents.contains("## stdout\npartial\n"));
+            assert!(contents.contains("## stderr\nstuck\n"));
+
+            let launch = run_cli(&project_root, &["--timeout", "1s", "hang"], None);
+            assert_eq!(launch.exit_code, supervisor::TIMEOUT_EXIT_CODE);
+            assert_eq!(launch.stderr, "engine hang timed out after 1s\n");
+        }
+
+        #[test]
+        fn cli_run_preflights_then_launches_and_records_swarm_runs() {
+            let temp = TestDir::new("integration-run");
+            let project_root = create_project_root(&temp);
+            let git = |args: &[&str]| {
+                let status = Command::new("git")
+                    .args(["-c", "user.name=Test", "-c", "user.email=test@example.com"])
+                    .args(args)
+                    .current_dir(&project_root)
+                    .status()
+                    .expect("git should run");
+                assert!(status.success(), "git {args:?} should succeed");
+            };
+
+            let init = run_cli(&project_root, &["init", "--role", "eng"], None);
+            assert_eq!(init.exit_code, 0);
+            git(&["init", "-q", "-b", "main"]);
+            fs::write(project_root.join("README.md"), "wind\n").expect("readme should be writable");
+            git(&["add", "README.md"]);
+            git(&["commit", "-q", "-m", "start"]);
+            fs::create_dir_all(project_root.join(".swarm-hug/wind"))
+                .expect("swarm dir should exist");
+            fs::write(
+                project_root.join(".swarm-hug/email.txt"),
+                "dev@example.com\n",
+            )
+            .expect("email should be writable");
+            fs::write(
+                project_root.join(".swarm-hug/wind/tasks.md"),
+                "- [ ] (#1) Blow [2 pts]\n",
+            )
+            .expect("tasks should be writable");
+
+            let mock_codex = MockCodex::new(temp.path(), 0);
+            let swarm_path = mock_codex.bin_dir.join("swarm");
+            fs::write(&swarm_path, "#!/usr/bin/env bash\necho \"swarm $*\"\n")
+                .expect("mock swarm should be writable");
+            let mut permissions = fs::metadata(&swarm_path)
+                .expect("mock swarm metadata should be readable")
+                .permissions();
+            permissions.set_mode(0o755);
+            fs::set_permissions(&swarm_path, permissions).expect("mock swarm should be executable");
+
+            let args = [
+                "run",
+                "--project",
+                "eng",
+                "--swarm-project",
+                "wind",
+                "--variations",
+                "2",
+                "--sprints",
+                "3",
+                "--engine",
+                "codex",
+            ];
+            let uncommitted = run_cli(&project_root, &args, Some(&mock_codex));
+            assert_eq!(uncommitted.exit_code, 1);
+            assert_eq!(
+                uncommitted.stderr,
+                ".swarm-hug/wind/tasks.md is not committed on main. Commit .swarm-hug/wind/ to main before launching.\n"
+            );
+
+            git(&["add", ".swarm-hug/wind"]);
+            git(&["commit", "-q", "-m", "init wind swarm artifacts"]);
+            let launched = run_cli(&project_root, &args, Some(&mock_codex));
+            assert_eq!(launched.exit_code, 0, "stderr: {}", launched.stderr);
+            assert!(launched
+                .stdout
+                .ends_with("check on them with: juliet processes check --project eng\n"));
+
+            let processes =
+                state_files::load_processes(&project_root, "eng").expect("processes should load");
+            assert_eq!(processes.active.len(), 2);
+            let run = &processes.active[1];
+            assert_eq!(run.target_branch.as_deref(), Some("feature/wind-try2"));
+            assert_eq!(run.source_branch.as_deref(), Some("main"));
+            assert_eq!(
+                run.log_path.as_deref(),
+                Some(".juliet/eng/artifacts/wind-feature-wind-try2-swarm.log")
+            );
+            assert!(launched.stdout.contains(&format!(
+                "started feature/wind-try2 (pid {}), log: .juliet/eng/artifacts/wind-feature-wind-try2-swarm.log\n",
+                run.pid.expect("run should record a pid")
+            )));
+
+            let log_path = project_root.join(run.log_path.as_deref().unwrap());
+            let expected_log = "swarm run --project wind --source-branch main --target-branch feature/wind-try2 --max-sprints 3 --no-tui --engine codex\n";
+            for _ in 0..50 {
+                if fs::read_to_string(&log_path).unwrap_or_default() == expected_log {
+                    break;
+                }
+                thread::sleep(Duration::from_millis(100));
+            }
+            assert_eq!(
+                fs::read_to_string(&log_path).expect("log should exist"),
+                expected_log
+            );
+            let projects =
+                state_files::load_projects(&project_root, "eng").expect("projects should load");
+            assert_eq!(
+                projects[0].target_branches,
+                vec!["feature/wind-try1", "feature/wind-try2"]
+            );
+        }
+
+        #[test]
+        fn cli_doctor_probes_environment_and_caches_session_fields() {
+            let temp = TestDir::new("integration-doctor");
+            let project_root = create_project_root(&temp);
+
+            let init = run_cli(&project_root, &["init", "--role", "eng"], None);
+            assert_eq!(init.exit_code, 0);
+            let git_init = Command::new("git")
+                .args(["init", "-q", "-b", "main"])
+                .current_dir(&project_root)
+                .status()
+                .expect("git init should run");
+            assert!(git_init.success());
+            fs::create_dir_all(project_root.join(".swarm-hug")).expect("swarm dir should exist");
+            fs::write(
+                project_root.join(".swarm-hug").join("email.txt"),
+                "dev@example.com\n",
+            )
+            .expect("email should be writable");
+
+            let mock_codex = MockCodex::new(temp.path(), 0);
+            for (name, script) in [
+                (
+                    "codex",
+                    "#!/usr/bin/env bash\necho 'Logged in using ChatGPT' >&2\n",
+                ),
+                ("claude", "#!/usr/bin/env bash\necho 'CLAUDE_READY'\n"),
+                (
+                    "swarm",
+                    "#!/usr/bin/env bash\necho 'Usage: swarm [OPTIONS] <COMMAND>'\necho '  -e, --engine <ENGINE>  Engine to use'\n",
+                ),
+            ] {
+                let path = mock_codex.bin_dir.join(name);
+                fs::write(&path, script).expect("mock script should be writable");
+                let mut permissions = fs::metadata(&path)
+                    .expect("mock script metadata should be readable")
+                    .permissions();
+                permissions.set_mode(0o755);
+                fs::set_permissions(&path, permissions).expect("mock script should be executable");
+            }
+
+            let output = run_cli(&project_root, &["doctor"], Some(&mock_codex));
+
+            assert_eq!(output.stderr, "");
+            assert_eq!(output.exit_code, 0);
+            assert!(output.stdout.contains("ok    codex: logged in\n"));
+            assert!(output.stdout.contains("ok    email: dev@example.com\n"));
+            assert!(output.stdout.contains("ok    git: on branch main ("));
+            assert!(output.stdout.contains("ok    gitignore: "));
+
+            let session =
+                state_files::load_session(&project_root, "eng").expect("session should load");
+            assert_eq!(session.status.as_deref(), Some("active"));
+            assert_eq!(session.available_engines, vec!["codex", "claude"]);
+            assert_eq!(session.default_engine.as_deref(), Some("codex"));
+            assert_eq!(
+                session.swarm_engine_property_syntax.as_deref(),
+                Some("--engine <ENGINE>")
+            );
+            assert!(session.started_at_utc.is_some());
+        }
+
         #[test]
         fn cli_exec_json_outputs_normalized_envelope() {
             let temp = TestDir::new("integration-exec-json-envelope");
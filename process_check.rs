@@ -0,0 +1,350 @@
+use crate::state_files::{self, ProcessEntry};
+use std::fs::File;
+use std::io::{self, Read, Seek, SeekFrom};
+use std::path::Path;
+
+pub const LOG_TAIL_LINES: usize = 50;
+pub const OUTCOME_PLACEHOLDER: &str = "TODO: review log and summarize outcome";
+const LOG_TAIL_BYTES: u64 = 64 * 1024;
+const ERROR_MARKERS: [&str; 4] = ["error", "panicked", "failed", "fatal"];
+
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct ProcessCheck {
+    pub running: Vec<ProcessEntry>,
+    pub finished: Vec<ProcessEntry>,
+}
+
+impl ProcessCheck {
+    pub fn changed(&self) -> bool {
+        !self.finished.is_empty()
+    }
+}
+
+/// Reports whether `pid` is alive. Zombies count as exited since they will never write again.
+#[cfg(target_os = "linux")]
+pub fn pid_is_running(pid: u32) -> bool {
+    let Ok(stat) = std::fs::read_to_string(format!("/proc/{pid}/stat")) else {
+        return false;
+    };
+    // The state follows the parenthesized command name, which may itself contain spaces.
+    stat.rsplit_once(')')
+        .and_then(|(_, rest)| rest.split_whitespace().next())
+        .is_some_and(|state| state != "Z" && state != "X")
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn pid_is_running(pid: u32) -> bool {
+    std::process::Command::new("kill")
+        .arg("-0")
+        .arg(pid.to_string())
+        .stderr(std::process::Stdio::null())
+        .status()
+        .is_ok_and(|status| status.success())
+}
+
+/// Probes every Active entry in `processes.md`, moving exited runs to Completed with a
+/// placeholder outcome and a one-line log insight. The file is only rewritten when
+/// something moved.
+pub fn check_processes<F>(
+    project_root: &Path,
+    role_name: &str,
+    is_running: F,
+) -> io::Result<ProcessCheck>
+where
+    F: Fn(u32) -> bool,
+{
+    let mut processes = state_files::load_processes(project_root, role_name)?;
+    let mut check = ProcessCheck::default();
+
+    let mut still_active = Vec::new();
+    for entry in processes.active.drain(..) {
+        match entry.pid {
+            Some(pid) if !is_running(pid) => {
+                let finished = finish_entry(project_root, entry);
+                check.finished.push(finished.clone());
+                processes.completed.push(finished);
+            }
+            _ => {
+                check.running.push(entry.clone());
+                still_active.push(entry);
+            }
+        }
+    }
+    processes.active = still_active;
+
+    if check.changed() {
+        state_files::save_processes(project_root, role_name, &processes)?;
+    }
+    Ok(check)
+}
+
+pub fn format_process_check(check: &ProcessCheck) -> String {
+    if check.running.is_empty() && check.finished.is_empty() {
+        return "no active processes\n".to_string();
+    }
+
+    let mut output = String::new();
+    for entry in &check.finished {
+        output.push_str(&format!(
+            "finished: {} ({})\n",
+            describe_entry(entry),
+            entry.outcome.as_deref().unwrap_or(OUTCOME_PLACEHOLDER)
+        ));
+    }
+    for entry in &check.running {
+        output.push_str(&format!("running: {}\n", describe_entry(entry)));
+    }
+    output
+}
+
+fn describe_entry(entry: &ProcessEntry) -> String {
+    format!(
+        "pid {} -> {}",
+        entry
+            .pid
+            .map(|pid| pid.to_string())
+            .unwrap_or_else(|| "?".to_string()),
+        entry.target_branch.as_deref().unwrap_or("?")
+    )
+}
+
+fn finish_entry(project_root: &Path, mut entry: ProcessEntry) -> ProcessEntry {
+    let insight = match entry.log_path.as_deref() {
+        None => "exited; no log_path recorded".to_string(),
+        Some(log_path) => match tail_lines(&project_root.join(log_path), LOG_TAIL_LINES) {
+            Err(err) => format!("exited; failed to read log: {err}"),
+            Ok(lines) => {
+                if let Some(last_line) = lines.last() {
+                    entry
+                        .extra
+                        .push(("last_log_line".to_string(), last_line.clone()));
+                }
+                if lines.iter().any(|line| looks_like_error(line)) {
+                    format!("exited; log shows errors in last {LOG_TAIL_LINES} lines")
+                } else {
+                    format!("exited; no obvious errors in last {LOG_TAIL_LINES} lines")
+                }
+            }
+        },
+    };
+
+    entry.outcome = Some(format!("{OUTCOME_PLACEHOLDER}; {insight}"));
+    entry
+}
+
+fn looks_like_error(line: &str) -> bool {
+    let lowered = line.to_ascii_lowercase();
+    ERROR_MARKERS.iter().any(|marker| lowered.contains(marker))
+}
+
+fn tail_lines(path: &Path, count: usize) -> io::Result<Vec<String>> {
+    let mut file = File::open(path)?;
+    let length = file.metadata()?.len();
+    file.seek(SeekFrom::Start(length.saturating_sub(LOG_TAIL_BYTES)))?;
+
+    let mut bytes = Vec::new();
+    file.read_to_end(&mut bytes)?;
+    let contents = String::from_utf8_lossy(&bytes);
+
+    let lines = contents
+        .lines()
+        .map(str::trim_end)
+        .filter(|line| !line.is_empty())
+        .collect::<Vec<_>>();
+    let start = lines.len().saturating_sub(count);
+    Ok(lines[start..].iter().map(|line| line.to_string()).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::role_state;
+    use crate::state_files::Processes;
+    use crate::test_support::TestDir;
+    use std::fs;
+    use std::process;
+
+    fn active_entry(pid: u32, target_branch: &str, log_path: &str) -> ProcessEntry {
+        ProcessEntry {
+            pid: Some(pid),
+            command: Some("nohup swarm run --no-tui".to_string()),
+            target_branch: Some(target_branch.to_string()),
+            log_path: Some(log_path.to_string()),
+            ..ProcessEntry::default()
+        }
+    }
+
+    fn seed_processes(root: &Path, processes: &Processes) {
+        role_state::create_role_state(root, "eng").expect("role state should be created");
+        state_files::save_processes(root, "eng", processes).expect("processes should save");
+    }
+
+    #[test]
+    fn check_moves_exited_runs_to_completed_with_log_insight() {
+        let temp = TestDir::new("moves");
+        fs::write(
+            temp.path().join("ok.log"),
+            "sprint 1 started\nall tasks merged\n",
+        )
+        .expect("log should be written");
+        fs::write(
+            temp.path().join("bad.log"),
+            "sprint 1 started\nError: merge conflict\nexiting\n",
+        )
+        .expect("log should be written");
+        seed_processes(
+            temp.path(),
+            &Processes {
+                active: vec![
+                    active_entry(10, "feature/ok", "ok.log"),
+                    active_entry(11, "feature/running", "running.log"),
+                    active_entry(12, "feature/bad", "bad.log"),
+                ],
+                completed: Vec::new(),
+            },
+        );
+
+        let check = check_processes(temp.path(), "eng", |pid| pid == 11).expect("check should run");
+
+        assert!(check.changed());
+        assert_eq!(
+            check.running,
+            vec![active_entry(11, "feature/running", "running.log")]
+        );
+        assert_eq!(
+            check
+                .finished
+                .iter()
+                .map(|entry| entry.outcome.as_deref().unwrap_or_default())
+                .collect::<Vec<_>>(),
+            vec![
+                "TODO: review log and summarize outcome; exited; no obvious errors in last 50 lines",
+                "TODO: review log and summarize outcome; exited; log shows errors in last 50 lines",
+            ]
+        );
+        assert_eq!(
+            check.finished[1].extra,
+            vec![("last_log_line".to_string(), "exiting".to_string())]
+        );
+        assert_eq!(check.finished[0].reported_on, None);
+
+        let saved = state_files::load_processes(temp.path(), "eng").expect("processes should load");
+        assert_eq!(saved.active, check.running);
+        assert_eq!(saved.completed, check.finished);
+    }
+
+    #[test]
+    fn check_leaves_file_untouched_when_nothing_exited() {
+        let temp = TestDir::new("unchanged");
+        role_state::create_role_state(temp.path(), "eng").expect("role state should be created");
+        let processes_path = role_state::processes_path(temp.path(), "eng");
+        let original = "## Active\n- PID: 42\n  - Log path: run.log\n";
+        fs::write(&processes_path, original).expect("processes should be written");
+
+        let check = check_processes(temp.path(), "eng", |_| true).expect("check should run");
+
+        assert!(!check.changed());
+        assert_eq!(check.running.len(), 1);
+        assert_eq!(
+            fs::read_to_string(&processes_path).expect("processes should be readable"),
+            original
+        );
+    }
+
+    #[test]
+    fn check_keeps_notes_and_unknown_sections_when_moving_runs() {
+        let temp = TestDir::new("preserve");
+        role_state::create_role_state(temp.path(), "eng").expect("role state should be created");
+        let processes_path = role_state::processes_path(temp.path(), "eng");
+        fs::write(
+            &processes_path,
+            "# Processes\n\nOperator: leave paused runs alone.\n\n## Active\n\n- pid: 30\n  target_branch: feature/done\n\n## Paused\n\n- pid: 31\n  target_branch: feature/later\n\n## Completed\n",
+        )
+        .expect("processes should be written");
+
+        let check = check_processes(temp.path(), "eng", |_| false).expect("check should run");
+
+        assert_eq!(check.finished.len(), 1);
+        assert_eq!(
+            fs::read_to_string(&processes_path).expect("processes should be readable"),
+            "# Processes\n\nOperator: leave paused runs alone.\n\n## Active\n\n## Paused\n\n- pid: 31\n  target_branch: feature/later\n\n## Completed\n\n- pid: 30\n  target_branch: feature/done\n  outcome: TODO: review log and summarize outcome; exited; no log_path recorded\n"
+        );
+    }
+
+    #[test]
+    fn check_notes_missing_logs_in_outcome() {
+        let temp = TestDir::new("missing-log");
+        seed_processes(
+            temp.path(),
+            &Processes {
+                active: vec![
+                    active_entry(20, "feature/gone", "missing.log"),
+                    ProcessEntry {
+                        pid: Some(21),
+                        ..ProcessEntry::default()
+                    },
+                ],
+                completed: Vec::new(),
+            },
+        );
+
+        let check = check_processes(temp.path(), "eng", |_| false).expect("check should run");
+
+        let outcomes = check
+            .finished
+            .iter()
+            .map(|entry| entry.outcome.clone().unwrap_or_default())
+            .collect::<Vec<_>>();
+        assert!(outcomes[0]
+            .starts_with("TODO: review log and summarize outcome; exited; failed to read log:"));
+        assert_eq!(
+            outcomes[1],
+            "TODO: review log and summarize outcome; exited; no log_path recorded"
+        );
+    }
+
+    #[test]
+    fn tail_lines_returns_last_non_empty_lines() {
+        let temp = TestDir::new("tail");
+        let log_path = temp.path().join("run.log");
+        let contents = (1..=60)
+            .map(|index| format!("line {index}\n\n"))
+            .collect::<String>();
+        fs::write(&log_path, contents).expect("log should be written");
+
+        let lines = tail_lines(&log_path, 3).expect("tail should read");
+        assert_eq!(lines, vec!["line 58", "line 59", "line 60"]);
+    }
+
+    #[test]
+    fn format_process_check_lists_finished_then_running() {
+        assert_eq!(
+            format_process_check(&ProcessCheck::default()),
+            "no active processes\n"
+        );
+
+        let mut finished = active_entry(1, "feature/a", "a.log");
+        finished.outcome = Some("exited".to_string());
+        let check = ProcessCheck {
+            running: vec![active_entry(2, "feature/b", "b.log")],
+            finished: vec![finished],
+        };
+        assert_eq!(
+            format_process_check(&check),
+            "finished: pid 1 -> feature/a (exited)\nrunning: pid 2 -> feature/b\n"
+        );
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn pid_is_running_detects_live_and_reaped_processes() {
+        assert!(pid_is_running(process::id()));
+
+        let mut child = process::Command::new("true")
+            .spawn()
+            .expect("true should spawn");
+        let pid = child.id();
+        child.wait().expect("child should exit");
+        assert!(!pid_is_running(pid));
+    }
+}
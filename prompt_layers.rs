@@ -0,0 +1,157 @@
+/// Expands to the seed of the role's template (the built-in default seed unless recorded).
+pub const SEED_INCLUDE: &str = "{{include seed}}";
+/// Expands to the project-wide `.juliet/.shared/prompt.md`.
+pub const SHARED_INCLUDE: &str = "{{include shared}}";
+
+/// Builds the runtime prompt from its layers: the built-in seed, the optional project-wide
+/// shared prompt, and the role's own prompt. Include directives must sit on a line of their
+/// own. When the role prompt does not place the shared layer itself, it follows the included
+/// seed, or the prompt's leading `# <role>` heading if the role copies the seed instead of
+/// including it.
+pub fn compose_prompt(
+    role_prompt: &str,
+    seed: &str,
+    shared: Option<&str>,
+) -> Result<String, String> {
+    let shared = shared
+        .map(|shared| expand_includes(shared, seed, None, "the shared prompt"))
+        .transpose()?;
+    let places_shared = has_directive(role_prompt, SHARED_INCLUDE);
+    let includes_seed = has_directive(role_prompt, SEED_INCLUDE);
+
+    let seed_layer = match (&shared, places_shared, includes_seed) {
+        (Some(shared), false, true) => format!("{}\n\n{shared}", seed.trim_end()),
+        _ => seed.to_string(),
+    };
+    let composed = expand_includes(
+        role_prompt,
+        &seed_layer,
+        Some(shared.as_deref().unwrap_or_default()),
+        "the role prompt",
+    )?;
+
+    Ok(match shared {
+        Some(shared) if !places_shared && !includes_seed => after_heading(&composed, &shared),
+        _ => composed,
+    })
+}
+
+/// Inserts `layer` after a leading `# ` heading line, or before everything when there is none.
+fn after_heading(prompt: &str, layer: &str) -> String {
+    let layer = layer.trim_end();
+    match prompt.split_once('\n') {
+        Some((heading, rest)) if heading.starts_with("# ") => {
+            format!("{heading}\n\n{layer}\n\n{}", rest.trim_start_matches('\n'))
+        }
+        None if prompt.starts_with("# ") => format!("{prompt}\n\n{layer}\n"),
+        _ => format!("{layer}\n\n{prompt}"),
+    }
+}
+
+/// True when any line of `prompt` is exactly `directive`, ignoring surrounding whitespace.
+pub fn has_directive(prompt: &str, directive: &str) -> bool {
+    prompt.lines().any(|line| line.trim() == directive)
+}
+
+/// Replaces each include line with its layer. `shared` is `None` while expanding the shared
+/// layer itself, which cannot include itself.
+fn expand_includes(
+    text: &str,
+    seed: &str,
+    shared: Option<&str>,
+    source: &str,
+) -> Result<String, String> {
+    let mut output = String::with_capacity(text.len() + seed.len());
+    for line in text.split_inclusive('\n') {
+        let directive = line.trim();
+        let layer = if directive == SEED_INCLUDE {
+            seed
+        } else if directive == SHARED_INCLUDE {
+            shared.ok_or_else(|| format!("{SHARED_INCLUDE} cannot be used in {source}"))?
+        } else if directive.starts_with("{{include ") && directive.ends_with("}}") {
+            return Err(format!(
+                "unknown include `{directive}` in {source}; use {SEED_INCLUDE} or {SHARED_INCLUDE}"
+            ));
+        } else {
+            output.push_str(line);
+            continue;
+        };
+
+        // An absent layer drops its directive line instead of leaving a blank one.
+        if layer.is_empty() {
+            continue;
+        }
+        output.push_str(layer);
+        if line.ends_with('\n') && !layer.ends_with('\n') {
+            output.push('\n');
+        }
+    }
+    Ok(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SEED: &str = "Seed rules.\n";
+
+    #[test]
+    fn compose_without_directives_or_shared_layer_is_identity() {
+        let prompt = "# eng\n\nCopied seed.\n";
+        assert_eq!(compose_prompt(prompt, SEED, None), Ok(prompt.to_string()));
+    }
+
+    #[test]
+    fn compose_expands_seed_and_shared_includes_in_place() {
+        let prompt = "# eng\n\n{{include shared}}\n\n  {{include seed}}\n\nRole overrides.\n";
+
+        assert_eq!(
+            compose_prompt(prompt, SEED, Some("Shared policy.")),
+            Ok("# eng\n\nShared policy.\n\nSeed rules.\n\nRole overrides.\n".to_string())
+        );
+        assert_eq!(
+            compose_prompt(prompt, SEED, None),
+            Ok("# eng\n\n\nSeed rules.\n\nRole overrides.\n".to_string())
+        );
+    }
+
+    #[test]
+    fn compose_places_unreferenced_shared_layer_after_seed_or_heading() {
+        assert_eq!(
+            compose_prompt(
+                "# eng\n\n{{include seed}}\n\nRole overrides.\n",
+                SEED,
+                Some("Shared policy.\n")
+            ),
+            Ok("# eng\n\nSeed rules.\n\nShared policy.\n\nRole overrides.\n".to_string())
+        );
+        assert_eq!(
+            compose_prompt("# eng\n\nCopied seed.\n", SEED, Some("Shared policy.\n")),
+            Ok("# eng\n\nShared policy.\n\nCopied seed.\n".to_string())
+        );
+        assert_eq!(
+            compose_prompt("Copied seed.\n", SEED, Some("Shared policy.\n")),
+            Ok("Shared policy.\n\nCopied seed.\n".to_string())
+        );
+    }
+
+    #[test]
+    fn compose_expands_seed_inside_shared_layer_and_rejects_bad_includes() {
+        assert_eq!(
+            compose_prompt(
+                "# eng\n{{include shared}}\n",
+                SEED,
+                Some("{{include seed}}\nShared policy.\n")
+            ),
+            Ok("# eng\nSeed rules.\nShared policy.\n".to_string())
+        );
+        assert_eq!(
+            compose_prompt("# eng\n", SEED, Some("{{include shared}}\n")),
+            Err("{{include shared}} cannot be used in the shared prompt".to_string())
+        );
+        assert_eq!(
+            compose_prompt("{{include seeds}}\n", SEED, None),
+            Err("unknown include `{{include seeds}}` in the role prompt; use {{include seed}} or {{include shared}}".to_string())
+        );
+    }
+}
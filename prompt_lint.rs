@@ -0,0 +1,243 @@
+use crate::prompt_layers;
+use crate::prompt_merge;
+use crate::prompt_vars;
+
+const EXACT_PHRASES_HEADING: &str = "## Exact phrases";
+const USER_INPUT_MARKER: &str = "User input:";
+
+/// One lint finding. `line` is 1-based; `None` means the problem is something missing.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LintProblem {
+    pub line: Option<usize>,
+    pub message: String,
+}
+
+impl LintProblem {
+    fn at(line: usize, message: impl Into<String>) -> Self {
+        Self {
+            line: Some(line),
+            message: message.into(),
+        }
+    }
+
+    fn missing(message: impl Into<String>) -> Self {
+        Self {
+            line: None,
+            message: message.into(),
+        }
+    }
+}
+
+/// State files the seed tells the agent to maintain, written the way the seed writes them.
+/// Prompts that drop them lose state between turns.
+pub const REQUIRED_STATE_REFERENCES: [&str; 6] = [
+    ".juliet/<role>/needs-from-operator.md",
+    ".juliet/<role>/projects.md",
+    ".juliet/<role>/processes.md",
+    ".juliet/<role>/session.md",
+    ".juliet/<role>/artifacts/",
+    ".juliet/.shared/learnings.md",
+];
+
+/// Backticked phrases listed under the seed's `## Exact phrases` heading.
+pub fn exact_phrases(seed: &str) -> Vec<String> {
+    seed.lines()
+        .skip_while(|line| line.trim() != EXACT_PHRASES_HEADING)
+        .skip(1)
+        .take_while(|line| !line.starts_with("## "))
+        .filter(|line| line.trim_start().starts_with("- "))
+        .filter_map(|line| {
+            let start = line.find('`')?;
+            let length = line[start + 1..].rfind('`')?;
+            Some(line[start + 1..start + 1 + length].to_string())
+        })
+        .collect()
+}
+
+/// Checks the contracts the CLI and the seed rely on. A prompt that pulls the seed in with
+/// `{{include seed}}` gets the state references, `User input:` convention, and exact phrases
+/// from the seed, so only its own lines are checked.
+pub fn lint_prompt(
+    role_name: &str,
+    prompt: &str,
+    seed: &str,
+    operator_placeholder: &str,
+) -> Vec<LintProblem> {
+    let mut problems = Vec::new();
+    let lines = prompt.lines().collect::<Vec<_>>();
+
+    let heading = format!("# {role_name}");
+    match lines
+        .iter()
+        .enumerate()
+        .find(|(_, line)| !line.trim().is_empty())
+    {
+        Some((_, line)) if line.trim_end() == heading || line.trim_end() == "# {{role}}" => {}
+        Some((index, line)) => problems.push(LintProblem::at(
+            index + 1,
+            format!("first line should be the heading `{heading}`, found `{line}`"),
+        )),
+        None => problems.push(LintProblem::missing(format!(
+            "prompt is empty; expected the heading `{heading}`"
+        ))),
+    }
+
+    let conflict_lines = prompt_merge::conflict_marker_lines(prompt);
+    let unknown_variables = prompt_vars::unknown_variables(prompt);
+    for (index, line) in lines.iter().enumerate() {
+        let trimmed = line.trim();
+        if line.contains(operator_placeholder) {
+            problems.push(LintProblem::at(
+                index + 1,
+                "operator placeholder is still present; replace it with role-specific instructions",
+            ));
+        }
+        if trimmed == USER_INPUT_MARKER {
+            problems.push(LintProblem::at(
+                index + 1,
+                format!("`{USER_INPUT_MARKER}` on its own line is reserved for operator input that juliet appends at launch"),
+            ));
+        }
+        if conflict_lines.contains(&(index + 1)) {
+            problems.push(LintProblem::at(
+                index + 1,
+                "unresolved merge conflict marker",
+            ));
+        }
+        for (_, name) in unknown_variables
+            .iter()
+            .filter(|(line, _)| *line == index + 1)
+        {
+            problems.push(LintProblem::at(
+                index + 1,
+                format!(
+                    "unknown prompt variable `{{{{{name}}}}}`; known variables: {}",
+                    prompt_vars::VARIABLES.join(", ")
+                ),
+            ));
+        }
+        if trimmed.starts_with("{{include ")
+            && trimmed != prompt_layers::SEED_INCLUDE
+            && trimmed != prompt_layers::SHARED_INCLUDE
+        {
+            problems.push(LintProblem::at(
+                index + 1,
+                format!(
+                    "unknown include `{trimmed}`; use {} or {}",
+                    prompt_layers::SEED_INCLUDE,
+                    prompt_layers::SHARED_INCLUDE
+                ),
+            ));
+        }
+    }
+
+    if prompt_layers::has_directive(prompt, prompt_layers::SEED_INCLUDE) {
+        return problems;
+    }
+
+    for reference in REQUIRED_STATE_REFERENCES {
+        // Either the seed's `<role>` spelling or the role's own name satisfies a reference.
+        if !prompt.contains(reference) && !prompt.contains(&reference.replace("<role>", role_name))
+        {
+            problems.push(LintProblem::missing(format!(
+                "missing reference to state file `{reference}`"
+            )));
+        }
+    }
+    if !prompt.contains(&format!("`{USER_INPUT_MARKER}`")) {
+        problems.push(LintProblem::missing(format!(
+            "missing the `{USER_INPUT_MARKER}` convention that tells the agent where operator input starts"
+        )));
+    }
+
+    let phrases_line = lines
+        .iter()
+        .position(|line| line.trim() == EXACT_PHRASES_HEADING)
+        .map(|index| index + 1);
+    for phrase in exact_phrases(seed) {
+        if prompt.contains(&format!("`{phrase}`")) {
+            continue;
+        }
+        let message = format!("exact phrase changed or missing: `{phrase}`");
+        problems.push(match phrases_line {
+            Some(line) => LintProblem::at(line, message),
+            None => LintProblem::missing(message),
+        });
+    }
+
+    problems
+}
+
+pub fn format_problems(path: &str, problems: &[LintProblem]) -> String {
+    problems
+        .iter()
+        .map(|problem| match problem.line {
+            Some(line) => format!("{path}:{line}: {}\n", problem.message),
+            None => format!("{path}: {}\n", problem.message),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PLACEHOLDER: &str = "<!-- TODO: placeholder -->";
+    const SEED: &str = "Read `.juliet/<role>/session.md`.\n\n## Exact phrases\n\n- `i'm still working`\n- (done) `all done: <pathtofiles>.`\n\n## Behavior\n\n- `not a phrase`\n";
+
+    fn complete_prompt() -> String {
+        format!(
+            "# eng\n\nOwn the wind project.\n\nOperator input follows `User input:`.\nState: {}\n\n## Exact phrases\n\n- `i'm still working`\n- `all done: <pathtofiles>.`\n",
+            REQUIRED_STATE_REFERENCES.join(", ").replace("<role>", "eng")
+        )
+    }
+
+    #[test]
+    fn exact_phrases_are_read_from_the_seed_section() {
+        assert_eq!(
+            exact_phrases(SEED),
+            vec!["i'm still working", "all done: <pathtofiles>."]
+        );
+    }
+
+    #[test]
+    fn lint_accepts_complete_and_seed_including_prompts() {
+        assert_eq!(
+            lint_prompt("eng", &complete_prompt(), SEED, PLACEHOLDER),
+            vec![]
+        );
+        assert_eq!(
+            lint_prompt(
+                "eng",
+                "# {{role}}\n\nWind project\n=======\n\n{{include seed}}\n",
+                SEED,
+                PLACEHOLDER
+            ),
+            vec![]
+        );
+    }
+
+    #[test]
+    fn lint_reports_problems_with_line_numbers() {
+        let prompt = complete_prompt()
+            .replace("# eng", "# Engineering")
+            .replace("Own the wind project.", PLACEHOLDER)
+            .replace("`i'm still working`", "`still working`")
+            .replace(".juliet/eng/processes.md, ", "")
+            + "<<<<<<< prompt.md\n{{include seeds}}\n{{branch}}\nUser input:\n";
+
+        let problems = lint_prompt("eng", &prompt, SEED, PLACEHOLDER);
+
+        assert_eq!(
+            format_problems(".juliet/eng/prompt.md", &problems),
+            ".juliet/eng/prompt.md:1: first line should be the heading `# eng`, found `# Engineering`\n\
+.juliet/eng/prompt.md:3: operator placeholder is still present; replace it with role-specific instructions\n\
+.juliet/eng/prompt.md:12: unresolved merge conflict marker\n\
+.juliet/eng/prompt.md:13: unknown include `{{include seeds}}`; use {{include seed}} or {{include shared}}\n\
+.juliet/eng/prompt.md:14: unknown prompt variable `{{branch}}`; known variables: role, now_utc, git_branch, available_engines, pending_needs\n\
+.juliet/eng/prompt.md:15: `User input:` on its own line is reserved for operator input that juliet appends at launch\n\
+.juliet/eng/prompt.md: missing reference to state file `.juliet/<role>/processes.md`\n\
+.juliet/eng/prompt.md:8: exact phrase changed or missing: `i'm still working`\n"
+        );
+    }
+}
@@ -0,0 +1,198 @@
+/// Result of a line-based three-way merge.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MergeOutcome {
+    pub text: String,
+    pub conflicts: usize,
+}
+
+/// Merges the changes `ours` and `theirs` each made to `base`, diff3 style. Regions both
+/// sides changed differently are kept as `<<<<<<<`/`=======`/`>>>>>>>` blocks labelled with
+/// `ours_label` and `theirs_label`.
+pub fn merge3(
+    base: &str,
+    ours: &str,
+    theirs: &str,
+    ours_label: &str,
+    theirs_label: &str,
+) -> MergeOutcome {
+    let base = split_lines(base);
+    let ours = split_lines(ours);
+    let theirs = split_lines(theirs);
+    let ours_match = match_lines(&base, &ours);
+    let theirs_match = match_lines(&base, &theirs);
+
+    let mut text = String::new();
+    let mut conflicts = 0;
+    let (mut b, mut o, mut t) = (0, 0, 0);
+    loop {
+        // Next base line that both sides kept: everything before it is one changed hunk.
+        let sync = (b..base.len())
+            .find_map(|index| Some((index, ours_match[index]?, theirs_match[index]?)));
+        let (base_end, ours_end, theirs_end) =
+            sync.unwrap_or((base.len(), ours.len(), theirs.len()));
+
+        let base_hunk = &base[b..base_end];
+        let ours_hunk = &ours[o..ours_end];
+        let theirs_hunk = &theirs[t..theirs_end];
+        if ours_hunk == base_hunk || ours_hunk == theirs_hunk {
+            text.extend(theirs_hunk.iter().copied());
+        } else if theirs_hunk == base_hunk {
+            text.extend(ours_hunk.iter().copied());
+        } else {
+            conflicts += 1;
+            push_line(&mut text, &format!("<<<<<<< {ours_label}"));
+            ours_hunk.iter().for_each(|line| push_line(&mut text, line));
+            push_line(&mut text, "=======");
+            theirs_hunk
+                .iter()
+                .for_each(|line| push_line(&mut text, line));
+            push_line(&mut text, &format!(">>>>>>> {theirs_label}"));
+        }
+
+        let Some((base_index, ours_index, theirs_index)) = sync else {
+            break;
+        };
+        text.push_str(base[base_index]);
+        (b, o, t) = (base_index + 1, ours_index + 1, theirs_index + 1);
+    }
+
+    MergeOutcome { text, conflicts }
+}
+
+/// 1-based lines holding conflict markers left by [`merge3`] or git. A `=======` line only
+/// counts inside a `<<<<<<<` block, so markdown setext underlines are not flagged.
+pub fn conflict_marker_lines(text: &str) -> Vec<usize> {
+    let mut lines = Vec::new();
+    let mut in_conflict = false;
+    for (index, line) in text.lines().enumerate() {
+        let line = line.trim_end();
+        if is_marker(line, "<<<<<<<") {
+            in_conflict = true;
+        } else if is_marker(line, ">>>>>>>") {
+            in_conflict = false;
+        } else if !(in_conflict && line == "=======") {
+            continue;
+        }
+        lines.push(index + 1);
+    }
+    lines
+}
+
+fn is_marker(line: &str, marker: &str) -> bool {
+    line.strip_prefix(marker)
+        .is_some_and(|rest| rest.is_empty() || rest.starts_with(' '))
+}
+
+fn split_lines(text: &str) -> Vec<&str> {
+    text.split_inclusive('\n').collect()
+}
+
+/// Appends `line`, making sure it ends with a newline so markers stay on their own lines.
+fn push_line(text: &mut String, line: &str) {
+    text.push_str(line);
+    if !line.ends_with('\n') {
+        text.push('\n');
+    }
+}
+
+/// For every base line, the index of the line it is paired with in `other` by a longest
+/// common subsequence, or `None` when the line was removed or changed.
+fn match_lines(base: &[&str], other: &[&str]) -> Vec<Option<usize>> {
+    let width = other.len() + 1;
+    let mut lengths = vec![0usize; (base.len() + 1) * width];
+    for i in (0..base.len()).rev() {
+        for j in (0..other.len()).rev() {
+            lengths[i * width + j] = if base[i] == other[j] {
+                lengths[(i + 1) * width + j + 1] + 1
+            } else {
+                lengths[(i + 1) * width + j].max(lengths[i * width + j + 1])
+            };
+        }
+    }
+
+    let mut matches = vec![None; base.len()];
+    let (mut i, mut j) = (0, 0);
+    while i < base.len() && j < other.len() {
+        if base[i] == other[j] {
+            matches[i] = Some(j);
+            i += 1;
+            j += 1;
+        } else if lengths[(i + 1) * width + j] >= lengths[i * width + j + 1] {
+            i += 1;
+        } else {
+            j += 1;
+        }
+    }
+    matches
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn merge(base: &str, ours: &str, theirs: &str) -> MergeOutcome {
+        merge3(base, ours, theirs, "ours", "theirs")
+    }
+
+    #[test]
+    fn merge3_combines_non_overlapping_edits() {
+        let base = "# eng\n\nTODO\n\n## Seed\n\nstep one\nstep two\nstep three\n";
+        let ours = "# eng\n\nOwn the wind project.\n\n## Seed\n\nstep one\nstep two\nstep three\n";
+        let theirs =
+            "# eng\n\nTODO\n\n## Seed\n\nstep one\nstep two, faster\nstep three\nstep four\n";
+
+        assert_eq!(
+            merge(base, ours, theirs),
+            MergeOutcome {
+                text: "# eng\n\nOwn the wind project.\n\n## Seed\n\nstep one\nstep two, faster\nstep three\nstep four\n".to_string(),
+                conflicts: 0,
+            }
+        );
+    }
+
+    #[test]
+    fn merge3_accepts_identical_changes_and_deletions() {
+        let base = "a\nb\nc\nd\n";
+
+        assert_eq!(
+            merge(base, "a\nB\nc\nd\n", "a\nB\nc\nd\n").text,
+            "a\nB\nc\nd\n"
+        );
+        assert_eq!(merge(base, "a\nc\nd\n", base).text, "a\nc\nd\n");
+        assert_eq!(merge(base, base, "a\nb\nc\n").text, "a\nb\nc\n");
+        assert_eq!(merge(base, base, base).text, base);
+    }
+
+    #[test]
+    fn merge3_marks_conflicting_edits() {
+        let outcome = merge("a\nb\nc\n", "a\nmine\nc\n", "a\nyours\nc\n");
+
+        assert_eq!(outcome.conflicts, 1);
+        assert_eq!(
+            outcome.text,
+            "a\n<<<<<<< ours\nmine\n=======\nyours\n>>>>>>> theirs\nc\n"
+        );
+    }
+
+    #[test]
+    fn merge3_marks_conflicts_at_end_without_trailing_newline() {
+        let outcome = merge("a\nb", "a\nmine", "a\nyours");
+
+        assert_eq!(outcome.conflicts, 1);
+        assert_eq!(
+            outcome.text,
+            "a\n<<<<<<< ours\nmine\n=======\nyours\n>>>>>>> theirs\n"
+        );
+    }
+
+    #[test]
+    fn conflict_marker_lines_ignore_separators_outside_conflicts() {
+        assert_eq!(
+            conflict_marker_lines(
+                "Title\n=======\na\n<<<<<<< ours\nmine\n=======\nyours\n>>>>>>> theirs\n"
+            ),
+            vec![4, 6, 8]
+        );
+        assert_eq!(conflict_marker_lines("a\n<<<<<<<<\n"), Vec::<usize>::new());
+    }
+}
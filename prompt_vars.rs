@@ -0,0 +1,142 @@
+use std::collections::HashMap;
+
+/// Variables `{{name}}` that may appear in `prompt.md` and `.juliet/.shared/prompt.md`.
+pub const VARIABLES: [&str; 5] = [
+    "role",
+    "now_utc",
+    "git_branch",
+    "available_engines",
+    "pending_needs",
+];
+
+/// Replaces every `{{name}}` in `text` with `resolve(name)`. Each variable is resolved at most
+/// once, and only if it is used. Braces around anything other than a lowercase identifier are
+/// left alone; identifiers outside `VARIABLES` are reported together with their line numbers.
+pub fn expand_variables<R>(text: &str, source: &str, mut resolve: R) -> Result<String, String>
+where
+    R: FnMut(&str) -> String,
+{
+    let mut values: HashMap<&str, String> = HashMap::new();
+    let mut unknown = Vec::new();
+    let mut output = String::with_capacity(text.len());
+
+    for (index, line) in text.split_inclusive('\n').enumerate() {
+        let mut copied = 0;
+        for (start, end, name) in variable_spans(line) {
+            output.push_str(&line[copied..start]);
+            if let Some(variable) = VARIABLES.iter().find(|variable| **variable == name) {
+                let value = values.entry(*variable).or_insert_with(|| resolve(variable));
+                output.push_str(value);
+            } else {
+                unknown.push(format!("`{{{{{name}}}}}` (line {})", index + 1));
+            }
+            copied = end;
+        }
+        output.push_str(&line[copied..]);
+    }
+
+    if unknown.is_empty() {
+        Ok(output)
+    } else {
+        Err(format!(
+            "unknown prompt variables in {source}: {}. Known variables: {}",
+            unknown.join(", "),
+            VARIABLES.join(", ")
+        ))
+    }
+}
+
+/// `(1-based line, name)` for each `{{name}}` in `text` that is not one of `VARIABLES`.
+pub fn unknown_variables(text: &str) -> Vec<(usize, String)> {
+    text.lines()
+        .enumerate()
+        .flat_map(|(index, line)| {
+            variable_spans(line)
+                .into_iter()
+                .filter(|(_, _, name)| !VARIABLES.contains(name))
+                .map(move |(_, _, name)| (index + 1, name.to_string()))
+        })
+        .collect()
+}
+
+/// Byte range and name of each `{{name}}` in `line` whose name is a lowercase identifier.
+fn variable_spans(line: &str) -> Vec<(usize, usize, &str)> {
+    let mut spans = Vec::new();
+    let mut offset = 0;
+    while let Some(start) = line[offset..].find("{{").map(|start| offset + start) {
+        let Some(length) = line[start + 2..].find("}}") else {
+            break;
+        };
+        let end = start + 2 + length + 2;
+        let name = line[start + 2..end - 2].trim();
+        if is_variable_name(name) {
+            spans.push((start, end, name));
+        }
+        offset = end;
+    }
+    spans
+}
+
+fn is_variable_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|character| character.is_ascii_lowercase() || character == '_')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn expand_variables_substitutes_and_resolves_each_name_once() {
+        let mut resolved = Vec::new();
+        let expanded = expand_variables(
+            "# {{role}}\n\nToday is {{ now_utc }}. Role {{role}} on {{git_branch}}.\n",
+            "prompt.md",
+            |name| {
+                resolved.push(name.to_string());
+                match name {
+                    "role" => "eng".to_string(),
+                    "now_utc" => "2026-02-06T10:00:00Z".to_string(),
+                    _ => "main".to_string(),
+                }
+            },
+        );
+
+        assert_eq!(
+            expanded,
+            Ok("# eng\n\nToday is 2026-02-06T10:00:00Z. Role eng on main.\n".to_string())
+        );
+        assert_eq!(resolved, vec!["role", "now_utc", "git_branch"]);
+    }
+
+    #[test]
+    fn expand_variables_leaves_non_identifier_braces_alone() {
+        let text = "Example: {{\"key\": 1}} and {{ }} and {{unclosed\n";
+        assert_eq!(
+            expand_variables(text, "prompt.md", |_| unreachable!()),
+            Ok(text.to_string())
+        );
+    }
+
+    #[test]
+    fn expand_variables_reports_unknown_names_with_lines() {
+        assert_eq!(
+            expand_variables(
+                "# {{role}}\n\n{{branch}}\n{{now}} {{now_utc}}\n",
+                ".juliet/eng/prompt.md",
+                |_| String::new()
+            ),
+            Err("unknown prompt variables in .juliet/eng/prompt.md: `{{branch}}` (line 3), `{{now}}` (line 4). Known variables: role, now_utc, git_branch, available_engines, pending_needs".to_string())
+        );
+    }
+
+    #[test]
+    fn unknown_variables_lists_each_name_with_its_line() {
+        assert_eq!(
+            unknown_variables("# {{role}}\n\n{{branch}} {{\"key\": 1}}\n{{now}} {{now_utc}}\n"),
+            vec![(3, "branch".to_string()), (4, "now".to_string())]
+        );
+    }
+}
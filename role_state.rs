@@ -1,23 +1,31 @@
 #![allow(dead_code)]
 
-use std::fs;
-use std::io;
+use crate::timestamp;
+use std::fs::{self, OpenOptions};
+use std::io::{self, Read, Seek, SeekFrom, Write};
 use std::path::{Path, PathBuf};
 
 const JULIET_STATE_DIR: &str = ".juliet";
 const SHARED_STATE_DIR: &str = ".shared";
+const ARCHIVE_DIR: &str = ".archive";
 const ARTIFACTS_DIR: &str = "artifacts";
+const HISTORY_DIR: &str = "history";
 const LEARNINGS_FILE: &str = "learnings.md";
+const ENGINES_CONFIG_FILE: &str = "engines.toml";
 const STATE_GITIGNORE_FILE: &str = ".gitignore";
-const STATE_GITIGNORE_CONTENTS: &str = "# Managed by juliet: keep role prompt customizations, ignore runtime state.\n*\n!.gitignore\n!*/\n!*/prompt.md\n";
+pub const STATE_GITIGNORE_CONTENTS: &str = "# Managed by juliet: keep role prompt customizations, ignore runtime state.\n*\n!.gitignore\n!engines.toml\n!*/\n!*/prompt.md\n!*/prompt-seed.md\n!*/template.toml\n!*/template-base.md\n";
 const PROMPT_FILE: &str = "prompt.md";
+const PROMPT_SEED_FILE: &str = "prompt-seed.md";
+const TEMPLATE_RECORD_FILE: &str = "template.toml";
+const TEMPLATE_BASE_FILE: &str = "template-base.md";
 const RUNTIME_PROMPT_FILE: &str = "juliet-prompt.md";
-const STATE_FILES: [&str; 4] = [
-    "session.md",
-    "needs-from-operator.md",
-    "projects.md",
-    "processes.md",
-];
+const SESSION_FILE: &str = "session.md";
+const NEEDS_FILE: &str = "needs-from-operator.md";
+const PROJECTS_FILE: &str = "projects.md";
+const PROCESSES_FILE: &str = "processes.md";
+const INBOX_FILE: &str = "inbox.md";
+const SESSIONS_FILE: &str = "sessions.jsonl";
+const STATE_FILES: [&str; 4] = [SESSION_FILE, NEEDS_FILE, PROJECTS_FILE, PROCESSES_FILE];
 
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct ConfiguredRole {
@@ -25,8 +33,20 @@ pub struct ConfiguredRole {
     pub prompt_path: PathBuf,
 }
 
+/// Renders `path` relative to `project_root` for messages, or as-is when it lies outside.
+pub fn display_relative(project_root: &Path, path: &Path) -> String {
+    path.strip_prefix(project_root)
+        .unwrap_or(path)
+        .display()
+        .to_string()
+}
+
+pub fn state_root(project_root: &Path) -> PathBuf {
+    project_root.join(JULIET_STATE_DIR)
+}
+
 pub fn role_state_dir(project_root: &Path, role_name: &str) -> PathBuf {
-    project_root.join(JULIET_STATE_DIR).join(role_name)
+    state_root(project_root).join(role_name)
 }
 
 pub fn state_gitignore_path(project_root: &Path) -> PathBuf {
@@ -39,18 +59,82 @@ pub fn shared_state_dir(project_root: &Path) -> PathBuf {
     project_root.join(JULIET_STATE_DIR).join(SHARED_STATE_DIR)
 }
 
+pub fn archive_dir(project_root: &Path) -> PathBuf {
+    project_root.join(JULIET_STATE_DIR).join(ARCHIVE_DIR)
+}
+
 pub fn shared_learnings_path(project_root: &Path) -> PathBuf {
     shared_state_dir(project_root).join(LEARNINGS_FILE)
 }
 
+/// Optional project-wide prompt layer shared by every role.
+pub fn shared_prompt_path(project_root: &Path) -> PathBuf {
+    shared_state_dir(project_root).join(PROMPT_FILE)
+}
+
+pub fn engines_config_path(project_root: &Path) -> PathBuf {
+    project_root
+        .join(JULIET_STATE_DIR)
+        .join(ENGINES_CONFIG_FILE)
+}
+
 pub fn role_prompt_path(project_root: &Path, role_name: &str) -> PathBuf {
     role_state_dir(project_root, role_name).join(PROMPT_FILE)
 }
 
+/// Copy of the template seed `prompt.md` was generated from, used as the merge base by
+/// `upgrade-prompt`.
+pub fn prompt_seed_path(project_root: &Path, role_name: &str) -> PathBuf {
+    role_state_dir(project_root, role_name).join(PROMPT_SEED_FILE)
+}
+
+/// Names the template `prompt.md` was created from. Roles without one use the default seed.
+pub fn template_record_path(project_root: &Path, role_name: &str) -> PathBuf {
+    role_state_dir(project_root, role_name).join(TEMPLATE_RECORD_FILE)
+}
+
+/// The `init --from` source prompt as of the last sync, used as the merge base by
+/// `sync-template`.
+pub fn template_base_path(project_root: &Path, role_name: &str) -> PathBuf {
+    role_state_dir(project_root, role_name).join(TEMPLATE_BASE_FILE)
+}
+
 pub fn runtime_prompt_path(project_root: &Path, role_name: &str) -> PathBuf {
     role_state_dir(project_root, role_name).join(RUNTIME_PROMPT_FILE)
 }
 
+pub fn session_path(project_root: &Path, role_name: &str) -> PathBuf {
+    role_state_dir(project_root, role_name).join(SESSION_FILE)
+}
+
+pub fn needs_path(project_root: &Path, role_name: &str) -> PathBuf {
+    role_state_dir(project_root, role_name).join(NEEDS_FILE)
+}
+
+pub fn projects_path(project_root: &Path, role_name: &str) -> PathBuf {
+    role_state_dir(project_root, role_name).join(PROJECTS_FILE)
+}
+
+pub fn processes_path(project_root: &Path, role_name: &str) -> PathBuf {
+    role_state_dir(project_root, role_name).join(PROCESSES_FILE)
+}
+
+pub fn inbox_path(project_root: &Path, role_name: &str) -> PathBuf {
+    role_state_dir(project_root, role_name).join(INBOX_FILE)
+}
+
+pub fn sessions_path(project_root: &Path, role_name: &str) -> PathBuf {
+    role_state_dir(project_root, role_name).join(SESSIONS_FILE)
+}
+
+pub fn artifacts_dir(project_root: &Path, role_name: &str) -> PathBuf {
+    role_state_dir(project_root, role_name).join(ARTIFACTS_DIR)
+}
+
+pub fn history_dir(project_root: &Path, role_name: &str) -> PathBuf {
+    role_state_dir(project_root, role_name).join(HISTORY_DIR)
+}
+
 pub fn role_state_exists(project_root: &Path, role_name: &str) -> bool {
     role_state_dir(project_root, role_name).is_dir()
 }
@@ -79,6 +163,29 @@ pub fn ensure_shared_learnings(project_root: &Path) -> io::Result<()> {
     ensure_file(&shared_learnings_path(project_root))
 }
 
+/// Appends one line to `.juliet/.shared/learnings.md`, creating it if needed.
+pub fn append_shared_learning(project_root: &Path, entry: &str) -> io::Result<()> {
+    ensure_shared_learnings(project_root)?;
+    let mut file = OpenOptions::new()
+        .read(true)
+        .create(true)
+        .append(true)
+        .open(shared_learnings_path(project_root))?;
+    let mut last_byte = [0u8; 1];
+    let separator = if file.metadata()?.len() > 0 {
+        file.seek(SeekFrom::End(-1))?;
+        file.read_exact(&mut last_byte)?;
+        if last_byte[0] == b'\n' {
+            ""
+        } else {
+            "\n"
+        }
+    } else {
+        ""
+    };
+    writeln!(file, "{separator}{entry}")
+}
+
 pub fn ensure_state_gitignore(project_root: &Path) -> io::Result<()> {
     let state_root = project_root.join(JULIET_STATE_DIR);
     fs::create_dir_all(&state_root)?;
@@ -105,7 +212,7 @@ pub fn discover_configured_roles(project_root: &Path) -> io::Result<Vec<Configur
             Err(_) => continue,
         };
 
-        if role_name == SHARED_STATE_DIR {
+        if role_name == SHARED_STATE_DIR || role_name == ARCHIVE_DIR {
             continue;
         }
 
@@ -141,18 +248,25 @@ fn has_role_state_layout(role_dir: &Path) -> bool {
         && role_dir.join(ARTIFACTS_DIR).is_dir()
 }
 
-pub fn clear_role_history(project_root: &Path, role_name: &str) -> io::Result<()> {
+/// Snapshots the files it is about to empty or delete into `history/<timestamp>/`, then
+/// clears them. Returns the snapshot directory.
+pub fn clear_role_history(project_root: &Path, role_name: &str) -> io::Result<PathBuf> {
     let role_dir = role_state_dir(project_root, role_name);
+    let snapshot_dir = snapshot_role_history(project_root, role_name)?;
 
     // Empty state files
     for file in STATE_FILES {
         fs::write(role_dir.join(file), "")?;
     }
 
-    // Delete runtime prompt if present
-    let runtime_path = runtime_prompt_path(project_root, role_name);
-    if runtime_path.exists() {
-        fs::remove_file(&runtime_path)?;
+    // Delete runtime prompt and recorded exec sessions if present
+    for path in [
+        runtime_prompt_path(project_root, role_name),
+        sessions_path(project_root, role_name),
+    ] {
+        if path.exists() {
+            fs::remove_file(&path)?;
+        }
     }
 
     // Clear artifacts directory contents while preserving the directory
@@ -169,6 +283,119 @@ pub fn clear_role_history(project_root: &Path, role_name: &str) -> io::Result<()
         }
     }
 
+    Ok(snapshot_dir)
+}
+
+/// Copies the most recent `clear_role_history` snapshot back into place and removes it.
+/// Artifacts written since the clear are kept. Returns `None` when there is no snapshot.
+pub fn restore_latest_history_snapshot(
+    project_root: &Path,
+    role_name: &str,
+) -> io::Result<Option<PathBuf>> {
+    let Some(snapshot_dir) = history_snapshots(project_root, role_name)?.pop() else {
+        return Ok(None);
+    };
+    let role_dir = role_state_dir(project_root, role_name);
+
+    for file in STATE_FILES {
+        let saved = snapshot_dir.join(file);
+        if saved.is_file() {
+            fs::copy(&saved, role_dir.join(file))?;
+        }
+    }
+    for file in [RUNTIME_PROMPT_FILE, SESSIONS_FILE] {
+        let saved = snapshot_dir.join(file);
+        if saved.is_file() {
+            fs::copy(&saved, role_dir.join(file))?;
+        }
+    }
+    let saved_artifacts = snapshot_dir.join(ARTIFACTS_DIR);
+    if saved_artifacts.is_dir() {
+        copy_dir_all(&saved_artifacts, &role_dir.join(ARTIFACTS_DIR))?;
+    }
+
+    fs::remove_dir_all(&snapshot_dir)?;
+    Ok(Some(snapshot_dir))
+}
+
+/// Snapshot directories under `history/`, oldest first.
+pub fn history_snapshots(project_root: &Path, role_name: &str) -> io::Result<Vec<PathBuf>> {
+    let entries = match fs::read_dir(history_dir(project_root, role_name)) {
+        Ok(entries) => entries,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => return Err(err),
+    };
+
+    let mut snapshots = Vec::new();
+    for entry in entries {
+        let entry = entry?;
+        if entry.file_type()?.is_dir() {
+            snapshots.push(entry.path());
+        }
+    }
+    snapshots.sort_by_key(|path| snapshot_sort_key(path));
+    Ok(snapshots)
+}
+
+fn snapshot_role_history(project_root: &Path, role_name: &str) -> io::Result<PathBuf> {
+    let role_dir = role_state_dir(project_root, role_name);
+    let history_dir = history_dir(project_root, role_name);
+    fs::create_dir_all(&history_dir)?;
+
+    // Clears within the same second get a numeric suffix so no snapshot is overwritten.
+    let stamp = timestamp::now_utc().replace(['-', ':'], "");
+    let mut snapshot_dir = history_dir.join(&stamp);
+    let mut attempt = 1;
+    loop {
+        match fs::create_dir(&snapshot_dir) {
+            Ok(()) => break,
+            Err(err) if err.kind() == io::ErrorKind::AlreadyExists => {
+                attempt += 1;
+                snapshot_dir = history_dir.join(format!("{stamp}-{attempt}"));
+            }
+            Err(err) => return Err(err),
+        }
+    }
+
+    for file in STATE_FILES
+        .iter()
+        .chain(&[RUNTIME_PROMPT_FILE, SESSIONS_FILE])
+    {
+        let path = role_dir.join(file);
+        if path.is_file() {
+            fs::copy(&path, snapshot_dir.join(file))?;
+        }
+    }
+    let artifacts_dir = role_dir.join(ARTIFACTS_DIR);
+    if artifacts_dir.is_dir() {
+        copy_dir_all(&artifacts_dir, &snapshot_dir.join(ARTIFACTS_DIR))?;
+    }
+
+    Ok(snapshot_dir)
+}
+
+fn snapshot_sort_key(path: &Path) -> (String, u32) {
+    let name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    match name.split_once('-') {
+        Some((stamp, attempt)) => (stamp.to_string(), attempt.parse().unwrap_or(0)),
+        None => (name, 1),
+    }
+}
+
+fn copy_dir_all(source: &Path, destination: &Path) -> io::Result<()> {
+    fs::create_dir_all(destination)?;
+    for entry in fs::read_dir(source)? {
+        let entry = entry?;
+        let target = destination.join(entry.file_name());
+        if entry.file_type()?.is_dir() {
+            copy_dir_all(&entry.path(), &target)?;
+        } else {
+            fs::copy(entry.path(), target)?;
+        }
+    }
     Ok(())
 }
 
@@ -190,43 +417,12 @@ fn ensure_file(path: &Path) -> io::Result<()> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::env;
+    use crate::test_support::TestDir;
+
     #[cfg(unix)]
     use std::ffi::OsString;
     #[cfg(unix)]
     use std::os::unix::ffi::OsStringExt;
-    use std::process;
-    use std::time::{SystemTime, UNIX_EPOCH};
-
-    struct TestDir {
-        path: PathBuf,
-    }
-
-    impl TestDir {
-        fn new(name: &str) -> Self {
-            let timestamp = SystemTime::now()
-                .duration_since(UNIX_EPOCH)
-                .expect("time drift should not occur in tests")
-                .as_nanos();
-            let path = env::temp_dir().join(format!(
-                "juliet-role-state-{name}-{}-{timestamp}",
-                process::id()
-            ));
-            fs::create_dir_all(&path).expect("test directory should be created");
-
-            Self { path }
-        }
-
-        fn path(&self) -> &Path {
-            &self.path
-        }
-    }
-
-    impl Drop for TestDir {
-        fn drop(&mut self) {
-            let _ = fs::remove_dir_all(&self.path);
-        }
-    }
 
     #[test]
     fn create_role_state_builds_expected_layout() {
@@ -304,6 +500,17 @@ mod tests {
                 .join(SHARED_STATE_DIR)
                 .join(LEARNINGS_FILE)
         );
+        assert_eq!(
+            engines_config_path(temp.path()),
+            temp.path().join(JULIET_STATE_DIR).join(ENGINES_CONFIG_FILE)
+        );
+        assert_eq!(
+            processes_path(temp.path(), role_name),
+            temp.path()
+                .join(JULIET_STATE_DIR)
+                .join(role_name)
+                .join(PROCESSES_FILE)
+        );
     }
 
     #[test]
@@ -327,6 +534,25 @@ mod tests {
         );
     }
 
+    #[test]
+    fn append_shared_learning_appends_lines_without_rewriting_existing_text() {
+        let temp = TestDir::new("append-learning");
+
+        append_shared_learning(temp.path(), "- first").expect("first append should succeed");
+        let learnings_path = shared_learnings_path(temp.path());
+        assert_eq!(
+            fs::read_to_string(&learnings_path).expect("learnings should be readable"),
+            "- first\n"
+        );
+
+        fs::write(&learnings_path, "# Learnings\n- first").expect("seed unterminated line");
+        append_shared_learning(temp.path(), "- second").expect("second append should succeed");
+        assert_eq!(
+            fs::read_to_string(&learnings_path).expect("learnings should be readable"),
+            "# Learnings\n- first\n- second\n"
+        );
+    }
+
     #[test]
     fn ensure_state_gitignore_rewrites_customized_contents_to_expected_template() {
         let temp = TestDir::new("state-gitignore");
@@ -444,6 +670,8 @@ mod tests {
             "shared",
         )
         .expect("shared learnings file should be created");
+        fs::create_dir_all(state_root.join(ARCHIVE_DIR))
+            .expect("archive directory should be created");
         fs::create_dir_all(state_root.join("juliet")).expect("juliet role should be created");
         fs::write(state_root.join("README.md"), "not a role")
             .expect("non-directory entry should be created");
@@ -534,4 +762,64 @@ mod tests {
             }]
         );
     }
+
+    #[test]
+    fn clear_role_history_snapshots_and_undo_restores_latest() {
+        let temp = TestDir::new("history-snapshot");
+        let role_name = "eng";
+        create_role_state(temp.path(), role_name).expect("role state should be scaffolded");
+        let role_dir = role_state_dir(temp.path(), role_name);
+        fs::write(role_dir.join(PROJECTS_FILE), "- name: wind\n").expect("write projects");
+        fs::write(role_dir.join(SESSIONS_FILE), "{}\n").expect("write sessions");
+        fs::write(role_dir.join(ARTIFACTS_DIR).join("report.md"), "report")
+            .expect("write artifact");
+
+        let first = clear_role_history(temp.path(), role_name).expect("first clear should work");
+        fs::write(role_dir.join(PROJECTS_FILE), "- name: fire\n").expect("write projects");
+        let second = clear_role_history(temp.path(), role_name).expect("second clear should work");
+
+        assert_ne!(first, second);
+        assert_eq!(
+            history_snapshots(temp.path(), role_name).expect("snapshots should list"),
+            vec![first.clone(), second.clone()]
+        );
+        assert_eq!(
+            fs::read_to_string(first.join(ARTIFACTS_DIR).join("report.md"))
+                .expect("artifact should be snapshotted"),
+            "report"
+        );
+
+        fs::write(role_dir.join(ARTIFACTS_DIR).join("new.md"), "new").expect("write artifact");
+        assert_eq!(
+            restore_latest_history_snapshot(temp.path(), role_name).expect("undo should work"),
+            Some(second)
+        );
+        assert_eq!(
+            fs::read_to_string(role_dir.join(PROJECTS_FILE)).expect("projects should restore"),
+            "- name: fire\n"
+        );
+        assert!(role_dir.join(ARTIFACTS_DIR).join("new.md").is_file());
+
+        assert_eq!(
+            restore_latest_history_snapshot(temp.path(), role_name).expect("undo should work"),
+            Some(first)
+        );
+        assert_eq!(
+            fs::read_to_string(role_dir.join(PROJECTS_FILE)).expect("projects should restore"),
+            "- name: wind\n"
+        );
+        assert_eq!(
+            fs::read_to_string(role_dir.join(SESSIONS_FILE)).expect("sessions should restore"),
+            "{}\n"
+        );
+        assert_eq!(
+            fs::read_to_string(role_dir.join(ARTIFACTS_DIR).join("report.md"))
+                .expect("artifact should restore"),
+            "report"
+        );
+        assert_eq!(
+            restore_latest_history_snapshot(temp.path(), role_name).expect("undo should work"),
+            None
+        );
+    }
 }
@@ -0,0 +1,409 @@
+use crate::role_name;
+use crate::state_files::{self, Need, ProcessEntry, Processes, Project, Session};
+use crate::swarm_tasks::{self, TaskProblem};
+use crate::table;
+use serde_json::{json, Value};
+use std::fs;
+use std::io;
+use std::path::Path;
+
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct RoleStatus {
+    pub role: String,
+    pub session: Session,
+    pub needs: Vec<Need>,
+    pub projects: Vec<Project>,
+    pub processes: Processes,
+    /// Set when the active project's `tasks.md` fails `juliet tasks check`.
+    pub tasks_warning: Option<TasksWarning>,
+}
+
+/// A `tasks.md` that should be fixed before the next `swarm run`.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct TasksWarning {
+    pub project: String,
+    pub tasks_path: String,
+    pub problems: Vec<TaskProblem>,
+}
+
+pub fn collect_role_status(project_root: &Path, role_name: &str) -> io::Result<RoleStatus> {
+    let projects = state_files::load_projects(project_root, role_name)?;
+    Ok(RoleStatus {
+        role: role_name.to_string(),
+        session: state_files::load_session(project_root, role_name)?,
+        needs: state_files::load_needs(project_root, role_name)?,
+        tasks_warning: check_active_project(project_root, &projects)?,
+        projects,
+        processes: state_files::load_processes(project_root, role_name)?,
+    })
+}
+
+/// Checks the tasks file of the role's active project, the last one in `projects.md`. A
+/// project whose tasks file does not exist yet is not flagged.
+fn check_active_project(
+    project_root: &Path,
+    projects: &[Project],
+) -> io::Result<Option<TasksWarning>> {
+    let Some(project) = projects.last() else {
+        return Ok(None);
+    };
+    let tasks_path = match &project.tasks_path {
+        Some(tasks_path) => project_root.join(tasks_path),
+        None if role_name::is_valid_role_name(&project.name) => {
+            swarm_tasks::tasks_path(project_root, &project.name)
+        }
+        None => return Ok(None),
+    };
+    let contents = match fs::read_to_string(&tasks_path) {
+        Ok(contents) => contents,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(None),
+        Err(err) => return Err(err),
+    };
+
+    let problems = swarm_tasks::check_tasks(&contents);
+    Ok((!problems.is_empty()).then(|| TasksWarning {
+        project: project.name.clone(),
+        tasks_path: tasks_path
+            .strip_prefix(project_root)
+            .unwrap_or(&tasks_path)
+            .display()
+            .to_string(),
+        problems,
+    }))
+}
+
+pub fn format_status_json(statuses: &[RoleStatus]) -> String {
+    let roles = statuses
+        .iter()
+        .map(|status| {
+            json!({
+                "role": status.role,
+                "session": session_to_json(&status.session),
+                "needs": status.needs.iter().map(|need| need.text.as_str()).collect::<Vec<_>>(),
+                "projects": status.projects.iter().map(project_to_json).collect::<Vec<_>>(),
+                "processes": {
+                    "active": status.processes.active.iter().map(process_to_json).collect::<Vec<_>>(),
+                    "completed": status.processes.completed.iter().map(process_to_json).collect::<Vec<_>>(),
+                },
+                "tasks_warning": status.tasks_warning.as_ref().map(|warning| json!({
+                    "project": warning.project,
+                    "tasks_path": warning.tasks_path,
+                    "problems": warning.problems.iter().map(|problem| json!({
+                        "line": problem.line,
+                        "message": problem.message,
+                    })).collect::<Vec<_>>(),
+                })),
+            })
+        })
+        .collect::<Vec<_>>();
+
+    json!({ "roles": roles }).to_string()
+}
+
+pub fn format_status_table(statuses: &[RoleStatus]) -> String {
+    let headers = [
+        "ROLE",
+        "SESSION",
+        "ENGINE",
+        "NEEDS",
+        "ACTIVE",
+        "COMPLETED",
+        "PROJECTS",
+    ];
+    let rows = statuses
+        .iter()
+        .map(|status| {
+            [
+                status.role.clone(),
+                status.session.status.as_deref().unwrap_or("-").to_string(),
+                status
+                    .session
+                    .default_engine
+                    .as_deref()
+                    .unwrap_or("-")
+                    .to_string(),
+                status.needs.len().to_string(),
+                status.processes.active.len().to_string(),
+                status.processes.completed.len().to_string(),
+                project_names(status),
+            ]
+        })
+        .collect::<Vec<_>>();
+
+    let mut output = table::format_table(headers, &rows);
+
+    for status in statuses {
+        if !status.needs.is_empty() {
+            output.push_str(&format!("\n{} needs from operator:\n", status.role));
+            for need in &status.needs {
+                output.push_str(&format!("  - {}\n", need.text));
+            }
+        }
+        if !status.processes.active.is_empty() {
+            output.push_str(&format!("\n{} active processes:\n", status.role));
+            for entry in &status.processes.active {
+                output.push_str(&format!(
+                    "  - pid {} -> {} (log: {})\n",
+                    entry
+                        .pid
+                        .map(|pid| pid.to_string())
+                        .unwrap_or_else(|| "?".to_string()),
+                    entry.target_branch.as_deref().unwrap_or("?"),
+                    entry.log_path.as_deref().unwrap_or("?"),
+                ));
+            }
+        }
+        if let Some(warning) = &status.tasks_warning {
+            output.push_str(&format!(
+                "\n{} blocked: {} tasks.md is not ready for swarm run (see juliet tasks check --swarm-project {}):\n",
+                status.role, warning.project, warning.project
+            ));
+            output.push_str(
+                &swarm_tasks::format_problems(&warning.tasks_path, &warning.problems)
+                    .lines()
+                    .map(|line| format!("  - {line}\n"))
+                    .collect::<String>(),
+            );
+        }
+    }
+
+    output
+}
+
+fn project_names(status: &RoleStatus) -> String {
+    let names = status
+        .projects
+        .iter()
+        .map(|project| project.name.as_str())
+        .filter(|name| !name.is_empty())
+        .collect::<Vec<_>>();
+    if names.is_empty() {
+        "-".to_string()
+    } else {
+        names.join(",")
+    }
+}
+
+/// Session fields as JSON, with `available_engines` as an array.
+fn session_to_json(session: &Session) -> Value {
+    let mut value = fields_to_json(&session.fields());
+    value["available_engines"] = json!(session.available_engines);
+    value
+}
+
+/// Project fields as JSON, with every target branch under a `target_branches` array.
+fn project_to_json(project: &Project) -> Value {
+    let mut value = fields_to_json(&project.fields());
+    if let Value::Object(map) = &mut value {
+        map.remove("target_branch");
+    }
+    value["target_branches"] = json!(project.target_branches);
+    value
+}
+
+/// Process fields as JSON, with `pid` as a number when it parsed as one.
+fn process_to_json(entry: &ProcessEntry) -> Value {
+    let mut value = fields_to_json(&entry.fields());
+    if let Some(pid) = entry.pid {
+        value["pid"] = json!(pid);
+    }
+    value
+}
+
+fn fields_to_json(fields: &[(String, String)]) -> Value {
+    Value::Object(
+        fields
+            .iter()
+            .map(|(key, value)| (key.clone(), Value::String(value.clone())))
+            .collect(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::role_state;
+    use crate::test_support::TestDir;
+    use std::fs;
+
+    fn seed_role(root: &Path, role_name: &str) {
+        role_state::create_role_state(root, role_name).expect("role state should be created");
+        let role_dir = role_state::role_state_dir(root, role_name);
+        fs::write(
+            role_dir.join("session.md"),
+            "# Session\n\n- started_at_utc: 2026-02-06T10:00:00Z\n- status: active\n- available_engines: codex, claude\n- default_engine: codex\n",
+        )
+        .expect("session should be written");
+        fs::write(
+            role_dir.join("needs-from-operator.md"),
+            "# Needs\n\n- before i start sprints, what email should i save in .swarm-hug/email.txt?\n",
+        )
+        .expect("needs should be written");
+        fs::write(
+            role_dir.join("projects.md"),
+            "# Projects\n\n- name: wind-sonnets\n  tasks_path: .swarm-hug/wind-sonnets/tasks.md\n  target_branch: feature/wind-sonnets\n",
+        )
+        .expect("projects should be written");
+        fs::write(
+            role_dir.join("processes.md"),
+            "# Processes\n\n## Active\n- pid: 4242\n  command: `nohup swarm run --project wind-sonnets`\n  target_branch: feature/wind-sonnets\n  log_path: .juliet/eng/artifacts/wind.log\n\n## Completed\n- pid: 4100\n  results_path: feature/wind-sonnets-try1\n  reported_on: 2026-02-06T11:00:00Z\n",
+        )
+        .expect("processes should be written");
+    }
+
+    #[test]
+    fn collect_role_status_reads_state_files() {
+        let temp = TestDir::new("collect");
+        seed_role(temp.path(), "eng");
+
+        let status = collect_role_status(temp.path(), "eng").expect("status should load");
+        assert_eq!(
+            status,
+            RoleStatus {
+                role: "eng".to_string(),
+                session: Session {
+                    started_at_utc: Some("2026-02-06T10:00:00Z".to_string()),
+                    status: Some("active".to_string()),
+                    available_engines: vec!["codex".to_string(), "claude".to_string()],
+                    default_engine: Some("codex".to_string()),
+                    ..Session::default()
+                },
+                needs: vec![Need {
+                    text:
+                        "before i start sprints, what email should i save in .swarm-hug/email.txt?"
+                            .to_string(),
+                }],
+                projects: vec![Project {
+                    name: "wind-sonnets".to_string(),
+                    tasks_path: Some(".swarm-hug/wind-sonnets/tasks.md".to_string()),
+                    target_branches: vec!["feature/wind-sonnets".to_string()],
+                    ..Project::default()
+                }],
+                processes: Processes {
+                    active: vec![ProcessEntry {
+                        pid: Some(4242),
+                        command: Some("nohup swarm run --project wind-sonnets".to_string()),
+                        target_branch: Some("feature/wind-sonnets".to_string()),
+                        log_path: Some(".juliet/eng/artifacts/wind.log".to_string()),
+                        ..ProcessEntry::default()
+                    }],
+                    completed: vec![ProcessEntry {
+                        pid: Some(4100),
+                        results_path: Some("feature/wind-sonnets-try1".to_string()),
+                        reported_on: Some("2026-02-06T11:00:00Z".to_string()),
+                        ..ProcessEntry::default()
+                    }],
+                },
+                tasks_warning: None,
+            }
+        );
+    }
+
+    #[test]
+    fn collect_role_status_warns_when_active_project_tasks_are_a_scaffold() {
+        let temp = TestDir::new("tasks-warning");
+        seed_role(temp.path(), "eng");
+        let swarm_dir = temp.path().join(".swarm-hug").join("wind-sonnets");
+        fs::create_dir_all(&swarm_dir).expect("swarm dir should be created");
+        fs::write(swarm_dir.join("tasks.md"), "# Tasks\n\n## Backlog\n")
+            .expect("tasks should be written");
+
+        let status = collect_role_status(temp.path(), "eng").expect("status should load");
+        assert_eq!(
+            status.tasks_warning,
+            Some(TasksWarning {
+                project: "wind-sonnets".to_string(),
+                tasks_path: ".swarm-hug/wind-sonnets/tasks.md".to_string(),
+                problems: swarm_tasks::check_tasks("# Tasks\n\n## Backlog\n"),
+            })
+        );
+        assert!(format_status_table(&[status]).ends_with(
+            "\neng blocked: wind-sonnets tasks.md is not ready for swarm run (see juliet tasks check --swarm-project wind-sonnets):\n\
+\x20 - .swarm-hug/wind-sonnets/tasks.md: no tasks found; this looks like the `swarm project init` scaffold\n"
+        ));
+
+        fs::write(
+            swarm_dir.join("tasks.md"),
+            "# Tasks\n\n## Backlog\n- [ ] (#1) Write a wind sonnet [3 pts]\n",
+        )
+        .expect("tasks should be written");
+        let status = collect_role_status(temp.path(), "eng").expect("status should load");
+        assert_eq!(status.tasks_warning, None);
+    }
+
+    #[test]
+    fn collect_role_status_treats_empty_and_none_state_as_idle() {
+        let temp = TestDir::new("idle");
+        role_state::create_role_state(temp.path(), "qa").expect("role state should be created");
+        let role_dir = role_state::role_state_dir(temp.path(), "qa");
+        fs::write(
+            role_dir.join("needs-from-operator.md"),
+            "(none)\n- (none)\n",
+        )
+        .expect("needs should be written");
+        fs::remove_file(role_dir.join("session.md")).expect("session should be removable");
+
+        let status = collect_role_status(temp.path(), "qa").expect("status should load");
+        assert_eq!(
+            status,
+            RoleStatus {
+                role: "qa".to_string(),
+                ..RoleStatus::default()
+            }
+        );
+    }
+
+    #[test]
+    fn format_status_json_is_machine_readable() {
+        let temp = TestDir::new("json");
+        seed_role(temp.path(), "eng");
+        let status = collect_role_status(temp.path(), "eng").expect("status should load");
+
+        let payload: Value =
+            serde_json::from_str(&format_status_json(&[status])).expect("output should be JSON");
+        assert_eq!(payload["roles"][0]["role"], "eng");
+        assert_eq!(payload["roles"][0]["session"]["default_engine"], "codex");
+        assert_eq!(
+            payload["roles"][0]["needs"][0],
+            "before i start sprints, what email should i save in .swarm-hug/email.txt?"
+        );
+        assert_eq!(payload["roles"][0]["projects"][0]["name"], "wind-sonnets");
+        assert_eq!(
+            payload["roles"][0]["session"]["available_engines"],
+            json!(["codex", "claude"])
+        );
+        assert_eq!(
+            payload["roles"][0]["projects"][0]["target_branches"],
+            json!(["feature/wind-sonnets"])
+        );
+        assert_eq!(payload["roles"][0]["processes"]["active"][0]["pid"], 4242);
+        assert_eq!(
+            payload["roles"][0]["processes"]["completed"][0]["results_path"],
+            "feature/wind-sonnets-try1"
+        );
+    }
+
+    #[test]
+    fn format_status_table_aligns_columns_and_lists_details() {
+        let temp = TestDir::new("table");
+        seed_role(temp.path(), "eng");
+        role_state::create_role_state(temp.path(), "qa").expect("role state should be created");
+        let statuses = vec![
+            collect_role_status(temp.path(), "eng").expect("status should load"),
+            collect_role_status(temp.path(), "qa").expect("status should load"),
+        ];
+
+        assert_eq!(
+            format_status_table(&statuses),
+            "ROLE  SESSION  ENGINE  NEEDS  ACTIVE  COMPLETED  PROJECTS\n\
+eng   active   codex   1      1       1          wind-sonnets\n\
+qa    -        -       0      0       0          -\n\
+\n\
+eng needs from operator:\n\
+\x20 - before i start sprints, what email should i save in .swarm-hug/email.txt?\n\
+\n\
+eng active processes:\n\
+\x20 - pid 4242 -> feature/wind-sonnets (log: .juliet/eng/artifacts/wind.log)\n"
+        );
+    }
+}
@@ -0,0 +1,428 @@
+use crate::role_name;
+use crate::role_state;
+use crate::sessions;
+use crate::state_files;
+use crate::table;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+/// One row of `juliet roles list`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RoleSummary {
+    pub name: String,
+    pub prompt: PromptState,
+    pub projects: usize,
+    pub active_processes: usize,
+    pub last_turn: Option<String>,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum PromptState {
+    Missing,
+    /// Still contains the operator placeholder written by `juliet init`.
+    Template,
+    Customized,
+}
+
+impl PromptState {
+    fn label(self) -> &'static str {
+        match self {
+            PromptState::Missing => "missing",
+            PromptState::Template => "template",
+            PromptState::Customized => "customized",
+        }
+    }
+}
+
+pub fn list_roles(project_root: &Path, operator_placeholder: &str) -> io::Result<Vec<RoleSummary>> {
+    role_state::discover_configured_roles(project_root)?
+        .into_iter()
+        .map(|role| {
+            let prompt = match fs::read_to_string(&role.prompt_path) {
+                Ok(contents) if contents.contains(operator_placeholder) => PromptState::Template,
+                Ok(_) => PromptState::Customized,
+                Err(err) if err.kind() == io::ErrorKind::NotFound => PromptState::Missing,
+                Err(err) => return Err(err),
+            };
+            let last_turn = sessions::load_sessions(project_root, &role.name)?
+                .pop()
+                .map(|record| record.timestamp);
+
+            Ok(RoleSummary {
+                projects: state_files::load_projects(project_root, &role.name)?.len(),
+                active_processes: active_process_count(project_root, &role.name)?,
+                name: role.name,
+                prompt,
+                last_turn,
+            })
+        })
+        .collect()
+}
+
+pub fn format_role_list(roles: &[RoleSummary]) -> String {
+    if roles.is_empty() {
+        return "no roles configured\n".to_string();
+    }
+
+    let rows = roles
+        .iter()
+        .map(|role| {
+            [
+                role.name.clone(),
+                role.prompt.label().to_string(),
+                role.projects.to_string(),
+                role.active_processes.to_string(),
+                role.last_turn.clone().unwrap_or_else(|| "-".to_string()),
+            ]
+        })
+        .collect::<Vec<_>>();
+    table::format_table(["ROLE", "PROMPT", "PROJECTS", "ACTIVE", "LAST_TURN"], &rows)
+}
+
+pub fn active_process_count(project_root: &Path, role_name: &str) -> io::Result<usize> {
+    Ok(state_files::load_processes(project_root, role_name)?
+        .active
+        .len())
+}
+
+/// Moves `.juliet/<from>/` to `.juliet/<to>/` and retitles the prompt heading. Roles with active
+/// runs are refused, since those runs still report to `.juliet/<from>/`.
+pub fn rename_role(project_root: &Path, from: &str, to: &str) -> Result<(), String> {
+    require_existing_role(project_root, from)?;
+    require_new_role(project_root, to)?;
+    require_no_active_processes(project_root, from)?;
+
+    fs::rename(
+        role_state::role_state_dir(project_root, from),
+        role_state::role_state_dir(project_root, to),
+    )
+    .map_err(|err| format!("failed to rename role {from} to {to}: {err}"))?;
+
+    retitle_prompt(project_root, to, from)?;
+    retitle_heading(&role_state::template_base_path(project_root, to), to, from)
+}
+
+/// Creates `<target>` with a copy of `<source>`'s prompt (and seed, template, and template base
+/// records) and fresh, empty state.
+pub fn clone_role(project_root: &Path, source: &str, target: &str) -> Result<(), String> {
+    require_existing_role(project_root, source)?;
+    require_new_role(project_root, target)?;
+
+    let source_prompt = role_state::role_prompt_path(project_root, source);
+    let prompt = fs::read_to_string(&source_prompt)
+        .map_err(|err| format!("failed to read {}: {err}", source_prompt.display()))?;
+
+    role_state::create_role_state(project_root, target)
+        .map_err(|err| format!("failed to initialize state for role {target}: {err}"))?;
+    let target_prompt = role_state::role_prompt_path(project_root, target);
+    fs::write(&target_prompt, prompt)
+        .map_err(|err| format!("failed to write {}: {err}", target_prompt.display()))?;
+
+    // Keep the seed, template, and template base records so `upgrade-prompt` and
+    // `sync-template` can merge the clone like its source.
+    for record_path in [
+        role_state::prompt_seed_path,
+        role_state::template_record_path,
+        role_state::template_base_path,
+    ] {
+        let source_record = record_path(project_root, source);
+        if source_record.is_file() {
+            let target_record = record_path(project_root, target);
+            fs::copy(&source_record, &target_record)
+                .map_err(|err| format!("failed to write {}: {err}", target_record.display()))?;
+        }
+    }
+
+    retitle_prompt(project_root, target, source)?;
+    retitle_heading(
+        &role_state::template_base_path(project_root, target),
+        target,
+        source,
+    )
+}
+
+/// Removes `.juliet/<role>/` once `confirm` agrees. Roles with active runs are refused
+/// before `confirm` is asked.
+pub fn delete_role<C>(project_root: &Path, role_name: &str, confirm: C) -> Result<(), String>
+where
+    C: FnOnce(&str) -> Result<(), String>,
+{
+    require_existing_role(project_root, role_name)?;
+    require_no_active_processes(project_root, role_name)?;
+    confirm(role_name)?;
+
+    fs::remove_dir_all(role_state::role_state_dir(project_root, role_name))
+        .map_err(|err| format!("failed to delete role {role_name}: {err}"))
+}
+
+fn require_existing_role(project_root: &Path, role_name: &str) -> Result<(), String> {
+    role_name::validate_role_name(role_name)?;
+    if role_state::role_state_exists(project_root, role_name) {
+        Ok(())
+    } else {
+        Err(format!(
+            "Role not found: {role_name}. Run: juliet init --project {role_name}"
+        ))
+    }
+}
+
+fn require_no_active_processes(project_root: &Path, role_name: &str) -> Result<(), String> {
+    let active = active_process_count(project_root, role_name)
+        .map_err(|err| format!("failed to read processes for role {role_name}: {err}"))?;
+    if active > 0 {
+        return Err(format!(
+            "Role {role_name} has {active} active process(es) in processes.md. Wait for them to finish or run: juliet processes check --project {role_name}"
+        ));
+    }
+    Ok(())
+}
+
+fn require_new_role(project_root: &Path, role_name: &str) -> Result<(), String> {
+    role_name::validate_role_name(role_name)?;
+    if role_state::role_state_exists(project_root, role_name) {
+        Err(format!("Role already exists: {role_name}"))
+    } else {
+        Ok(())
+    }
+}
+
+fn retitle_prompt(project_root: &Path, role_name: &str, previous: &str) -> Result<(), String> {
+    retitle_heading(
+        &role_state::role_prompt_path(project_root, role_name),
+        role_name,
+        previous,
+    )
+}
+
+/// Rewrites a leading `# <previous>` heading to `# <role_name>`. Files that are missing or whose
+/// first line is something else are left alone.
+fn retitle_heading(prompt_path: &Path, role_name: &str, previous: &str) -> Result<(), String> {
+    let prompt = match fs::read_to_string(prompt_path) {
+        Ok(prompt) => prompt,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(()),
+        Err(err) => return Err(format!("failed to read {}: {err}", prompt_path.display())),
+    };
+
+    let (first_line, rest) = prompt.split_once('\n').unwrap_or((prompt.as_str(), ""));
+    if first_line.trim_end() != format!("# {previous}") {
+        return Ok(());
+    }
+
+    let separator = if prompt.contains('\n') { "\n" } else { "" };
+    fs::write(prompt_path, format!("# {role_name}{separator}{rest}"))
+        .map_err(|err| format!("failed to write {}: {err}", prompt_path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state_files::{ProcessEntry, Processes, Project};
+    use crate::test_support::TestDir;
+
+    const PLACEHOLDER: &str = "<!-- TODO: placeholder -->";
+
+    fn create_role(root: &Path, name: &str, prompt: &str) {
+        role_state::create_role_state(root, name).expect("role state should be created");
+        fs::write(role_state::role_prompt_path(root, name), prompt)
+            .expect("role prompt should be written");
+    }
+
+    fn set_active_process(root: &Path, name: &str) {
+        state_files::save_processes(
+            root,
+            name,
+            &Processes {
+                active: vec![ProcessEntry {
+                    pid: Some(42),
+                    ..ProcessEntry::default()
+                }],
+                completed: Vec::new(),
+            },
+        )
+        .expect("processes should save");
+    }
+
+    #[test]
+    fn list_roles_reports_prompt_state_and_counts() {
+        let temp = TestDir::new("list");
+        create_role(temp.path(), "eng", "# eng\n\nShip features.\n");
+        create_role(temp.path(), "ops", &format!("# ops\n\n{PLACEHOLDER}\n"));
+        state_files::save_projects(
+            temp.path(),
+            "eng",
+            &[Project {
+                name: "wind".to_string(),
+                ..Project::default()
+            }],
+        )
+        .expect("projects should save");
+        set_active_process(temp.path(), "eng");
+        sessions::record_session(
+            temp.path(),
+            "eng",
+            &sessions::SessionRecord::new("2026-02-06T10:00:00Z", "codex", "thread-1", "hi"),
+        )
+        .expect("session should record");
+
+        let roles = list_roles(temp.path(), PLACEHOLDER).expect("roles should list");
+
+        assert_eq!(
+            format_role_list(&roles),
+            "ROLE  PROMPT      PROJECTS  ACTIVE  LAST_TURN\n\
+eng   customized  1         1       2026-02-06T10:00:00Z\n\
+ops   template    0         0       -\n"
+        );
+        assert_eq!(format_role_list(&[]), "no roles configured\n");
+    }
+
+    #[test]
+    fn rename_moves_state_and_retitles_prompt() {
+        let temp = TestDir::new("rename");
+        create_role(temp.path(), "eng", "# eng\n\nShip features.\n");
+        fs::write(
+            role_state::needs_path(temp.path(), "eng"),
+            "- pick a branch\n",
+        )
+        .expect("needs should be written");
+
+        rename_role(temp.path(), "eng", "platform").expect("rename should succeed");
+
+        assert!(!role_state::role_state_exists(temp.path(), "eng"));
+        assert_eq!(
+            fs::read_to_string(role_state::role_prompt_path(temp.path(), "platform"))
+                .expect("prompt should be readable"),
+            "# platform\n\nShip features.\n"
+        );
+        assert_eq!(
+            fs::read_to_string(role_state::needs_path(temp.path(), "platform"))
+                .expect("needs should be readable"),
+            "- pick a branch\n"
+        );
+    }
+
+    #[test]
+    fn rename_refuses_roles_with_active_processes() {
+        let temp = TestDir::new("rename-active");
+        create_role(temp.path(), "eng", "# eng\n");
+        set_active_process(temp.path(), "eng");
+
+        assert_eq!(
+            rename_role(temp.path(), "eng", "platform"),
+            Err("Role eng has 1 active process(es) in processes.md. Wait for them to finish or run: juliet processes check --project eng".to_string())
+        );
+        assert!(role_state::role_state_exists(temp.path(), "eng"));
+        assert!(!role_state::role_state_exists(temp.path(), "platform"));
+    }
+
+    #[test]
+    fn rename_and_clone_reject_missing_sources_and_existing_targets() {
+        let temp = TestDir::new("rename-errors");
+        create_role(temp.path(), "eng", "# eng\n");
+        create_role(temp.path(), "ops", "# ops\n");
+
+        assert_eq!(
+            rename_role(temp.path(), "qa", "test"),
+            Err("Role not found: qa. Run: juliet init --project qa".to_string())
+        );
+        assert_eq!(
+            rename_role(temp.path(), "eng", "ops"),
+            Err("Role already exists: ops".to_string())
+        );
+        assert_eq!(
+            clone_role(temp.path(), "eng", "Bad_Name"),
+            Err(
+                "Invalid role name: Bad_Name. Use lowercase letters, numbers, and hyphens."
+                    .to_string()
+            )
+        );
+    }
+
+    #[test]
+    fn clone_copies_prompt_with_fresh_state() {
+        let temp = TestDir::new("clone");
+        create_role(temp.path(), "eng", "# eng\n\nTuned instructions.\n");
+        fs::write(
+            role_state::projects_path(temp.path(), "eng"),
+            "- name: wind\n",
+        )
+        .expect("projects should be written");
+
+        clone_role(temp.path(), "eng", "eng-2").expect("clone should succeed");
+
+        assert_eq!(
+            fs::read_to_string(role_state::role_prompt_path(temp.path(), "eng-2"))
+                .expect("prompt should be readable"),
+            "# eng-2\n\nTuned instructions.\n"
+        );
+        assert!(role_state::role_state_is_scaffolded(temp.path(), "eng-2"));
+        assert_eq!(
+            fs::read_to_string(role_state::projects_path(temp.path(), "eng-2"))
+                .expect("projects should be readable"),
+            ""
+        );
+        assert_eq!(
+            fs::read_to_string(role_state::role_prompt_path(temp.path(), "eng"))
+                .expect("source prompt should be readable"),
+            "# eng\n\nTuned instructions.\n"
+        );
+    }
+
+    #[test]
+    fn clone_copies_and_retitles_template_base() {
+        let temp = TestDir::new("clone-template-base");
+        create_role(temp.path(), "eng", "# eng\n\nTuned instructions.\n");
+        fs::write(
+            role_state::template_base_path(temp.path(), "eng"),
+            "# eng\n\nTemplate instructions.\n",
+        )
+        .expect("template base should be written");
+
+        clone_role(temp.path(), "eng", "eng-2").expect("clone should succeed");
+
+        assert_eq!(
+            fs::read_to_string(role_state::template_base_path(temp.path(), "eng-2"))
+                .expect("template base should be readable"),
+            "# eng-2\n\nTemplate instructions.\n"
+        );
+    }
+
+    #[test]
+    fn retitle_leaves_custom_headings_alone() {
+        let temp = TestDir::new("retitle");
+        create_role(temp.path(), "eng", "# Engineering lead\n");
+
+        rename_role(temp.path(), "eng", "lead").expect("rename should succeed");
+
+        assert_eq!(
+            fs::read_to_string(role_state::role_prompt_path(temp.path(), "lead"))
+                .expect("prompt should be readable"),
+            "# Engineering lead\n"
+        );
+    }
+
+    #[test]
+    fn delete_refuses_roles_with_active_processes() {
+        let temp = TestDir::new("delete");
+        create_role(temp.path(), "eng", "# eng\n");
+        set_active_process(temp.path(), "eng");
+
+        assert_eq!(
+            delete_role(temp.path(), "eng", |_| panic!("active roles are never confirmed")),
+            Err("Role eng has 1 active process(es) in processes.md. Wait for them to finish or run: juliet processes check --project eng".to_string())
+        );
+        assert!(role_state::role_state_exists(temp.path(), "eng"));
+
+        state_files::save_processes(temp.path(), "eng", &Processes::default())
+            .expect("processes should save");
+        assert_eq!(
+            delete_role(temp.path(), "eng", |_| Err(
+                "Deletion cancelled.".to_string()
+            )),
+            Err("Deletion cancelled.".to_string())
+        );
+        assert!(role_state::role_state_exists(temp.path(), "eng"));
+        delete_role(temp.path(), "eng", |_| Ok(())).expect("delete should succeed");
+        assert!(!role_state::role_state_exists(temp.path(), "eng"));
+    }
+}
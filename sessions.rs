@@ -0,0 +1,238 @@
+use crate::digest;
+use crate::role_state;
+use crate::table;
+use serde_json::{json, Value};
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::Path;
+
+/// One exec turn as recorded in `.juliet/<role>/sessions.jsonl`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SessionRecord {
+    pub timestamp: String,
+    pub engine: String,
+    pub resume_id: String,
+    pub message_digest: String,
+}
+
+/// All recorded turns that share an engine and resume id.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SessionThread {
+    pub engine: String,
+    pub resume_id: String,
+    pub turns: Vec<SessionRecord>,
+}
+
+impl SessionRecord {
+    pub fn new(timestamp: &str, engine: &str, resume_id: &str, message: &str) -> Self {
+        Self {
+            timestamp: timestamp.to_string(),
+            engine: engine.to_string(),
+            resume_id: resume_id.to_string(),
+            message_digest: message_digest(message),
+        }
+    }
+
+    fn to_json_line(&self) -> String {
+        json!({
+            "timestamp": self.timestamp,
+            "engine": self.engine,
+            "resume_id": self.resume_id,
+            "message_digest": self.message_digest,
+        })
+        .to_string()
+    }
+
+    fn from_json(value: &Value) -> Option<Self> {
+        let field = |key: &str| value.get(key).and_then(Value::as_str).map(str::to_string);
+        Some(Self {
+            timestamp: field("timestamp")?,
+            engine: field("engine")?,
+            resume_id: field("resume_id")?,
+            message_digest: field("message_digest").unwrap_or_default(),
+        })
+    }
+}
+
+impl SessionThread {
+    pub fn last_turn(&self) -> &SessionRecord {
+        self.turns.last().expect("threads always have a turn")
+    }
+}
+
+/// Stable 64-bit FNV-1a digest of an exec message, so turns can be correlated without
+/// storing operator input in plain text.
+pub fn message_digest(message: &str) -> String {
+    digest::fnv1a64(message)
+}
+
+pub fn record_session(
+    project_root: &Path,
+    role_name: &str,
+    record: &SessionRecord,
+) -> io::Result<()> {
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(role_state::sessions_path(project_root, role_name))?;
+    writeln!(file, "{}", record.to_json_line())
+}
+
+/// Reads every recorded turn in the order it was written. Malformed lines are skipped.
+pub fn load_sessions(project_root: &Path, role_name: &str) -> io::Result<Vec<SessionRecord>> {
+    let contents = match fs::read_to_string(role_state::sessions_path(project_root, role_name)) {
+        Ok(contents) => contents,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => return Err(err),
+    };
+
+    Ok(contents
+        .lines()
+        .filter_map(|line| serde_json::from_str::<Value>(line).ok())
+        .filter_map(|value| SessionRecord::from_json(&value))
+        .collect())
+}
+
+/// Most recent turn recorded for `engine_name`.
+pub fn latest_session(records: &[SessionRecord], engine_name: &str) -> Option<SessionRecord> {
+    records
+        .iter()
+        .rev()
+        .find(|record| record.engine == engine_name)
+        .cloned()
+}
+
+/// Groups turns into threads ordered by their first turn.
+pub fn group_threads(records: &[SessionRecord]) -> Vec<SessionThread> {
+    let mut threads: Vec<SessionThread> = Vec::new();
+    for record in records {
+        match threads
+            .iter_mut()
+            .find(|thread| thread.engine == record.engine && thread.resume_id == record.resume_id)
+        {
+            Some(thread) => thread.turns.push(record.clone()),
+            None => threads.push(SessionThread {
+                engine: record.engine.clone(),
+                resume_id: record.resume_id.clone(),
+                turns: vec![record.clone()],
+            }),
+        }
+    }
+    threads
+}
+
+pub fn format_thread_list(threads: &[SessionThread]) -> String {
+    if threads.is_empty() {
+        return "no recorded sessions\n".to_string();
+    }
+
+    let rows = threads
+        .iter()
+        .map(|thread| {
+            [
+                thread.engine.clone(),
+                thread.resume_id.clone(),
+                thread.turns.len().to_string(),
+                thread.last_turn().timestamp.clone(),
+            ]
+        })
+        .collect::<Vec<_>>();
+    table::format_table(["ENGINE", "RESUME_ID", "TURNS", "LAST_TURN"], &rows)
+}
+
+pub fn format_thread(thread: &SessionThread) -> String {
+    let mut output = format!(
+        "engine: {}\nresume_id: {}\nturns: {}\n",
+        thread.engine,
+        thread.resume_id,
+        thread.turns.len()
+    );
+    for turn in &thread.turns {
+        output.push_str(&format!("- {} {}\n", turn.timestamp, turn.message_digest));
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::TestDir;
+
+    fn sample_records() -> Vec<SessionRecord> {
+        vec![
+            SessionRecord::new("2026-02-06T10:00:00Z", "codex", "thread-1", "hello"),
+            SessionRecord::new("2026-02-06T10:05:00Z", "claude", "session-1", "hi"),
+            SessionRecord::new("2026-02-06T10:10:00Z", "codex", "thread-1", "status?"),
+        ]
+    }
+
+    #[test]
+    fn message_digest_is_stable_and_hides_message() {
+        assert_eq!(message_digest(""), "fnv1a64:cbf29ce484222325");
+        assert_eq!(message_digest("a"), "fnv1a64:af63dc4c8601ec8c");
+        assert_ne!(
+            message_digest("fix the bug"),
+            message_digest("fix the bug!")
+        );
+    }
+
+    #[test]
+    fn records_round_trip_through_sessions_file() {
+        let temp = TestDir::new("round-trip");
+        role_state::create_role_state(temp.path(), "eng").expect("role state should be created");
+
+        assert!(load_sessions(temp.path(), "eng")
+            .expect("missing file should load")
+            .is_empty());
+        for record in sample_records() {
+            record_session(temp.path(), "eng", &record).expect("record should append");
+        }
+        fs::OpenOptions::new()
+            .append(true)
+            .open(role_state::sessions_path(temp.path(), "eng"))
+            .and_then(|mut file| writeln!(file, "not json"))
+            .expect("garbage line should append");
+
+        assert_eq!(
+            load_sessions(temp.path(), "eng").expect("sessions should load"),
+            sample_records()
+        );
+    }
+
+    #[test]
+    fn latest_session_is_scoped_by_engine() {
+        let records = sample_records();
+
+        assert_eq!(
+            latest_session(&records, "codex").map(|record| record.timestamp),
+            Some("2026-02-06T10:10:00Z".to_string())
+        );
+        assert_eq!(
+            latest_session(&records, "claude").map(|record| record.resume_id),
+            Some("session-1".to_string())
+        );
+        assert_eq!(latest_session(&records, "mytool"), None);
+    }
+
+    #[test]
+    fn threads_group_turns_and_format_for_display() {
+        let threads = group_threads(&sample_records());
+
+        assert_eq!(threads.len(), 2);
+        assert_eq!(threads[0].turns.len(), 2);
+        assert_eq!(
+            format_thread_list(&threads),
+            "ENGINE  RESUME_ID  TURNS  LAST_TURN\n\
+codex   thread-1   2      2026-02-06T10:10:00Z\n\
+claude  session-1  1      2026-02-06T10:05:00Z\n"
+        );
+        assert_eq!(
+            format_thread(&threads[1]),
+            format!(
+                "engine: claude\nresume_id: session-1\nturns: 1\n- 2026-02-06T10:05:00Z {}\n",
+                message_digest("hi")
+            )
+        );
+        assert_eq!(format_thread_list(&[]), "no recorded sessions\n");
+    }
+}
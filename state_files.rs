@@ -0,0 +1,973 @@
+use crate::role_state;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+pub type Fields = Vec<(String, String)>;
+
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Session {
+    pub started_at_utc: Option<String>,
+    pub status: Option<String>,
+    pub available_engines: Vec<String>,
+    pub default_engine: Option<String>,
+    pub swarm_engine_property_syntax: Option<String>,
+    pub extra: Fields,
+}
+
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Need {
+    pub text: String,
+}
+
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Project {
+    pub name: String,
+    pub prd_path: Option<String>,
+    pub tasks_path: Option<String>,
+    pub specs_path: Option<String>,
+    pub source_branch: Option<String>,
+    pub target_branches: Vec<String>,
+    pub engine: Option<String>,
+    pub extra: Fields,
+}
+
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct ProcessEntry {
+    pub pid: Option<u32>,
+    pub command: Option<String>,
+    pub source_branch: Option<String>,
+    pub target_branch: Option<String>,
+    pub log_path: Option<String>,
+    pub started_at: Option<String>,
+    pub results_path: Option<String>,
+    pub outcome: Option<String>,
+    pub reported_on: Option<String>,
+    pub extra: Fields,
+}
+
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Processes {
+    pub active: Vec<ProcessEntry>,
+    pub completed: Vec<ProcessEntry>,
+}
+
+impl Session {
+    fn from_fields(mut fields: Fields) -> Self {
+        Self {
+            started_at_utc: take_field(&mut fields, &["started_at_utc", "started_at"]),
+            status: take_field(&mut fields, &["status"]),
+            available_engines: take_field(&mut fields, &["available_engines"])
+                .map(|value| split_list_value(&value))
+                .unwrap_or_default(),
+            default_engine: take_field(&mut fields, &["default_engine"]),
+            swarm_engine_property_syntax: take_field(
+                &mut fields,
+                &["swarm_engine_property_syntax"],
+            ),
+            extra: fields,
+        }
+    }
+
+    /// Fields in the order they are written to `session.md`.
+    pub fn fields(&self) -> Fields {
+        let mut fields = Fields::new();
+        push_field(
+            &mut fields,
+            "started_at_utc",
+            self.started_at_utc.as_deref(),
+        );
+        push_field(&mut fields, "status", self.status.as_deref());
+        if !self.available_engines.is_empty() {
+            push_field(
+                &mut fields,
+                "available_engines",
+                Some(&self.available_engines.join(", ")),
+            );
+        }
+        push_field(
+            &mut fields,
+            "default_engine",
+            self.default_engine.as_deref(),
+        );
+        push_field(
+            &mut fields,
+            "swarm_engine_property_syntax",
+            self.swarm_engine_property_syntax.as_deref(),
+        );
+        fields.extend(self.extra.iter().cloned());
+        fields
+    }
+}
+
+impl Project {
+    fn from_fields(mut fields: Fields) -> Self {
+        let mut target_branches = Vec::new();
+        while let Some(value) = take_field(&mut fields, &["target_branch", "target_branches"]) {
+            target_branches.extend(split_list_value(&value));
+        }
+
+        Self {
+            name: take_field(&mut fields, &["name", "project"]).unwrap_or_default(),
+            prd_path: take_field(&mut fields, &["prd_path", "prd"]),
+            tasks_path: take_field(&mut fields, &["tasks_path", "tasks"]),
+            specs_path: take_field(&mut fields, &["specs_path", "specs"]),
+            source_branch: take_field(&mut fields, &["source_branch"]),
+            target_branches,
+            engine: take_field(&mut fields, &["engine"]),
+            extra: fields,
+        }
+    }
+
+    /// Fields in the order they are written to `projects.md`; `name` always comes first.
+    pub fn fields(&self) -> Fields {
+        let mut fields = vec![("name".to_string(), self.name.clone())];
+        push_field(&mut fields, "prd_path", self.prd_path.as_deref());
+        push_field(&mut fields, "tasks_path", self.tasks_path.as_deref());
+        push_field(&mut fields, "specs_path", self.specs_path.as_deref());
+        push_field(&mut fields, "source_branch", self.source_branch.as_deref());
+        match self.target_branches.as_slice() {
+            [] => {}
+            [branch] => push_field(&mut fields, "target_branch", Some(branch)),
+            branches => push_field(&mut fields, "target_branches", Some(&branches.join(", "))),
+        }
+        push_field(&mut fields, "engine", self.engine.as_deref());
+        fields.extend(self.extra.iter().cloned());
+        fields
+    }
+}
+
+impl ProcessEntry {
+    fn from_fields(mut fields: Fields) -> Self {
+        let pid = match take_field(&mut fields, &["pid"]) {
+            Some(value) => match value.parse() {
+                Ok(pid) => Some(pid),
+                Err(_) => {
+                    fields.insert(0, ("pid".to_string(), value));
+                    None
+                }
+            },
+            None => None,
+        };
+
+        Self {
+            pid,
+            command: take_field(&mut fields, &["command"]),
+            source_branch: take_field(&mut fields, &["source_branch"]),
+            target_branch: take_field(&mut fields, &["target_branch"]),
+            log_path: take_field(&mut fields, &["log_path", "log"]),
+            started_at: take_field(&mut fields, &["started_at", "start_time", "started_at_utc"]),
+            results_path: take_field(&mut fields, &["results_path"]),
+            outcome: take_field(&mut fields, &["outcome", "outcome_summary", "summary"]),
+            reported_on: take_field(&mut fields, &["reported_on"]),
+            extra: fields,
+        }
+    }
+
+    /// Fields in the order they are written to `processes.md`.
+    pub fn fields(&self) -> Fields {
+        let mut fields = Fields::new();
+        if let Some(pid) = self.pid {
+            push_field(&mut fields, "pid", Some(&pid.to_string()));
+        }
+        push_field(&mut fields, "command", self.command.as_deref());
+        push_field(&mut fields, "source_branch", self.source_branch.as_deref());
+        push_field(&mut fields, "target_branch", self.target_branch.as_deref());
+        push_field(&mut fields, "log_path", self.log_path.as_deref());
+        push_field(&mut fields, "started_at", self.started_at.as_deref());
+        push_field(&mut fields, "results_path", self.results_path.as_deref());
+        push_field(&mut fields, "outcome", self.outcome.as_deref());
+        push_field(&mut fields, "reported_on", self.reported_on.as_deref());
+        fields.extend(self.extra.iter().cloned());
+        fields
+    }
+}
+
+pub fn parse_session(contents: &str) -> Session {
+    let fields = contents
+        .lines()
+        .filter(|line| !line.trim_start().starts_with('#'))
+        .filter_map(|line| split_key_value(list_item_text(line).unwrap_or(line)))
+        .collect();
+    Session::from_fields(fields)
+}
+
+pub fn render_session(session: &Session) -> String {
+    let mut output = "# Session\n\n".to_string();
+    for (key, value) in session.fields() {
+        push_key_value(&mut output, &key, &value);
+    }
+    output
+}
+
+pub fn parse_needs(contents: &str) -> Vec<Need> {
+    let mut needs: Vec<Need> = Vec::new();
+    let mut continues_need = false;
+    for line in contents.lines() {
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        if line.starts_with(char::is_whitespace) {
+            if let (true, Some(need)) = (continues_need, needs.last_mut()) {
+                need.text.push('\n');
+                need.text.push_str(line.trim());
+            }
+            continue;
+        }
+
+        continues_need = false;
+        if let Some(text) = list_item_text(line) {
+            if !text.is_empty() && text != "(none)" {
+                needs.push(Need {
+                    text: text.to_string(),
+                });
+                continues_need = true;
+            }
+        }
+    }
+    needs
+}
+
+pub fn parse_projects(contents: &str) -> Vec<Project> {
+    parse_sectioned_entries(contents)
+        .into_iter()
+        .flat_map(|(_, entries)| entries)
+        .map(Project::from_fields)
+        .collect()
+}
+
+pub fn render_projects(projects: &[Project]) -> String {
+    let mut output = "# Projects\n".to_string();
+    for project in projects {
+        output.push('\n');
+        push_entry(&mut output, &project.fields());
+    }
+    output
+}
+
+pub fn parse_processes(contents: &str) -> Processes {
+    let mut processes = Processes::default();
+    for (section, entries) in parse_sectioned_entries(contents) {
+        let target = if section.starts_with("active") {
+            &mut processes.active
+        } else if section.starts_with("completed") {
+            &mut processes.completed
+        } else {
+            continue;
+        };
+        target.extend(entries.into_iter().map(ProcessEntry::from_fields));
+    }
+    processes
+}
+
+pub fn render_processes(processes: &Processes) -> String {
+    let mut output = "# Processes\n".to_string();
+    for (heading, entries) in [
+        ("Active", &processes.active),
+        ("Completed", &processes.completed),
+    ] {
+        output.push_str(&format!("\n## {heading}\n"));
+        for entry in entries {
+            output.push('\n');
+            push_entry(&mut output, &entry.fields());
+        }
+    }
+    output
+}
+
+/// Writes `session` over `existing`, replacing only the `key: value` lines whose value changed.
+/// Other lines keep their place and text; new fields are appended.
+pub fn splice_session(existing: &str, session: &Session) -> String {
+    if existing.trim().is_empty() {
+        return render_session(session);
+    }
+
+    let mut pending = session.fields();
+    let mut output = String::new();
+    for line in existing.lines() {
+        let item = list_item_text(line);
+        let pair = if line.trim_start().starts_with('#') {
+            None
+        } else {
+            split_key_value(item.unwrap_or(line))
+        };
+        let Some((key, value)) = pair else {
+            output.push_str(line);
+            output.push('\n');
+            continue;
+        };
+
+        let key = match key.as_str() {
+            "started_at" => "started_at_utc".to_string(),
+            _ => key,
+        };
+        let Some(index) = pending
+            .iter()
+            .position(|(pending_key, _)| *pending_key == key)
+        else {
+            continue;
+        };
+        let (key, new_value) = pending.remove(index);
+        if new_value == value {
+            output.push_str(line);
+            output.push('\n');
+        } else {
+            if item.is_some() {
+                output.push_str("- ");
+            }
+            push_key_value(&mut output, &key, &new_value);
+        }
+    }
+    for (key, value) in pending {
+        push_key_value(&mut output, &key, &value);
+    }
+    output
+}
+
+/// Writes `projects` over `existing`: unchanged entries, prose, and headings are kept as
+/// written, changed entries are rewritten in place, and new entries follow the last one.
+pub fn splice_projects(existing: &str, projects: &[Project]) -> String {
+    if existing.trim().is_empty() {
+        return render_projects(projects);
+    }
+    splice_entries(
+        existing,
+        &[projects.iter().map(Project::fields).collect()],
+        |_| Some(0),
+        |fields| Project::from_fields(fields).fields(),
+        |_| None,
+    )
+}
+
+/// Writes `processes` over `existing`, touching only entries under the Active and Completed
+/// headings. Notes, other sections such as `## Paused`, and unchanged entries are kept as written.
+pub fn splice_processes(existing: &str, processes: &Processes) -> String {
+    if existing.trim().is_empty() {
+        return render_processes(processes);
+    }
+    splice_entries(
+        existing,
+        &[
+            processes.active.iter().map(ProcessEntry::fields).collect(),
+            processes
+                .completed
+                .iter()
+                .map(ProcessEntry::fields)
+                .collect(),
+        ],
+        |heading| {
+            if heading.starts_with("active") {
+                Some(0)
+            } else if heading.starts_with("completed") {
+                Some(1)
+            } else {
+                None
+            }
+        },
+        |fields| ProcessEntry::from_fields(fields).fields(),
+        |list| Some(["Active", "Completed"][list]),
+    )
+}
+
+pub fn load_session(project_root: &Path, role_name: &str) -> io::Result<Session> {
+    read_state_file(&role_state::session_path(project_root, role_name))
+        .map(|contents| parse_session(&contents))
+}
+
+pub fn save_session(project_root: &Path, role_name: &str, session: &Session) -> io::Result<()> {
+    let path = role_state::session_path(project_root, role_name);
+    let existing = read_state_file(&path)?;
+    fs::write(path, splice_session(&existing, session))
+}
+
+pub fn load_needs(project_root: &Path, role_name: &str) -> io::Result<Vec<Need>> {
+    read_state_file(&role_state::needs_path(project_root, role_name))
+        .map(|contents| parse_needs(&contents))
+}
+
+pub fn load_projects(project_root: &Path, role_name: &str) -> io::Result<Vec<Project>> {
+    read_state_file(&role_state::projects_path(project_root, role_name))
+        .map(|contents| parse_projects(&contents))
+}
+
+pub fn save_projects(project_root: &Path, role_name: &str, projects: &[Project]) -> io::Result<()> {
+    let path = role_state::projects_path(project_root, role_name);
+    let existing = read_state_file(&path)?;
+    fs::write(path, splice_projects(&existing, projects))
+}
+
+pub fn load_processes(project_root: &Path, role_name: &str) -> io::Result<Processes> {
+    read_state_file(&role_state::processes_path(project_root, role_name))
+        .map(|contents| parse_processes(&contents))
+}
+
+pub fn save_processes(
+    project_root: &Path,
+    role_name: &str,
+    processes: &Processes,
+) -> io::Result<()> {
+    let path = role_state::processes_path(project_root, role_name);
+    let existing = read_state_file(&path)?;
+    fs::write(path, splice_processes(&existing, processes))
+}
+
+fn read_state_file(path: &Path) -> io::Result<String> {
+    match fs::read_to_string(path) {
+        Ok(contents) => Ok(contents),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(String::new()),
+        Err(err) => Err(err),
+    }
+}
+
+fn take_field(fields: &mut Fields, keys: &[&str]) -> Option<String> {
+    let index = fields
+        .iter()
+        .position(|(key, _)| keys.contains(&key.as_str()))?;
+    let (_, value) = fields.remove(index);
+    if value.is_empty() {
+        None
+    } else {
+        Some(value)
+    }
+}
+
+fn push_field(fields: &mut Fields, key: &str, value: Option<&str>) {
+    if let Some(value) = value {
+        fields.push((key.to_string(), value.to_string()));
+    }
+}
+
+fn push_key_value(output: &mut String, key: &str, value: &str) {
+    output.push_str(format!("{key}: {value}").trim_end());
+    output.push('\n');
+}
+
+fn push_entry(output: &mut String, fields: &[(String, String)]) {
+    for (index, (key, value)) in fields.iter().enumerate() {
+        output.push_str(if index == 0 { "- " } else { "  " });
+        push_key_value(output, key, value);
+    }
+}
+
+fn split_list_value(value: &str) -> Vec<String> {
+    value
+        .trim_start_matches('[')
+        .trim_end_matches(']')
+        .split(',')
+        .map(|item| item.trim().trim_matches('`').to_string())
+        .filter(|item| !item.is_empty())
+        .collect()
+}
+
+fn normalize_key(key: &str) -> String {
+    key.trim()
+        .trim_matches(|character| character == '*' || character == '`')
+        .to_ascii_lowercase()
+        .replace([' ', '-'], "_")
+}
+
+fn clean_value(value: &str) -> String {
+    value.trim().trim_matches('`').to_string()
+}
+
+fn split_key_value(line: &str) -> Option<(String, String)> {
+    let (key, value) = line.split_once(':')?;
+    let key = normalize_key(key);
+    if key.is_empty() || key.contains(char::is_whitespace) {
+        return None;
+    }
+    Some((key, clean_value(value)))
+}
+
+fn list_item_text(line: &str) -> Option<&str> {
+    let trimmed = line.trim_start();
+    if let Some(rest) = trimmed
+        .strip_prefix("- ")
+        .or_else(|| trimmed.strip_prefix("* "))
+    {
+        return Some(rest.trim());
+    }
+
+    let digits = trimmed.chars().take_while(char::is_ascii_digit).count();
+    if digits > 0 {
+        if let Some(rest) = trimmed[digits..].strip_prefix(". ") {
+            return Some(rest.trim());
+        }
+    }
+    None
+}
+
+/// A piece of a sectioned state file: a heading, a top-level list item with its continuation
+/// lines, or any other single line.
+#[derive(Debug)]
+enum Block<'a> {
+    Heading(&'a str),
+    Entry(Vec<&'a str>),
+    Other(&'a str),
+}
+
+fn is_heading(line: &str) -> bool {
+    line.trim_start().starts_with('#')
+}
+
+fn heading_key(line: &str) -> String {
+    line.trim()
+        .trim_start_matches('#')
+        .trim()
+        .to_ascii_lowercase()
+}
+
+fn starts_entry(line: &str) -> bool {
+    !line.starts_with(char::is_whitespace) && list_item_text(line).is_some()
+}
+
+/// An entry continues over indented lines and unindented `key: value` lines, including across
+/// blank lines that are followed by one.
+fn continues_entry(line: &str) -> bool {
+    if line.trim().is_empty() || is_heading(line) || starts_entry(line) {
+        return false;
+    }
+    line.starts_with(char::is_whitespace) || split_key_value(line.trim()).is_some()
+}
+
+fn split_blocks(contents: &str) -> Vec<Block<'_>> {
+    let lines = contents.lines().collect::<Vec<_>>();
+    let mut blocks = Vec::new();
+    let mut index = 0;
+    while index < lines.len() {
+        let line = lines[index];
+        if is_heading(line) {
+            blocks.push(Block::Heading(line));
+            index += 1;
+        } else if starts_entry(line) {
+            let mut end = index + 1;
+            let mut scan = end;
+            while scan < lines.len() {
+                if lines[scan].trim().is_empty() {
+                    scan += 1;
+                } else if continues_entry(lines[scan]) {
+                    scan += 1;
+                    end = scan;
+                } else {
+                    break;
+                }
+            }
+            blocks.push(Block::Entry(lines[index..end].to_vec()));
+            index = end;
+        } else {
+            blocks.push(Block::Other(line));
+            index += 1;
+        }
+    }
+    blocks
+}
+
+/// The `key: value` fields of an entry block, and its continuation lines that are not fields.
+fn entry_fields<'a>(lines: &[&'a str]) -> (Fields, Vec<&'a str>) {
+    let mut fields = Fields::new();
+    let mut notes = Vec::new();
+    let Some((first, rest)) = lines.split_first() else {
+        return (fields, notes);
+    };
+    let text = list_item_text(first).unwrap_or(first.trim());
+    match split_key_value(text) {
+        Some(pair) => fields.push(pair),
+        None => fields.push(("name".to_string(), clean_value(text))),
+    }
+    for line in rest {
+        if line.trim().is_empty() {
+            continue;
+        }
+        match split_key_value(list_item_text(line).unwrap_or(line.trim())) {
+            Some(pair) => fields.push(pair),
+            None => notes.push(*line),
+        }
+    }
+    (fields, notes)
+}
+
+/// Splits a markdown file into `(lowercased heading, entries)` where each top-level list
+/// item starts an entry and its continuation `key: value` lines extend it.
+fn parse_sectioned_entries(contents: &str) -> Vec<(String, Vec<Fields>)> {
+    let mut sections: Vec<(String, Vec<Fields>)> = Vec::new();
+    for block in split_blocks(contents) {
+        match block {
+            Block::Heading(line) => sections.push((heading_key(line), Vec::new())),
+            Block::Entry(lines) => {
+                if sections.is_empty() {
+                    sections.push((String::new(), Vec::new()));
+                }
+                let entries = &mut sections.last_mut().expect("section should exist").1;
+                entries.push(entry_fields(&lines).0);
+            }
+            Block::Other(_) => {}
+        }
+    }
+    sections
+}
+
+/// Rewrites the entries of a sectioned state file. `list_of` maps a lowercased heading to the
+/// index in `lists` its entries belong to; entries under other headings are left alone. The
+/// n-th existing entry of a list is kept verbatim when `normalize` of its fields equals the
+/// n-th new entry, rewritten otherwise, and dropped when the list got shorter. Extra entries
+/// go after the list's last entry, at the end of its section, or under a new
+/// `## {missing_heading}` appended to the file.
+fn splice_entries<L, N, H>(
+    existing: &str,
+    lists: &[Vec<Fields>],
+    list_of: L,
+    normalize: N,
+    missing_heading: H,
+) -> String
+where
+    L: Fn(&str) -> Option<usize>,
+    N: Fn(Fields) -> Fields,
+    H: Fn(usize) -> Option<&'static str>,
+{
+    let blocks = split_blocks(existing);
+    let mut heading = String::new();
+    let block_lists = blocks
+        .iter()
+        .map(|block| {
+            if let Block::Heading(line) = block {
+                heading = heading_key(line);
+            }
+            list_of(&heading)
+        })
+        .collect::<Vec<_>>();
+
+    // Where each list's extra entries go: after its last entry, else after its first section.
+    let insert_after = (0..lists.len())
+        .map(|list| {
+            let last_entry = (0..blocks.len()).rev().find(|&index| {
+                block_lists[index] == Some(list) && matches!(blocks[index], Block::Entry(_))
+            });
+            last_entry.or_else(|| {
+                let first = block_lists.iter().position(|&owner| owner == Some(list))?;
+                let section_end = (first + 1..blocks.len())
+                    .find(|&index| matches!(blocks[index], Block::Heading(_)))
+                    .unwrap_or(blocks.len());
+                Some(section_end - 1)
+            })
+        })
+        .collect::<Vec<_>>();
+
+    let mut used = vec![0; lists.len()];
+    let mut output = String::new();
+    let mut needs_gap = false;
+    let mut dropped = false;
+    for (index, block) in blocks.iter().enumerate() {
+        match block {
+            Block::Heading(line) => {
+                if needs_gap && !output.ends_with("\n\n") {
+                    output.push('\n');
+                }
+                push_line(&mut output, line);
+            }
+            Block::Other(line) => {
+                let redundant_blank = dropped
+                    && line.trim().is_empty()
+                    && (output.is_empty() || output.ends_with("\n\n"));
+                if !redundant_blank {
+                    push_line(&mut output, line);
+                }
+            }
+            Block::Entry(lines) => match block_lists[index] {
+                Some(list) if used[list] < lists[list].len() => {
+                    let (fields, notes) = entry_fields(lines);
+                    let replacement = &lists[list][used[list]];
+                    if normalize(fields) == *replacement {
+                        lines.iter().for_each(|line| push_line(&mut output, line));
+                    } else {
+                        push_entry(&mut output, replacement);
+                        notes.iter().for_each(|line| push_line(&mut output, line));
+                    }
+                    used[list] += 1;
+                    dropped = false;
+                }
+                Some(_) => {
+                    dropped = true;
+                    continue;
+                }
+                None => lines.iter().for_each(|line| push_line(&mut output, line)),
+            },
+        }
+        if !matches!(block, Block::Other(_)) {
+            dropped = false;
+        }
+        needs_gap = false;
+
+        for list in 0..lists.len() {
+            if insert_after[list] == Some(index) && used[list] < lists[list].len() {
+                for fields in &lists[list][used[list]..] {
+                    if !output.ends_with("\n\n") {
+                        output.push('\n');
+                    }
+                    push_entry(&mut output, fields);
+                }
+                used[list] = lists[list].len();
+                needs_gap = true;
+            }
+        }
+    }
+
+    for (list, entries) in lists.iter().enumerate() {
+        if insert_after[list].is_some() {
+            continue;
+        }
+        let Some(heading) = missing_heading(list) else {
+            continue;
+        };
+        if !output.ends_with("\n\n") {
+            output.push('\n');
+        }
+        output.push_str(&format!("## {heading}\n"));
+        for fields in entries {
+            output.push('\n');
+            push_entry(&mut output, fields);
+        }
+    }
+    output
+}
+
+fn push_line(output: &mut String, line: &str) {
+    output.push_str(line);
+    output.push('\n');
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::TestDir;
+
+    fn sample_processes() -> Processes {
+        Processes {
+            active: vec![ProcessEntry {
+                pid: Some(4242),
+                command: Some("nohup swarm run --project wind-sonnets --no-tui".to_string()),
+                source_branch: Some("main".to_string()),
+                target_branch: Some("feature/wind-sonnets".to_string()),
+                log_path: Some(
+                    ".juliet/eng/artifacts/wind-sonnets-feature-wind-sonnets-swarm.log".to_string(),
+                ),
+                started_at: Some("2026-02-06T10:00:00Z".to_string()),
+                ..ProcessEntry::default()
+            }],
+            completed: vec![ProcessEntry {
+                pid: Some(4100),
+                target_branch: Some("feature/wind-sonnets-try1".to_string()),
+                results_path: Some("feature/wind-sonnets-try1".to_string()),
+                outcome: Some("no obvious errors in last 50 lines".to_string()),
+                reported_on: Some("2026-02-06T11:00:00Z".to_string()),
+                extra: vec![("follow_up".to_string(), "merge after review".to_string())],
+                ..ProcessEntry::default()
+            }],
+        }
+    }
+
+    #[test]
+    fn session_round_trips_and_reads_prompt_style_files() {
+        let session = parse_session(
+            "# Session\n\n- **started_at_utc**: 2026-02-06T10:00:00Z\n- status: `active`\n- available_engines: [codex, claude]\n- default_engine: codex\n- swarm_engine_property_syntax: --engine <name>\n- notes: cached\n",
+        );
+        assert_eq!(
+            session,
+            Session {
+                started_at_utc: Some("2026-02-06T10:00:00Z".to_string()),
+                status: Some("active".to_string()),
+                available_engines: vec!["codex".to_string(), "claude".to_string()],
+                default_engine: Some("codex".to_string()),
+                swarm_engine_property_syntax: Some("--engine <name>".to_string()),
+                extra: vec![("notes".to_string(), "cached".to_string())],
+            }
+        );
+
+        let rendered = render_session(&session);
+        assert_eq!(
+            rendered,
+            "# Session\n\nstarted_at_utc: 2026-02-06T10:00:00Z\nstatus: active\navailable_engines: codex, claude\ndefault_engine: codex\nswarm_engine_property_syntax: --engine <name>\nnotes: cached\n"
+        );
+        assert_eq!(parse_session(&rendered), session);
+        assert_eq!(parse_session(""), Session::default());
+    }
+
+    #[test]
+    fn needs_round_trip_with_continuation_lines_and_ignore_none() {
+        let needs = parse_needs(
+            "# Needs\n\n- before i start sprints, what email should i save in .swarm-hug/email.txt?\n1. review tasks for wind-sonnets\n   - engine: codex\n(none)\n- (none)\n",
+        );
+        assert_eq!(
+            needs,
+            vec![
+                Need {
+                    text:
+                        "before i start sprints, what email should i save in .swarm-hug/email.txt?"
+                            .to_string(),
+                },
+                Need {
+                    text: "review tasks for wind-sonnets\n- engine: codex".to_string(),
+                },
+            ]
+        );
+
+        assert!(parse_needs("# Needs from operator\n").is_empty());
+        assert!(parse_needs("(none)\n").is_empty());
+    }
+
+    #[test]
+    fn projects_round_trip_and_collect_target_branches() {
+        let projects = parse_projects(
+            "# Projects\n\n- wind-sonnets\n  - PRD path: `.juliet/eng/artifacts/wind.md`\n  - tasks: .swarm-hug/wind-sonnets/tasks.md\n  - source_branch: main\n  - target_branch: feature/wind-a\n  - target_branch: feature/wind-b\n  - next: review tasks\n- name: fire-haiku\n",
+        );
+        assert_eq!(
+            projects,
+            vec![
+                Project {
+                    name: "wind-sonnets".to_string(),
+                    prd_path: Some(".juliet/eng/artifacts/wind.md".to_string()),
+                    tasks_path: Some(".swarm-hug/wind-sonnets/tasks.md".to_string()),
+                    source_branch: Some("main".to_string()),
+                    target_branches: vec![
+                        "feature/wind-a".to_string(),
+                        "feature/wind-b".to_string()
+                    ],
+                    extra: vec![("next".to_string(), "review tasks".to_string())],
+                    ..Project::default()
+                },
+                Project {
+                    name: "fire-haiku".to_string(),
+                    ..Project::default()
+                },
+            ]
+        );
+
+        let rendered = render_projects(&projects);
+        assert_eq!(
+            rendered,
+            "# Projects\n\n- name: wind-sonnets\n  prd_path: .juliet/eng/artifacts/wind.md\n  tasks_path: .swarm-hug/wind-sonnets/tasks.md\n  source_branch: main\n  target_branches: feature/wind-a, feature/wind-b\n  next: review tasks\n\n- name: fire-haiku\n"
+        );
+        assert_eq!(parse_projects(&rendered), projects);
+    }
+
+    #[test]
+    fn processes_round_trip_with_active_and_completed_sections() {
+        let processes = sample_processes();
+        let rendered = render_processes(&processes);
+        assert_eq!(
+            rendered,
+            "# Processes\n\n## Active\n\n- pid: 4242\n  command: nohup swarm run --project wind-sonnets --no-tui\n  source_branch: main\n  target_branch: feature/wind-sonnets\n  log_path: .juliet/eng/artifacts/wind-sonnets-feature-wind-sonnets-swarm.log\n  started_at: 2026-02-06T10:00:00Z\n\n## Completed\n\n- pid: 4100\n  target_branch: feature/wind-sonnets-try1\n  results_path: feature/wind-sonnets-try1\n  outcome: no obvious errors in last 50 lines\n  reported_on: 2026-02-06T11:00:00Z\n  follow_up: merge after review\n"
+        );
+        assert_eq!(parse_processes(&rendered), processes);
+        assert_eq!(
+            render_processes(&Processes::default()),
+            "# Processes\n\n## Active\n\n## Completed\n"
+        );
+    }
+
+    #[test]
+    fn processes_parse_prompt_style_entries_and_legacy_completed_runs() {
+        let processes = parse_processes(
+            "## Active runs\n- PID: 777\n  - Command: `nohup swarm run --no-tui &`\n  - Log path: run.log\n  - Start time: 2026-02-06T10:00:00Z\n\n## Completed\n- pid: not-a-pid\n  results_path: feature/x\n",
+        );
+
+        assert_eq!(processes.active.len(), 1);
+        assert_eq!(processes.active[0].pid, Some(777));
+        assert_eq!(
+            processes.active[0].command.as_deref(),
+            Some("nohup swarm run --no-tui &")
+        );
+        assert_eq!(processes.active[0].log_path.as_deref(), Some("run.log"));
+        assert_eq!(
+            processes.active[0].started_at.as_deref(),
+            Some("2026-02-06T10:00:00Z")
+        );
+
+        let legacy = &processes.completed[0];
+        assert_eq!(legacy.pid, None);
+        assert_eq!(
+            legacy.extra,
+            vec![("pid".to_string(), "not-a-pid".to_string())]
+        );
+        assert_eq!(legacy.reported_on, None);
+    }
+
+    #[test]
+    fn splice_processes_keeps_notes_and_unknown_sections() {
+        let existing = "# Processes\n\nKeep one swarm run per project.\n\n## Active\n\n- pid: 4242\n  command: nohup swarm run --project wind-sonnets --no-tui\n  note from operator: do not stop\n\n- pid: 4300\n  target_branch: feature/fog\n\n## Paused\n\n- pid: 3900\n  target_branch: feature/rain\n\n## Completed\n";
+        let mut processes = parse_processes(existing);
+        let finished = processes.active.remove(1);
+        processes.completed.push(ProcessEntry {
+            outcome: Some("done".to_string()),
+            ..finished
+        });
+
+        let spliced = splice_processes(existing, &processes);
+
+        assert_eq!(
+            spliced,
+            "# Processes\n\nKeep one swarm run per project.\n\n## Active\n\n- pid: 4242\n  command: nohup swarm run --project wind-sonnets --no-tui\n  note from operator: do not stop\n\n## Paused\n\n- pid: 3900\n  target_branch: feature/rain\n\n## Completed\n\n- pid: 4300\n  target_branch: feature/fog\n  outcome: done\n"
+        );
+        assert_eq!(parse_processes(&spliced), processes);
+        assert_eq!(splice_processes(&spliced, &processes), spliced);
+    }
+
+    #[test]
+    fn splice_projects_and_session_rewrite_only_changed_lines() {
+        let existing = "# Projects\n\nOne entry per swarm project.\n\n- wind\n  target_branch: feature/wind\n  Poems only, no prose.\n";
+        let mut projects = parse_projects(existing);
+        projects[0].engine = Some("codex".to_string());
+        projects.push(Project {
+            name: "fog".to_string(),
+            ..Project::default()
+        });
+
+        assert_eq!(
+            splice_projects(existing, &projects),
+            "# Projects\n\nOne entry per swarm project.\n\n- name: wind\n  target_branch: feature/wind\n  engine: codex\n  Poems only, no prose.\n\n- name: fog\n"
+        );
+
+        let existing = "# Session\n\nWritten at startup.\n\n- started_at: 2026-02-06T10:00:00Z\n- status: ready\n";
+        let mut session = parse_session(existing);
+        session.status = Some("busy".to_string());
+        session.default_engine = Some("claude".to_string());
+
+        assert_eq!(
+            splice_session(existing, &session),
+            "# Session\n\nWritten at startup.\n\n- started_at: 2026-02-06T10:00:00Z\n- status: busy\ndefault_engine: claude\n"
+        );
+    }
+
+    #[test]
+    fn load_and_save_use_role_state_paths() {
+        let temp = TestDir::new("load-save");
+        role_state::create_role_state(temp.path(), "eng").expect("role state should be created");
+
+        assert_eq!(
+            load_processes(temp.path(), "eng").expect("empty processes should load"),
+            Processes::default()
+        );
+        save_processes(temp.path(), "eng", &sample_processes()).expect("processes should save");
+        assert_eq!(
+            load_processes(temp.path(), "eng").expect("processes should load"),
+            sample_processes()
+        );
+
+        fs::write(
+            role_state::needs_path(temp.path(), "eng"),
+            "# Needs from operator\n\n- review tasks\n",
+        )
+        .expect("needs should be written");
+        assert_eq!(
+            load_needs(temp.path(), "eng").expect("needs should load"),
+            vec![Need {
+                text: "review tasks".to_string(),
+            }]
+        );
+
+        assert_eq!(
+            load_session(temp.path(), "missing").expect("missing session should load"),
+            Session::default()
+        );
+    }
+}
@@ -0,0 +1,460 @@
+use std::io::{self, BufRead, BufReader, Read};
+use std::process::{Child, Command, ExitStatus, Stdio};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Same code GNU `timeout` uses, so wrappers can tell a timeout from an engine failure.
+pub const TIMEOUT_EXIT_CODE: i32 = 124;
+/// How long a child gets to exit after SIGTERM before it is sent SIGKILL.
+pub const TERM_GRACE_PERIOD: Duration = Duration::from_secs(5);
+const POLL_INTERVAL: Duration = Duration::from_millis(50);
+const DRAIN_AFTER_KILL: Duration = Duration::from_millis(200);
+
+/// Output of a child whose stdout and stderr were piped.
+#[derive(Debug)]
+pub struct CapturedRun {
+    /// `None` when the child was killed for exceeding its timeout.
+    pub status: Option<ExitStatus>,
+    pub stdout: String,
+    pub stderr: String,
+}
+
+/// Parses `90`, `90s`, `5m`, `2h`, or combinations such as `1h30m`. A bare number is seconds;
+/// digits after a unit must have a unit of their own.
+pub fn parse_duration(value: &str) -> Result<Duration, String> {
+    let invalid =
+        || format!("invalid duration `{value}`; use seconds or units like 90s, 5m, 1h30m");
+    if value.trim().is_empty() {
+        return Err(invalid());
+    }
+
+    let mut total_secs: u64 = 0;
+    let mut digits = String::new();
+    for character in value.trim().chars() {
+        if character.is_ascii_digit() {
+            digits.push(character);
+            continue;
+        }
+        let unit_secs = match character {
+            's' => 1,
+            'm' => 60,
+            'h' => 3600,
+            _ => return Err(invalid()),
+        };
+        let amount = digits.parse::<u64>().map_err(|_| invalid())?;
+        total_secs = amount
+            .checked_mul(unit_secs)
+            .and_then(|secs| total_secs.checked_add(secs))
+            .ok_or_else(invalid)?;
+        digits.clear();
+    }
+    if !digits.is_empty() {
+        // A bare number is seconds, but `2m10` is more likely a typo than 130 seconds.
+        if total_secs > 0 {
+            return Err(invalid());
+        }
+        total_secs = digits.parse::<u64>().map_err(|_| invalid())?;
+    }
+
+    if total_secs == 0 {
+        return Err("timeout must be at least one second".to_string());
+    }
+    Ok(Duration::from_secs(total_secs))
+}
+
+pub fn format_duration(duration: Duration) -> String {
+    format!("{}s", duration.as_secs())
+}
+
+/// Runs `command` with a null stdin and piped stdout and stderr, handing each stdout line to
+/// `on_stdout_line` as it arrives. With a `timeout` the child gets its own process group, and
+/// when the timeout elapses the whole group is sent SIGTERM, then SIGKILL after
+/// `TERM_GRACE_PERIOD`, and whatever was captured is returned. Without one the child stays in
+/// Juliet's group so Ctrl-C reaches it too.
+pub fn run_captured<S>(
+    command: &mut Command,
+    timeout: Option<Duration>,
+    mut on_stdout_line: S,
+) -> io::Result<CapturedRun>
+where
+    S: FnMut(&str),
+{
+    if timeout.is_some() {
+        isolate_process_group(command);
+    }
+    let mut child = command
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+    let deadline = timeout.map(|timeout| Instant::now() + timeout);
+
+    let stdout_lines = spawn_line_reader(child.stdout.take().expect("stdout should be piped"));
+    let stderr_lines = spawn_line_reader(child.stderr.take().expect("stderr should be piped"));
+
+    let mut stdout = String::new();
+    let mut stderr = String::new();
+    let mut timed_out = false;
+    loop {
+        stderr.extend(stderr_lines.try_iter());
+        let wait = match deadline {
+            Some(deadline) => match deadline.checked_duration_since(Instant::now()) {
+                Some(remaining) => remaining.min(POLL_INTERVAL),
+                None => {
+                    timed_out = true;
+                    break;
+                }
+            },
+            None => POLL_INTERVAL,
+        };
+        match stdout_lines.recv_timeout(wait) {
+            Ok(line) => {
+                on_stdout_line(line.trim_end_matches(['\r', '\n']));
+                stdout.push_str(&line);
+            }
+            Err(RecvTimeoutError::Timeout) => {}
+            Err(RecvTimeoutError::Disconnected) => break,
+        }
+    }
+
+    let status = if timed_out {
+        terminate(&mut child, true)?;
+        None
+    } else {
+        match wait_until(&mut child, deadline)? {
+            Some(status) => Some(status),
+            None => {
+                terminate(&mut child, true)?;
+                None
+            }
+        }
+    };
+
+    if status.is_some() {
+        stderr.extend(stderr_lines.iter());
+    } else {
+        // Stragglers that escaped the group may keep the pipes open; do not wait on them.
+        drain_for(&stdout_lines, &mut stdout, DRAIN_AFTER_KILL);
+        drain_for(&stderr_lines, &mut stderr, DRAIN_AFTER_KILL);
+    }
+
+    Ok(CapturedRun {
+        status,
+        stdout,
+        stderr,
+    })
+}
+
+/// Runs `command` with inherited stdio, returning `None` if it was killed for exceeding
+/// `timeout`. With a `timeout` the child gets its own process group, which is handed the
+/// terminal while it runs so interactive engines keep working. When the timeout elapses the
+/// whole group is sent SIGTERM, then SIGKILL after `TERM_GRACE_PERIOD`, and Juliet takes the
+/// terminal back. Without one the child simply stays in Juliet's group.
+pub fn run_interactive(
+    command: &mut Command,
+    timeout: Option<Duration>,
+) -> io::Result<Option<ExitStatus>> {
+    let Some(timeout) = timeout else {
+        return command.spawn()?.wait().map(Some);
+    };
+
+    isolate_process_group(command);
+    let handed_off = foreground::hand_terminal_to_child(command);
+    let deadline = Instant::now() + timeout;
+    let result =
+        command
+            .spawn()
+            .and_then(|mut child| match wait_until(&mut child, Some(deadline))? {
+                Some(status) => Ok(Some(status)),
+                None => terminate(&mut child, true).map(|()| None),
+            });
+    if handed_off {
+        foreground::reclaim_terminal();
+    }
+    result
+}
+
+fn spawn_line_reader<R>(pipe: R) -> Receiver<String>
+where
+    R: Read + Send + 'static,
+{
+    let (sender, receiver) = mpsc::channel();
+    thread::spawn(move || {
+        let mut reader = BufReader::new(pipe);
+        let mut line = Vec::new();
+        while let Ok(read) = reader.read_until(b'\n', &mut line) {
+            if read == 0
+                || sender
+                    .send(String::from_utf8_lossy(&line).into_owned())
+                    .is_err()
+            {
+                break;
+            }
+            line.clear();
+        }
+    });
+    receiver
+}
+
+fn drain_for(lines: &Receiver<String>, output: &mut String, limit: Duration) {
+    let deadline = Instant::now() + limit;
+    while let Some(remaining) = deadline.checked_duration_since(Instant::now()) {
+        match lines.recv_timeout(remaining) {
+            Ok(line) => output.push_str(&line),
+            Err(_) => break,
+        }
+    }
+}
+
+/// Waits for the child to exit, returning `None` once `deadline` passes.
+fn wait_until(child: &mut Child, deadline: Option<Instant>) -> io::Result<Option<ExitStatus>> {
+    let Some(deadline) = deadline else {
+        return child.wait().map(Some);
+    };
+    loop {
+        if let Some(status) = child.try_wait()? {
+            return Ok(Some(status));
+        }
+        let Some(remaining) = deadline.checked_duration_since(Instant::now()) else {
+            return Ok(None);
+        };
+        thread::sleep(remaining.min(POLL_INTERVAL));
+    }
+}
+
+fn terminate(child: &mut Child, whole_group: bool) -> io::Result<()> {
+    send_signal(child, "TERM", whole_group);
+    if wait_until(child, Some(Instant::now() + TERM_GRACE_PERIOD))?.is_none() {
+        send_signal(child, "KILL", whole_group);
+        let _ = child.kill();
+    }
+    child.wait()?;
+    Ok(())
+}
+
+#[cfg(unix)]
+fn isolate_process_group(command: &mut Command) {
+    use std::os::unix::process::CommandExt;
+    command.process_group(0);
+}
+
+#[cfg(not(unix))]
+fn isolate_process_group(_command: &mut Command) {}
+
+/// Moves the terminal between Juliet and a timed interactive child, which runs in its own
+/// process group. `std` has no wrapper for `tcsetpgrp`, so the libc calls are declared here.
+#[cfg(unix)]
+mod foreground {
+    use std::io;
+    use std::os::raw::c_int;
+    use std::os::unix::process::CommandExt;
+    use std::process::Command;
+
+    type Pid = i32;
+
+    const STDIN_FD: c_int = 0;
+    // SIGTTOU is 22 on Linux, macOS, and the BSDs.
+    const SIGTTOU: c_int = 22;
+    const SIG_DFL: usize = 0;
+    const SIG_IGN: usize = 1;
+
+    extern "C" {
+        fn getpgrp() -> Pid;
+        fn setpgid(pid: Pid, pgid: Pid) -> c_int;
+        fn tcgetpgrp(fd: c_int) -> Pid;
+        fn tcsetpgrp(fd: c_int, pgrp: Pid) -> c_int;
+        fn signal(signum: c_int, handler: usize) -> usize;
+    }
+
+    /// Arranges for the child to make its group the terminal's foreground group before it
+    /// execs. Only a foreground Juliet does this; a backgrounded one would take the terminal
+    /// from the shell. Returns whether the terminal was handed off.
+    pub fn hand_terminal_to_child(command: &mut Command) -> bool {
+        // SAFETY: getpgrp and tcgetpgrp only read process state.
+        let in_foreground = unsafe { tcgetpgrp(STDIN_FD) == getpgrp() };
+        if !in_foreground {
+            return false;
+        }
+        // SAFETY: the hook runs in the forked child and only makes async-signal-safe calls.
+        // SIGTTOU is ignored around tcsetpgrp because the child's new group is not yet in
+        // the foreground, then restored so the engine starts with default signal handling.
+        unsafe {
+            command.pre_exec(|| {
+                if setpgid(0, 0) != 0 {
+                    return Err(io::Error::last_os_error());
+                }
+                signal(SIGTTOU, SIG_IGN);
+                tcsetpgrp(STDIN_FD, getpgrp());
+                signal(SIGTTOU, SIG_DFL);
+                Ok(())
+            });
+        }
+        true
+    }
+
+    /// Makes Juliet's group the terminal's foreground group again once the child is gone.
+    pub fn reclaim_terminal() {
+        // SAFETY: Juliet is in a background group at this point, so SIGTTOU is ignored while
+        // it takes the terminal back and its previous disposition restored afterwards.
+        unsafe {
+            let previous = signal(SIGTTOU, SIG_IGN);
+            tcsetpgrp(STDIN_FD, getpgrp());
+            signal(SIGTTOU, previous);
+        }
+    }
+}
+
+#[cfg(not(unix))]
+mod foreground {
+    use std::process::Command;
+
+    pub fn hand_terminal_to_child(_command: &mut Command) -> bool {
+        false
+    }
+
+    pub fn reclaim_terminal() {}
+}
+
+#[cfg(unix)]
+fn send_signal(child: &mut Child, signal: &str, whole_group: bool) {
+    // A negative pid addresses the process group led by the child.
+    let target = if whole_group {
+        format!("-{}", child.id())
+    } else {
+        child.id().to_string()
+    };
+    let _ = Command::new("kill")
+        .arg("-s")
+        .arg(signal)
+        .arg("--")
+        .arg(target)
+        .stderr(Stdio::null())
+        .status();
+}
+
+#[cfg(not(unix))]
+fn send_signal(child: &mut Child, _signal: &str, _whole_group: bool) {
+    let _ = child.kill();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(target_os = "linux")]
+    use crate::process_check;
+    #[cfg(target_os = "linux")]
+    use crate::test_support::TestDir;
+    #[cfg(target_os = "linux")]
+    use std::fs;
+
+    #[test]
+    fn parse_duration_accepts_seconds_and_units() {
+        assert_eq!(parse_duration("90"), Ok(Duration::from_secs(90)));
+        assert_eq!(parse_duration("45s"), Ok(Duration::from_secs(45)));
+        assert_eq!(parse_duration("5m"), Ok(Duration::from_secs(300)));
+        assert_eq!(parse_duration("1h30m"), Ok(Duration::from_secs(5400)));
+
+        assert!(parse_duration("").is_err());
+        assert!(parse_duration("m").is_err());
+        assert!(parse_duration("5d").is_err());
+        assert!(parse_duration("2m10").is_err());
+        assert!(parse_duration("-5s").is_err());
+        assert_eq!(
+            parse_duration("0s"),
+            Err("timeout must be at least one second".to_string())
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn run_captured_collects_output_and_exit_status() {
+        let mut lines = Vec::new();
+        let run = run_captured(
+            Command::new("sh")
+                .arg("-c")
+                .arg("echo one; echo oops >&2; echo two; exit 3"),
+            Some(Duration::from_secs(30)),
+            |line| lines.push(line.to_string()),
+        )
+        .expect("sh should run");
+
+        assert_eq!(run.status.and_then(|status| status.code()), Some(3));
+        assert_eq!(run.stdout, "one\ntwo\n");
+        assert_eq!(run.stderr, "oops\n");
+        assert_eq!(lines, vec!["one", "two"]);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn run_captured_kills_process_group_on_timeout_and_keeps_partial_output() {
+        let started = Instant::now();
+        let run = run_captured(
+            Command::new("sh")
+                .arg("-c")
+                .arg("echo started; echo warming up >&2; sleep 30 & wait"),
+            Some(Duration::from_millis(300)),
+            |_| {},
+        )
+        .expect("sh should run");
+
+        assert!(run.status.is_none());
+        assert!(started.elapsed() < Duration::from_secs(10));
+        assert_eq!(run.stdout, "started\n");
+        assert_eq!(run.stderr, "warming up\n");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn run_captured_closes_stdin() {
+        let run = run_captured(
+            Command::new("sh").arg("-c").arg("cat; echo done"),
+            None,
+            |_| {},
+        )
+        .expect("sh should run");
+
+        assert_eq!(run.status.and_then(|status| status.code()), Some(0));
+        assert_eq!(run.stdout, "done\n");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn run_interactive_reports_timeout() {
+        let status = run_interactive(
+            Command::new("sleep").arg("30"),
+            Some(Duration::from_millis(200)),
+        )
+        .expect("sleep should run");
+        assert!(status.is_none());
+
+        let status = run_interactive(&mut Command::new("true"), None).expect("true should run");
+        assert!(status.is_some_and(|status| status.success()));
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn run_interactive_timeout_stops_processes_the_engine_started() {
+        let temp = TestDir::new("interactive-group");
+        let pid_path = temp.path().join("sleep.pid");
+        let status = run_interactive(
+            Command::new("sh")
+                .arg("-c")
+                .arg(format!("sleep 30 & echo $! > {}; wait", pid_path.display())),
+            Some(Duration::from_millis(300)),
+        )
+        .expect("sh should run");
+        assert!(status.is_none());
+
+        let pid = fs::read_to_string(&pid_path)
+            .expect("sleep pid should be written")
+            .trim()
+            .parse::<u32>()
+            .expect("sleep pid should parse");
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while process_check::pid_is_running(pid) && Instant::now() < deadline {
+            thread::sleep(POLL_INTERVAL);
+        }
+        assert!(!process_check::pid_is_running(pid));
+    }
+}
@@ -0,0 +1,424 @@
+use crate::doctor;
+use crate::role_name;
+use crate::role_state;
+use crate::state_files::{self, ProcessEntry, Project, Session};
+use crate::timestamp;
+use std::fs::{self, File};
+use std::io;
+use std::path::Path;
+use std::process::{Command, Stdio};
+
+const DEFAULT_ENGINE_SYNTAX: &str = "--engine <ENGINE>";
+
+/// What `juliet run` was asked to launch, before preflight fills in the defaults.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RunRequest {
+    pub swarm_project: String,
+    pub variations: u32,
+    pub sprints: u32,
+    pub engine: Option<String>,
+    pub source_branch: Option<String>,
+    pub target_branch: Option<String>,
+}
+
+/// A checked set of `swarm run` invocations, one per target branch.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RunPlan {
+    pub swarm_project: String,
+    pub source_branch: String,
+    pub target_branches: Vec<String>,
+    pub sprints: u32,
+    pub engine: String,
+    pub engine_args: Vec<String>,
+}
+
+/// Resolves defaults and runs the launch preflight: a valid `.swarm-hug/email.txt`, the
+/// checkout on the source branch, and the project's `tasks.md` committed on that branch.
+/// `git` runs one git command in the project root and returns its stdout.
+pub fn plan_runs<G>(
+    project_root: &Path,
+    request: &RunRequest,
+    session: &Session,
+    mut git: G,
+) -> Result<RunPlan, String>
+where
+    G: FnMut(&[&str]) -> Result<String, String>,
+{
+    let project = &request.swarm_project;
+    if !role_name::is_valid_role_name(project) {
+        return Err(format!(
+            "Invalid swarm project name: {project}. Use lowercase letters, numbers, and hyphens."
+        ));
+    }
+    let engine = request
+        .engine
+        .clone()
+        .or_else(|| session.default_engine.clone())
+        .ok_or_else(|| {
+            "No engine given and session.md has no default_engine. Pass --engine, or run juliet doctor first.".to_string()
+        })?;
+
+    doctor::check_email(project_root).map_err(|reason| {
+        format!(
+            "{} {reason}. Swarm needs it before sprints can start.",
+            doctor::EMAIL_FILE
+        )
+    })?;
+
+    let current_branch = git(&["branch", "--show-current"])?.trim().to_string();
+    let source_branch = request
+        .source_branch
+        .clone()
+        .unwrap_or_else(|| current_branch.clone());
+    if source_branch != current_branch {
+        return Err(format!(
+            "The checked-out branch is '{current_branch}', not the source branch '{source_branch}'. Switch branches or pass --source-branch {current_branch}."
+        ));
+    }
+
+    let tasks_path = format!(".swarm-hug/{project}/tasks.md");
+    let listed = git(&[
+        "ls-tree",
+        "-r",
+        "--name-only",
+        &source_branch,
+        "--",
+        &tasks_path,
+    ])?;
+    if listed.trim().is_empty() {
+        return Err(format!(
+            "{tasks_path} is not committed on {source_branch}. Commit .swarm-hug/{project}/ to {source_branch} before launching."
+        ));
+    }
+
+    Ok(RunPlan {
+        swarm_project: project.clone(),
+        target_branches: target_branches(
+            project,
+            request.variations,
+            request.target_branch.as_deref(),
+        ),
+        source_branch,
+        sprints: request.sprints,
+        engine_args: swarm_engine_args(session.swarm_engine_property_syntax.as_deref(), &engine),
+        engine,
+    })
+}
+
+/// `feature/<project>` for a single run, `-try1` through `-tryN` suffixes for variations.
+/// An explicit target replaces `feature/<project>` as the base name.
+pub fn target_branches(project: &str, variations: u32, target: Option<&str>) -> Vec<String> {
+    let base = target
+        .map(str::to_string)
+        .unwrap_or_else(|| format!("feature/{project}"));
+    if variations <= 1 {
+        return vec![base];
+    }
+    (1..=variations)
+        .map(|variation| format!("{base}-try{variation}"))
+        .collect()
+}
+
+/// Arguments selecting `engine`, following the syntax `juliet doctor` captured from
+/// `swarm --help` (`--engine <ENGINE>`, `--engine=<name>`, or `engine=<name>`).
+pub fn swarm_engine_args(syntax: Option<&str>, engine: &str) -> Vec<String> {
+    let syntax = syntax.unwrap_or(DEFAULT_ENGINE_SYNTAX);
+    if let Some((prefix, _)) = syntax.split_once('=') {
+        return vec![format!("{prefix}={engine}")];
+    }
+    let flag = syntax.split_whitespace().next().unwrap_or("--engine");
+    vec![flag.to_string(), engine.to_string()]
+}
+
+pub fn swarm_args(plan: &RunPlan, target_branch: &str) -> Vec<String> {
+    let mut args = [
+        "run",
+        "--project",
+        &plan.swarm_project,
+        "--source-branch",
+        &plan.source_branch,
+        "--target-branch",
+        target_branch,
+        "--max-sprints",
+        &plan.sprints.to_string(),
+        "--no-tui",
+    ]
+    .map(str::to_string)
+    .to_vec();
+    args.extend(plan.engine_args.iter().cloned());
+    args
+}
+
+/// `.juliet/<role>/artifacts/<project>-<target-branch-sanitized>-swarm.log`, relative to the
+/// project root.
+pub fn log_path(role_name: &str, project: &str, target_branch: &str) -> String {
+    format!(
+        ".juliet/{role_name}/artifacts/{project}-{}-swarm.log",
+        target_branch.replace('/', "-")
+    )
+}
+
+/// Starts one `swarm run` per target branch through `spawn`, which receives the swarm
+/// arguments and the log path and returns the PID. Each run is appended to the Active
+/// section of `processes.md` as soon as it starts, so a later failure keeps earlier entries.
+pub fn launch_runs<S>(
+    project_root: &Path,
+    role_name: &str,
+    plan: &RunPlan,
+    mut spawn: S,
+) -> Result<Vec<ProcessEntry>, String>
+where
+    S: FnMut(&[String], &Path) -> io::Result<u32>,
+{
+    let artifacts_dir = role_state::artifacts_dir(project_root, role_name);
+    fs::create_dir_all(&artifacts_dir)
+        .map_err(|err| format!("failed to create {}: {err}", artifacts_dir.display()))?;
+
+    let mut launched = Vec::new();
+    for target_branch in &plan.target_branches {
+        let args = swarm_args(plan, target_branch);
+        let log_path = log_path(role_name, &plan.swarm_project, target_branch);
+        let pid = spawn(&args, &project_root.join(&log_path))
+            .map_err(|err| format!("failed to start swarm run for {target_branch}: {err}"))?;
+
+        let entry = ProcessEntry {
+            pid: Some(pid),
+            command: Some(format!(
+                "nohup swarm {} > {log_path} 2>&1 &",
+                args.join(" ")
+            )),
+            source_branch: Some(plan.source_branch.clone()),
+            target_branch: Some(target_branch.clone()),
+            log_path: Some(log_path),
+            started_at: Some(timestamp::now_utc()),
+            ..ProcessEntry::default()
+        };
+        let mut processes = state_files::load_processes(project_root, role_name)
+            .map_err(|err| format!("failed to read processes for role {role_name}: {err}"))?;
+        processes.active.push(entry.clone());
+        state_files::save_processes(project_root, role_name, &processes)
+            .map_err(|err| format!("failed to write processes for role {role_name}: {err}"))?;
+        launched.push(entry);
+    }
+    Ok(launched)
+}
+
+/// Records the launched branches and engine on the project's `projects.md` entry, adding the
+/// entry if the role does not track the project yet.
+pub fn record_project(project_root: &Path, role_name: &str, plan: &RunPlan) -> io::Result<()> {
+    let mut projects = state_files::load_projects(project_root, role_name)?;
+    let index = match projects
+        .iter()
+        .position(|project| project.name == plan.swarm_project)
+    {
+        Some(index) => index,
+        None => {
+            projects.push(Project {
+                name: plan.swarm_project.clone(),
+                tasks_path: Some(format!(".swarm-hug/{}/tasks.md", plan.swarm_project)),
+                specs_path: Some(format!(".swarm-hug/{}/specs.md", plan.swarm_project)),
+                ..Project::default()
+            });
+            projects.len() - 1
+        }
+    };
+
+    let project = &mut projects[index];
+    project.source_branch = Some(plan.source_branch.clone());
+    project.target_branches = plan.target_branches.clone();
+    project.engine = Some(plan.engine.clone());
+    state_files::save_projects(project_root, role_name, &projects)
+}
+
+/// Spawns `swarm` detached from Juliet: in its own process group, with stdin closed and both
+/// output streams appended to `log_path`.
+pub fn spawn_swarm(project_root: &Path, args: &[String], log_path: &Path) -> io::Result<u32> {
+    let log = File::options().create(true).append(true).open(log_path)?;
+    let mut command = Command::new("swarm");
+    command
+        .args(args)
+        .current_dir(project_root)
+        .stdin(Stdio::null())
+        .stdout(log.try_clone()?)
+        .stderr(log);
+    detach(&mut command);
+    Ok(command.spawn()?.id())
+}
+
+#[cfg(unix)]
+fn detach(command: &mut Command) {
+    use std::os::unix::process::CommandExt;
+    command.process_group(0);
+}
+
+#[cfg(not(unix))]
+fn detach(_command: &mut Command) {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::TestDir;
+
+    fn request(variations: u32) -> RunRequest {
+        RunRequest {
+            swarm_project: "wind".to_string(),
+            variations,
+            sprints: 3,
+            engine: None,
+            source_branch: None,
+            target_branch: None,
+        }
+    }
+
+    fn session() -> Session {
+        Session {
+            default_engine: Some("codex".to_string()),
+            swarm_engine_property_syntax: Some("--engine <ENGINE>".to_string()),
+            ..Session::default()
+        }
+    }
+
+    fn git_on(
+        branch: &'static str,
+        tasks: &'static str,
+    ) -> impl FnMut(&[&str]) -> Result<String, String> {
+        move |args| match args[0] {
+            "branch" => Ok(format!("{branch}\n")),
+            "ls-tree" => Ok(tasks.to_string()),
+            other => Err(format!("unexpected git {other}")),
+        }
+    }
+
+    #[test]
+    fn target_branches_and_engine_args_follow_swarm_conventions() {
+        assert_eq!(target_branches("wind", 1, None), vec!["feature/wind"]);
+        assert_eq!(
+            target_branches("wind", 2, Some("feature/gusts")),
+            vec!["feature/gusts-try1", "feature/gusts-try2"]
+        );
+        assert_eq!(
+            swarm_engine_args(Some("--engine <ENGINE>"), "codex"),
+            vec!["--engine", "codex"]
+        );
+        assert_eq!(
+            swarm_engine_args(Some("--engine=<claude|codex>"), "claude"),
+            vec!["--engine=claude"]
+        );
+        assert_eq!(
+            swarm_engine_args(Some("engine=<name>"), "codex"),
+            vec!["engine=codex"]
+        );
+        assert_eq!(swarm_engine_args(None, "codex"), vec!["--engine", "codex"]);
+    }
+
+    #[test]
+    fn plan_runs_checks_email_branch_and_committed_tasks() {
+        let temp = TestDir::new("plan");
+        let root = temp.path();
+        let session = session();
+
+        assert_eq!(
+            plan_runs(root, &request(1), &session, git_on("main", "x")),
+            Err(
+                ".swarm-hug/email.txt is missing. Swarm needs it before sprints can start."
+                    .to_string()
+            )
+        );
+        fs::create_dir_all(root.join(".swarm-hug")).expect("swarm dir should be created");
+        fs::write(root.join(".swarm-hug/email.txt"), "dev@example.com\n")
+            .expect("email should be written");
+
+        let mut other_source = request(1);
+        other_source.source_branch = Some("develop".to_string());
+        assert_eq!(
+            plan_runs(root, &other_source, &session, git_on("main", "x")),
+            Err("The checked-out branch is 'main', not the source branch 'develop'. Switch branches or pass --source-branch main.".to_string())
+        );
+        assert_eq!(
+            plan_runs(root, &request(1), &session, git_on("main", "")),
+            Err(".swarm-hug/wind/tasks.md is not committed on main. Commit .swarm-hug/wind/ to main before launching.".to_string())
+        );
+        assert_eq!(
+            plan_runs(
+                root,
+                &request(1),
+                &Session::default(),
+                git_on("main", "x")
+            ),
+            Err("No engine given and session.md has no default_engine. Pass --engine, or run juliet doctor first.".to_string())
+        );
+
+        assert_eq!(
+            plan_runs(
+                root,
+                &request(2),
+                &session,
+                git_on("main", ".swarm-hug/wind/tasks.md\n")
+            ),
+            Ok(RunPlan {
+                swarm_project: "wind".to_string(),
+                source_branch: "main".to_string(),
+                target_branches: vec![
+                    "feature/wind-try1".to_string(),
+                    "feature/wind-try2".to_string()
+                ],
+                sprints: 3,
+                engine: "codex".to_string(),
+                engine_args: vec!["--engine".to_string(), "codex".to_string()],
+            })
+        );
+    }
+
+    #[test]
+    fn launch_runs_records_each_run_as_active() {
+        let temp = TestDir::new("launch");
+        let root = temp.path();
+        role_state::create_role_state(root, "eng").expect("role state should be created");
+        let plan = RunPlan {
+            swarm_project: "wind".to_string(),
+            source_branch: "main".to_string(),
+            target_branches: vec![
+                "feature/wind-try1".to_string(),
+                "feature/wind-try2".to_string(),
+            ],
+            sprints: 2,
+            engine: "codex".to_string(),
+            engine_args: vec!["--engine".to_string(), "codex".to_string()],
+        };
+
+        let mut spawned = Vec::new();
+        let launched = launch_runs(root, "eng", &plan, |args, log_path| {
+            spawned.push((args.to_vec(), log_path.to_path_buf()));
+            Ok(4100 + spawned.len() as u32)
+        })
+        .expect("runs should launch");
+        record_project(root, "eng", &plan).expect("project should be recorded");
+
+        assert_eq!(launched.len(), 2);
+        assert_eq!(
+            spawned[1],
+            (
+                "run --project wind --source-branch main --target-branch feature/wind-try2 --max-sprints 2 --no-tui --engine codex"
+                    .split(' ')
+                    .map(str::to_string)
+                    .collect(),
+                root.join(".juliet/eng/artifacts/wind-feature-wind-try2-swarm.log")
+            )
+        );
+        let processes = state_files::load_processes(root, "eng").expect("processes should load");
+        assert_eq!(processes.active, launched);
+        assert_eq!(processes.active[0].pid, Some(4101));
+        assert_eq!(
+            processes.active[0].command.as_deref(),
+            Some("nohup swarm run --project wind --source-branch main --target-branch feature/wind-try1 --max-sprints 2 --no-tui --engine codex > .juliet/eng/artifacts/wind-feature-wind-try1-swarm.log 2>&1 &")
+        );
+
+        let projects = state_files::load_projects(root, "eng").expect("projects should load");
+        assert_eq!(projects.len(), 1);
+        assert_eq!(
+            projects[0].target_branches,
+            vec!["feature/wind-try1", "feature/wind-try2"]
+        );
+        assert_eq!(projects[0].engine.as_deref(), Some("codex"));
+    }
+}
@@ -0,0 +1,316 @@
+use crate::role_name;
+use crate::role_state;
+use crate::state_files::{self, Project};
+use crate::swarm_tasks::{self, TaskReport};
+use crate::table;
+use serde_json::Value;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+const SPRINT_HISTORY_FILE: &str = "sprint-history.json";
+const TEAM_STATE_FILE: &str = "team-state.json";
+
+/// What swarm and Juliet know about one project under `.swarm-hug/`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SprintSummary {
+    pub project: String,
+    /// `total_sprints` from `sprint-history.json`.
+    pub total_sprints: Option<u64>,
+    /// `feature_branch` from `team-state.json`.
+    pub feature_branch: Option<String>,
+    /// Roles whose `projects.md` lists the project.
+    pub roles: Vec<String>,
+    pub tasks: Option<TaskReport>,
+    /// Files that could not be read or parsed. Their values show as missing.
+    pub errors: Vec<String>,
+}
+
+/// Loads every role's `projects.md` so summaries can name the role that launched a project.
+pub fn load_role_projects(project_root: &Path) -> io::Result<Vec<(String, Vec<Project>)>> {
+    role_state::discover_configured_roles(project_root)?
+        .into_iter()
+        .map(|role| {
+            let projects = state_files::load_projects(project_root, &role.name)?;
+            Ok((role.name, projects))
+        })
+        .collect()
+}
+
+/// Swarm projects under `.swarm-hug/`, sorted. Dot directories such as `.shared` are skipped.
+pub fn list_swarm_projects(project_root: &Path) -> io::Result<Vec<String>> {
+    let entries = match fs::read_dir(swarm_tasks::swarm_root(project_root)) {
+        Ok(entries) => entries,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => return Err(err),
+    };
+
+    let mut projects = Vec::new();
+    for entry in entries {
+        let entry = entry?;
+        if !entry.file_type()?.is_dir() {
+            continue;
+        }
+        if let Ok(name) = entry.file_name().into_string() {
+            if role_name::is_valid_role_name(&name) {
+                projects.push(name);
+            }
+        }
+    }
+    projects.sort();
+    Ok(projects)
+}
+
+/// Summary for one swarm project. A file that cannot be read or parsed is recorded in
+/// `errors` instead of failing, so one broken project does not hide the others.
+pub fn load_summary(
+    project_root: &Path,
+    swarm_project: &str,
+    role_projects: &[(String, Vec<Project>)],
+) -> SprintSummary {
+    let project_dir = swarm_tasks::swarm_project_dir(project_root, swarm_project);
+    let mut errors = Vec::new();
+    let mut read = |path: &Path| match read_json(project_root, path) {
+        Ok(value) => value,
+        Err(err) => {
+            errors.push(err);
+            None
+        }
+    };
+    let sprint_history = read(&project_dir.join(SPRINT_HISTORY_FILE));
+    let team_state = read(&project_dir.join(TEAM_STATE_FILE));
+
+    let tasks_path = swarm_tasks::tasks_path(project_root, swarm_project);
+    let tasks = match fs::read_to_string(&tasks_path) {
+        Ok(contents) => Some(TaskReport {
+            project: swarm_project.to_string(),
+            tasks: swarm_tasks::parse_tasks(&contents),
+            tasks_path,
+        }),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => None,
+        Err(err) => {
+            errors.push(format!(
+                "failed to read {}: {err}",
+                role_state::display_relative(project_root, &tasks_path)
+            ));
+            None
+        }
+    };
+
+    SprintSummary {
+        project: swarm_project.to_string(),
+        total_sprints: sprint_history
+            .as_ref()
+            .and_then(|history| history.get("total_sprints"))
+            .and_then(Value::as_u64),
+        feature_branch: team_state
+            .as_ref()
+            .and_then(|state| state.get("feature_branch"))
+            .and_then(Value::as_str)
+            .map(str::to_string),
+        roles: role_projects
+            .iter()
+            .filter(|(_, projects)| projects.iter().any(|project| project.name == swarm_project))
+            .map(|(role, _)| role.clone())
+            .collect(),
+        tasks,
+        errors,
+    }
+}
+
+/// Summaries for one swarm project, or for every project under `.swarm-hug/` when `None`.
+pub fn collect_summaries(
+    project_root: &Path,
+    swarm_project: Option<&str>,
+) -> Result<Vec<SprintSummary>, String> {
+    let projects = match swarm_project {
+        Some(project) => {
+            if !role_name::is_valid_role_name(project) {
+                return Err(format!(
+                    "Invalid swarm project name: {project}. Use lowercase letters, numbers, and hyphens."
+                ));
+            }
+            if !swarm_tasks::swarm_project_dir(project_root, project).is_dir() {
+                return Err(format!("Swarm project not found: .swarm-hug/{project}"));
+            }
+            vec![project.to_string()]
+        }
+        None => list_swarm_projects(project_root)
+            .map_err(|err| format!("failed to list swarm projects: {err}"))?,
+    };
+    let role_projects = load_role_projects(project_root)
+        .map_err(|err| format!("failed to read role projects: {err}"))?;
+
+    Ok(projects
+        .iter()
+        .map(|project| load_summary(project_root, project, &role_projects))
+        .collect())
+}
+
+fn read_json(project_root: &Path, path: &Path) -> Result<Option<Value>, String> {
+    let contents = match fs::read_to_string(path) {
+        Ok(contents) => contents,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(None),
+        Err(err) => {
+            return Err(format!(
+                "failed to read {}: {err}",
+                role_state::display_relative(project_root, path)
+            ))
+        }
+    };
+    serde_json::from_str(&contents).map(Some).map_err(|err| {
+        format!(
+            "failed to parse {}: {err}",
+            role_state::display_relative(project_root, path)
+        )
+    })
+}
+
+pub fn format_sprints(summaries: &[SprintSummary]) -> String {
+    if summaries.is_empty() {
+        return "no swarm projects found under .swarm-hug/\n".to_string();
+    }
+
+    let headers = ["PROJECT", "SPRINTS", "BRANCH", "ROLE", "TASKS", "POINTS"];
+    let rows = summaries
+        .iter()
+        .map(|summary| {
+            [
+                summary.project.clone(),
+                summary
+                    .total_sprints
+                    .map(|sprints| sprints.to_string())
+                    .unwrap_or_else(|| "-".to_string()),
+                summary
+                    .feature_branch
+                    .clone()
+                    .unwrap_or_else(|| "-".to_string()),
+                if summary.roles.is_empty() {
+                    "-".to_string()
+                } else {
+                    summary.roles.join(",")
+                },
+                summary
+                    .tasks
+                    .as_ref()
+                    .map(|tasks| format!("{}/{}", tasks.done_tasks(), tasks.tasks.len()))
+                    .unwrap_or_else(|| "-".to_string()),
+                summary
+                    .tasks
+                    .as_ref()
+                    .map(|tasks| format!("{}/{}", tasks.done_points(), tasks.total_points()))
+                    .unwrap_or_else(|| "-".to_string()),
+            ]
+        })
+        .collect::<Vec<_>>();
+
+    let table = table::format_table(headers, &rows);
+    let mut lines = table.lines();
+    let mut output = format!("{}\n", lines.next().unwrap_or_default());
+    for (line, summary) in lines.zip(summaries) {
+        output.push_str(&format!("{line}\n"));
+        for error in &summary.errors {
+            output.push_str(&format!("  error: {error}\n"));
+        }
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::TestDir;
+
+    fn seed_swarm_project(root: &Path, project: &str, files: &[(&str, &str)]) {
+        let dir = swarm_tasks::swarm_project_dir(root, project);
+        fs::create_dir_all(&dir).expect("swarm project dir should be created");
+        for (name, contents) in files {
+            fs::write(dir.join(name), contents).expect("swarm file should be written");
+        }
+    }
+
+    #[test]
+    fn collect_summaries_joins_swarm_state_with_role_projects() {
+        let temp = TestDir::new("collect");
+        seed_swarm_project(
+            temp.path(),
+            "wind",
+            &[
+                (
+                    "sprint-history.json",
+                    r#"{"team": "wind", "total_sprints": 3}"#,
+                ),
+                (
+                    "team-state.json",
+                    r#"{"team": "wind", "feature_branch": "wind-sprint-3-abc123"}"#,
+                ),
+                (
+                    "tasks.md",
+                    "## Poems\n- [x] (#1) Draft [5 pts] (A)\n- [ ] (#2) Edit [3 pts] (B)\n",
+                ),
+            ],
+        );
+        seed_swarm_project(temp.path(), "fog", &[]);
+        fs::create_dir_all(temp.path().join(".swarm-hug/.shared/worktrees"))
+            .expect("shared dir should be created");
+        role_state::create_role_state(temp.path(), "eng").expect("role state should be created");
+        fs::write(
+            role_state::projects_path(temp.path(), "eng"),
+            "- name: wind\n  target_branch: feature/wind\n",
+        )
+        .expect("projects should be written");
+
+        let summaries = collect_summaries(temp.path(), None).expect("summaries should load");
+
+        assert_eq!(
+            summaries
+                .iter()
+                .map(|summary| summary.project.as_str())
+                .collect::<Vec<_>>(),
+            vec!["fog", "wind"]
+        );
+        assert_eq!(summaries[1].total_sprints, Some(3));
+        assert_eq!(
+            summaries[1].feature_branch.as_deref(),
+            Some("wind-sprint-3-abc123")
+        );
+        assert_eq!(summaries[1].roles, vec!["eng"]);
+        assert_eq!(
+            format_sprints(&summaries),
+            "PROJECT  SPRINTS  BRANCH                ROLE  TASKS  POINTS\n\
+fog      -        -                     -     -      -\n\
+wind     3        wind-sprint-3-abc123  eng   1/2    5/8\n"
+        );
+    }
+
+    #[test]
+    fn collect_summaries_validates_project_and_reports_bad_json_on_its_row() {
+        let temp = TestDir::new("errors");
+        seed_swarm_project(
+            temp.path(),
+            "wind",
+            &[
+                ("sprint-history.json", r#"{"total_sprints": 2}"#),
+                ("team-state.json", "{not json"),
+            ],
+        );
+        seed_swarm_project(
+            temp.path(),
+            "zephyr",
+            &[("sprint-history.json", r#"{"total_sprints": 4}"#)],
+        );
+
+        assert_eq!(
+            collect_summaries(temp.path(), Some("fog")).expect_err("must fail"),
+            "Swarm project not found: .swarm-hug/fog"
+        );
+        let summaries = collect_summaries(temp.path(), None).expect("summaries should load");
+        assert_eq!(
+            format_sprints(&summaries),
+            "PROJECT  SPRINTS  BRANCH  ROLE  TASKS  POINTS\n\
+wind     2        -       -     -      -\n  \
+error: failed to parse .swarm-hug/wind/team-state.json: key must be a string at line 1 column 2\n\
+zephyr   4        -       -     -      -\n"
+        );
+    }
+}
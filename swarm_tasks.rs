@@ -0,0 +1,669 @@
+use serde_json::{json, Value};
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::role_name;
+
+const SWARM_DIR: &str = ".swarm-hug";
+const TASKS_FILE: &str = "tasks.md";
+const BLOCKED_BY_PREFIX: &str = "(blocked by ";
+/// Lowercased starts of task titles nobody has written yet.
+const PLACEHOLDER_PREFIXES: [&str; 5] = ["todo:", "tbd:", "fixme:", "lorem ipsum", "<!--"];
+/// Lowercased scaffold lines that make up a whole placeholder title, ignoring trailing
+/// numbering and punctuation.
+const PLACEHOLDER_TITLES: [&str; 7] = [
+    "todo",
+    "tbd",
+    "placeholder",
+    "add your tasks",
+    "add your tasks here",
+    "add tasks here",
+    "example task",
+];
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum TaskStatus {
+    Open,
+    Done,
+}
+
+/// One `- [x] (#N) title [5 pts] (blocked by #M) (A)` line of a swarm `tasks.md`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Task {
+    pub id: Option<u32>,
+    pub title: String,
+    pub section: Option<String>,
+    pub status: TaskStatus,
+    pub points: Option<u32>,
+    pub blocked_by: Vec<u32>,
+    pub assignee: Option<char>,
+    /// 1-based line in `tasks.md`.
+    pub line: usize,
+}
+
+impl Task {
+    pub fn is_done(&self) -> bool {
+        self.status == TaskStatus::Done
+    }
+}
+
+/// One `juliet tasks check` finding. `line` is 1-based; `None` means the whole file.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TaskProblem {
+    pub line: Option<usize>,
+    pub message: String,
+}
+
+/// Per-agent totals for `juliet tasks`.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct AssigneeLoad {
+    pub tasks: usize,
+    pub done: usize,
+    pub points: u32,
+    pub done_points: u32,
+}
+
+/// Progress computed from a swarm project's `tasks.md`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TaskReport {
+    pub project: String,
+    pub tasks_path: PathBuf,
+    pub tasks: Vec<Task>,
+}
+
+impl TaskReport {
+    pub fn done_tasks(&self) -> usize {
+        self.tasks.iter().filter(|task| task.is_done()).count()
+    }
+
+    pub fn total_points(&self) -> u32 {
+        self.tasks.iter().filter_map(|task| task.points).sum()
+    }
+
+    pub fn done_points(&self) -> u32 {
+        self.tasks
+            .iter()
+            .filter(|task| task.is_done())
+            .filter_map(|task| task.points)
+            .sum()
+    }
+
+    pub fn remaining_points(&self) -> u32 {
+        self.total_points() - self.done_points()
+    }
+
+    pub fn unestimated(&self) -> usize {
+        self.tasks
+            .iter()
+            .filter(|task| task.points.is_none())
+            .count()
+    }
+
+    /// True once there are tasks and every one is done, i.e. no more sprints are needed.
+    pub fn is_complete(&self) -> bool {
+        !self.tasks.is_empty() && self.tasks.iter().all(Task::is_done)
+    }
+
+    /// Unfinished tasks paired with the blockers that are not done yet. A blocker that does
+    /// not name a task in the file counts as not done.
+    pub fn blocked(&self) -> Vec<(&Task, Vec<u32>)> {
+        self.tasks
+            .iter()
+            .filter(|task| !task.is_done())
+            .filter_map(|task| {
+                let waiting_on = task
+                    .blocked_by
+                    .iter()
+                    .copied()
+                    .filter(|blocker| !self.is_done(*blocker))
+                    .collect::<Vec<_>>();
+                (!waiting_on.is_empty()).then_some((task, waiting_on))
+            })
+            .collect()
+    }
+
+    pub fn assignees(&self) -> BTreeMap<char, AssigneeLoad> {
+        let mut assignees = BTreeMap::<char, AssigneeLoad>::new();
+        for task in &self.tasks {
+            let Some(assignee) = task.assignee else {
+                continue;
+            };
+            let load = assignees.entry(assignee).or_default();
+            let points = task.points.unwrap_or(0);
+            load.tasks += 1;
+            load.points += points;
+            if task.is_done() {
+                load.done += 1;
+                load.done_points += points;
+            }
+        }
+        assignees
+    }
+
+    /// For every task that blocks others, the tasks waiting on it, in file order.
+    pub fn dependents(&self) -> BTreeMap<u32, Vec<u32>> {
+        let mut dependents = BTreeMap::<u32, Vec<u32>>::new();
+        for task in &self.tasks {
+            let Some(id) = task.id else {
+                continue;
+            };
+            for blocker in &task.blocked_by {
+                let waiting = dependents.entry(*blocker).or_default();
+                if !waiting.contains(&id) {
+                    waiting.push(id);
+                }
+            }
+        }
+        dependents
+    }
+
+    fn is_done(&self, id: u32) -> bool {
+        // Swarm sometimes reuses an id for a follow-up; the id is done once every copy is.
+        let mut copies = self.tasks.iter().filter(|task| task.id == Some(id));
+        copies.clone().next().is_some() && copies.all(Task::is_done)
+    }
+}
+
+pub fn swarm_root(project_root: &Path) -> PathBuf {
+    project_root.join(SWARM_DIR)
+}
+
+pub fn swarm_project_dir(project_root: &Path, swarm_project: &str) -> PathBuf {
+    swarm_root(project_root).join(swarm_project)
+}
+
+pub fn tasks_path(project_root: &Path, swarm_project: &str) -> PathBuf {
+    swarm_project_dir(project_root, swarm_project).join(TASKS_FILE)
+}
+
+pub fn load_report(project_root: &Path, swarm_project: &str) -> Result<TaskReport, String> {
+    let (tasks_path, contents) = read_tasks(project_root, swarm_project)?;
+    Ok(TaskReport {
+        project: swarm_project.to_string(),
+        tasks: parse_tasks(&contents),
+        tasks_path,
+    })
+}
+
+/// Runs `check_tasks` on a swarm project's `tasks.md`, returning its path with the problems.
+pub fn load_check(
+    project_root: &Path,
+    swarm_project: &str,
+) -> Result<(PathBuf, Vec<TaskProblem>), String> {
+    let (tasks_path, contents) = read_tasks(project_root, swarm_project)?;
+    Ok((tasks_path, check_tasks(&contents)))
+}
+
+fn read_tasks(project_root: &Path, swarm_project: &str) -> Result<(PathBuf, String), String> {
+    if !role_name::is_valid_role_name(swarm_project) {
+        return Err(format!(
+            "Invalid swarm project name: {swarm_project}. Use lowercase letters, numbers, and hyphens."
+        ));
+    }
+    let tasks_path = tasks_path(project_root, swarm_project);
+    match fs::read_to_string(&tasks_path) {
+        Ok(contents) => Ok((tasks_path, contents)),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => Err(format!(
+            "Swarm project '{swarm_project}' has no tasks.md. Run `swarm project init {swarm_project}` first."
+        )),
+        Err(err) => Err(format!("failed to read {}: {err}", tasks_path.display())),
+    }
+}
+
+/// Flags a `tasks.md` that is not ready for `swarm run`: the scaffold `swarm project init`
+/// sometimes leaves behind, placeholder task titles, empty sections, and unfinished tasks
+/// without a point estimate.
+pub fn check_tasks(contents: &str) -> Vec<TaskProblem> {
+    let tasks = parse_tasks(contents);
+    if tasks.is_empty() {
+        return vec![TaskProblem {
+            line: None,
+            message: "no tasks found; this looks like the `swarm project init` scaffold"
+                .to_string(),
+        }];
+    }
+
+    let mut problems = Vec::new();
+    let mut open_section: Option<(usize, &str)> = None;
+    for (index, line) in contents.lines().enumerate() {
+        let trimmed = line.trim();
+        if let Some(heading) = trimmed.strip_prefix("## ") {
+            if let Some((line, heading)) = open_section {
+                problems.push(empty_section(line, heading));
+            }
+            open_section = Some((index + 1, heading.trim()));
+        } else if parse_task_line(trimmed).is_some() {
+            open_section = None;
+        }
+    }
+    if let Some((line, heading)) = open_section {
+        problems.push(empty_section(line, heading));
+    }
+
+    for task in &tasks {
+        if is_placeholder_title(&task.title) {
+            problems.push(TaskProblem {
+                line: Some(task.line),
+                message: format!("placeholder task: `{}`", task.title),
+            });
+        } else if task.points.is_none() && !task.is_done() {
+            problems.push(TaskProblem {
+                line: Some(task.line),
+                message: format!("{} has no point estimate", task_label(task)),
+            });
+        }
+    }
+    problems.sort_by_key(|problem| problem.line);
+    problems
+}
+
+fn is_placeholder_title(title: &str) -> bool {
+    let title = title.to_lowercase();
+    let bare = title.trim_end_matches(|c: char| {
+        c.is_whitespace() || c.is_ascii_digit() || c.is_ascii_punctuation()
+    });
+    bare.is_empty()
+        || PLACEHOLDER_PREFIXES
+            .iter()
+            .any(|prefix| title.starts_with(prefix))
+        || PLACEHOLDER_TITLES.contains(&bare)
+}
+
+fn empty_section(line: usize, heading: &str) -> TaskProblem {
+    TaskProblem {
+        line: Some(line),
+        message: format!("section `{heading}` has no tasks"),
+    }
+}
+
+pub fn format_problems(path: &str, problems: &[TaskProblem]) -> String {
+    problems
+        .iter()
+        .map(|problem| match problem.line {
+            Some(line) => format!("{path}:{line}: {}\n", problem.message),
+            None => format!("{path}: {}\n", problem.message),
+        })
+        .collect()
+}
+
+/// Parses every checkbox list item; other lines only contribute `##` section headings.
+pub fn parse_tasks(contents: &str) -> Vec<Task> {
+    let mut section = None;
+    let mut tasks = Vec::new();
+    for (index, line) in contents.lines().enumerate() {
+        let trimmed = line.trim();
+        if let Some(heading) = trimmed.strip_prefix("## ") {
+            section = Some(heading.trim().to_string());
+            continue;
+        }
+        if let Some(task) = parse_task_line(trimmed) {
+            tasks.push(Task {
+                section: section.clone(),
+                line: index + 1,
+                ..task
+            });
+        }
+    }
+    tasks
+}
+
+fn parse_task_line(line: &str) -> Option<Task> {
+    let rest = line.strip_prefix("- [")?;
+    let (marker, rest) = rest.split_once(']')?;
+    let status = match marker {
+        " " | "" => TaskStatus::Open,
+        "x" | "X" => TaskStatus::Done,
+        _ => return None,
+    };
+
+    let mut rest = rest.trim();
+    let mut id = None;
+    if let Some(after) = rest.strip_prefix("(#") {
+        if let Some((number, after)) = after.split_once(')') {
+            if let Ok(number) = number.parse() {
+                id = Some(number);
+                rest = after.trim_start();
+            }
+        }
+    }
+
+    // Trailing annotations may come in any order; peel them off until none match.
+    let (mut points, mut blocked_by, mut assignee) = (None, Vec::new(), None);
+    loop {
+        if let Some((before, letter)) = trailing_group(rest, '(', ')')
+            .filter(|(_, inner)| inner.len() == 1 && inner.chars().all(|c| c.is_ascii_uppercase()))
+        {
+            assignee = letter.chars().next();
+            rest = before;
+        } else if let Some((before, ids)) = trailing_blocked_by(rest) {
+            blocked_by = ids;
+            rest = before;
+        } else if let Some((before, estimate)) =
+            trailing_group(rest, '[', ']').and_then(|(before, inner)| {
+                let number = inner
+                    .strip_suffix(" pts")
+                    .or_else(|| inner.strip_suffix(" pt"))?;
+                Some((before, number.trim().parse().ok()?))
+            })
+        {
+            points = Some(estimate);
+            rest = before;
+        } else {
+            break;
+        }
+    }
+
+    Some(Task {
+        id,
+        title: rest.to_string(),
+        section: None,
+        status,
+        points,
+        blocked_by,
+        assignee,
+        line: 0,
+    })
+}
+
+/// Splits `text` ending in `<open>inner<close>` into the text before it and `inner`.
+fn trailing_group(text: &str, open: char, close: char) -> Option<(&str, &str)> {
+    let without_close = text.strip_suffix(close)?;
+    let start = without_close.rfind(open)?;
+    Some((
+        text[..start].trim_end(),
+        &without_close[start + open.len_utf8()..],
+    ))
+}
+
+fn trailing_blocked_by(text: &str) -> Option<(&str, Vec<u32>)> {
+    let without_close = text.strip_suffix(')')?;
+    let start = without_close.rfind(BLOCKED_BY_PREFIX)?;
+    let ids = without_close[start + BLOCKED_BY_PREFIX.len()..]
+        .split(',')
+        .map(|id| id.trim().strip_prefix('#')?.parse().ok())
+        .collect::<Option<Vec<_>>>()?;
+    Some((text[..start].trim_end(), ids))
+}
+
+pub fn format_report(report: &TaskReport, display_path: &str) -> String {
+    let mut output = format!("project: {} ({display_path})\n", report.project);
+    output.push_str(&format!(
+        "tasks: {}/{} done\n",
+        report.done_tasks(),
+        report.tasks.len()
+    ));
+    output.push_str(&format!(
+        "points: {}/{} done, {} remaining",
+        report.done_points(),
+        report.total_points(),
+        report.remaining_points()
+    ));
+    match report.unestimated() {
+        0 => output.push('\n'),
+        count => output.push_str(&format!(" ({count} unestimated)\n")),
+    }
+    output.push_str(if report.is_complete() {
+        "status: complete\n"
+    } else {
+        "status: more sprints remain\n"
+    });
+
+    let blocked = report.blocked();
+    if !blocked.is_empty() {
+        output.push_str("blocked:\n");
+        for (task, waiting_on) in blocked {
+            output.push_str(&format!(
+                "  {} waiting on {}\n",
+                task_label(task),
+                id_list(&waiting_on)
+            ));
+        }
+    }
+
+    let assignees = report.assignees();
+    if !assignees.is_empty() {
+        output.push_str("assignees:\n");
+        for (letter, load) in assignees {
+            output.push_str(&format!(
+                "  {letter}  {}/{} tasks, {}/{} pts done\n",
+                load.done, load.tasks, load.done_points, load.points
+            ));
+        }
+    }
+
+    let dependents = report.dependents();
+    if !dependents.is_empty() {
+        output.push_str("dependencies:\n");
+        for (blocker, waiting) in dependents {
+            output.push_str(&format!("  #{blocker} -> {}\n", id_list(&waiting)));
+        }
+    }
+    output
+}
+
+pub fn format_report_json(report: &TaskReport, display_path: &str) -> String {
+    let tasks = report
+        .tasks
+        .iter()
+        .map(|task| {
+            let status = match task.status {
+                TaskStatus::Open => "open",
+                TaskStatus::Done => "done",
+            };
+            json!({
+                "id": task.id,
+                "title": task.title,
+                "section": task.section,
+                "status": status,
+                "points": task.points,
+                "blocked_by": task.blocked_by,
+                "assignee": task.assignee.map(String::from),
+                "line": task.line,
+            })
+        })
+        .collect::<Vec<_>>();
+    let blocked = report
+        .blocked()
+        .into_iter()
+        .map(|(task, waiting_on)| json!({ "id": task.id, "waiting_on": waiting_on }))
+        .collect::<Vec<_>>();
+    let assignees = report
+        .assignees()
+        .into_iter()
+        .map(|(letter, load)| {
+            (
+                letter.to_string(),
+                json!({
+                    "tasks": load.tasks,
+                    "done": load.done,
+                    "points": load.points,
+                    "done_points": load.done_points,
+                }),
+            )
+        })
+        .collect::<serde_json::Map<_, _>>();
+    let dependencies = report
+        .dependents()
+        .into_iter()
+        .map(|(blocker, waiting)| (blocker.to_string(), json!(waiting)))
+        .collect::<serde_json::Map<_, _>>();
+
+    json!({
+        "project": report.project,
+        "tasks_path": display_path,
+        "summary": {
+            "tasks": report.tasks.len(),
+            "done_tasks": report.done_tasks(),
+            "points": report.total_points(),
+            "done_points": report.done_points(),
+            "remaining_points": report.remaining_points(),
+            "unestimated_tasks": report.unestimated(),
+            "complete": report.is_complete(),
+        },
+        "tasks": tasks,
+        "blocked": blocked,
+        "assignees": Value::Object(assignees),
+        "dependencies": Value::Object(dependencies),
+    })
+    .to_string()
+}
+
+fn task_label(task: &Task) -> String {
+    match task.id {
+        Some(id) => format!("#{id} {}", task.title),
+        None => task.title.clone(),
+    }
+}
+
+fn id_list(ids: &[u32]) -> String {
+    ids.iter()
+        .map(|id| format!("#{id}"))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TASKS: &str = "# Tasks\n\n## Build\n\n- [x] (#1) Lay out the CLI [5 pts] (A)\n- [ ] (#2) Parse `tasks.md` [3 pts] (blocked by #1) (B)\n- [ ] (#3) Report progress [5 pts] (blocked by #1, #2) (A)\n\n## Follow-up tasks (from sprint review)\n- [ ] (#4) Fix the (odd) title (blocked by #9) (C)\nNot a task line.\n";
+
+    fn report() -> TaskReport {
+        TaskReport {
+            project: "wind".to_string(),
+            tasks_path: PathBuf::from(".swarm-hug/wind/tasks.md"),
+            tasks: parse_tasks(TASKS),
+        }
+    }
+
+    #[test]
+    fn parse_tasks_reads_markers_ids_points_blockers_and_assignees() {
+        let tasks = parse_tasks(TASKS);
+
+        assert_eq!(tasks.len(), 4);
+        assert_eq!(
+            tasks[1],
+            Task {
+                id: Some(2),
+                title: "Parse `tasks.md`".to_string(),
+                section: Some("Build".to_string()),
+                status: TaskStatus::Open,
+                points: Some(3),
+                blocked_by: vec![1],
+                assignee: Some('B'),
+                line: 6,
+            }
+        );
+        assert_eq!(tasks[2].blocked_by, vec![1, 2]);
+        assert_eq!(tasks[3].title, "Fix the (odd) title");
+        assert_eq!(tasks[3].points, None);
+        assert_eq!(
+            tasks[3].section.as_deref(),
+            Some("Follow-up tasks (from sprint review)")
+        );
+        assert_eq!(
+            parse_tasks(
+                "- [ ] Draft three poems [5 pts]\n- [X] (#2) Review (blocked by #1)\n- [A] (#3) Not a swarm marker\n"
+            )
+            .iter()
+            .map(|task| (task.id, task.title.as_str(), task.points, task.is_done()))
+            .collect::<Vec<_>>(),
+            vec![
+                (None, "Draft three poems", Some(5), false),
+                (Some(2), "Review", None, true)
+            ]
+        );
+    }
+
+    #[test]
+    fn format_report_summarizes_points_blockers_assignees_and_graph() {
+        assert_eq!(
+            format_report(&report(), ".swarm-hug/wind/tasks.md"),
+            "project: wind (.swarm-hug/wind/tasks.md)\n\
+tasks: 1/4 done\n\
+points: 5/13 done, 8 remaining (1 unestimated)\n\
+status: more sprints remain\n\
+blocked:\n  #3 Report progress waiting on #2\n  #4 Fix the (odd) title waiting on #9\n\
+assignees:\n  A  1/2 tasks, 5/10 pts done\n  B  0/1 tasks, 0/3 pts done\n  C  0/1 tasks, 0/0 pts done\n\
+dependencies:\n  #1 -> #2, #3\n  #2 -> #3\n  #9 -> #4\n"
+        );
+    }
+
+    #[test]
+    fn is_complete_requires_every_task_done_and_at_least_one_task() {
+        let mut report = report();
+        assert!(!report.is_complete());
+        report.tasks = parse_tasks("## Build\n- [x] (#1) Draft [5 pts]\n- [X] (#2) Edit [3 pts]\n");
+        assert!(report.is_complete());
+        report.tasks = parse_tasks("# Tasks\n\n## Backlog\n");
+        assert!(!report.is_complete());
+    }
+
+    #[test]
+    fn check_tasks_flags_scaffold_placeholders_empty_sections_and_missing_points() {
+        assert_eq!(
+            check_tasks("# Tasks\n\n## Backlog\n"),
+            vec![TaskProblem {
+                line: None,
+                message: "no tasks found; this looks like the `swarm project init` scaffold"
+                    .to_string(),
+            }]
+        );
+        assert_eq!(
+            check_tasks(
+                "# Tasks\n\n## Setup\n\n## Build\n- [ ] (#1) TODO: add tasks from the PRD [5 pts]\n- [ ] (#2) Write the parser\n- [x] (#3) Old follow-up\n\n## Review\n"
+            ),
+            vec![
+                empty_section(3, "Setup"),
+                TaskProblem {
+                    line: Some(6),
+                    message: "placeholder task: `TODO: add tasks from the PRD`".to_string(),
+                },
+                TaskProblem {
+                    line: Some(7),
+                    message: "#2 Write the parser has no point estimate".to_string(),
+                },
+                empty_section(10, "Review"),
+            ]
+        );
+        assert!(check_tasks(TASKS)
+            .iter()
+            .all(|problem| problem.line == Some(10)));
+    }
+
+    #[test]
+    fn check_tasks_only_flags_whole_placeholder_titles_and_prefixes() {
+        assert_eq!(
+            check_tasks(
+                "## Build\n- [ ] (#1) TBD [1 pts]\n- [ ] (#2) Example task 2. [1 pts]\n- [ ] (#3) FIXME: split this up [1 pts]\n"
+            )
+            .into_iter()
+            .map(|problem| problem.line)
+            .collect::<Vec<_>>(),
+            vec![Some(2), Some(3), Some(4)]
+        );
+        assert_eq!(
+            check_tasks(
+                "## Build\n- [ ] (#1) Replace the placeholder logo [2 pts]\n- [ ] (#2) Remove example task fixtures [1 pts]\n- [ ] (#3) Todo list sorting [3 pts]\n- [ ] (#4) Document TBD fields in the API [1 pts]\n"
+            ),
+            Vec::new()
+        );
+    }
+
+    #[test]
+    fn format_report_json_is_machine_readable() {
+        let value: Value =
+            serde_json::from_str(&format_report_json(&report(), ".swarm-hug/wind/tasks.md"))
+                .expect("output should be JSON");
+
+        assert_eq!(value["summary"]["remaining_points"], 8);
+        assert_eq!(value["summary"]["complete"], false);
+        assert_eq!(value["tasks"][0]["status"], "done");
+        assert_eq!(value["tasks"][1]["status"], "open");
+        assert_eq!(value["blocked"][0], json!({ "id": 3, "waiting_on": [2] }));
+        assert_eq!(value["assignees"]["A"]["points"], 10);
+        assert_eq!(value["dependencies"]["1"], json!([2, 3]));
+    }
+}
@@ -0,0 +1,48 @@
+/// Renders `rows` under `headers` as left-aligned columns separated by two spaces, one line
+/// per row with trailing spaces trimmed.
+pub fn format_table<const N: usize>(headers: [&str; N], rows: &[[String; N]]) -> String {
+    let mut widths = headers.map(str::len);
+    for row in rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.len());
+        }
+    }
+
+    let mut output = String::new();
+    push_row(&mut output, &headers, &widths);
+    for row in rows {
+        push_row(&mut output, row, &widths);
+    }
+    output
+}
+
+fn push_row<S: AsRef<str>>(output: &mut String, cells: &[S], widths: &[usize]) {
+    let line = cells
+        .iter()
+        .zip(widths)
+        .map(|(cell, width)| format!("{:<width$}", cell.as_ref()))
+        .collect::<Vec<_>>()
+        .join("  ");
+    output.push_str(line.trim_end());
+    output.push('\n');
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn format_table_pads_columns_to_the_widest_cell_and_trims_line_ends() {
+        assert_eq!(
+            format_table(
+                ["ROLE", "ACTIVE", "NOTE"],
+                &[
+                    ["platform".to_string(), "2".to_string(), String::new()],
+                    ["qa".to_string(), "10".to_string(), "paused".to_string()],
+                ]
+            ),
+            "ROLE      ACTIVE  NOTE\nplatform  2\nqa        10      paused\n"
+        );
+        assert_eq!(format_table(["ROLE"], &[]), "ROLE\n");
+    }
+}
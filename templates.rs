@@ -0,0 +1,442 @@
+use crate::role_name;
+use crate::role_state;
+use crate::table;
+use std::env;
+use std::ffi::OsStr;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use toml::{Table, Value};
+
+pub const DEFAULT_TEMPLATE: &str = "orchestrator";
+pub const DEFAULT_SEED: &str = include_str!("prompts/juliet.md");
+const TEMPLATE_EXTENSION: &str = "md";
+const TEMPLATE_KEY: &str = "template";
+const FROM_KEY: &str = "from";
+const SOURCE_PROMPT_FILE: &str = "prompt.md";
+const SOURCE_SEED_FILE: &str = "prompt-seed.md";
+
+/// Templates bundled into the binary as `(name, description, seed)`.
+const BUILTIN_TEMPLATES: [(&str, &str, &str); 4] = [
+    (
+        DEFAULT_TEMPLATE,
+        "Plans swarm projects, launches sprints, and reports their results",
+        DEFAULT_SEED,
+    ),
+    (
+        "reviewer",
+        "Reviews branches and reports findings by severity",
+        include_str!("prompts/reviewer.md"),
+    ),
+    (
+        "release-manager",
+        "Plans versions and changelogs, and tags releases once approved",
+        include_str!("prompts/release-manager.md"),
+    ),
+    (
+        "triage",
+        "Sorts incoming requests into prioritized, owned work",
+        include_str!("prompts/triage.md"),
+    ),
+];
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum TemplateSource {
+    Builtin,
+    User(PathBuf),
+}
+
+/// A seed that `juliet init --template` builds a role prompt from.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PromptTemplate {
+    pub name: String,
+    pub description: String,
+    pub source: TemplateSource,
+    pub seed: String,
+}
+
+/// `~/.config/juliet/templates/`, where each `<name>.md` is a user template.
+pub fn user_templates_dir() -> Option<PathBuf> {
+    env::var_os("HOME")
+        .filter(|home| !home.is_empty())
+        .map(|home| PathBuf::from(home).join(".config/juliet/templates"))
+}
+
+/// Built-in templates in their fixed order, then user templates sorted by name. User
+/// templates cannot replace a built-in one: files named like a built-in template, or not
+/// named like a role, are skipped.
+pub fn list_templates(user_dir: Option<&Path>) -> Result<Vec<PromptTemplate>, String> {
+    let mut templates = BUILTIN_TEMPLATES
+        .iter()
+        .map(|(name, description, seed)| PromptTemplate {
+            name: name.to_string(),
+            description: description.to_string(),
+            source: TemplateSource::Builtin,
+            seed: seed.to_string(),
+        })
+        .collect::<Vec<_>>();
+    if let Some(user_dir) = user_dir {
+        templates.extend(user_templates(user_dir)?);
+    }
+    Ok(templates)
+}
+
+pub fn find_template(name: &str, user_dir: Option<&Path>) -> Result<PromptTemplate, String> {
+    let templates = list_templates(user_dir)?;
+    let available = templates
+        .iter()
+        .map(|template| template.name.as_str())
+        .collect::<Vec<_>>()
+        .join(", ");
+    templates
+        .into_iter()
+        .find(|template| template.name == name)
+        .ok_or_else(|| format!("Unknown template '{name}'. Available templates: {available}"))
+}
+
+pub fn format_template_list(templates: &[PromptTemplate]) -> String {
+    let rows = templates
+        .iter()
+        .map(|template| {
+            let source = match &template.source {
+                TemplateSource::Builtin => "built-in".to_string(),
+                TemplateSource::User(path) => path.display().to_string(),
+            };
+            [template.name.clone(), source, template.description.clone()]
+        })
+        .collect::<Vec<_>>();
+    table::format_table(["TEMPLATE", "SOURCE", "DESCRIPTION"], &rows)
+}
+
+/// What `.juliet/<role>/template.toml` says a role was created from: a named template, or a
+/// prompt copied with `init --from`.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct TemplateRecord {
+    pub template: Option<String>,
+    pub from: Option<PathBuf>,
+}
+
+/// Prompt, and seed if there is one, read from an `init --from` path.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PromptSource {
+    pub prompt: String,
+    pub seed: Option<String>,
+}
+
+/// The role's template record, or `None` for roles created from the default seed.
+pub fn read_template_record(
+    project_root: &Path,
+    role_name: &str,
+) -> Result<Option<TemplateRecord>, String> {
+    let record_path = role_state::template_record_path(project_root, role_name);
+    let contents = match fs::read_to_string(&record_path) {
+        Ok(contents) => contents,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(None),
+        Err(err) => return Err(format!("failed to read {}: {err}", record_path.display())),
+    };
+    let invalid = |detail: String| {
+        format!(
+            "invalid template record at {}: {detail}",
+            record_path.display()
+        )
+    };
+    let table = contents
+        .parse::<Table>()
+        .map_err(|err| invalid(err.to_string()))?;
+
+    let string_field = |key: &str| match table.get(key) {
+        None => Ok(None),
+        Some(Value::String(value)) => Ok(Some(value.clone())),
+        Some(_) => Err(invalid(format!("{key} must be a string"))),
+    };
+    let record = TemplateRecord {
+        template: string_field(TEMPLATE_KEY)?,
+        from: string_field(FROM_KEY)?.map(PathBuf::from),
+    };
+    if record.template.is_none() && record.from.is_none() {
+        return Err(invalid(format!("expected {TEMPLATE_KEY} or {FROM_KEY}")));
+    }
+    Ok(Some(record))
+}
+
+pub fn write_template_record(
+    project_root: &Path,
+    role_name: &str,
+    record: &TemplateRecord,
+) -> Result<(), String> {
+    let mut table = Table::new();
+    if let Some(template) = &record.template {
+        table.insert(TEMPLATE_KEY.to_string(), Value::String(template.clone()));
+    }
+    if let Some(from) = &record.from {
+        table.insert(
+            FROM_KEY.to_string(),
+            Value::String(from.display().to_string()),
+        );
+    }
+    let record_path = role_state::template_record_path(project_root, role_name);
+    fs::write(&record_path, table.to_string())
+        .map_err(|err| format!("failed to write {}: {err}", record_path.display()))
+}
+
+/// Current seed for `role_name`: the text of its recorded template, or the default seed when
+/// none is recorded. Roles copied with `init --from`, and roles whose recorded template is not
+/// available on this machine, use their committed `prompt-seed.md`.
+pub fn role_seed(
+    project_root: &Path,
+    role_name: &str,
+    user_dir: Option<&Path>,
+) -> Result<String, String> {
+    let Some(record) = read_template_record(project_root, role_name)? else {
+        return Ok(DEFAULT_SEED.to_string());
+    };
+    let missing = match &record.template {
+        Some(template_name) => match find_template(template_name, user_dir) {
+            Ok(template) => return Ok(template.seed),
+            Err(err) => Some(format!(
+                "Role '{role_name}' uses template '{template_name}': {err}"
+            )),
+        },
+        None => None,
+    };
+
+    let seed_path = role_state::prompt_seed_path(project_root, role_name);
+    match fs::read_to_string(&seed_path) {
+        Ok(seed) => Ok(seed),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => match missing {
+            Some(missing) => Err(missing),
+            None => Ok(DEFAULT_SEED.to_string()),
+        },
+        Err(err) => Err(format!("failed to read {}: {err}", seed_path.display())),
+    }
+}
+
+/// Reads a prompt file, or a role directory holding `prompt.md` and optionally
+/// `prompt-seed.md`.
+pub fn read_prompt_source(path: &Path) -> Result<PromptSource, String> {
+    let read = |path: &Path| {
+        fs::read_to_string(path).map_err(|err| format!("failed to read {}: {err}", path.display()))
+    };
+    if !path.is_dir() {
+        return Ok(PromptSource {
+            prompt: read(path)?,
+            seed: None,
+        });
+    }
+
+    let seed_path = path.join(SOURCE_SEED_FILE);
+    Ok(PromptSource {
+        prompt: read(&path.join(SOURCE_PROMPT_FILE))?,
+        seed: seed_path.is_file().then(|| read(&seed_path)).transpose()?,
+    })
+}
+
+/// Checks that `prompt` opens with a `# <name>` heading and points it at `role_name`. A
+/// `# {{role}}` heading is kept as written.
+pub fn adopt_heading(prompt: &str, role_name: &str, source: &str) -> Result<String, String> {
+    let Some(heading) = prompt.lines().find(|line| !line.trim().is_empty()) else {
+        return Err(format!("{source} is empty; expected a `# <role>` heading"));
+    };
+    let Some(title) = heading.strip_prefix("# ").map(str::trim) else {
+        return Err(format!(
+            "{source} must start with a `# <role>` heading, found `{heading}`"
+        ));
+    };
+    if title.is_empty() {
+        return Err(format!("{source} has an empty `# ` heading"));
+    }
+    if title == "{{role}}" || title == role_name {
+        return Ok(prompt.to_string());
+    }
+    Ok(prompt.replacen(heading, &format!("# {role_name}"), 1))
+}
+
+fn user_templates(user_dir: &Path) -> Result<Vec<PromptTemplate>, String> {
+    let entries = match fs::read_dir(user_dir) {
+        Ok(entries) => entries,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => return Err(format!("failed to read {}: {err}", user_dir.display())),
+    };
+
+    let mut templates = Vec::new();
+    for entry in entries {
+        let path = entry
+            .map_err(|err| format!("failed to read {}: {err}", user_dir.display()))?
+            .path();
+        if path.extension().and_then(OsStr::to_str) != Some(TEMPLATE_EXTENSION) {
+            continue;
+        }
+        let Some(name) = path.file_stem().and_then(OsStr::to_str) else {
+            continue;
+        };
+        if !role_name::is_valid_role_name(name)
+            || BUILTIN_TEMPLATES
+                .iter()
+                .any(|(builtin, _, _)| *builtin == name)
+        {
+            continue;
+        }
+
+        let seed = fs::read_to_string(&path)
+            .map_err(|err| format!("failed to read {}: {err}", path.display()))?;
+        templates.push(PromptTemplate {
+            name: name.to_string(),
+            description: describe(&seed),
+            source: TemplateSource::User(path.clone()),
+            seed,
+        });
+    }
+    templates.sort_by(|left, right| left.name.cmp(&right.name));
+    Ok(templates)
+}
+
+/// First non-empty line of a user template, without heading marks.
+fn describe(seed: &str) -> String {
+    seed.lines()
+        .map(|line| line.trim_start_matches('#').trim())
+        .find(|line| !line.is_empty())
+        .unwrap_or("-")
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::TestDir;
+
+    fn names(templates: &[PromptTemplate]) -> Vec<&str> {
+        templates
+            .iter()
+            .map(|template| template.name.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn list_templates_appends_valid_user_templates_after_builtins() {
+        let temp = TestDir::new("list");
+        let user_dir = temp.path().join("templates");
+        fs::create_dir_all(&user_dir).expect("user dir should be created");
+        fs::write(
+            user_dir.join("oncall.md"),
+            "\n# On-call rotation\n\nPage people.\n",
+        )
+        .expect("template should be written");
+        fs::write(user_dir.join("docs-writer.md"), "Writes docs.\n")
+            .expect("template should be written");
+        fs::write(user_dir.join("reviewer.md"), "Shadowing attempt.\n")
+            .expect("template should be written");
+        fs::write(user_dir.join("Bad_Name.md"), "Invalid.\n").expect("template should be written");
+        fs::write(user_dir.join("notes.txt"), "Not a template.\n").expect("file should be written");
+
+        let templates = list_templates(Some(&user_dir)).expect("templates should list");
+
+        assert_eq!(
+            names(&templates),
+            vec![
+                "orchestrator",
+                "reviewer",
+                "release-manager",
+                "triage",
+                "docs-writer",
+                "oncall"
+            ]
+        );
+        assert_eq!(templates[1].seed, include_str!("prompts/reviewer.md"));
+        assert_eq!(templates[5].description, "On-call rotation");
+        assert_eq!(
+            templates[5].source,
+            TemplateSource::User(user_dir.join("oncall.md"))
+        );
+        assert_eq!(
+            names(&list_templates(Some(&temp.path().join("missing"))).unwrap()),
+            vec!["orchestrator", "reviewer", "release-manager", "triage"]
+        );
+    }
+
+    #[test]
+    fn find_template_lists_available_names_when_unknown() {
+        assert_eq!(
+            find_template("triage", None).map(|template| template.seed),
+            Ok(include_str!("prompts/triage.md").to_string())
+        );
+        assert_eq!(
+            find_template("qa", None),
+            Err("Unknown template 'qa'. Available templates: orchestrator, reviewer, release-manager, triage".to_string())
+        );
+    }
+
+    #[test]
+    fn role_seed_follows_record_and_falls_back_to_recorded_seed() {
+        let temp = TestDir::new("role-seed");
+        let root = temp.path();
+        role_state::create_role_state(root, "qa").expect("role state should be created");
+
+        assert_eq!(role_seed(root, "qa", None), Ok(DEFAULT_SEED.to_string()));
+
+        let reviewer = TemplateRecord {
+            template: Some("reviewer".to_string()),
+            from: None,
+        };
+        write_template_record(root, "qa", &reviewer).expect("record should be written");
+        assert_eq!(read_template_record(root, "qa"), Ok(Some(reviewer)));
+        assert_eq!(
+            role_seed(root, "qa", None),
+            Ok(include_str!("prompts/reviewer.md").to_string())
+        );
+
+        let oncall = TemplateRecord {
+            template: Some("oncall".to_string()),
+            from: None,
+        };
+        write_template_record(root, "qa", &oncall).expect("record should be written");
+        assert_eq!(
+            role_seed(root, "qa", None),
+            Err("Role 'qa' uses template 'oncall': Unknown template 'oncall'. Available templates: orchestrator, reviewer, release-manager, triage".to_string())
+        );
+        fs::write(role_state::prompt_seed_path(root, "qa"), "Recorded seed.\n")
+            .expect("seed should be written");
+        assert_eq!(
+            role_seed(root, "qa", None),
+            Ok("Recorded seed.\n".to_string())
+        );
+    }
+
+    #[test]
+    fn prompt_source_reads_role_dirs_and_adopts_heading() {
+        let temp = TestDir::new("prompt-source");
+        let source_dir = temp.path().join("eng-lead");
+        fs::create_dir_all(&source_dir).expect("source dir should be created");
+        fs::write(source_dir.join("prompt.md"), "\n# eng-lead\n\nLead.\n")
+            .expect("prompt should be written");
+
+        assert_eq!(
+            read_prompt_source(&source_dir),
+            Ok(PromptSource {
+                prompt: "\n# eng-lead\n\nLead.\n".to_string(),
+                seed: None,
+            })
+        );
+        fs::write(source_dir.join("prompt-seed.md"), "Seed.\n").expect("seed should be written");
+        assert_eq!(
+            read_prompt_source(&source_dir).map(|source| source.seed),
+            Ok(Some("Seed.\n".to_string()))
+        );
+
+        assert_eq!(
+            adopt_heading("\n# eng-lead\n\nLead # eng-lead.\n", "qa", "p.md"),
+            Ok("\n# qa\n\nLead # eng-lead.\n".to_string())
+        );
+        assert_eq!(
+            adopt_heading("# {{role}}\n", "qa", "p.md"),
+            Ok("# {{role}}\n".to_string())
+        );
+        assert_eq!(
+            adopt_heading("## Lead\n", "qa", "p.md"),
+            Err("p.md must start with a `# <role>` heading, found `## Lead`".to_string())
+        );
+        assert_eq!(
+            adopt_heading("\n\n", "qa", "p.md"),
+            Err("p.md is empty; expected a `# <role>` heading".to_string())
+        );
+    }
+}
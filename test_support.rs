@@ -0,0 +1,45 @@
+use std::env;
+use std::fs;
+use std::panic::Location;
+use std::path::{Path, PathBuf};
+use std::process;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// A fresh directory under the system temp dir, removed on drop. It is named
+/// `juliet-<module>-<name>-<pid>-<nanos>` after the calling module so leftovers are easy to trace.
+pub struct TestDir {
+    path: PathBuf,
+}
+
+impl TestDir {
+    #[track_caller]
+    pub fn new(name: &str) -> Self {
+        let module = Path::new(Location::caller().file())
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .unwrap_or("test")
+            .replace('_', "-");
+        let module = if module == "juliet" { "cli" } else { &module };
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("time drift should not occur in tests")
+            .as_nanos();
+        let path = env::temp_dir().join(format!(
+            "juliet-{module}-{name}-{}-{timestamp}",
+            process::id()
+        ));
+        fs::create_dir_all(&path).expect("test directory should be created");
+
+        Self { path }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl Drop for TestDir {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.path);
+    }
+}
@@ -0,0 +1,340 @@
+use crate::process_check;
+use crate::role_state;
+use crate::state_files::{self, ProcessEntry};
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum WatchEvent {
+    RunFinished(ProcessEntry),
+    LogGrew { log_path: String, bytes: u64 },
+    NewNeed(String),
+    OperatorInput(String),
+}
+
+impl WatchEvent {
+    /// Log growth is reported but only means a run is still busy, so it never costs a turn.
+    pub fn wakes_engine(&self) -> bool {
+        !matches!(self, WatchEvent::LogGrew { .. })
+    }
+
+    pub fn describe(&self) -> String {
+        match self {
+            WatchEvent::RunFinished(entry) => format!(
+                "run pid {} -> {} exited",
+                entry
+                    .pid
+                    .map(|pid| pid.to_string())
+                    .unwrap_or_else(|| "?".to_string()),
+                entry.target_branch.as_deref().unwrap_or("?")
+            ),
+            WatchEvent::LogGrew { log_path, bytes } => format!("{log_path} grew by {bytes} bytes"),
+            WatchEvent::NewNeed(text) => format!("new need from operator: {text}"),
+            WatchEvent::OperatorInput(_) => "operator input received".to_string(),
+        }
+    }
+}
+
+/// Remembers what the last handled turn saw so only changes surface as events. Events stay
+/// pending until [`Watcher::acknowledge`] is called after a successful turn.
+#[derive(Debug, Default)]
+pub struct Watcher {
+    known_needs: Vec<String>,
+    log_sizes: HashMap<String, u64>,
+    /// Runs already moved to Completed by a poll whose turn has not succeeded yet.
+    unreported_runs: Vec<ProcessEntry>,
+}
+
+impl Watcher {
+    /// Seeds the watcher with the current needs and log sizes so pre-existing state does not
+    /// trigger a turn on the first poll.
+    pub fn new(project_root: &Path, role_name: &str) -> io::Result<Self> {
+        let mut watcher = Self {
+            known_needs: need_texts(project_root, role_name)?,
+            ..Self::default()
+        };
+        for entry in state_files::load_processes(project_root, role_name)?.active {
+            if let Some(log_path) = entry.log_path {
+                let size = file_size(&project_root.join(&log_path));
+                watcher.log_sizes.insert(log_path, size);
+            }
+        }
+        Ok(watcher)
+    }
+
+    /// Marks `events` as handled once their turn succeeded: consumed operator input leaves
+    /// the inbox, and only the runs and needs the events named become known. Anything that
+    /// changed while the turn ran still surfaces on the next poll.
+    pub fn acknowledge(
+        &mut self,
+        project_root: &Path,
+        role_name: &str,
+        events: &[WatchEvent],
+    ) -> io::Result<()> {
+        for event in events {
+            match event {
+                WatchEvent::RunFinished(entry) => self.unreported_runs.retain(|run| run != entry),
+                WatchEvent::NewNeed(need) => self.known_needs.push(need.clone()),
+                WatchEvent::OperatorInput(input) => {
+                    clear_operator_input(project_root, role_name, input)?
+                }
+                WatchEvent::LogGrew { .. } => {}
+            }
+        }
+        Ok(())
+    }
+
+    pub fn poll<F>(
+        &mut self,
+        project_root: &Path,
+        role_name: &str,
+        is_running: F,
+    ) -> io::Result<Vec<WatchEvent>>
+    where
+        F: Fn(u32) -> bool,
+    {
+        let check = process_check::check_processes(project_root, role_name, is_running)?;
+        for entry in check.finished {
+            if let Some(log_path) = &entry.log_path {
+                self.log_sizes.remove(log_path);
+            }
+            self.unreported_runs.push(entry);
+        }
+        let mut events = self
+            .unreported_runs
+            .iter()
+            .cloned()
+            .map(WatchEvent::RunFinished)
+            .collect::<Vec<_>>();
+        for log_path in check.running.into_iter().filter_map(|entry| entry.log_path) {
+            let size = file_size(&project_root.join(&log_path));
+            let previous = self.log_sizes.insert(log_path.clone(), size).unwrap_or(0);
+            if size > previous {
+                events.push(WatchEvent::LogGrew {
+                    log_path,
+                    bytes: size - previous,
+                });
+            }
+        }
+
+        let needs = need_texts(project_root, role_name)?;
+        // A need that was resolved and later raised again counts as new.
+        self.known_needs.retain(|known| needs.contains(known));
+        for need in needs {
+            if !self.known_needs.contains(&need) {
+                events.push(WatchEvent::NewNeed(need));
+            }
+        }
+
+        if let Some(input) = read_operator_input(project_root, role_name)? {
+            events.push(WatchEvent::OperatorInput(input));
+        }
+
+        Ok(events)
+    }
+}
+
+/// Builds the exec message for a turn, or `None` when no event warrants waking the engine.
+/// Operator input is passed through verbatim; other events are summarized after it.
+pub fn build_turn_message(events: &[WatchEvent]) -> Option<String> {
+    if !events.iter().any(WatchEvent::wakes_engine) {
+        return None;
+    }
+
+    let mut sections = events
+        .iter()
+        .filter_map(|event| match event {
+            WatchEvent::OperatorInput(input) => Some(input.clone()),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+
+    let notes = events
+        .iter()
+        .filter(|event| event.wakes_engine() && !matches!(event, WatchEvent::OperatorInput(_)))
+        .map(|event| format!("- {}", event.describe()))
+        .collect::<Vec<_>>();
+    if !notes.is_empty() {
+        sections.push(format!(
+            "juliet watch noticed:\n{}\nCheck on runs and needs, then report.",
+            notes.join("\n")
+        ));
+    }
+
+    Some(sections.join("\n\n"))
+}
+
+fn need_texts(project_root: &Path, role_name: &str) -> io::Result<Vec<String>> {
+    Ok(state_files::load_needs(project_root, role_name)?
+        .into_iter()
+        .map(|need| need.text)
+        .collect())
+}
+
+fn read_operator_input(project_root: &Path, role_name: &str) -> io::Result<Option<String>> {
+    let contents = match fs::read_to_string(role_state::inbox_path(project_root, role_name)) {
+        Ok(contents) => contents,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(None),
+        Err(err) => return Err(err),
+    };
+
+    let input = contents.trim();
+    Ok((!input.is_empty()).then(|| input.to_string()))
+}
+
+/// Removes `input` from the front of the inbox, keeping anything the operator appended while
+/// the turn ran.
+fn clear_operator_input(project_root: &Path, role_name: &str, input: &str) -> io::Result<()> {
+    let inbox_path = role_state::inbox_path(project_root, role_name);
+    let contents = match fs::read_to_string(&inbox_path) {
+        Ok(contents) => contents,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(()),
+        Err(err) => return Err(err),
+    };
+
+    match contents.trim_start().strip_prefix(input) {
+        Some(rest) => fs::write(&inbox_path, rest.trim_start()),
+        None => Ok(()),
+    }
+}
+
+fn file_size(path: &Path) -> u64 {
+    fs::metadata(path)
+        .map(|metadata| metadata.len())
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state_files::Processes;
+    use crate::test_support::TestDir;
+
+    fn seed_role(root: &Path) {
+        role_state::create_role_state(root, "eng").expect("role state should be created");
+        fs::write(
+            role_state::needs_path(root, "eng"),
+            "# Needs from operator\n\n- existing need\n",
+        )
+        .expect("needs should be written");
+        fs::write(root.join("run.log"), "booting\n").expect("log should be written");
+        state_files::save_processes(
+            root,
+            "eng",
+            &Processes {
+                active: vec![ProcessEntry {
+                    pid: Some(7),
+                    target_branch: Some("feature/wind".to_string()),
+                    log_path: Some("run.log".to_string()),
+                    ..ProcessEntry::default()
+                }],
+                completed: Vec::new(),
+            },
+        )
+        .expect("processes should save");
+    }
+
+    #[test]
+    fn first_poll_ignores_existing_state() {
+        let temp = TestDir::new("quiet");
+        seed_role(temp.path());
+
+        let mut watcher = Watcher::new(temp.path(), "eng").expect("watcher should start");
+        let events = watcher
+            .poll(temp.path(), "eng", |_| true)
+            .expect("poll should succeed");
+
+        assert!(events.is_empty());
+        assert_eq!(build_turn_message(&events), None);
+    }
+
+    #[test]
+    fn log_growth_is_reported_without_waking_the_engine() {
+        let temp = TestDir::new("log-growth");
+        seed_role(temp.path());
+        let mut watcher = Watcher::new(temp.path(), "eng").expect("watcher should start");
+
+        fs::write(temp.path().join("run.log"), "booting\nsprint 1\n")
+            .expect("log should be written");
+        let events = watcher
+            .poll(temp.path(), "eng", |_| true)
+            .expect("poll should succeed");
+
+        assert_eq!(
+            events,
+            vec![WatchEvent::LogGrew {
+                log_path: "run.log".to_string(),
+                bytes: 9,
+            }]
+        );
+        assert_eq!(build_turn_message(&events), None);
+    }
+
+    #[test]
+    fn finished_runs_new_needs_and_inbox_input_wake_the_engine() {
+        let temp = TestDir::new("wake");
+        seed_role(temp.path());
+        let mut watcher = Watcher::new(temp.path(), "eng").expect("watcher should start");
+
+        fs::write(
+            role_state::needs_path(temp.path(), "eng"),
+            "# Needs from operator\n\n- existing need\n- pick a winner branch\n",
+        )
+        .expect("needs should be written");
+        let inbox_path = role_state::inbox_path(temp.path(), "eng");
+        fs::write(&inbox_path, "use codex for the next sprint\n").expect("inbox should be written");
+
+        let events = watcher
+            .poll(temp.path(), "eng", |_| false)
+            .expect("poll should succeed");
+
+        assert_eq!(events.len(), 3);
+        assert!(matches!(&events[0], WatchEvent::RunFinished(entry) if entry.pid == Some(7)));
+        assert_eq!(
+            events[1],
+            WatchEvent::NewNeed("pick a winner branch".to_string())
+        );
+        assert_eq!(
+            events[2],
+            WatchEvent::OperatorInput("use codex for the next sprint".to_string())
+        );
+        assert_eq!(
+            build_turn_message(&events).expect("events should wake the engine"),
+            "use codex for the next sprint\n\njuliet watch noticed:\n- run pid 7 -> feature/wind exited\n- new need from operator: pick a winner branch\nCheck on runs and needs, then report."
+        );
+
+        // Until the turn is acknowledged, the same events come back on the next poll.
+        let retry = watcher
+            .poll(temp.path(), "eng", |_| false)
+            .expect("poll should succeed");
+        assert_eq!(retry, events);
+
+        // While the turn runs, a new need is raised and the operator queues more input.
+        fs::write(
+            role_state::needs_path(temp.path(), "eng"),
+            "# Needs from operator\n\n- existing need\n- pick a winner branch\n- merge feature/wind\n",
+        )
+        .expect("needs should be written");
+        fs::write(&inbox_path, "use codex for the next sprint\n\nthen stop\n")
+            .expect("inbox should be written");
+        watcher
+            .acknowledge(temp.path(), "eng", &retry)
+            .expect("events should be acknowledged");
+
+        assert_eq!(
+            fs::read_to_string(&inbox_path).expect("inbox should be readable"),
+            "then stop\n"
+        );
+        assert_eq!(
+            watcher
+                .poll(temp.path(), "eng", |_| false)
+                .expect("poll should succeed"),
+            vec![
+                WatchEvent::NewNeed("merge feature/wind".to_string()),
+                WatchEvent::OperatorInput("then stop".to_string())
+            ]
+        );
+    }
+}
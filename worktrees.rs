@@ -0,0 +1,456 @@
+use crate::role_state;
+use crate::state_files;
+use crate::swarm_tasks;
+use crate::table;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+const SHARED_DIR: &str = ".shared";
+const WORKTREES_DIR: &str = "worktrees";
+
+/// One directory under `.swarm-hug/.shared/worktrees/`, joined with git and Juliet state.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Worktree {
+    pub name: String,
+    pub path: PathBuf,
+    /// The branch git has checked out there, or the branch decoded from the directory name
+    /// when git does not report one.
+    pub branch: String,
+    /// Whether `git worktree list` knows about the directory.
+    pub registered: bool,
+    pub role: Option<String>,
+    pub pid: Option<u32>,
+    /// Whether the matching process is still in the Active section of `processes.md`.
+    pub active: bool,
+    /// Why the worktree can be pruned; `None` while it may still be in use.
+    pub orphaned: Option<String>,
+}
+
+impl Worktree {
+    fn status(&self) -> String {
+        match (&self.orphaned, self.active, self.registered) {
+            (Some(reason), _, _) => format!("orphaned ({reason})"),
+            (None, true, _) => "active".to_string(),
+            (None, false, false) => "unregistered".to_string(),
+            (None, false, true) => "ok".to_string(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum PruneAction {
+    WouldRemove,
+    Removed,
+    Skipped(String),
+    Failed(String),
+}
+
+pub fn worktrees_dir(project_root: &Path) -> PathBuf {
+    swarm_tasks::swarm_root(project_root)
+        .join(SHARED_DIR)
+        .join(WORKTREES_DIR)
+}
+
+/// Swarm stores `feature/wind` as `feature%2Fwind`.
+pub fn decode_worktree_name(name: &str) -> String {
+    name.replace("%2F", "/")
+        .replace("%2f", "/")
+        .replace("%25", "%")
+}
+
+/// Lists swarm worktrees and flags the orphaned ones: a branch that no longer exists, or one
+/// a merge commit on the checked-out branch already brought in. Worktrees of Active runs are never orphaned.
+/// `git` runs one git command in the project root and returns its stdout.
+pub fn list_worktrees<G>(project_root: &Path, mut git: G) -> Result<Vec<Worktree>, String>
+where
+    G: FnMut(&[&str]) -> Result<String, String>,
+{
+    let dir = worktrees_dir(project_root);
+    let entries = match fs::read_dir(&dir) {
+        Ok(entries) => entries,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => return Err(format!("failed to read {}: {err}", dir.display())),
+    };
+    let mut names = Vec::new();
+    for entry in entries {
+        let entry = entry.map_err(|err| format!("failed to read {}: {err}", dir.display()))?;
+        if entry.path().is_dir() {
+            if let Ok(name) = entry.file_name().into_string() {
+                names.push(name);
+            }
+        }
+    }
+    if names.is_empty() {
+        return Ok(Vec::new());
+    }
+    names.sort();
+
+    let registered = parse_worktree_list(&git(&["worktree", "list", "--porcelain"])?);
+    let branches = lines(&git(&[
+        "for-each-ref",
+        "--format=%(refname:short)",
+        "refs/heads/",
+    ])?);
+    let current = git(&["branch", "--show-current"])?.trim().to_string();
+    let owners =
+        load_owners(project_root).map_err(|err| format!("failed to read role processes: {err}"))?;
+
+    let mut worktrees = Vec::new();
+    for name in names {
+        let path = dir.join(&name);
+        let git_entry = path
+            .canonicalize()
+            .ok()
+            .and_then(|path| registered.get(&path));
+        let branch = git_entry
+            .cloned()
+            .flatten()
+            .unwrap_or_else(|| decode_worktree_name(&name));
+        let owner = owners.get(&branch);
+        let active = owner.is_some_and(|owner| owner.active);
+        let orphaned = if active {
+            None
+        } else if !branches.contains(&branch) {
+            Some("branch deleted".to_string())
+        } else if !current.is_empty() && branch != current && merged_into_head(&mut git, &branch)? {
+            Some(format!("merged into {current}"))
+        } else {
+            None
+        };
+        worktrees.push(Worktree {
+            registered: git_entry.is_some(),
+            role: owner.map(|owner| owner.role.clone()),
+            pid: owner.and_then(|owner| owner.pid),
+            name,
+            path,
+            branch,
+            active,
+            orphaned,
+        });
+    }
+    Ok(worktrees)
+}
+
+/// Whether `branch`'s work reached HEAD, either through a merge commit that brought in its tip
+/// or by fast-forward. Ancestry alone is not enough: a branch just created from HEAD, with no
+/// commits of its own, is an ancestor too, so a fast-forward only counts when the branch's reflog
+/// shows it moved past the commit it was created at. Squash merges leave no trace in the history
+/// and are not detected.
+fn merged_into_head<G>(git: &mut G, branch: &str) -> Result<bool, String>
+where
+    G: FnMut(&[&str]) -> Result<String, String>,
+{
+    let reference = format!("refs/heads/{branch}");
+    let tip = git(&["rev-parse", "--verify", &reference])?
+        .trim()
+        .to_string();
+    let merges = git(&[
+        "rev-list",
+        "--merges",
+        "--parents",
+        &format!("{reference}..HEAD"),
+    ])?;
+    if merges
+        .lines()
+        .any(|line| line.split_whitespace().skip(2).any(|parent| parent == tip))
+    {
+        return Ok(true);
+    }
+
+    let merged = git(&["branch", "--merged", "HEAD", "--format=%(refname:short)"])?;
+    if !merged.lines().any(|line| line.trim() == branch) {
+        return Ok(false);
+    }
+    let reflog = git(&["reflog", "show", "--format=%H", &reference])?;
+    Ok(reflog
+        .lines()
+        .last()
+        .is_some_and(|created_at| created_at.trim() != tip))
+}
+
+/// Removes orphaned worktrees with `git worktree remove`, or only reports them on a dry run.
+pub fn prune_worktrees<G>(
+    worktrees: &[Worktree],
+    dry_run: bool,
+    mut git: G,
+) -> Vec<(&Worktree, PruneAction)>
+where
+    G: FnMut(&[&str]) -> Result<String, String>,
+{
+    worktrees
+        .iter()
+        .filter(|worktree| worktree.orphaned.is_some())
+        .map(|worktree| {
+            let action = if !worktree.registered {
+                PruneAction::Skipped("not a registered git worktree".to_string())
+            } else if dry_run {
+                PruneAction::WouldRemove
+            } else {
+                match git(&["worktree", "remove", &worktree.path.display().to_string()]) {
+                    Ok(_) => PruneAction::Removed,
+                    Err(err) => PruneAction::Failed(err),
+                }
+            };
+            (worktree, action)
+        })
+        .collect()
+}
+
+pub fn format_worktrees(project_root: &Path, worktrees: &[Worktree]) -> String {
+    if worktrees.is_empty() {
+        return format!(
+            "no swarm worktrees under {}\n",
+            role_state::display_relative(project_root, &worktrees_dir(project_root))
+        );
+    }
+
+    let headers = ["BRANCH", "ROLE", "PID", "STATUS", "PATH"];
+    let rows = worktrees
+        .iter()
+        .map(|worktree| {
+            [
+                worktree.branch.clone(),
+                worktree.role.clone().unwrap_or_else(|| "-".to_string()),
+                worktree
+                    .pid
+                    .map(|pid| pid.to_string())
+                    .unwrap_or_else(|| "-".to_string()),
+                worktree.status(),
+                role_state::display_relative(project_root, &worktree.path),
+            ]
+        })
+        .collect::<Vec<_>>();
+
+    table::format_table(headers, &rows)
+}
+
+pub fn format_prune(project_root: &Path, results: &[(&Worktree, PruneAction)]) -> String {
+    if results.is_empty() {
+        return "no orphaned worktrees\n".to_string();
+    }
+
+    results
+        .iter()
+        .map(|(worktree, action)| {
+            let path = role_state::display_relative(project_root, &worktree.path);
+            let reason = worktree.orphaned.as_deref().unwrap_or_default();
+            match action {
+                PruneAction::WouldRemove => format!("would remove {path} ({reason})\n"),
+                PruneAction::Removed => format!("removed {path} ({reason})\n"),
+                PruneAction::Skipped(why) => format!("skipped {path}: {why}\n"),
+                PruneAction::Failed(err) => format!("failed to remove {path}: {err}\n"),
+            }
+        })
+        .collect()
+}
+
+struct Owner {
+    role: String,
+    pid: Option<u32>,
+    active: bool,
+}
+
+/// Maps target branches to the role and process that own them. Active processes win over
+/// completed ones, which win over branches only listed in `projects.md`.
+fn load_owners(project_root: &Path) -> io::Result<HashMap<String, Owner>> {
+    let mut owners = HashMap::<String, Owner>::new();
+    let mut claim = |branch: &str, owner: Owner| {
+        let rank = |owner: &Owner| (owner.active, owner.pid.is_some());
+        match owners.get(branch) {
+            Some(existing) if rank(existing) >= rank(&owner) => {}
+            _ => {
+                owners.insert(branch.to_string(), owner);
+            }
+        }
+    };
+
+    for role in role_state::discover_configured_roles(project_root)? {
+        let processes = state_files::load_processes(project_root, &role.name)?;
+        for (entries, active) in [(&processes.active, true), (&processes.completed, false)] {
+            for entry in entries {
+                if let Some(branch) = &entry.target_branch {
+                    claim(
+                        branch,
+                        Owner {
+                            role: role.name.clone(),
+                            pid: entry.pid,
+                            active,
+                        },
+                    );
+                }
+            }
+        }
+        for project in state_files::load_projects(project_root, &role.name)? {
+            for branch in &project.target_branches {
+                claim(
+                    branch,
+                    Owner {
+                        role: role.name.clone(),
+                        pid: None,
+                        active: false,
+                    },
+                );
+            }
+        }
+    }
+    Ok(owners)
+}
+
+/// Worktree paths from `git worktree list --porcelain`, with the branch each has checked out
+/// (`None` when detached).
+fn parse_worktree_list(porcelain: &str) -> HashMap<PathBuf, Option<String>> {
+    let mut worktrees = HashMap::new();
+    let mut current: Option<PathBuf> = None;
+    for line in porcelain.lines() {
+        if let Some(path) = line.strip_prefix("worktree ") {
+            let path = PathBuf::from(path);
+            let path = path.canonicalize().unwrap_or(path);
+            worktrees.insert(path.clone(), None);
+            current = Some(path);
+        } else if let Some(branch) = line.strip_prefix("branch ") {
+            if let Some(path) = &current {
+                let branch = branch.strip_prefix("refs/heads/").unwrap_or(branch);
+                worktrees.insert(path.clone(), Some(branch.to_string()));
+            }
+        }
+    }
+    worktrees
+}
+
+fn lines(output: &str) -> HashSet<String> {
+    output
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::TestDir;
+
+    #[test]
+    fn decode_worktree_name_restores_slashes() {
+        assert_eq!(
+            decode_worktree_name("feature%2Fwind-try1"),
+            "feature/wind-try1"
+        );
+        assert_eq!(decode_worktree_name("a%2fb%25c"), "a/b%c");
+    }
+
+    #[test]
+    fn list_worktrees_maps_owners_and_flags_orphans() {
+        let temp = TestDir::new("list");
+        let root = temp.path();
+        let dir = worktrees_dir(root);
+        for name in [
+            "feature%2Fwind",
+            "feature%2Fwind-try1",
+            "feature%2Fgone",
+            "feature%2Ffog",
+            "feature%2Fgust",
+        ] {
+            fs::create_dir_all(dir.join(name)).expect("worktree dir should be created");
+        }
+        role_state::create_role_state(root, "eng").expect("role state should be created");
+        fs::write(
+            role_state::processes_path(root, "eng"),
+            "## Active\n- pid: 42\n  target_branch: feature/wind-try1\n\n## Completed\n- pid: 7\n  target_branch: feature/wind\n",
+        )
+        .expect("processes should be written");
+        let porcelain = format!(
+            "worktree {root}\nHEAD abc\nbranch refs/heads/main\n\nworktree {wind}\nHEAD abc\nbranch refs/heads/feature/wind\n\nworktree {try1}\nHEAD abc\nbranch refs/heads/feature/wind-try1\n\nworktree {gone}\nHEAD abc\ndetached\n\nworktree {gust}\nHEAD abc\nbranch refs/heads/feature/gust\n",
+            root = root.display(),
+            wind = dir.join("feature%2Fwind").display(),
+            try1 = dir.join("feature%2Fwind-try1").display(),
+            gone = dir.join("feature%2Fgone").display(),
+            gust = dir.join("feature%2Fgust").display(),
+        );
+        let git = |args: &[&str]| match args {
+            ["worktree", "list", ..] => Ok(porcelain.clone()),
+            ["for-each-ref", ..] => Ok(
+                "main\nfeature/wind\nfeature/wind-try1\nfeature/fog\nfeature/gust\n".to_string(),
+            ),
+            ["branch", "--show-current"] => Ok("main\n".to_string()),
+            ["rev-parse", "--verify", "refs/heads/feature/wind"] => Ok("w1\n".to_string()),
+            // feature/fog was created from main and never committed to.
+            ["rev-parse", "--verify", "refs/heads/feature/fog"] => Ok("m0\n".to_string()),
+            // feature/gust was committed to and then fast-forwarded into main.
+            ["rev-parse", "--verify", "refs/heads/feature/gust"] => Ok("g1\n".to_string()),
+            ["rev-list", "--merges", "--parents", _] => Ok("m2 m1 w1\n".to_string()),
+            ["branch", "--merged", ..] => Ok("main\nfeature/fog\nfeature/gust\n".to_string()),
+            ["reflog", "show", _, "refs/heads/feature/fog"] => Ok("m0\n".to_string()),
+            ["reflog", "show", _, "refs/heads/feature/gust"] => Ok("g1\nm0\n".to_string()),
+            other => Err(format!("unexpected git {other:?}")),
+        };
+
+        let worktrees = list_worktrees(root, git).expect("worktrees should list");
+
+        assert_eq!(
+            format_worktrees(root, &worktrees),
+            "BRANCH             ROLE  PID  STATUS                       PATH\n\
+feature/fog        -     -    unregistered                 .swarm-hug/.shared/worktrees/feature%2Ffog\n\
+feature/gone       -     -    orphaned (branch deleted)    .swarm-hug/.shared/worktrees/feature%2Fgone\n\
+feature/gust       -     -    orphaned (merged into main)  .swarm-hug/.shared/worktrees/feature%2Fgust\n\
+feature/wind       eng   7    orphaned (merged into main)  .swarm-hug/.shared/worktrees/feature%2Fwind\n\
+feature/wind-try1  eng   42   active                       .swarm-hug/.shared/worktrees/feature%2Fwind-try1\n"
+        );
+
+        let mut removed = Vec::new();
+        let results = prune_worktrees(&worktrees, false, |args: &[&str]| {
+            removed.push(args.join(" "));
+            Ok(String::new())
+        });
+        assert_eq!(
+            format_prune(root, &results),
+            "removed .swarm-hug/.shared/worktrees/feature%2Fgone (branch deleted)\n\
+removed .swarm-hug/.shared/worktrees/feature%2Fgust (merged into main)\n\
+removed .swarm-hug/.shared/worktrees/feature%2Fwind (merged into main)\n"
+        );
+        assert_eq!(
+            removed,
+            vec![
+                format!("worktree remove {}", dir.join("feature%2Fgone").display()),
+                format!("worktree remove {}", dir.join("feature%2Fgust").display()),
+                format!("worktree remove {}", dir.join("feature%2Fwind").display()),
+            ]
+        );
+    }
+
+    #[test]
+    fn prune_worktrees_dry_run_and_unregistered_skip_git() {
+        let worktree = Worktree {
+            name: "feature%2Fold".to_string(),
+            path: PathBuf::from("/repo/.swarm-hug/.shared/worktrees/feature%2Fold"),
+            branch: "feature/old".to_string(),
+            registered: true,
+            role: None,
+            pid: None,
+            active: false,
+            orphaned: Some("branch deleted".to_string()),
+        };
+        let stale = Worktree {
+            registered: false,
+            ..worktree.clone()
+        };
+        let worktrees = [worktree, stale];
+        let never = |_: &[&str]| -> Result<String, String> { panic!("git should not run") };
+
+        assert_eq!(
+            format_prune(
+                Path::new("/repo"),
+                &prune_worktrees(&worktrees, true, never)
+            ),
+            "would remove .swarm-hug/.shared/worktrees/feature%2Fold (branch deleted)\n\
+skipped .swarm-hug/.shared/worktrees/feature%2Fold: not a registered git worktree\n"
+        );
+        assert_eq!(
+            format_prune(Path::new("/repo"), &prune_worktrees(&[], false, never)),
+            "no orphaned worktrees\n"
+        );
+    }
+}